@@ -4,6 +4,12 @@
 //! - Ed25519 authentication
 //! - Rate limiting (configurable, default 10 req/sec)
 //! - Automatic retries with exponential backoff
+//! - Optional API key pool with failover on 401/403 and sustained 429s
+//! - Transparent gzip/brotli/deflate response decompression
+//! - Configurable connection pool, with in-flight and connection reuse
+//!   counters
+//! - Opt-in `trace`-level logging of request and response bodies, with
+//!   credentials redacted
 //! - Typed responses
 //!
 //! Endpoint paths match the official Polymarket US API documentation:
@@ -15,26 +21,178 @@
 #![allow(dead_code)]
 
 use governor::{Quota, RateLimiter};
+use hyper_util::client::legacy::connect::HttpInfo;
 use reqwest::Client;
 use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::net::SocketAddr;
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tracing::{debug, warn};
+use tracing::{debug, trace, warn};
 
-use crate::auth::PolymarketAuth;
+use crate::auth::{AuthHeaders, PolymarketAuth};
 use crate::data::models::*;
 use crate::data::orderbook::parse_book_response;
 
 use super::errors::ApiError;
 
+/// Consecutive 429s on one key before rotating to the next key in the pool.
+const RATE_LIMIT_ROTATE_AFTER: u32 = 2;
+
+/// Wait used when a 429 carries no usable `Retry-After` header.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Upper bound on a server-requested wait.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Distinct local addresses remembered for reuse detection before the set
+/// is reset, bounding memory when connections churn.
+const MAX_TRACKED_CONNECTIONS: usize = 4096;
+
+/// Replacement for credential values in logged bodies and headers.
+const REDACTED: &str = "[REDACTED]";
+
+/// Field-name fragments (lowercase, separators removed) whose values are
+/// never logged.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "signature",
+    "accesskey",
+    "apikey",
+    "privatekey",
+    "secret",
+    "token",
+    "password",
+    "authorization",
+];
+
+/// Connection pool settings for the underlying HTTP client.
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    /// Idle keep-alive connections kept per host. Size it to the feed's
+    /// poll concurrency so each concurrent poll can reuse a connection.
+    pub pool_max_idle_per_host: usize,
+    /// Idle connections are closed after this long. Keep it above the poll
+    /// interval or every cycle reconnects.
+    pub pool_idle_timeout: Duration,
+    /// TCP keepalive probe interval; `None` disables.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: Duration::from_secs(90),
+            tcp_keepalive: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+/// Point-in-time request and connection counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConnectionStats {
+    /// Requests sent and awaiting response headers.
+    pub in_flight: usize,
+    pub peak_in_flight: usize,
+    /// Responses received (any status).
+    pub responses: u64,
+    /// Responses that arrived on an already-used connection.
+    pub reused: u64,
+}
+
+impl ConnectionStats {
+    /// Fraction of responses served over a reused connection.
+    pub fn reuse_rate(&self) -> f64 {
+        if self.responses == 0 {
+            0.0
+        } else {
+            self.reused as f64 / self.responses as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConnectionCounters {
+    in_flight: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+    responses: AtomicU64,
+    reused: AtomicU64,
+    /// Local addresses seen so far; a repeat means a pooled connection.
+    seen: Mutex<HashSet<SocketAddr>>,
+}
+
+impl ConnectionCounters {
+    fn start(&self) -> InFlightGuard<'_> {
+        let now = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak_in_flight.fetch_max(now, Ordering::Relaxed);
+        InFlightGuard(self)
+    }
+
+    fn record_response(&self, local_addr: Option<SocketAddr>) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        let Some(addr) = local_addr else {
+            return;
+        };
+        let mut seen = self.seen.lock().unwrap();
+        if seen.len() >= MAX_TRACKED_CONNECTIONS {
+            seen.clear();
+        }
+        if !seen.insert(addr) {
+            self.reused.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> ConnectionStats {
+        ConnectionStats {
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            peak_in_flight: self.peak_in_flight.load(Ordering::Relaxed),
+            responses: self.responses.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Decrements the in-flight count when the request completes or is dropped.
+struct InFlightGuard<'a>(&'a ConnectionCounters);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn build_http_client(timeout: Duration, config: &ConnectionConfig) -> Result<Client, ApiError> {
+    // Compression only affects the response body encoding; signatures are
+    // over timestamp + method + path, so auth is unaffected.
+    Client::builder()
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .timeout(timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .tcp_keepalive(config.tcp_keepalive)
+        .build()
+        .map_err(|e| ApiError::Network(e.to_string()))
+}
+
 /// Async REST client for Polymarket US.
 pub struct PolymarketClient {
-    auth: PolymarketAuth,
+    /// Credential pool (never empty). GET requests round-robin across it;
+    /// order calls stick to `order_key` so retries keep one identity.
+    auths: Vec<PolymarketAuth>,
+    next_read_key: AtomicUsize,
+    order_key: AtomicUsize,
     base_url: String,
     client: Client,
+    timeout: Duration,
+    connections: Arc<ConnectionCounters>,
     rate_limiter: Arc<RateLimiter<governor::state::NotKeyed, governor::state::InMemoryState, governor::clock::DefaultClock>>,
     max_retries: u32,
+    /// Log full request/response bodies at `trace` level.
+    log_bodies: bool,
 }
 
 impl PolymarketClient {
@@ -45,34 +203,101 @@ impl PolymarketClient {
         max_retries: u32,
         timeout_secs: u64,
     ) -> Result<Self, ApiError> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .pool_max_idle_per_host(20)
-            .tcp_keepalive(Duration::from_secs(30))
-            .build()
-            .map_err(|e| ApiError::Network(e.to_string()))?;
-
-        let quota = Quota::per_second(NonZeroU32::new(rate_limit).unwrap_or(NonZeroU32::new(10).unwrap()));
+        Self::with_key_pool(vec![auth], base_url, rate_limit, max_retries, timeout_secs)
+    }
+
+    /// Create with a pool of API keys. `rate_limit` is per key, so the
+    /// client-side limiter allows `rate_limit * auths.len()` requests/sec.
+    pub fn with_key_pool(
+        auths: Vec<PolymarketAuth>,
+        base_url: &str,
+        rate_limit: u32,
+        max_retries: u32,
+        timeout_secs: u64,
+    ) -> Result<Self, ApiError> {
+        if auths.is_empty() {
+            return Err(ApiError::Authentication("API key pool is empty".to_string()));
+        }
+
+        let timeout = Duration::from_secs(timeout_secs);
+        let client = build_http_client(timeout, &ConnectionConfig::default())?;
+
+        let per_key = NonZeroU32::new(rate_limit).unwrap_or(NonZeroU32::new(10).unwrap());
+        let pool_size = NonZeroU32::new(auths.len() as u32).unwrap_or(NonZeroU32::MIN);
+        let quota = Quota::per_second(per_key.saturating_mul(pool_size));
         let rate_limiter = Arc::new(RateLimiter::direct(quota));
 
         Ok(Self {
-            auth,
+            auths,
+            next_read_key: AtomicUsize::new(0),
+            order_key: AtomicUsize::new(0),
             base_url: base_url.trim_end_matches('/').to_string(),
             client,
+            timeout,
+            connections: Arc::new(ConnectionCounters::default()),
             rate_limiter,
             max_retries,
+            log_bodies: false,
         })
     }
 
+    /// Log each request body (with redacted auth headers) and raw response
+    /// text at `trace` level. Off by default; only takes effect when the
+    /// subscriber also enables `trace` for this module.
+    pub fn with_body_logging(mut self, enabled: bool) -> Self {
+        self.log_bodies = enabled;
+        self
+    }
+
+    /// Rebuild the HTTP client with these pool settings.
+    pub fn with_connection_config(mut self, config: &ConnectionConfig) -> Result<Self, ApiError> {
+        self.client = build_http_client(self.timeout, config)?;
+        Ok(self)
+    }
+
+    /// In-flight and connection reuse counters since construction.
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.connections.snapshot()
+    }
+
+    fn body_logging_enabled(&self) -> bool {
+        self.log_bodies && tracing::enabled!(tracing::Level::TRACE)
+    }
+
     /// Create with default settings.
     pub fn with_defaults(auth: PolymarketAuth, base_url: &str) -> Result<Self, ApiError> {
         Self::new(auth, base_url, 10, 3, 30)
     }
 
+    /// Number of API keys in the pool.
+    pub fn key_count(&self) -> usize {
+        self.auths.len()
+    }
+
     // =========================================================================
     // Core request method
     // =========================================================================
 
+    /// Pick the starting key: round-robin for reads, sticky for writes.
+    fn initial_key(&self, method: &reqwest::Method) -> usize {
+        if *method == reqwest::Method::GET {
+            self.next_read_key.fetch_add(1, Ordering::Relaxed) % self.auths.len()
+        } else {
+            self.order_key.load(Ordering::Relaxed) % self.auths.len()
+        }
+    }
+
+    /// Move to the next key in the pool. Writes persist the switch so later
+    /// order calls keep using the working key.
+    fn rotate_key(&self, method: &reqwest::Method, key: usize, reason: &str) -> usize {
+        let next = (key + 1) % self.auths.len();
+        if *method != reqwest::Method::GET {
+            self.order_key.store(next, Ordering::Relaxed);
+        }
+        warn!(from_key = key, to_key = next, reason, "Rotating API key");
+        next
+    }
+
     async fn request(
         &self,
         method: reqwest::Method,
@@ -82,13 +307,26 @@ impl PolymarketClient {
     ) -> Result<serde_json::Value, ApiError> {
         let url = format!("{}{}", self.base_url, path);
         let mut last_error: Option<ApiError> = None;
+        let can_rotate = self.auths.len() > 1;
+        let mut key = self.initial_key(&method);
+        let mut auth_rotations = 0;
+        let mut consecutive_429 = 0;
 
         for attempt in 0..self.max_retries {
             self.rate_limiter.until_ready().await;
 
-            let auth_headers = self.auth.sign_request(method.as_str(), path);
+            let auth_headers = self.auths[key].sign_request(method.as_str(), path);
 
             debug!(method = %method, path = %path, attempt = attempt + 1, "API request");
+            if self.body_logging_enabled() {
+                trace!(
+                    method = %method,
+                    path = %path,
+                    headers = %redact_headers(&auth_headers),
+                    body = %body.map(redact_json).unwrap_or_default(),
+                    "API request body"
+                );
+            }
 
             let mut req = self
                 .client
@@ -102,60 +340,116 @@ impl PolymarketClient {
                 req = req.query(params);
             }
 
-            let result = req.send().await;
+            let result = {
+                let _in_flight = self.connections.start();
+                req.send().await
+            };
 
             match result {
                 Ok(response) => {
                     let status = response.status();
+                    self.connections.record_response(
+                        response.extensions().get::<HttpInfo>().map(|i| i.local_addr()),
+                    );
 
                     if status.is_success() {
-                        let text = response
-                            .text()
+                        // reqwest strips Content-Length when it decodes a
+                        // compressed body, so its presence means identity.
+                        let wire_bytes = response.content_length();
+                        let body = response
+                            .bytes()
                             .await
                             .map_err(|e| ApiError::Network(e.to_string()))?;
-                        let json: serde_json::Value = serde_json::from_str(&text)
+                        let decoded_bytes = body.len() as u64;
+                        if self.body_logging_enabled() {
+                            trace!(
+                                path = %path,
+                                status = status.as_u16(),
+                                body = %redact_text(&String::from_utf8_lossy(&body)),
+                                "API response body (raw)"
+                            );
+                        }
+                        debug!(
+                            path = %path,
+                            decoded_bytes,
+                            wire_bytes = wire_bytes.unwrap_or(0),
+                            compressed = wire_bytes.is_none(),
+                            "API response body"
+                        );
+                        let json: serde_json::Value = serde_json::from_slice(&body)
                             .map_err(|e| ApiError::Deserialization(e.to_string()))?;
                         return Ok(json);
                     }
 
                     if status.as_u16() == 429 {
-                        let retry_after = response
+                        let wait = response
                             .headers()
                             .get("Retry-After")
                             .and_then(|v| v.to_str().ok())
-                            .and_then(|v| v.parse::<u64>().ok())
-                            .unwrap_or(1);
-                        warn!(retry_after, attempt = attempt + 1, "Rate limited");
-                        tokio::time::sleep(Duration::from_secs(retry_after)).await;
+                            .map(parse_retry_after)
+                            .unwrap_or(DEFAULT_RETRY_AFTER);
+                        let retry_after = wait.as_secs();
+                        warn!(retry_after, attempt = attempt + 1, key, "Rate limited");
                         last_error = Some(ApiError::RateLimited { retry_after });
+                        consecutive_429 += 1;
+                        if can_rotate && consecutive_429 >= RATE_LIMIT_ROTATE_AFTER {
+                            // A fresh key has its own budget; no need to wait.
+                            key = self.rotate_key(&method, key, "rate limited");
+                            consecutive_429 = 0;
+                            continue;
+                        }
+                        tokio::time::sleep(wait).await;
                         continue;
                     }
 
-                    if status.as_u16() >= 500 {
-                        let delay_ms = 500 * 2u64.pow(attempt);
-                        warn!(status_code = status.as_u16(), delay_ms, attempt = attempt + 1, "Server error, retrying");
-                        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                        last_error = Some(ApiError::Http {
-                            status_code: status.as_u16(),
-                            error_code: "SERVER_ERROR".to_string(),
-                            message: status.to_string(),
-                        });
+                    let body_text = response.text().await.unwrap_or_default();
+                    if self.body_logging_enabled() {
+                        trace!(
+                            path = %path,
+                            status = status.as_u16(),
+                            body = %redact_text(&body_text),
+                            "API response body (raw)"
+                        );
+                    }
+                    let error = ApiError::from_response(status.as_u16(), &body_text);
+                    if error.is_auth_error()
+                        && can_rotate
+                        && auth_rotations + 1 < self.auths.len()
+                    {
+                        auth_rotations += 1;
+                        consecutive_429 = 0;
+                        key = self.rotate_key(&method, key, "authentication rejected");
+                        last_error = Some(error);
                         continue;
                     }
-
-                    let body_text = response.text().await.unwrap_or_default();
-                    return Err(ApiError::from_response(status.as_u16(), &body_text));
+                    if !error.is_retryable() {
+                        return Err(error);
+                    }
+                    let delay = backoff_delay(attempt);
+                    warn!(
+                        error = %error,
+                        delay_ms = delay.as_millis() as u64,
+                        attempt = attempt + 1,
+                        "Retryable API error, backing off"
+                    );
+                    tokio::time::sleep(delay).await;
+                    last_error = Some(error);
                 }
                 Err(e) => {
-                    let delay_ms = 500 * 2u64.pow(attempt);
-                    warn!(error = %e, delay_ms, attempt = attempt + 1, "Network error, retrying");
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                    if e.is_timeout() {
-                        last_error = Some(ApiError::Timeout(e.to_string()));
+                    let error = if e.is_timeout() {
+                        ApiError::Timeout(e.to_string())
                     } else {
-                        last_error = Some(ApiError::Network(e.to_string()));
-                    }
-                    continue;
+                        ApiError::Network(e.to_string())
+                    };
+                    let delay = backoff_delay(attempt);
+                    warn!(
+                        error = %error,
+                        delay_ms = delay.as_millis() as u64,
+                        attempt = attempt + 1,
+                        "Network error, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    last_error = Some(error);
                 }
             }
         }
@@ -321,8 +615,17 @@ impl PolymarketClient {
         serde_json::from_value(data).map_err(|e| ApiError::Deserialization(e.to_string()))
     }
 
-    pub async fn close_position(&self, market_slug: &str) -> Result<serde_json::Value, ApiError> {
-        let body = serde_json::json!({"marketSlug": market_slug});
+    /// Close a position at market. With `side`, only that side's holding
+    /// is closed; otherwise the whole market position is.
+    pub async fn close_position(
+        &self,
+        market_slug: &str,
+        side: Option<Side>,
+    ) -> Result<serde_json::Value, ApiError> {
+        let mut body = serde_json::json!({"marketSlug": market_slug});
+        if let Some(side) = side {
+            body["side"] = serde_json::json!(side);
+        }
         self.request(reqwest::Method::POST, "/v1/order/close-position", Some(&body), None).await
     }
 
@@ -422,3 +725,312 @@ impl PolymarketClient {
         })
     }
 }
+
+/// Whether a field name holds a credential: matched on its lowercase form
+/// with `-` and `_` removed, so `X-PM-Signature` and `api_key` both count.
+fn is_sensitive_field(name: &str) -> bool {
+    let normalized: String = name
+        .chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    SENSITIVE_FIELDS.iter().any(|f| normalized.contains(f))
+}
+
+fn redact_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if is_sensitive_field(name) {
+                    *field = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// `value` as compact JSON with credential fields masked at any depth.
+pub fn redact_json(value: &serde_json::Value) -> String {
+    let mut value = value.clone();
+    redact_value(&mut value);
+    value.to_string()
+}
+
+/// Response text for logging: JSON has credential fields masked; anything
+/// else is logged as received.
+pub fn redact_text(text: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) => redact_json(&value),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// Auth headers for logging: only the timestamp is shown.
+fn redact_headers(headers: &AuthHeaders) -> String {
+    format!(
+        "X-PM-Access-Key={REDACTED} X-PM-Timestamp={} X-PM-Signature={REDACTED}",
+        headers.timestamp
+    )
+}
+
+/// Exponential backoff before retry `attempt` (0-based): 500ms, 1s, 2s, ...
+fn backoff_delay(attempt: u32) -> Duration {
+    Duration::from_millis(500 * 2u64.pow(attempt))
+}
+
+/// Parse a `Retry-After` value: either delay seconds or an HTTP-date.
+/// Malformed values fall back to `DEFAULT_RETRY_AFTER`; waits are capped at
+/// `MAX_RETRY_AFTER`.
+pub fn parse_retry_after(value: &str) -> Duration {
+    parse_retry_after_at(value, chrono::Utc::now())
+}
+
+fn parse_retry_after_at(value: &str, now: chrono::DateTime<chrono::Utc>) -> Duration {
+    let value = value.trim();
+    let wait = if let Ok(secs) = value.parse::<u64>() {
+        Duration::from_secs(secs)
+    } else if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value) {
+        // A date already past means retry now.
+        (date.with_timezone(&chrono::Utc) - now).to_std().unwrap_or(Duration::ZERO)
+    } else {
+        DEFAULT_RETRY_AFTER
+    };
+    wait.min(MAX_RETRY_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::{Signature, SigningKey, Verifier};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve exactly one request with a gzipped body, returning the raw
+    /// request head and the number of body bytes sent on the wire.
+    async fn serve_gzipped_once(listener: TcpListener, json: String) -> (String, usize) {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..n]);
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let gz = encoder.finish().unwrap();
+
+        let header = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+             Content-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            gz.len()
+        );
+        socket.write_all(header.as_bytes()).await.unwrap();
+        socket.write_all(&gz).await.unwrap();
+        socket.shutdown().await.unwrap();
+
+        (String::from_utf8_lossy(&head).to_string(), gz.len())
+    }
+
+    #[tokio::test]
+    async fn test_gzipped_markets_response_deserializes_and_auth_is_unchanged() {
+        let markets: Vec<serde_json::Value> = (0..200)
+            .map(|i| {
+                serde_json::json!({
+                    "slug": format!("nba-game-{}", i),
+                    "title": format!("Game {}", i),
+                    "active": true,
+                    "yesBid": "0.45",
+                    "yesAsk": "0.47",
+                })
+            })
+            .collect();
+        let json = serde_json::json!({ "markets": markets }).to_string();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_gzipped_once(listener, json.clone()));
+
+        let key_bytes = [42u8; 32];
+        let auth = PolymarketAuth::new("test-key-id", &BASE64.encode(key_bytes)).unwrap();
+        let client = PolymarketClient::new(auth, &format!("http://{}", addr), 100, 1, 5).unwrap();
+
+        let parsed = client
+            .get_markets(None, None, 200, 0, None)
+            .await
+            .unwrap();
+        let (head, wire_len) = server.await.unwrap();
+
+        assert_eq!(parsed.len(), 200);
+        assert_eq!(parsed[199].slug, "nba-game-199");
+        assert_eq!(parsed[0].yes_ask, Some(Decimal::new(47, 2)));
+        assert!(wire_len < json.len(), "Body must be compressed on the wire");
+
+        let head_lower = head.to_lowercase();
+        let accept = head_lower
+            .lines()
+            .find(|l| l.starts_with("accept-encoding:"))
+            .expect("Accept-Encoding header sent");
+        assert!(accept.contains("gzip") && accept.contains("br"));
+
+        // Signature is over timestamp + METHOD + path (no query, no body).
+        let header_value = |name: &str| {
+            head.lines()
+                .find(|l| l.to_lowercase().starts_with(&format!("{}:", name)))
+                .and_then(|l| l.split_once(':'))
+                .map(|(_, v)| v.trim().to_string())
+                .unwrap()
+        };
+        let timestamp = header_value("x-pm-timestamp");
+        let signature_bytes = BASE64.decode(header_value("x-pm-signature")).unwrap();
+        let signature = Signature::from_slice(&signature_bytes).unwrap();
+        let message = format!("{}GET/v1/markets", timestamp);
+        SigningKey::from_bytes(&key_bytes)
+            .verifying_key()
+            .verify(message.as_bytes(), &signature)
+            .expect("Signature must match timestamp + method + path");
+    }
+
+    /// Serve requests until aborted, answering 429 to `limited_key` and an
+    /// empty market list to every other key. Records each request's key.
+    async fn serve_by_key(
+        listener: TcpListener,
+        limited_key: &'static str,
+        seen: Arc<std::sync::Mutex<Vec<String>>>,
+    ) {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..n]);
+            }
+            let head = String::from_utf8_lossy(&head).to_string();
+            let key = head
+                .lines()
+                .find(|l| l.to_lowercase().starts_with("x-pm-access-key:"))
+                .and_then(|l| l.split_once(':'))
+                .map(|(_, v)| v.trim().to_string())
+                .unwrap_or_default();
+
+            let response = if key == limited_key {
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\n\
+                 Content-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            } else {
+                let body = r#"{"markets":[]}"#;
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            };
+            seen.lock().unwrap().push(key);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_key_pool_fails_over_from_rate_limited_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server = tokio::spawn(serve_by_key(listener, "key-a", seen.clone()));
+
+        let auths = vec![
+            PolymarketAuth::new("key-a", &BASE64.encode([1u8; 32])).unwrap(),
+            PolymarketAuth::new("key-b", &BASE64.encode([2u8; 32])).unwrap(),
+        ];
+        let client =
+            PolymarketClient::with_key_pool(auths, &format!("http://{}", addr), 100, 4, 5).unwrap();
+
+        // Read: key-a is rate limited twice, then the request is re-signed
+        // with key-b and succeeds.
+        let markets = client.get_markets(None, None, 10, 0, None).await.unwrap();
+        assert!(markets.is_empty());
+        assert_eq!(*seen.lock().unwrap(), vec!["key-a", "key-a", "key-b"]);
+
+        // Orders fail over the same way, then stick to the working key.
+        seen.lock().unwrap().clear();
+        client.cancel_all_orders(None).await.unwrap();
+        client.cancel_all_orders(None).await.unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["key-a", "key-a", "key-b", "key-b"]
+        );
+
+        server.abort();
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_date_and_malformed() {
+        assert_eq!(parse_retry_after("7"), Duration::from_secs(7));
+        assert_eq!(parse_retry_after(" 0 "), Duration::ZERO);
+        assert_eq!(parse_retry_after("3600"), MAX_RETRY_AFTER);
+
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T00:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let date = |v: &str| parse_retry_after_at(v, now);
+        assert_eq!(date("Sun, 01 Mar 2026 00:30:12 GMT"), Duration::from_secs(12));
+        assert_eq!(date("Sun, 01 Mar 2026 00:29:00 GMT"), Duration::ZERO, "Past date");
+        assert_eq!(date("Sun, 01 Mar 2026 02:00:00 GMT"), MAX_RETRY_AFTER);
+
+        assert_eq!(parse_retry_after("soon"), DEFAULT_RETRY_AFTER);
+        assert_eq!(parse_retry_after("-5"), DEFAULT_RETRY_AFTER);
+        assert_eq!(parse_retry_after(""), DEFAULT_RETRY_AFTER);
+    }
+
+    #[test]
+    fn test_logged_bodies_and_headers_mask_credentials() {
+        let body = serde_json::json!({
+            "marketSlug": "nba-lal-bos",
+            "price": {"value": "0.55", "currency": "USD"},
+            "apiKeyId": "key-uuid-1234",
+            "auth": {"X-PM-Signature": "c2lnbmF0dXJl", "private_key": "cHJpdmF0ZQ=="},
+            "legs": [{"token": "abc", "quantity": 10}],
+        });
+        let logged = redact_json(&body);
+        for secret in ["key-uuid-1234", "c2lnbmF0dXJl", "cHJpdmF0ZQ==", "abc"] {
+            assert!(!logged.contains(secret), "{secret} leaked: {logged}");
+        }
+        assert_eq!(logged.matches(REDACTED).count(), 4);
+        // Everything else is logged verbatim.
+        assert!(logged.contains("nba-lal-bos") && logged.contains("0.55"));
+        assert!(logged.contains("\"quantity\":10"));
+
+        // Raw response text: JSON is masked, anything else passes through.
+        assert_eq!(
+            redact_text(r#"{"estimatedFill":{"price":"0.5"},"secret":"s3cr3t"}"#),
+            r#"{"estimatedFill":{"price":"0.5"},"secret":"[REDACTED]"}"#
+        );
+        assert_eq!(redact_text("upstream timeout"), "upstream timeout");
+
+        let headers = AuthHeaders {
+            access_key: "key-uuid-1234".to_string(),
+            timestamp: "1700000000000".to_string(),
+            signature: "c2lnbmF0dXJl".to_string(),
+        };
+        let logged = redact_headers(&headers);
+        assert!(!logged.contains("key-uuid-1234") && !logged.contains("c2lnbmF0dXJl"));
+        assert!(logged.contains("X-PM-Timestamp=1700000000000"));
+    }
+}
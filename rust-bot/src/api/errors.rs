@@ -16,6 +16,11 @@ pub enum ApiError {
     #[error("Rate limited (retry after {retry_after}s)")]
     RateLimited { retry_after: u64 },
 
+    /// Exchange-wide load shedding (503 or a `THROTTLED` code). Unlike a
+    /// per-key 429, another API key does not help; only backing off does.
+    #[error("Throttled: {status_code} - {message}")]
+    Throttled { status_code: u16, message: String },
+
     #[error("Insufficient balance: {0}")]
     InsufficientBalance(String),
 
@@ -63,6 +68,14 @@ impl ApiError {
                 "MARKET_CLOSED" => Self::MarketClosed(message),
                 "INVALID_PRICE" | "INVALID_QUANTITY" => Self::InvalidOrder(message),
                 "RATE_LIMITED" => Self::RateLimited { retry_after: 1 },
+                "THROTTLED" => Self::Throttled {
+                    status_code,
+                    message,
+                },
+                _ if status_code == 503 => Self::Throttled {
+                    status_code,
+                    message,
+                },
                 _ => Self::Http {
                     status_code,
                     error_code: code,
@@ -71,6 +84,12 @@ impl ApiError {
             };
         }
 
+        if status_code == 503 {
+            return Self::Throttled {
+                status_code,
+                message: body.to_string(),
+            };
+        }
         Self::Http {
             status_code,
             error_code: "UNKNOWN".to_string(),
@@ -78,11 +97,12 @@ impl ApiError {
         }
     }
 
-    /// Whether this error is retryable.
+    /// Whether the same request may succeed if retried after a backoff.
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
             Self::RateLimited { .. }
+                | Self::Throttled { .. }
                 | Self::Network(_)
                 | Self::Timeout(_)
                 | Self::Http {
@@ -91,4 +111,90 @@ impl ApiError {
                 }
         )
     }
+
+    /// Whether the credentials were rejected, so another API key may
+    /// succeed where retrying this one will not.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Authentication(_)
+                | Self::Http {
+                    status_code: 401 | 403,
+                    ..
+                }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_variant_reports_retryability_and_auth() {
+        let http = |status_code: u16| ApiError::Http {
+            status_code,
+            error_code: "UNKNOWN".to_string(),
+            message: String::new(),
+        };
+        let cases: Vec<(ApiError, bool, bool)> = vec![
+            (http(400), false, false),
+            (http(401), false, true),
+            (http(403), false, true),
+            (http(404), false, false),
+            (http(500), true, false),
+            (http(502), true, false),
+            (ApiError::RateLimited { retry_after: 2 }, true, false),
+            (
+                ApiError::Throttled {
+                    status_code: 503,
+                    message: String::new(),
+                },
+                true,
+                false,
+            ),
+            (ApiError::InsufficientBalance(String::new()), false, false),
+            (ApiError::MarketClosed(String::new()), false, false),
+            (ApiError::InvalidOrder(String::new()), false, false),
+            (ApiError::Authentication(String::new()), false, true),
+            (ApiError::Network(String::new()), true, false),
+            (ApiError::Timeout(String::new()), true, false),
+            (ApiError::Deserialization(String::new()), false, false),
+            (
+                ApiError::MaxRetriesExceeded {
+                    attempts: 3,
+                    last_error: String::new(),
+                },
+                false,
+                false,
+            ),
+        ];
+        for (error, retryable, auth) in cases {
+            assert_eq!(error.is_retryable(), retryable, "{error:?} retryable");
+            assert_eq!(error.is_auth_error(), auth, "{error:?} auth");
+        }
+    }
+
+    #[test]
+    fn test_from_response_classifies_throttling_apart_from_http() {
+        let throttled = ApiError::from_response(503, "upstream overloaded");
+        assert!(matches!(throttled, ApiError::Throttled { status_code: 503, .. }));
+
+        let coded = ApiError::from_response(
+            400,
+            r#"{"error":{"code":"THROTTLED","message":"slow down"}}"#,
+        );
+        assert!(matches!(coded, ApiError::Throttled { status_code: 400, .. }));
+        assert!(coded.is_retryable());
+
+        let server = ApiError::from_response(500, "boom");
+        assert!(matches!(server, ApiError::Http { status_code: 500, .. }));
+
+        let rejected = ApiError::from_response(
+            400,
+            r#"{"error":{"code":"INVALID_PRICE","message":"bad tick"}}"#,
+        );
+        assert!(matches!(rejected, ApiError::InvalidOrder(_)));
+        assert!(!rejected.is_retryable());
+    }
 }
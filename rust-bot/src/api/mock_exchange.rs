@@ -0,0 +1,76 @@
+//! Scripted exchange for tests.
+//!
+//! A bare HTTP/1.1 responder on a local listener: each connection carries
+//! one request, answered with whatever status and JSON body the test's
+//! `respond` closure returns for its method and path (query string
+//! stripped). 429s carry `Retry-After: 0` so clients do not stall.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::api::client::PolymarketClient;
+use crate::auth::PolymarketAuth;
+
+/// Status code and body sent back for one request.
+pub(crate) type Reply = (u16, String);
+
+/// Bind an ephemeral local port and serve `respond` on it until the
+/// returned task is aborted. Returns the base URL alongside the task.
+pub(crate) async fn spawn<F>(respond: F) -> (String, JoinHandle<()>)
+where
+    F: Fn(&str, &str) -> Reply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    (base_url, tokio::spawn(serve(listener, respond)))
+}
+
+/// Answer every request on `listener` with `respond(method, path)`.
+pub(crate) async fn serve<F>(listener: TcpListener, respond: F)
+where
+    F: Fn(&str, &str) -> Reply,
+{
+    loop {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            match socket.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => head.extend_from_slice(&buf[..n]),
+            }
+        }
+        let head = String::from_utf8_lossy(&head);
+        let mut request_line = head.split_whitespace();
+        let method = request_line.next().unwrap_or("");
+        let path = request_line.next().unwrap_or("");
+        let (status, body) = respond(method, path.split('?').next().unwrap_or(""));
+        let retry_after = if status == 429 {
+            "Retry-After: 0\r\n"
+        } else {
+            ""
+        };
+        let response = format!(
+            "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\n{retry_after}\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = socket.write_all(response.as_bytes()).await;
+        let _ = socket.shutdown().await;
+    }
+}
+
+/// `200` with `body`.
+pub(crate) fn ok(body: &str) -> Reply {
+    (200, body.to_string())
+}
+
+/// Client against `base_url` with a throwaway key: one attempt per
+/// request, so error responses surface immediately.
+pub(crate) fn client(base_url: &str) -> PolymarketClient {
+    let auth = PolymarketAuth::new("key", &BASE64.encode([1u8; 32])).unwrap();
+    PolymarketClient::new(auth, base_url, 100, 1, 5).unwrap()
+}
@@ -1,2 +1,4 @@
 pub mod client;
 pub mod errors;
+#[cfg(test)]
+pub(crate) mod mock_exchange;
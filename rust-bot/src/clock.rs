@@ -0,0 +1,79 @@
+//! Injectable time source.
+//!
+//! Components with time-dependent behavior (quote refresh, stop-loss hold
+//! time, resting-order age) read the current time through a `Clock` so
+//! tests can drive time deterministically with `MockClock` instead of
+//! sleeping. Production code uses `RealClock`.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, RwLock};
+
+/// A source of the current UTC time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl std::fmt::Debug for dyn Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Clock({})", self.now())
+    }
+}
+
+/// Shared, dynamically-dispatched clock handle.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Wall-clock time (`Utc::now()`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl RealClock {
+    /// Convenience constructor for a shared real clock.
+    pub fn shared() -> SharedClock {
+        Arc::new(RealClock)
+    }
+}
+
+/// Manually-advanced clock for tests. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<RwLock<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(RwLock::new(start)),
+        }
+    }
+
+    /// Move time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.write().unwrap();
+        *now += by;
+    }
+
+    /// Jump to an absolute time.
+    pub fn set(&self, to: DateTime<Utc>) {
+        *self.now.write().unwrap() = to;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap()
+    }
+}
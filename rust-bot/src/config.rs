@@ -8,12 +8,20 @@
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
+use crate::data::models::{parse_fee_override, DEFAULT_CASH_BUFFER_PCT};
+use crate::execution::equity_series::DEFAULT_EQUITY_SAMPLE_CAPACITY;
+use crate::risk::risk_manager::parse_confidence_floor;
+use crate::risk::size_ramp::parse_size_ramp;
+use crate::strategies::statistical_edge::PairConfig;
+
 /// Application configuration loaded from environment.
 #[derive(Debug, Clone)]
 pub struct Settings {
     // Polymarket API
     pub pm_api_key_id: String,
     pub pm_private_key: String,
+    /// Additional `key_id:private_key` credentials for API key failover.
+    pub pm_extra_api_keys: Vec<String>,
     pub pm_base_url: String,
     pub pm_ws_url: String,
 
@@ -21,70 +29,291 @@ pub struct Settings {
     pub trading_mode: TradingMode,
     pub initial_balance: Decimal,
     pub tick_interval_secs: f64,
+    /// Priced updates a market needs before it is traded.
+    pub warmup_min_updates: u64,
+    /// Seconds of fresh data a market needs before it is traded.
+    pub warmup_secs: f64,
+    /// Update gap (seconds) that restarts a market's warmup.
+    pub warmup_max_gap_secs: f64,
+    /// Relative mid jump per poll, on either side, treated as a suspect tick.
+    /// Zero (the default) disables.
+    pub price_band_max_jump_pct: Decimal,
+    /// Consecutive out-of-band polls after which the move is accepted.
+    pub price_band_confirm_polls: u32,
 
     // Market Selection
     pub market_slugs: Vec<String>,
     pub leagues: Vec<String>,
     pub market_types: Vec<String>,
     pub min_liquidity: Decimal,
+    pub min_volume_24h: Decimal,
+    /// Cap on discovered markets after ranking by liquidity. Zero keeps all.
+    pub max_markets: usize,
+    /// Discovery retries until it finds at least this many markets.
+    pub min_discovered_markets: usize,
+    /// Attempts for startup discovery and the order book probe.
+    pub startup_retry_attempts: u32,
+    /// Wait after the first failed startup attempt; doubles per retry.
+    pub startup_retry_backoff_secs: f64,
 
     // Risk
     pub max_position_per_market: Decimal,
+    /// Per-market limit as a fraction of equity, alongside the dollar
+    /// limit (0 = off).
+    pub max_position_pct_of_equity: Decimal,
+    /// Contracts held per market, on top of the dollar limit (0 = no cap).
+    pub max_contracts_per_market: i64,
     pub max_portfolio_exposure: Decimal,
     pub max_portfolio_exposure_pct: Decimal,
     pub max_daily_loss: Decimal,
     pub kelly_fraction: Decimal,
     pub min_edge: Decimal,
+    /// Minimum contracts at the best level before quoting or arbing.
+    pub min_top_depth: i64,
+    /// Largest market maker quote as a fraction of the depth at its price
+    /// level. Zero disables.
+    pub market_maker_max_depth_fraction: Decimal,
+    /// Smoothing of the mid the market maker quotes around (1 = raw mid).
+    pub market_maker_mid_ewma_alpha: Decimal,
+    /// Per-market spread of quote refresh intervals, as a fraction of the
+    /// interval (0 refreshes every market on the same tick).
+    pub market_maker_refresh_jitter_pct: f64,
+    /// How far top-of-book imbalance shrinks the market maker's quote on
+    /// the heavy side and grows the other, in [0, 1]. Zero disables.
+    pub market_maker_imbalance_size_factor: Decimal,
+    /// Seconds a fair-value move must persist before the market maker
+    /// re-quotes (0 re-quotes at once).
+    pub market_maker_refresh_dwell_secs: f64,
+    /// Fair-value move that re-quotes without waiting out the dwell
+    /// (0 disables).
+    pub market_maker_refresh_hysteresis: Decimal,
+    /// Market maker quotes per side (1 = a single bid and ask).
+    pub market_maker_num_levels: usize,
+    /// Price step between the market maker's ladder levels.
+    pub market_maker_level_spacing: Decimal,
+    /// Size of each ladder level relative to the one inside it, in (0, 1].
+    pub market_maker_level_size_decay: Decimal,
+    /// Max deviation of YES bid + NO ask (and YES ask + NO bid) from $1.00
+    /// before the arb scanner treats a book as inconsistent. Zero disables.
+    pub arb_max_price_skew: Decimal,
+    /// YES spread above which a book is flagged as wide.
+    pub book_wide_spread: Decimal,
+    /// Minimum book quality score (0-1) to quote or arb. Zero disables.
+    pub book_quality_floor: Decimal,
+    pub fee_rate: Decimal,
+    /// Fee on resting (maker) fills; `fee_rate` is the taker fee.
+    pub maker_fee_rate: Decimal,
+    /// `slug:fee_rate` entries for markets whose taker fee differs from
+    /// `fee_rate`.
+    pub fee_overrides: Vec<String>,
     pub min_trade_size: Decimal,
     pub max_correlated_exposure: Decimal,
     pub max_positions: usize,
+    /// Net matched YES/NO holdings out of per-market exposure limits.
+    pub net_hedged_exposure: bool,
     pub max_drawdown_pct: Decimal,
     pub max_total_pnl_drawdown_pct_for_new_buys: Decimal,
+    pub max_var: Decimal,
+    pub var_confidence: Decimal,
+    /// Positions older than this (seconds) are exited. Zero disables.
+    pub max_position_age_secs: i64,
+    /// Seconds before re-sending an aging exit for a position still held.
+    pub position_exit_retry_secs: i64,
+    pub max_orders_per_sec: u32,
+    pub max_orders_per_sec_per_market: u32,
+    pub warning_threshold_pct: Decimal,
+    pub warning_size_scale: Decimal,
+    /// Marked-equity drawdown that exits every position. Zero disables.
+    pub panic_liquidate_drawdown_pct: Decimal,
+    /// Scale the Kelly fraction down linearly to zero at the max drawdown.
+    pub drawdown_scaled_kelly: bool,
+    /// Mark positions at the worse of their own bid and the opposite ask's
+    /// complement for risk checks.
+    pub conservative_marks: bool,
+    /// Mark positions at the last trade when it is newer than the quotes.
+    pub mark_last_trade: bool,
+    /// Markets resolving within this many seconds are skipped at discovery
+    /// and closed to new buys (exits still allowed). Zero disables.
+    pub min_resolution_horizon_secs: f64,
+    /// Minimum seconds between new orders in one market, across all
+    /// strategies (cancels and reduce-only exits exempt). Zero disables.
+    pub min_order_interval_secs: f64,
+    /// Block buying the opposite side of a held market, except reduce-only
+    /// exits and completeness-arb legs.
+    pub flip_protection: bool,
+    /// Per-strategy confidence floors for new buys, as `strategy:floor`.
+    pub min_confidence: Vec<String>,
+    /// Share of available cash a buy may commit, in (0, 1]; applied by risk
+    /// sizing and both executors alike.
+    pub cash_buffer_pct: Decimal,
+    /// Per-strategy buy size ramps, as
+    /// `strategy:initial_scale:trades_to_full[:secs_to_full]`.
+    pub size_ramp: Vec<String>,
 
     // Strategy flags
     pub enable_market_maker: bool,
     pub enable_live_arbitrage: bool,
     pub enable_statistical_edge: bool,
+    pub enable_completeness_arb: bool,
+    /// Keep only the highest-priority strategy per market-side each cycle.
+    pub resolve_signal_conflicts: bool,
+    /// Drop entries whose edge after fees, impact and the adverse-selection
+    /// haircut is below `net_edge_min`.
+    pub net_edge_gate: bool,
+    pub net_edge_min: Decimal,
+    pub net_edge_adverse_haircut: Decimal,
 
     // Market maker tuning
     pub market_maker_order_size: Decimal,
     pub market_maker_spread: Decimal,
+    /// Widest YES book spread the market maker quotes into.
+    pub market_maker_max_spread: Decimal,
+    /// Signed notional the market maker leans toward (+YES / -NO).
+    pub market_maker_inventory_target: Decimal,
+    /// Relative spread the book must offer beyond the round-trip fee cost.
+    pub market_maker_spread_safety_margin: Decimal,
+    /// Exchange price increment that quotes and exits are rounded to.
+    pub tick_size: Decimal,
 
     // Live arbitrage tuning
     pub live_arb_min_edge: Decimal,
     pub live_arb_order_size: Decimal,
     pub live_arb_cooldown_seconds: f64,
+    /// Seconds after a score change in which the lag model may fire (0 = off).
+    /// Leave off until the game-state feed populates `progress`.
+    pub live_arb_lag_window_seconds: f64,
+    pub live_arb_lag_logit_per_point: f64,
+    /// Edge at which live arb signals go out as market orders; thinner
+    /// ones post limits (0 = off).
+    pub live_arb_taker_margin: Decimal,
+
+    // Completeness arbitrage tuning
+    /// Cap on contracts per leg; zero sizes to the top-of-book depth.
+    pub completeness_arb_max_contracts: i64,
+    pub completeness_arb_cooldown_seconds: f64,
 
     // Statistical edge tuning
     pub stat_edge_min_edge: Decimal,
     pub stat_edge_order_size: Decimal,
     pub stat_edge_cooldown_seconds: f64,
+    /// Use the single best bookmaker line instead of the consensus.
+    pub stat_edge_line_shopping: bool,
+    /// Odds age (seconds) at which their confidence has halved.
+    pub stat_edge_confidence_half_life_seconds: f64,
+    /// Pairs-mode pairs as `slug_a:slug_b:hedge_ratio`.
+    pub stat_edge_pairs: Vec<String>,
+    pub stat_edge_pair_entry_z: f64,
+    pub stat_edge_pair_exit_z: f64,
+    pub stat_edge_pair_lookback: usize,
 
     // Feed configuration
     pub use_mock_feeds: bool,
 
+    // Paper execution
+    /// Simulate queue position for resting paper orders.
+    pub paper_queue_model: bool,
+    /// Fill resting paper orders only on liquidity better than their price.
+    pub paper_require_price_improvement: bool,
+    /// Simulate NO orders against the YES book's complement when NO is empty.
+    pub paper_synthesize_no_book: bool,
+    /// Adverse-fill stress mode for robustness runs.
+    pub paper_stress: bool,
+    pub paper_stress_seed: u64,
+    pub paper_stress_slippage_multiplier: Decimal,
+    pub paper_stress_partial_fill_prob: f64,
+    pub paper_stress_reject_prob: f64,
+
     // Live execution
     pub live_reconcile_interval_seconds: f64,
+    /// Position drift (USD notional) that triggers a reconciliation warning.
+    pub reconcile_drift_alert_usd: Decimal,
+    /// Orders younger than this are kept even if missing from open orders.
+    pub orphan_grace_secs: f64,
+    /// Window for rejecting near-duplicate live orders (0 disables).
+    pub order_dedupe_window_secs: f64,
+    /// Resting live orders allowed across all markets (0 = no cap).
+    pub max_open_orders: usize,
+    /// At the cap, cancel the order furthest from mid for a closer one.
+    pub open_order_eviction: bool,
+    /// Shrink or reject live buys whose estimated book-walk VWAP leaves
+    /// less than `RISK_MIN_EDGE` against the signal's probability.
+    pub live_impact_check: bool,
 
     // REST orderbook polling
     pub enable_rest_orderbook_polling: bool,
     pub rest_orderbook_poll_interval_seconds: f64,
     pub rest_orderbook_max_markets: usize,
     pub rest_orderbook_concurrency: usize,
+    /// Client-side request limit per API key (requests/sec).
+    pub api_rate_limit: u32,
+    /// Idle connections pooled per host (0 = match the poll concurrency).
+    pub http_pool_max_idle_per_host: usize,
+    /// Seconds before an idle pooled connection is closed.
+    pub http_pool_idle_timeout_secs: f64,
+    /// TCP keepalive probe interval in seconds (0 disables).
+    pub http_tcp_keepalive_secs: f64,
+    /// Poll busy markets (held, moving, tight) more often than quiet ones.
+    pub feed_priority_scheduling: bool,
+    /// Slowest per-market poll interval under priority scheduling.
+    pub feed_max_poll_interval_seconds: f64,
+    /// Most book polls per feed cycle under priority scheduling (0 = no cap).
+    pub feed_max_polls_per_cycle: usize,
+    /// Seconds between checks for closed/resolved markets.
+    pub market_status_check_secs: f64,
+    /// Seconds without a completed feed cycle before the feed is reported
+    /// stalled (0 disables).
+    pub feed_stall_secs: f64,
+    /// Restart the feed task when it stalls or dies.
+    pub feed_restart_on_stall: bool,
+    /// Consecutive all-failed feed cycles before polling backs off (0 = off).
+    pub feed_breaker_failures: u32,
+    /// Longest feed cycle interval while backing off.
+    pub feed_breaker_max_interval_secs: f64,
+
+    /// Executed signals kept with their book snapshot (0 disables).
+    pub signal_log_capacity: usize,
 
     // Logging
     pub log_level: String,
     pub log_json: bool,
-
-    // Health check
+    /// Log API request/response bodies (credentials redacted) at trace level.
+    pub log_api_bodies: bool,
+    /// Newline-delimited JSON file every fill is appended to (empty disables).
+    pub trade_log_path: String,
+    /// JSON run report written at shutdown (empty disables).
+    pub run_report_path: String,
+    /// CSV file the equity curve is sampled into (empty disables).
+    pub equity_export_path: String,
+    /// Seconds between equity export samples.
+    pub equity_export_interval_secs: f64,
+
+    // Performance metrics
+    /// Trading ticks between equity samples for Sharpe and Sortino.
+    pub perf_sample_every_ticks: u64,
+    /// Equity samples retained (0 disables the ratios).
+    pub perf_sample_capacity: usize,
+    /// Sample periods per year; zero derives it from the tick interval.
+    pub perf_annualization_factor: f64,
+
+    // Health check / status server
     pub health_host: String,
     pub health_port: u16,
+    /// Price levels per side served by `/book/{slug}`.
+    pub status_book_depth: usize,
+    /// Accept `POST /strategies/{name}/enable|disable` on the status server.
+    pub status_control_enabled: bool,
+    /// Bearer token the control routes require.
+    pub status_control_token: String,
+    /// Recent events kept for `/events` (0 disables).
+    pub event_log_capacity: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TradingMode {
     Paper,
     Live,
+    /// Full pipeline on live data; approved signals are logged, not sent.
+    Shadow,
 }
 
 impl FromStr for TradingMode {
@@ -94,6 +323,7 @@ impl FromStr for TradingMode {
         match s.to_lowercase().as_str() {
             "paper" => Ok(Self::Paper),
             "live" => Ok(Self::Live),
+            "shadow" => Ok(Self::Shadow),
             _ => Err(format!("Invalid trading mode: {s}")),
         }
     }
@@ -108,6 +338,7 @@ impl Settings {
         Self {
             pm_api_key_id: env_str("PM_API_KEY_ID", ""),
             pm_private_key: env_str("PM_PRIVATE_KEY", ""),
+            pm_extra_api_keys: env_csv("PM_EXTRA_API_KEYS"),
             pm_base_url: env_str("PM_BASE_URL", "https://api.polymarket.us"),
             pm_ws_url: env_str("PM_WS_URL", "wss://api.polymarket.us/v1/ws"),
 
@@ -116,56 +347,202 @@ impl Settings {
                 .unwrap_or(TradingMode::Paper),
             initial_balance: env_decimal("INITIAL_BALANCE", Decimal::new(1000, 0)),
             tick_interval_secs: env_f64("TICK_INTERVAL", 1.0),
+            warmup_min_updates: env_usize("WARMUP_MIN_UPDATES", 3) as u64,
+            warmup_secs: env_f64("WARMUP_SECS", 5.0),
+            warmup_max_gap_secs: env_f64("WARMUP_MAX_GAP_SECS", 30.0),
+            price_band_max_jump_pct: env_decimal("PRICE_BAND_MAX_JUMP_PCT", Decimal::ZERO),
+            price_band_confirm_polls: env_u32("PRICE_BAND_CONFIRM_POLLS", 3),
 
             market_slugs: env_csv("MARKET_SLUGS"),
             leagues: env_csv_default("LEAGUES", "nba,cbb"),
             market_types: env_csv_default("MARKET_TYPES", "aec"),
             min_liquidity: env_decimal("MIN_LIQUIDITY", Decimal::ZERO),
+            min_volume_24h: env_decimal("MIN_VOLUME_24H", Decimal::ZERO),
+            max_markets: env_usize("MAX_MARKETS", 0),
+            min_discovered_markets: env_usize("MIN_DISCOVERED_MARKETS", 1),
+            startup_retry_attempts: env_u32("STARTUP_RETRY_ATTEMPTS", 3),
+            startup_retry_backoff_secs: env_f64("STARTUP_RETRY_BACKOFF_SECS", 2.0),
 
             max_position_per_market: env_decimal("RISK_MAX_POSITION_PER_MARKET", Decimal::new(50, 0)),
+            max_position_pct_of_equity: env_decimal(
+                "RISK_MAX_POSITION_PCT_OF_EQUITY",
+                Decimal::ZERO,
+            ),
+            max_contracts_per_market: env_usize("RISK_MAX_CONTRACTS_PER_MARKET", 0) as i64,
             max_portfolio_exposure: env_decimal("RISK_MAX_PORTFOLIO_EXPOSURE", Decimal::new(350, 0)),
             max_portfolio_exposure_pct: env_decimal("RISK_MAX_PORTFOLIO_EXPOSURE_PCT", Decimal::new(35, 2)),
             max_daily_loss: env_decimal("RISK_MAX_DAILY_LOSS", Decimal::new(100, 0)),
             kelly_fraction: env_decimal("RISK_KELLY_FRACTION", Decimal::ONE),
             min_edge: env_decimal("RISK_MIN_EDGE", Decimal::new(2, 2)),
+            min_top_depth: i64::from(env_u32("MIN_TOP_DEPTH", 5)),
+            market_maker_max_depth_fraction: env_decimal(
+                "MARKET_MAKER_MAX_DEPTH_FRACTION",
+                Decimal::ZERO,
+            ),
+            market_maker_mid_ewma_alpha: env_decimal("MARKET_MAKER_MID_EWMA_ALPHA", Decimal::ONE),
+            market_maker_refresh_jitter_pct: env_f64("MARKET_MAKER_REFRESH_JITTER_PCT", 0.2),
+            market_maker_imbalance_size_factor: env_decimal(
+                "MARKET_MAKER_IMBALANCE_SIZE_FACTOR",
+                Decimal::ZERO,
+            ),
+            market_maker_refresh_dwell_secs: env_f64("MARKET_MAKER_REFRESH_DWELL_SECS", 0.0),
+            market_maker_refresh_hysteresis: env_decimal(
+                "MARKET_MAKER_REFRESH_HYSTERESIS",
+                Decimal::ZERO,
+            ),
+            market_maker_num_levels: env_usize("MARKET_MAKER_NUM_LEVELS", 1),
+            market_maker_level_spacing: env_decimal(
+                "MARKET_MAKER_LEVEL_SPACING",
+                Decimal::new(1, 2),
+            ),
+            market_maker_level_size_decay: env_decimal(
+                "MARKET_MAKER_LEVEL_SIZE_DECAY",
+                Decimal::ONE,
+            ),
+            arb_max_price_skew: env_decimal("ARB_MAX_PRICE_SKEW", Decimal::new(10, 2)),
+            book_wide_spread: env_decimal("BOOK_WIDE_SPREAD", Decimal::new(20, 2)),
+            book_quality_floor: env_decimal("BOOK_QUALITY_FLOOR", Decimal::new(5, 1)),
+            fee_rate: env_decimal("FEE_RATE", Decimal::new(1, 3)),
+            maker_fee_rate: env_decimal("MAKER_FEE_RATE", Decimal::ZERO),
+            fee_overrides: env_csv("FEE_OVERRIDES"),
             min_trade_size: env_decimal("RISK_MIN_TRADE_SIZE", Decimal::ONE),
             max_correlated_exposure: env_decimal("RISK_MAX_CORRELATED_EXPOSURE", Decimal::new(2500, 0)),
             max_positions: env_usize("RISK_MAX_POSITIONS", 10),
+            net_hedged_exposure: env_bool("RISK_NET_HEDGED_EXPOSURE", true),
             max_drawdown_pct: env_decimal("RISK_MAX_DRAWDOWN_PCT", Decimal::new(10, 2)),
             max_total_pnl_drawdown_pct_for_new_buys: env_decimal(
                 "RISK_MAX_TOTAL_PNL_DRAWDOWN_PCT_FOR_NEW_BUYS",
                 Decimal::new(5, 2),
             ),
+            max_var: env_decimal("RISK_MAX_VAR", Decimal::ZERO),
+            var_confidence: env_decimal("RISK_VAR_CONFIDENCE", Decimal::new(95, 2)),
+            max_position_age_secs: env_usize("RISK_MAX_POSITION_AGE_SECS", 0) as i64,
+            position_exit_retry_secs: env_usize("RISK_POSITION_EXIT_RETRY_SECS", 30) as i64,
+            max_orders_per_sec: env_u32("RISK_MAX_ORDERS_PER_SEC", 50),
+            max_orders_per_sec_per_market: env_u32("RISK_MAX_ORDERS_PER_SEC_PER_MARKET", 10),
+            warning_threshold_pct: env_decimal("RISK_WARNING_THRESHOLD_PCT", Decimal::new(70, 2)),
+            warning_size_scale: env_decimal("RISK_WARNING_SIZE_SCALE", Decimal::new(5, 1)),
+            panic_liquidate_drawdown_pct: env_decimal(
+                "RISK_PANIC_LIQUIDATE_DRAWDOWN_PCT",
+                Decimal::ZERO,
+            ),
+            drawdown_scaled_kelly: env_bool("RISK_DRAWDOWN_SCALED_KELLY", false),
+            conservative_marks: env_bool("RISK_CONSERVATIVE_MARKS", false),
+            mark_last_trade: env_bool("RISK_MARK_LAST_TRADE", false),
+            min_resolution_horizon_secs: env_f64("RISK_MIN_RESOLUTION_HORIZON_SECS", 0.0),
+            min_order_interval_secs: env_f64("RISK_MIN_ORDER_INTERVAL_SECS", 0.0),
+            flip_protection: env_bool("RISK_FLIP_PROTECTION", false),
+            min_confidence: env_csv("RISK_MIN_CONFIDENCE"),
+            cash_buffer_pct: env_decimal("RISK_CASH_BUFFER_PCT", DEFAULT_CASH_BUFFER_PCT),
+            size_ramp: env_csv("RISK_SIZE_RAMP"),
 
             enable_market_maker: env_bool("ENABLE_MARKET_MAKER", true),
             enable_live_arbitrage: env_bool("ENABLE_LIVE_ARBITRAGE", false),
             enable_statistical_edge: env_bool("ENABLE_STATISTICAL_EDGE", false),
+            enable_completeness_arb: env_bool("ENABLE_COMPLETENESS_ARB", true),
+            resolve_signal_conflicts: env_bool("RESOLVE_SIGNAL_CONFLICTS", true),
+            net_edge_gate: env_bool("NET_EDGE_GATE", false),
+            net_edge_min: env_decimal("NET_EDGE_MIN", Decimal::new(1, 2)),
+            net_edge_adverse_haircut: env_decimal("NET_EDGE_ADVERSE_HAIRCUT", Decimal::new(5, 3)),
 
             market_maker_order_size: env_decimal("MARKET_MAKER_ORDER_SIZE_USD", Decimal::new(10, 0)),
             market_maker_spread: env_decimal("MARKET_MAKER_SPREAD", Decimal::new(2, 2)),
+            market_maker_max_spread: env_decimal("MARKET_MAKER_MAX_SPREAD", Decimal::new(10, 2)),
+            market_maker_inventory_target: env_decimal(
+                "MARKET_MAKER_INVENTORY_TARGET",
+                Decimal::ZERO,
+            ),
+            market_maker_spread_safety_margin: env_decimal(
+                "MARKET_MAKER_SPREAD_SAFETY_MARGIN",
+                Decimal::new(2, 3),
+            ),
+            tick_size: env_decimal("TICK_SIZE", Decimal::new(1, 2)),
 
             live_arb_min_edge: env_decimal("LIVE_ARB_MIN_EDGE", Decimal::new(3, 2)),
             live_arb_order_size: env_decimal("LIVE_ARB_ORDER_SIZE", Decimal::new(10, 0)),
             live_arb_cooldown_seconds: env_f64("LIVE_ARB_COOLDOWN_SECONDS", 5.0),
+            live_arb_lag_window_seconds: env_f64("LIVE_ARB_LAG_WINDOW_SECONDS", 0.0),
+            live_arb_lag_logit_per_point: env_f64("LIVE_ARB_LAG_LOGIT_PER_POINT", 0.5),
+            live_arb_taker_margin: env_decimal("LIVE_ARB_TAKER_MARGIN", Decimal::ZERO),
+
+            completeness_arb_max_contracts: i64::from(env_u32("COMPLETENESS_ARB_MAX_CONTRACTS", 0)),
+            completeness_arb_cooldown_seconds: env_f64("COMPLETENESS_ARB_COOLDOWN_SECONDS", 30.0),
 
             stat_edge_min_edge: env_decimal("STAT_EDGE_MIN_EDGE", Decimal::new(2, 2)),
             stat_edge_order_size: env_decimal("STAT_EDGE_ORDER_SIZE", Decimal::new(10, 0)),
             stat_edge_cooldown_seconds: env_f64("STAT_EDGE_COOLDOWN_SECONDS", 10.0),
+            stat_edge_line_shopping: env_bool("STAT_EDGE_LINE_SHOPPING", false),
+            stat_edge_confidence_half_life_seconds: env_f64(
+                "STAT_EDGE_CONFIDENCE_HALF_LIFE_SECONDS",
+                300.0,
+            ),
+            stat_edge_pairs: env_csv("STAT_EDGE_PAIRS"),
+            stat_edge_pair_entry_z: env_f64("STAT_EDGE_PAIR_ENTRY_Z", 2.0),
+            stat_edge_pair_exit_z: env_f64("STAT_EDGE_PAIR_EXIT_Z", 0.5),
+            stat_edge_pair_lookback: env_usize("STAT_EDGE_PAIR_LOOKBACK", 30),
 
             use_mock_feeds: env_bool("USE_MOCK_FEEDS", true),
 
+            paper_queue_model: env_bool("PAPER_QUEUE_MODEL", false),
+            paper_require_price_improvement: env_bool("PAPER_REQUIRE_PRICE_IMPROVEMENT", false),
+            paper_synthesize_no_book: env_bool("PAPER_SYNTHESIZE_NO_BOOK", false),
+            paper_stress: env_bool("PAPER_STRESS", false),
+            paper_stress_seed: env_usize("PAPER_STRESS_SEED", 0) as u64,
+            paper_stress_slippage_multiplier: env_decimal(
+                "PAPER_STRESS_SLIPPAGE_MULTIPLIER",
+                Decimal::new(3, 0),
+            ),
+            paper_stress_partial_fill_prob: env_f64("PAPER_STRESS_PARTIAL_FILL_PROB", 0.2),
+            paper_stress_reject_prob: env_f64("PAPER_STRESS_REJECT_PROB", 0.05),
+
             live_reconcile_interval_seconds: env_f64("LIVE_RECONCILE_INTERVAL_SECONDS", 10.0),
+            reconcile_drift_alert_usd: env_decimal("RECONCILE_DRIFT_ALERT_USD", Decimal::ONE),
+            orphan_grace_secs: env_f64("ORPHAN_GRACE_SECS", 5.0),
+            order_dedupe_window_secs: env_f64("ORDER_DEDUPE_WINDOW_SECS", 2.0),
+            max_open_orders: env_usize("MAX_OPEN_ORDERS", 0),
+            open_order_eviction: env_bool("OPEN_ORDER_EVICTION", false),
+            live_impact_check: env_bool("LIVE_IMPACT_CHECK", false),
 
             enable_rest_orderbook_polling: env_bool("ENABLE_REST_ORDERBOOK_POLLING", false),
             rest_orderbook_poll_interval_seconds: env_f64("REST_ORDERBOOK_POLL_INTERVAL_SECONDS", 5.0),
             rest_orderbook_max_markets: env_usize("REST_ORDERBOOK_MAX_MARKETS", 50),
             rest_orderbook_concurrency: env_usize("REST_ORDERBOOK_CONCURRENCY", 5),
+            api_rate_limit: env_u32("API_RATE_LIMIT", 10),
+            http_pool_max_idle_per_host: env_usize("HTTP_POOL_MAX_IDLE_PER_HOST", 0),
+            http_pool_idle_timeout_secs: env_f64("HTTP_POOL_IDLE_TIMEOUT_SECS", 90.0),
+            http_tcp_keepalive_secs: env_f64("HTTP_TCP_KEEPALIVE_SECS", 30.0),
+            feed_priority_scheduling: env_bool("FEED_PRIORITY_SCHEDULING", false),
+            feed_max_poll_interval_seconds: env_f64("FEED_MAX_POLL_INTERVAL_SECONDS", 25.0),
+            feed_max_polls_per_cycle: env_usize("FEED_MAX_POLLS_PER_CYCLE", 0),
+            market_status_check_secs: env_f64("MARKET_STATUS_CHECK_SECS", 60.0),
+            feed_stall_secs: env_f64("FEED_STALL_SECS", 120.0),
+            feed_restart_on_stall: env_bool("FEED_RESTART_ON_STALL", false),
+            feed_breaker_failures: env_u32("FEED_BREAKER_FAILURES", 0),
+            feed_breaker_max_interval_secs: env_f64("FEED_BREAKER_MAX_INTERVAL_SECS", 60.0),
+
+            signal_log_capacity: env_usize("SIGNAL_LOG_CAPACITY", 200),
 
             log_level: env_str("LOG_LEVEL", "info"),
             log_json: env_bool("LOG_JSON", false),
+            log_api_bodies: env_bool("LOG_API_BODIES", false),
+            trade_log_path: env_str("TRADE_LOG_PATH", ""),
+            run_report_path: env_str("RUN_REPORT_PATH", ""),
+            equity_export_path: env_str("EQUITY_EXPORT_PATH", ""),
+            equity_export_interval_secs: env_f64("EQUITY_EXPORT_INTERVAL_SECS", 60.0),
+
+            perf_sample_every_ticks: env_usize("PERF_SAMPLE_EVERY_TICKS", 1) as u64,
+            perf_sample_capacity: env_usize(
+                "PERF_SAMPLE_CAPACITY",
+                DEFAULT_EQUITY_SAMPLE_CAPACITY,
+            ),
+            perf_annualization_factor: env_f64("PERF_ANNUALIZATION_FACTOR", 0.0),
 
             health_host: env_str("HEALTH_HOST", "0.0.0.0"),
             health_port: env_u16("HEALTH_PORT", 8080),
+            status_book_depth: env_usize("STATUS_BOOK_DEPTH", 5),
+            status_control_enabled: env_bool("STATUS_CONTROL_ENABLED", false),
+            status_control_token: env_str("STATUS_CONTROL_TOKEN", ""),
+            event_log_capacity: env_usize("EVENT_LOG_CAPACITY", 500),
         }
     }
 
@@ -182,6 +559,10 @@ impl Settings {
             }
         }
 
+        if self.pm_extra_api_keys.iter().any(|k| !k.contains(':')) {
+            errors.push("PM_EXTRA_API_KEYS entries must be key_id:private_key".to_string());
+        }
+
         if self.kelly_fraction <= Decimal::ZERO || self.kelly_fraction > Decimal::ONE {
             errors.push("RISK_KELLY_FRACTION must be in (0, 1]".to_string());
         }
@@ -190,6 +571,227 @@ impl Settings {
             errors.push("RISK_MIN_EDGE must be in [0, 1)".to_string());
         }
 
+        if self.panic_liquidate_drawdown_pct < Decimal::ZERO
+            || self.panic_liquidate_drawdown_pct >= Decimal::ONE
+        {
+            errors.push("RISK_PANIC_LIQUIDATE_DRAWDOWN_PCT must be in [0, 1)".to_string());
+        }
+
+        if self.max_position_pct_of_equity < Decimal::ZERO
+            || self.max_position_pct_of_equity > Decimal::ONE
+        {
+            errors.push("RISK_MAX_POSITION_PCT_OF_EQUITY must be in [0, 1]".to_string());
+        }
+
+        if self.book_quality_floor < Decimal::ZERO || self.book_quality_floor > Decimal::ONE {
+            errors.push("BOOK_QUALITY_FLOOR must be in [0, 1]".to_string());
+        }
+
+        if self.tick_size <= Decimal::ZERO || self.tick_size >= Decimal::ONE {
+            errors.push("TICK_SIZE must be in (0, 1)".to_string());
+        }
+
+        if self.fee_rate < Decimal::ZERO || self.fee_rate >= Decimal::ONE {
+            errors.push("FEE_RATE must be in [0, 1)".to_string());
+        }
+
+        if self.maker_fee_rate < Decimal::ZERO || self.maker_fee_rate >= Decimal::ONE {
+            errors.push("MAKER_FEE_RATE must be in [0, 1)".to_string());
+        }
+        if self.fee_overrides.iter().any(|e| parse_fee_override(e).is_none()) {
+            errors.push("FEE_OVERRIDES entries must be slug:rate, rate in [0, 1)".to_string());
+        }
+
+        if self.market_maker_max_depth_fraction < Decimal::ZERO
+            || self.market_maker_max_depth_fraction > Decimal::ONE
+        {
+            errors.push("MARKET_MAKER_MAX_DEPTH_FRACTION must be in [0, 1]".to_string());
+        }
+
+        if self.market_maker_mid_ewma_alpha <= Decimal::ZERO
+            || self.market_maker_mid_ewma_alpha > Decimal::ONE
+        {
+            errors.push("MARKET_MAKER_MID_EWMA_ALPHA must be in (0, 1]".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.market_maker_refresh_jitter_pct) {
+            errors.push("MARKET_MAKER_REFRESH_JITTER_PCT must be in [0, 1]".to_string());
+        }
+        if self.market_maker_imbalance_size_factor < Decimal::ZERO
+            || self.market_maker_imbalance_size_factor > Decimal::ONE
+        {
+            errors.push("MARKET_MAKER_IMBALANCE_SIZE_FACTOR must be in [0, 1]".to_string());
+        }
+        if self.market_maker_refresh_dwell_secs < 0.0 {
+            errors.push("MARKET_MAKER_REFRESH_DWELL_SECS must be >= 0".to_string());
+        }
+        if self.market_maker_refresh_hysteresis < Decimal::ZERO {
+            errors.push("MARKET_MAKER_REFRESH_HYSTERESIS must be >= 0".to_string());
+        }
+        if self.market_maker_num_levels == 0 {
+            errors.push("MARKET_MAKER_NUM_LEVELS must be >= 1".to_string());
+        }
+        if self.market_maker_num_levels > 1 && self.market_maker_level_spacing <= Decimal::ZERO {
+            errors.push("MARKET_MAKER_LEVEL_SPACING must be > 0 with several levels".to_string());
+        }
+        if self.market_maker_level_size_decay <= Decimal::ZERO
+            || self.market_maker_level_size_decay > Decimal::ONE
+        {
+            errors.push("MARKET_MAKER_LEVEL_SIZE_DECAY must be in (0, 1]".to_string());
+        }
+
+        if self.net_edge_adverse_haircut < Decimal::ZERO {
+            errors.push("NET_EDGE_ADVERSE_HAIRCUT must be >= 0".to_string());
+        }
+
+        if self.market_maker_spread_safety_margin < Decimal::ZERO {
+            errors.push("MARKET_MAKER_SPREAD_SAFETY_MARGIN must be >= 0".to_string());
+        }
+
+        if self.stat_edge_confidence_half_life_seconds < 0.0 {
+            errors.push("STAT_EDGE_CONFIDENCE_HALF_LIFE_SECONDS must be >= 0".to_string());
+        }
+
+        if self.stat_edge_pairs.iter().any(|p| PairConfig::parse(p).is_none()) {
+            errors.push("STAT_EDGE_PAIRS entries must be slug_a:slug_b:hedge_ratio".to_string());
+        }
+
+        if self.stat_edge_pair_exit_z < 0.0
+            || self.stat_edge_pair_exit_z >= self.stat_edge_pair_entry_z
+        {
+            errors.push("STAT_EDGE_PAIR_EXIT_Z must be in [0, STAT_EDGE_PAIR_ENTRY_Z)".to_string());
+        }
+
+        if self.paper_stress_slippage_multiplier < Decimal::ONE {
+            errors.push("PAPER_STRESS_SLIPPAGE_MULTIPLIER must be >= 1".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.paper_stress_partial_fill_prob)
+            || !(0.0..=1.0).contains(&self.paper_stress_reject_prob)
+        {
+            errors.push(
+                "PAPER_STRESS_PARTIAL_FILL_PROB and PAPER_STRESS_REJECT_PROB must be in [0, 1]"
+                    .to_string(),
+            );
+        }
+
+        if self.price_band_max_jump_pct < Decimal::ZERO {
+            errors.push("PRICE_BAND_MAX_JUMP_PCT must be >= 0".to_string());
+        }
+
+        if self.market_status_check_secs <= 0.0 {
+            errors.push("MARKET_STATUS_CHECK_SECS must be > 0".to_string());
+        }
+
+        if self.feed_max_poll_interval_seconds < self.rest_orderbook_poll_interval_seconds {
+            errors.push(
+                "FEED_MAX_POLL_INTERVAL_SECONDS must be >= REST_ORDERBOOK_POLL_INTERVAL_SECONDS"
+                    .to_string(),
+            );
+        }
+
+        if self.feed_stall_secs < 0.0
+            || (self.feed_stall_secs > 0.0
+                && self.feed_stall_secs <= self.rest_orderbook_poll_interval_seconds)
+        {
+            errors.push(
+                "FEED_STALL_SECS must be 0 or > REST_ORDERBOOK_POLL_INTERVAL_SECONDS".to_string(),
+            );
+        }
+        if self.feed_breaker_failures > 0
+            && (self.feed_breaker_max_interval_secs < self.rest_orderbook_poll_interval_seconds
+                || (self.feed_stall_secs > 0.0
+                    && self.feed_breaker_max_interval_secs >= self.feed_stall_secs))
+        {
+            errors.push(
+                "FEED_BREAKER_MAX_INTERVAL_SECS must be >= REST_ORDERBOOK_POLL_INTERVAL_SECONDS \
+                 and < FEED_STALL_SECS"
+                    .to_string(),
+            );
+        }
+
+        if self.api_rate_limit == 0 || self.rest_orderbook_concurrency == 0 {
+            errors.push("API_RATE_LIMIT and REST_ORDERBOOK_CONCURRENCY must be > 0".to_string());
+        }
+
+        if self.http_pool_idle_timeout_secs <= self.rest_orderbook_poll_interval_seconds {
+            errors.push(
+                "HTTP_POOL_IDLE_TIMEOUT_SECS must exceed REST_ORDERBOOK_POLL_INTERVAL_SECONDS \
+                 so pooled connections survive between polls"
+                    .to_string(),
+            );
+        }
+
+        if self.min_resolution_horizon_secs < 0.0 {
+            errors.push("RISK_MIN_RESOLUTION_HORIZON_SECS must be >= 0".to_string());
+        }
+        if self.min_order_interval_secs < 0.0 {
+            errors.push("RISK_MIN_ORDER_INTERVAL_SECS must be >= 0".to_string());
+        }
+        if self.cash_buffer_pct <= Decimal::ZERO || self.cash_buffer_pct > Decimal::ONE {
+            errors.push("RISK_CASH_BUFFER_PCT must be in (0, 1]".to_string());
+        }
+        if self.min_confidence.iter().any(|e| parse_confidence_floor(e).is_none()) {
+            errors.push(
+                "RISK_MIN_CONFIDENCE entries must be strategy:floor with floor in [0, 1]"
+                    .to_string(),
+            );
+        }
+        if self.size_ramp.iter().any(|e| parse_size_ramp(e).is_none()) {
+            errors.push(
+                "RISK_SIZE_RAMP entries must be strategy:initial_scale:trades_to_full\
+                 [:secs_to_full] with initial_scale in (0, 1]"
+                    .to_string(),
+            );
+        }
+
+        if self.perf_sample_every_ticks == 0 || self.perf_annualization_factor < 0.0 {
+            errors.push(
+                "PERF_SAMPLE_EVERY_TICKS must be > 0 and PERF_ANNUALIZATION_FACTOR >= 0"
+                    .to_string(),
+            );
+        }
+        if !self.equity_export_path.is_empty() && self.equity_export_interval_secs <= 0.0 {
+            errors.push("EQUITY_EXPORT_INTERVAL_SECS must be > 0".to_string());
+        }
+
+        if self.startup_retry_attempts == 0 || self.startup_retry_backoff_secs < 0.0 {
+            errors.push(
+                "STARTUP_RETRY_ATTEMPTS must be > 0 and STARTUP_RETRY_BACKOFF_SECS >= 0"
+                    .to_string(),
+            );
+        }
+
+        if self.http_tcp_keepalive_secs < 0.0 {
+            errors.push("HTTP_TCP_KEEPALIVE_SECS must be >= 0".to_string());
+        }
+
+        if self.live_arb_lag_window_seconds < 0.0 || self.live_arb_lag_logit_per_point <= 0.0 {
+            errors.push(
+                "LIVE_ARB_LAG_WINDOW_SECONDS must be >= 0 and LIVE_ARB_LAG_LOGIT_PER_POINT > 0"
+                    .to_string(),
+            );
+        }
+        if self.live_arb_taker_margin < Decimal::ZERO {
+            errors.push("LIVE_ARB_TAKER_MARGIN must be >= 0".to_string());
+        }
+
+        if self.warmup_secs < 0.0 || self.warmup_max_gap_secs < 0.0 {
+            errors.push("WARMUP_SECS and WARMUP_MAX_GAP_SECS must be >= 0".to_string());
+        }
+
+        if self.max_var < Decimal::ZERO {
+            errors.push("RISK_MAX_VAR must be >= 0".to_string());
+        }
+
+        if self.var_confidence <= Decimal::ZERO || self.var_confidence >= Decimal::ONE {
+            errors.push("RISK_VAR_CONFIDENCE must be in (0, 1)".to_string());
+        }
+
+        if self.status_control_enabled && self.status_control_token.trim().is_empty() {
+            errors.push("STATUS_CONTROL_ENABLED requires STATUS_CONTROL_TOKEN".to_string());
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -233,6 +835,13 @@ fn env_usize(key: &str, default: usize) -> usize {
         .unwrap_or(default)
 }
 
+fn env_u32(key: &str, default: u32) -> u32 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 fn env_u16(key: &str, default: u16) -> u16 {
     std::env::var(key)
         .ok()
@@ -0,0 +1,172 @@
+//! Prioritized poll scheduling for the REST market feed.
+//!
+//! Polling every market at one interval spends as much rate limit on a
+//! dead market as on a live one. The scheduler gives each market a score
+//! in [0, 1] and an interval between `min_interval` (score 1) and
+//! `max_interval` (score 0):
+//!
+//! - a held position scores 1;
+//! - recent movement scores the EWMA of absolute mid changes per poll
+//!   relative to `active_move`;
+//! - a tight spread scores up to 0.5, falling to 0 at `wide_spread`.
+//!
+//! The highest applies. Each cycle `due_markets` returns the markets whose
+//! interval has elapsed, most overdue first, capped at the per-cycle budget.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::clock::{RealClock, SharedClock};
+use crate::data::orderbook::TopOfBook;
+use crate::state::state_manager::StateManager;
+use crate::util::dec::to_f64;
+
+/// Weight of the newest mid change in the movement EWMA.
+const MOVE_EWMA_ALPHA: Decimal = Decimal::from_parts(3, 0, 0, false, 1);
+
+/// Scheduler configuration.
+#[derive(Debug, Clone)]
+pub struct FeedSchedulerConfig {
+    /// Interval for the most active markets (and the feed's cycle length).
+    pub min_interval: Duration,
+    /// Interval for markets with no activity at all.
+    pub max_interval: Duration,
+    /// Average mid change per poll that counts as fully active.
+    pub active_move: Decimal,
+    /// YES spread at or above which the spread adds no priority.
+    pub wide_spread: Decimal,
+    /// Most polls issued per cycle. Zero is unlimited.
+    pub max_polls_per_cycle: usize,
+}
+
+impl Default for FeedSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::seconds(5),
+            // Under the 30s staleness threshold, so quiet markets never
+            // read as stale.
+            max_interval: Duration::seconds(25),
+            active_move: Decimal::new(1, 2),  // 0.01
+            wide_spread: Decimal::new(20, 2), // 0.20
+            max_polls_per_cycle: 0,
+        }
+    }
+}
+
+/// What the scheduler remembers about one market.
+#[derive(Debug, Clone, Default)]
+struct MarketActivity {
+    last_mid: Option<Decimal>,
+    move_ewma: Decimal,
+    spread: Option<Decimal>,
+    last_polled: Option<DateTime<Utc>>,
+}
+
+/// Per-market poll intervals driven by activity and held positions.
+pub struct FeedScheduler {
+    config: FeedSchedulerConfig,
+    state: StateManager,
+    markets: HashMap<String, MarketActivity>,
+    clock: SharedClock,
+}
+
+impl FeedScheduler {
+    pub fn new(config: FeedSchedulerConfig, state: StateManager) -> Self {
+        Self {
+            config,
+            state,
+            markets: HashMap::new(),
+            clock: RealClock::shared(),
+        }
+    }
+
+    /// Replace the time source (tests inject a `MockClock`).
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    pub fn cycle_interval(&self) -> Duration {
+        self.config.min_interval
+    }
+
+    /// Record a polled book's top so movement and spread feed the score.
+    pub fn observe(&mut self, slug: &str, top: &TopOfBook) {
+        let activity = self.markets.entry(slug.to_string()).or_default();
+        if let Some(mid) = top.yes_mid() {
+            if let Some(last) = activity.last_mid {
+                activity.move_ewma = MOVE_EWMA_ALPHA * (mid - last).abs()
+                    + (Decimal::ONE - MOVE_EWMA_ALPHA) * activity.move_ewma;
+            }
+            activity.last_mid = Some(mid);
+        }
+        activity.spread = top.yes_spread();
+    }
+
+    /// Priority in [0, 1]; 1 polls at `min_interval`.
+    pub fn score(&self, slug: &str) -> Decimal {
+        let held = self
+            .state
+            .get_position(slug)
+            .is_some_and(|p| p.quantity > 0);
+        if held {
+            return Decimal::ONE;
+        }
+        let Some(activity) = self.markets.get(slug) else {
+            return Decimal::ZERO;
+        };
+
+        let movement = if self.config.active_move > Decimal::ZERO {
+            (activity.move_ewma / self.config.active_move).min(Decimal::ONE)
+        } else {
+            Decimal::ZERO
+        };
+        let spread = match activity.spread {
+            Some(s) if self.config.wide_spread > Decimal::ZERO => {
+                let tightness = Decimal::ONE - s / self.config.wide_spread;
+                tightness.max(Decimal::ZERO).min(Decimal::ONE) / Decimal::TWO
+            }
+            _ => Decimal::ZERO,
+        };
+        movement.max(spread)
+    }
+
+    /// Poll interval for `slug` at its current score.
+    pub fn poll_interval(&self, slug: &str) -> Duration {
+        let min = self.config.min_interval;
+        let range = (self.config.max_interval - min).max(Duration::zero());
+        let quiet = to_f64(Decimal::ONE - self.score(slug));
+        min + Duration::milliseconds((range.num_milliseconds() as f64 * quiet) as i64)
+    }
+
+    /// Markets among `slugs` due for a poll now, most overdue first and
+    /// capped at `max_polls_per_cycle`. Returned markets count as polled.
+    pub fn due_markets(&mut self, slugs: &[String]) -> Vec<String> {
+        let now = self.clock.now();
+        let mut due: Vec<(f64, &String)> = slugs
+            .iter()
+            .filter_map(|slug| {
+                let last = self.markets.get(slug).and_then(|a| a.last_polled);
+                let Some(last) = last else {
+                    // Never polled: ahead of everything else.
+                    return Some((f64::MAX, slug));
+                };
+                let interval = self.poll_interval(slug).num_milliseconds().max(1) as f64;
+                let overdue = (now - last).num_milliseconds() as f64 / interval;
+                (overdue >= 1.0).then_some((overdue, slug))
+            })
+            .collect();
+        due.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        if self.config.max_polls_per_cycle > 0 {
+            due.truncate(self.config.max_polls_per_cycle);
+        }
+
+        let due: Vec<String> = due.into_iter().map(|(_, slug)| slug.clone()).collect();
+        for slug in &due {
+            self.markets.entry(slug.clone()).or_default().last_polled = Some(now);
+        }
+        due
+    }
+}
@@ -0,0 +1,101 @@
+//! Detecting a wedged market feed task.
+//!
+//! The feed bumps its cycle counter after every completed poll cycle. If
+//! the task panics, or a poll future hangs, the counter stops moving while
+//! the trading loop keeps running on increasingly stale books. The trading
+//! loop checks the counter every tick; once it has not moved for
+//! `stall_after` the feed is reported stalled (once per stall), so the
+//! caller can alert and restart it.
+
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+/// Result of one heartbeat check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedHealth {
+    /// The counter moved within `stall_after`.
+    Healthy,
+    /// No completed cycle for `stalled_for`. `first` is set on the check
+    /// that crossed the threshold, so the caller alerts once per stall.
+    Stalled { stalled_for: Duration, first: bool },
+}
+
+/// Tracks the feed's cycle counter between checks.
+#[derive(Debug, Clone)]
+pub struct FeedWatchdog {
+    stall_after: Duration,
+    last_cycles: u64,
+    last_progress: Instant,
+    reported: bool,
+}
+
+impl FeedWatchdog {
+    /// A `stall_after` of zero never reports a stall.
+    pub fn new(stall_after: Duration, now: Instant) -> Self {
+        Self {
+            stall_after,
+            last_cycles: 0,
+            last_progress: now,
+            reported: false,
+        }
+    }
+
+    /// Compare the current cycle count with the last one seen.
+    pub fn check(&mut self, cycles: u64, now: Instant) -> FeedHealth {
+        if cycles != self.last_cycles {
+            self.last_cycles = cycles;
+            self.last_progress = now;
+            self.reported = false;
+            return FeedHealth::Healthy;
+        }
+        let stalled_for = now.saturating_duration_since(self.last_progress);
+        if self.stall_after.is_zero() || stalled_for < self.stall_after {
+            return FeedHealth::Healthy;
+        }
+        let first = !self.reported;
+        self.reported = true;
+        FeedHealth::Stalled { stalled_for, first }
+    }
+
+    /// Start a fresh grace period, e.g. after restarting the feed.
+    pub fn reset(&mut self, now: Instant) {
+        self.last_progress = now;
+        self.reported = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_stall_once_until_counter_moves() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut watchdog = FeedWatchdog::new(Duration::from_secs(30), start);
+
+        assert_eq!(watchdog.check(1, at(5)), FeedHealth::Healthy);
+        assert_eq!(watchdog.check(1, at(34)), FeedHealth::Healthy);
+        assert_eq!(
+            watchdog.check(1, at(35)),
+            FeedHealth::Stalled { stalled_for: Duration::from_secs(30), first: true }
+        );
+        assert_eq!(
+            watchdog.check(1, at(50)),
+            FeedHealth::Stalled { stalled_for: Duration::from_secs(45), first: false }
+        );
+
+        // A restart starts a new grace period; progress clears the stall.
+        watchdog.reset(at(50));
+        assert_eq!(watchdog.check(1, at(60)), FeedHealth::Healthy);
+        assert_eq!(watchdog.check(2, at(90)), FeedHealth::Healthy);
+        assert!(matches!(
+            watchdog.check(2, at(120)),
+            FeedHealth::Stalled { first: true, .. }
+        ));
+
+        let mut disabled = FeedWatchdog::new(Duration::ZERO, start);
+        assert_eq!(disabled.check(0, at(3600)), FeedHealth::Healthy);
+    }
+}
@@ -4,16 +4,30 @@
 //! and feeds it into the `OrderBookTracker` and `StateManager`.
 //! Runs as a background tokio task that can be cancelled via a shutdown
 //! signal (`tokio::sync::Notify`).
+//!
+//! Every `status_check_interval` it also re-reads each market's status,
+//! as many at once as book polls.
+//! A closed market is disabled in `StateManager` (the strategy engine then
+//! cancels its orders) and no longer polled; a resolved one has its outcome
+//! recorded there for the executor to settle.
+//!
+//! A cycle in which every book poll fails counts against a `PollBreaker`;
+//! once it opens, cycles back off exponentially until a poll succeeds.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::Notify;
 use tracing::{info, warn, debug};
 use chrono::Utc;
 
 use crate::api::client::PolymarketClient;
+use crate::data::models::Side;
+use crate::data::feed_scheduler::FeedScheduler;
 use crate::data::orderbook::OrderBookTracker;
-use crate::state::state_manager::{StateManager, MarketState};
+use crate::data::poll_breaker::{BreakerTransition, PollBreaker, PollBreakerConfig};
+use crate::state::event_log::EventKind;
+use crate::state::state_manager::{MarketState, PriceBandCheck, StateManager};
 
 // =============================================================================
 // Configuration
@@ -30,6 +44,12 @@ pub struct MarketFeedConfig {
 
     /// Warn if a market hasn't been updated for this long.
     pub staleness_threshold: Duration,
+
+    /// How often to check whether markets have closed or resolved.
+    pub status_check_interval: Duration,
+
+    /// Back-off after consecutive cycles in which every poll failed.
+    pub breaker: PollBreakerConfig,
 }
 
 impl Default for MarketFeedConfig {
@@ -38,6 +58,8 @@ impl Default for MarketFeedConfig {
             poll_interval: Duration::from_secs(5),
             max_concurrency: 5,
             staleness_threshold: Duration::from_secs(30),
+            status_check_interval: Duration::from_secs(60),
+            breaker: PollBreakerConfig::default(),
         }
     }
 }
@@ -54,6 +76,12 @@ pub struct MarketFeed {
     state: StateManager,
     config: MarketFeedConfig,
     shutdown: Arc<Notify>,
+    /// Polls busy markets more often than quiet ones when set.
+    scheduler: Option<FeedScheduler>,
+    last_status_check: Option<Instant>,
+    /// Completed poll cycles, readable after the feed task is spawned.
+    cycles: Arc<AtomicU64>,
+    breaker: PollBreaker,
 }
 
 impl MarketFeed {
@@ -68,11 +96,29 @@ impl MarketFeed {
             client,
             orderbook,
             state,
+            breaker: PollBreaker::new(config.breaker.clone()),
             config,
             shutdown,
+            scheduler: None,
+            last_status_check: None,
+            cycles: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Poll markets on per-market intervals instead of all every cycle.
+    /// The cycle length becomes the scheduler's shortest interval.
+    pub fn with_scheduler(mut self, scheduler: FeedScheduler) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Count completed poll cycles on a shared counter, continuing from its
+    /// value, so a restarted feed keeps the heartbeat its watchdog reads.
+    pub fn with_cycle_counter(mut self, cycles: Arc<AtomicU64>) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
     /// Run the feed loop. This consumes `self` and runs until the shutdown
     /// signal fires. Intended to be spawned as a background tokio task:
     ///
@@ -80,7 +126,7 @@ impl MarketFeed {
     /// let feed = MarketFeed::new(client, ob, state, config, shutdown);
     /// tokio::spawn(feed.run());
     /// ```
-    pub async fn run(self) {
+    pub async fn run(mut self) {
         info!(
             poll_interval_ms = self.config.poll_interval.as_millis() as u64,
             max_concurrency = self.config.max_concurrency,
@@ -88,7 +134,12 @@ impl MarketFeed {
             "MarketFeed starting"
         );
 
-        let mut cycle: u64 = 0;
+        let mut cycle = self.cycles.load(Ordering::Relaxed);
+        let cycle_interval = self
+            .scheduler
+            .as_ref()
+            .and_then(|s| s.cycle_interval().to_std().ok())
+            .unwrap_or(self.config.poll_interval);
 
         loop {
             tokio::select! {
@@ -96,9 +147,11 @@ impl MarketFeed {
                     info!("MarketFeed received shutdown signal, stopping");
                     break;
                 }
-                _ = tokio::time::sleep(self.config.poll_interval) => {
+                _ = tokio::time::sleep(self.breaker.interval(cycle_interval)) => {
                     cycle += 1;
-                    self.poll_cycle(cycle).await;
+                    let all_failed = self.poll_cycle(cycle).await;
+                    self.record_poll_outcome(cycle, all_failed, cycle_interval);
+                    self.cycles.store(cycle, Ordering::Relaxed);
                 }
             }
         }
@@ -106,6 +159,33 @@ impl MarketFeed {
         info!(total_cycles = cycle, "MarketFeed stopped");
     }
 
+    /// Feed a cycle's outcome to the breaker, alerting when it opens and
+    /// when polling recovers.
+    fn record_poll_outcome(&mut self, cycle: u64, all_failed: bool, base: Duration) {
+        let transition = if all_failed {
+            self.breaker.record_failure()
+        } else {
+            self.breaker.record_success()
+        };
+        let failures = self.breaker.consecutive_failures();
+        match transition {
+            BreakerTransition::Opened => {
+                let interval_ms = self.breaker.interval(base).as_millis() as u64;
+                warn!(cycle, failures, interval_ms, "Order book polls keep failing, backing off");
+                self.state.events().push(
+                    EventKind::FeedGap,
+                    None,
+                    format!("Feed backing off after {failures} failed cycles"),
+                    serde_json::json!({ "cycle": cycle, "interval_ms": interval_ms }),
+                );
+            }
+            BreakerTransition::Closed => {
+                info!(cycle, "Order book polls recovered, resuming normal cadence");
+            }
+            BreakerTransition::Unchanged => {}
+        }
+    }
+
     /// Spawn the feed as a background tokio task. Returns the `JoinHandle`.
     pub fn spawn(self) -> tokio::task::JoinHandle<()> {
         tokio::spawn(self.run())
@@ -116,8 +196,9 @@ impl MarketFeed {
     // =========================================================================
 
     /// Execute one full poll cycle: fetch order books for all tracked
-    /// markets with bounded concurrency, update trackers.
-    async fn poll_cycle(&self, cycle: u64) {
+    /// markets with bounded concurrency, update trackers. Returns whether
+    /// every poll failed.
+    async fn poll_cycle(&mut self, cycle: u64) -> bool {
         // Collect slugs to poll: union of orderbook-tracked and state-tracked.
         let mut slugs = self.orderbook.tracked_markets();
         for market in self.state.get_all_markets() {
@@ -126,9 +207,23 @@ impl MarketFeed {
             }
         }
 
+        let status_due = self
+            .last_status_check
+            .is_none_or(|t| t.elapsed() >= self.config.status_check_interval);
+        if status_due {
+            self.last_status_check = Some(Instant::now());
+            self.check_market_status(&slugs).await;
+        }
+        // A closed market has no book worth polling.
+        slugs.retain(|slug| !self.state.is_market_closed(slug));
+
+        if let Some(ref mut scheduler) = self.scheduler {
+            slugs = scheduler.due_markets(&slugs);
+        }
+
         if slugs.is_empty() {
             debug!(cycle, "No markets to poll");
-            return;
+            return false;
         }
 
         debug!(cycle, market_count = slugs.len(), "Polling order books");
@@ -165,17 +260,46 @@ impl MarketFeed {
         for handle in handles {
             match handle.await {
                 Ok((slug, Ok(book))) => {
+                    // Hold back a garbage tick on either side until it
+                    // proves to be a real move. Both sides are screened so
+                    // each band sees every poll.
+                    let polled = OrderBookTracker::compute_top(&book);
+                    let checks = [(Side::Yes, polled.yes_mid()), (Side::No, polled.no_mid())]
+                        .map(|(side, mid)| self.state.check_price_band(&slug, side, mid));
+                    if let Some(PriceBandCheck::Rejected {
+                        side,
+                        last_good_mid,
+                        mid,
+                        anomalies,
+                    }) = checks.into_iter().find(|c| *c != PriceBandCheck::Accepted)
+                    {
+                        warn!(
+                            market = %slug,
+                            side = %side,
+                            last_good_mid = %last_good_mid,
+                            mid = %mid,
+                            anomalies,
+                            "Mid jumped outside price band, keeping previous book"
+                        );
+                        success_count += 1;
+                        continue;
+                    }
+
                     // Update OrderBookTracker.
                     let top = self.orderbook.get_top(&slug);
                     self.orderbook.update(book);
 
                     // Update StateManager market state from the new top-of-book.
                     if let Some(new_top) = self.orderbook.get_top(&slug) {
+                        if let Some(ref mut scheduler) = self.scheduler {
+                            scheduler.observe(&slug, &new_top);
+                        }
+
                         // Preserve existing title if available, otherwise use slug.
-                        let title = self
-                            .state
-                            .get_market(&slug)
-                            .map(|m| m.title)
+                        let existing = self.state.get_market(&slug);
+                        let title = existing
+                            .as_ref()
+                            .map(|m| m.title.clone())
                             .unwrap_or_else(|| slug.clone());
 
                         self.state.update_market(MarketState {
@@ -185,6 +309,11 @@ impl MarketFeed {
                             yes_ask: new_top.yes_best_ask,
                             no_bid: new_top.no_best_bid,
                             no_ask: new_top.no_best_ask,
+                            yes_bid_depth: Some(new_top.yes_best_bid_qty),
+                            yes_ask_depth: Some(new_top.yes_best_ask_qty),
+                            resolution_date: existing.and_then(|m| m.resolution_date),
+                            tick_size: None,
+                            min_order_size: None,
                             last_updated: Utc::now(),
                         });
 
@@ -232,6 +361,12 @@ impl MarketFeed {
                 errors = error_count,
                 "Poll cycle: ALL order books failed"
             );
+            self.state.events().push(
+                EventKind::FeedGap,
+                None,
+                format!("All {error_count} order book polls failed"),
+                serde_json::json!({ "cycle": cycle }),
+            );
         } else if error_count > 0 {
             info!(
                 cycle,
@@ -249,8 +384,89 @@ impl MarketFeed {
         }
 
         // Staleness check (only every 12 cycles / ~60s to avoid log spam).
-        if cycle % 12 == 0 {
+        if cycle.is_multiple_of(12) {
             self.check_staleness();
+            let connections = self.client.connection_stats();
+            debug!(
+                cycle,
+                peak_in_flight = connections.peak_in_flight,
+                responses = connections.responses,
+                reuse_rate = connections.reuse_rate(),
+                "Feed connection stats"
+            );
+        }
+
+        error_count > 0 && success_count == 0
+    }
+
+    // =========================================================================
+    // Market Status
+    // =========================================================================
+
+    /// Re-read the status of every market not yet resolved, with the same
+    /// bounded concurrency as book polls. Closed markets stay in the check
+    /// until their resolution is known.
+    async fn check_market_status(&self, slugs: &[String]) {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.config.max_concurrency));
+        let mut handles = Vec::with_capacity(slugs.len());
+
+        for slug in slugs {
+            if self.state.market_resolution(slug).is_some() {
+                continue;
+            }
+            let client = Arc::clone(&self.client);
+            let sem = Arc::clone(&semaphore);
+            let slug = slug.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = match sem.acquire().await {
+                    Ok(p) => p,
+                    Err(_) => return (slug, Err("Semaphore closed".to_string())),
+                };
+                let market = client.get_market(&slug).await.map_err(|e| e.to_string());
+                (slug, market)
+            }));
+        }
+
+        for handle in handles {
+            let (slug, market) = match handle.await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!(error = %e, "Task join error during market status check");
+                    continue;
+                }
+            };
+            let slug = &slug;
+            let market = match market {
+                Ok(market) => market,
+                Err(e) => {
+                    debug!(market = %slug, error = %e, "Market status check failed");
+                    continue;
+                }
+            };
+            self.state.set_market_metadata(slug, market.metadata());
+            if !market.is_closed() {
+                // Resolution dates move (overtime, rescheduling); keep the
+                // risk manager's horizon check current.
+                self.state.set_resolution_date(slug, market.resolution_date);
+                continue;
+            }
+
+            let yes_won = market.yes_won();
+            if !self.state.is_market_closed(slug) {
+                warn!(
+                    market = %slug,
+                    status = ?market.status,
+                    "Market closed, stopping polling and trading"
+                );
+            }
+            if let Some(yes_won) = yes_won {
+                info!(
+                    market = %slug,
+                    outcome = if yes_won { "YES" } else { "NO" },
+                    "Market resolved"
+                );
+            }
+            self.state.mark_market_closed(slug, yes_won);
         }
     }
 
@@ -275,6 +491,12 @@ impl MarketFeed {
                     threshold_secs = threshold.num_seconds(),
                     "Stale market data detected"
                 );
+                self.state.events().push(
+                    EventKind::FeedGap,
+                    Some(&market.market_slug),
+                    format!("No update for {}s", age.num_seconds()),
+                    serde_json::Value::Null,
+                );
             }
         }
     }
@@ -0,0 +1,78 @@
+//! Discovery-time filter for "markets of interest".
+//!
+//! Drops markets too thin to work (below the 24h volume or liquidity
+//! thresholds), then ranks the survivors by liquidity so a market cap keeps
+//! the deepest books. A missing volume/liquidity figure counts as zero.
+
+#![allow(dead_code)]
+
+use rust_decimal::Decimal;
+
+use super::models::Market;
+
+/// Thresholds applied to discovered markets. Zero disables a check.
+#[derive(Debug, Clone, Default)]
+pub struct MarketFilterConfig {
+    pub min_volume_24h: Decimal,
+    pub min_liquidity: Decimal,
+    /// Keep at most this many markets after ranking. Zero keeps all.
+    pub max_markets: usize,
+}
+
+/// Outcome of filtering, with counts of why markets were dropped.
+#[derive(Debug, Clone, Default)]
+pub struct MarketSelection<'a> {
+    /// Markets to trade, most liquid first.
+    pub selected: Vec<&'a Market>,
+    pub below_volume: usize,
+    pub below_liquidity: usize,
+    /// Passed the thresholds but fell outside `max_markets`.
+    pub over_limit: usize,
+}
+
+impl MarketSelection<'_> {
+    pub fn filtered_out(&self) -> usize {
+        self.below_volume + self.below_liquidity + self.over_limit
+    }
+}
+
+/// Filter `markets` by the thresholds in `config` and rank by liquidity
+/// (then 24h volume, then slug for a stable order).
+pub fn select_markets<'a, I>(markets: I, config: &MarketFilterConfig) -> MarketSelection<'a>
+where
+    I: IntoIterator<Item = &'a Market>,
+{
+    let mut selection = MarketSelection::default();
+
+    for market in markets {
+        if volume_of(market) < config.min_volume_24h {
+            selection.below_volume += 1;
+        } else if liquidity_of(market) < config.min_liquidity {
+            selection.below_liquidity += 1;
+        } else {
+            selection.selected.push(market);
+        }
+    }
+
+    selection.selected.sort_by(|a, b| {
+        liquidity_of(b)
+            .cmp(&liquidity_of(a))
+            .then_with(|| volume_of(b).cmp(&volume_of(a)))
+            .then_with(|| a.slug.cmp(&b.slug))
+    });
+
+    if config.max_markets > 0 && selection.selected.len() > config.max_markets {
+        selection.over_limit = selection.selected.len() - config.max_markets;
+        selection.selected.truncate(config.max_markets);
+    }
+
+    selection
+}
+
+fn volume_of(market: &Market) -> Decimal {
+    market.volume_24h.unwrap_or(Decimal::ZERO)
+}
+
+fn liquidity_of(market: &Market) -> Decimal {
+    market.liquidity.unwrap_or(Decimal::ZERO)
+}
@@ -1,3 +1,9 @@
 pub mod models;
+pub mod market_filter;
 pub mod orderbook;
 pub mod market_feed;
+pub mod feed_scheduler;
+pub mod feed_watchdog;
+pub mod startup;
+pub mod odds_matching;
+pub mod poll_breaker;
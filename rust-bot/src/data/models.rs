@@ -25,20 +25,15 @@ pub enum MarketStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderType {
+    #[default]
     #[serde(rename = "ORDER_TYPE_LIMIT")]
     Limit,
     #[serde(rename = "ORDER_TYPE_MARKET")]
     Market,
 }
 
-impl Default for OrderType {
-    fn default() -> Self {
-        Self::Limit
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderIntent {
     #[serde(rename = "ORDER_INTENT_BUY_LONG")]
@@ -89,8 +84,9 @@ impl OrderStatus {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TimeInForce {
+    #[default]
     #[serde(rename = "TIME_IN_FORCE_GOOD_TILL_CANCEL")]
     GoodTillCancel,
     #[serde(rename = "TIME_IN_FORCE_GOOD_TILL_DATE")]
@@ -101,12 +97,6 @@ pub enum TimeInForce {
     FillOrKill,
 }
 
-impl Default for TimeInForce {
-    fn default() -> Self {
-        Self::GoodTillCancel
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Side {
@@ -157,6 +147,30 @@ impl OrderBookSide {
         self.asks.iter().map(|l| l.price).min()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// The other outcome's book implied by this one: a bid at `p` is an
+    /// ask at `1 - p` and an ask at `p` a bid at `1 - p`.
+    pub fn complement(&self) -> OrderBookSide {
+        let flip = |levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .map(|l| PriceLevel { price: Decimal::ONE - l.price, quantity: l.quantity })
+                .collect()
+        };
+        OrderBookSide {
+            bids: flip(&self.asks),
+            asks: flip(&self.bids),
+        }
+    }
+
+    /// Best bid above best ask: a feed glitch, not a tradable book.
+    pub fn is_crossed(&self) -> bool {
+        matches!((self.best_bid(), self.best_ask()), (Some(bid), Some(ask)) if bid > ask)
+    }
+
     pub fn spread(&self) -> Option<Decimal> {
         match (self.best_bid(), self.best_ask()) {
             (Some(bid), Some(ask)) => Some(ask - bid),
@@ -164,6 +178,30 @@ impl OrderBookSide {
         }
     }
 
+    /// Contracts available at the best bid price (0 if no bids).
+    pub fn best_bid_depth(&self) -> i64 {
+        let Some(best) = self.best_bid() else {
+            return 0;
+        };
+        self.bids
+            .iter()
+            .filter(|l| l.price == best)
+            .map(|l| l.quantity)
+            .sum()
+    }
+
+    /// Contracts available at the best ask price (0 if no asks).
+    pub fn best_ask_depth(&self) -> i64 {
+        let Some(best) = self.best_ask() else {
+            return 0;
+        };
+        self.asks
+            .iter()
+            .filter(|l| l.price == best)
+            .map(|l| l.quantity)
+            .sum()
+    }
+
     pub fn total_bid_depth(&self) -> i64 {
         self.bids.iter().map(|l| l.quantity).sum()
     }
@@ -171,6 +209,90 @@ impl OrderBookSide {
     pub fn total_ask_depth(&self) -> i64 {
         self.asks.iter().map(|l| l.quantity).sum()
     }
+
+    /// Best `n` bid levels, highest price first.
+    pub fn top_bids(&self, n: usize) -> Vec<PriceLevel> {
+        let mut bids = self.bids.clone();
+        bids.sort_by_key(|l| std::cmp::Reverse(l.price));
+        bids.truncate(n);
+        bids
+    }
+
+    /// Best `n` ask levels, lowest price first.
+    pub fn top_asks(&self, n: usize) -> Vec<PriceLevel> {
+        let mut asks = self.asks.clone();
+        asks.sort_by_key(|l| l.price);
+        asks.truncate(n);
+        asks
+    }
+}
+
+/// Check the binary no-arbitrage identities `yes_bid + no_ask ≈ 1` and
+/// `yes_ask + no_bid ≈ 1`. A pair with a missing price is not checked.
+pub fn complement_prices_consistent(
+    yes_bid: Option<Decimal>,
+    yes_ask: Option<Decimal>,
+    no_bid: Option<Decimal>,
+    no_ask: Option<Decimal>,
+    tolerance: Decimal,
+) -> bool {
+    let within = |a: Option<Decimal>, b: Option<Decimal>| match (a, b) {
+        (Some(a), Some(b)) => (a + b - Decimal::ONE).abs() <= tolerance,
+        _ => true,
+    };
+    within(yes_bid, no_ask) && within(yes_ask, no_bid)
+}
+
+/// Probability edge needed to cover fees on a contract bought at `price`:
+/// the entry fee on `price` plus an exit fee on up to the $1 payout.
+/// The exit leg does not shrink with price, so cheap contracts need
+/// proportionally more edge than ones near 0.50.
+pub fn fee_adjusted_min_edge(price: Decimal, fee_rate: Decimal) -> Decimal {
+    fee_rate * (price + Decimal::ONE)
+}
+
+/// Share of available cash a buy may commit unless configured otherwise;
+/// the rest absorbs fees and price drift between sizing and the fill.
+pub const DEFAULT_CASH_BUFFER_PCT: Decimal = Decimal::from_parts(98, 0, 0, false, 2);
+
+/// Whole contracts affordable at `price` with `cash_buffer_pct` of
+/// `balance`. Risk sizing and both executors cap buys to this, so a signal
+/// sizes the same on every path.
+pub fn max_affordable_quantity(balance: Decimal, price: Decimal, cash_buffer_pct: Decimal) -> i64 {
+    if price <= Decimal::ZERO || balance <= Decimal::ZERO {
+        return 0;
+    }
+    (balance * cash_buffer_pct / price)
+        .floor()
+        .to_string()
+        .parse::<i64>()
+        .unwrap_or(0)
+}
+
+/// Exchange fee rates as fractions of notional.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeeSchedule {
+    pub maker_fee_rate: Decimal,
+    pub taker_fee_rate: Decimal,
+}
+
+/// Parse a `slug:fee_rate` override entry, with the rate in [0, 1).
+pub fn parse_fee_override(entry: &str) -> Option<(String, Decimal)> {
+    let (slug, rate) = entry.trim().rsplit_once(':')?;
+    let slug = slug.trim();
+    let rate: Decimal = rate.trim().parse().ok()?;
+    if slug.is_empty() || rate < Decimal::ZERO || rate >= Decimal::ONE {
+        return None;
+    }
+    Some((slug.to_string(), rate))
+}
+
+impl FeeSchedule {
+    /// Worst-case cost of opening and closing a position, as a fraction of
+    /// price: either leg may rest or cross, so both fees count twice.
+    pub fn round_trip_rate(&self) -> Decimal {
+        Decimal::TWO * self.taker_fee_rate + Decimal::TWO * self.maker_fee_rate
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,6 +330,15 @@ pub struct Market {
     pub volume_24h: Option<Decimal>,
     #[serde(default)]
     pub liquidity: Option<Decimal>,
+    /// Winning outcome once resolved ("YES" / "NO").
+    #[serde(default, alias = "outcome", alias = "winningOutcome", alias = "resolution")]
+    pub resolved_outcome: Option<String>,
+    /// Price increment orders must be placed on, if the API reports it.
+    #[serde(default, alias = "tickSize", alias = "minimumTickSize", alias = "minTickSize")]
+    pub tick_size: Option<Decimal>,
+    /// Smallest order the exchange accepts, in contracts.
+    #[serde(default, alias = "minOrderSize", alias = "orderMinSize", alias = "minimumOrderSize")]
+    pub min_order_size: Option<Decimal>,
 }
 
 impl Market {
@@ -215,6 +346,24 @@ impl Market {
     pub fn is_tradeable(&self) -> bool {
         self.active && !self.closed
     }
+
+    /// Whether trading has ended, by either the flag or the status.
+    pub fn is_closed(&self) -> bool {
+        self.closed || matches!(self.status, MarketStatus::Closed | MarketStatus::Resolved)
+    }
+
+    /// Resolution outcome: `Some(true)` if YES won, `Some(false)` if NO
+    /// won, `None` while unresolved or when the outcome is unrecognized.
+    pub fn yes_won(&self) -> Option<bool> {
+        if self.status != MarketStatus::Resolved {
+            return None;
+        }
+        match self.resolved_outcome.as_deref().map(str::to_ascii_uppercase).as_deref() {
+            Some("YES") => Some(true),
+            Some("NO") => Some(false),
+            _ => None,
+        }
+    }
 }
 
 impl Market {
@@ -231,6 +380,26 @@ impl Market {
             _ => None,
         }
     }
+
+    /// Tick size and minimum order size, ignoring non-positive values. A
+    /// fractional minimum rounds up to whole contracts.
+    pub fn metadata(&self) -> MarketMetadata {
+        MarketMetadata {
+            tick_size: self.tick_size.filter(|t| *t > Decimal::ZERO),
+            min_order_size: self
+                .min_order_size
+                .filter(|m| *m > Decimal::ZERO)
+                .and_then(|m| m.ceil().to_string().parse::<i64>().ok()),
+        }
+    }
+}
+
+/// Per-market order constraints from the exchange; `None` fields fall back
+/// to the configured defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarketMetadata {
+    pub tick_size: Option<Decimal>,
+    pub min_order_size: Option<i64>,
 }
 
 /// Lightweight best-bid/offer response from `/v1/markets/{slug}/bbo`.
@@ -272,6 +441,9 @@ pub struct OrderRequest {
     pub price: Option<Price>,
     pub quantity: i64,
     pub tif: TimeInForce,
+    /// Expiry for `TimeInForce::GoodTillDate` orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
     pub intent: OrderIntent,
     pub manual_order_indicator: String,
 }
@@ -289,10 +461,18 @@ impl OrderRequest {
             price: Some(Price::usd(price)),
             quantity,
             tif: TimeInForce::GoodTillCancel,
+            expires_at: None,
             intent,
             manual_order_indicator: "MANUAL_ORDER_INDICATOR_AUTOMATIC".to_string(),
         }
     }
+
+    /// Make this a GoodTillDate order expiring at `expires_at`.
+    pub fn good_till(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.tif = TimeInForce::GoodTillDate;
+        self.expires_at = Some(expires_at);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -336,8 +516,59 @@ impl Order {
 pub struct CreateOrderResponse {
     #[serde(alias = "id", alias = "orderId")]
     pub order_id: String,
+    /// Fills that happened at placement, e.g. a marketable order.
     #[serde(default)]
-    pub executions: Vec<serde_json::Value>,
+    pub executions: Vec<Execution>,
+}
+
+impl CreateOrderResponse {
+    pub fn filled_quantity(&self) -> i64 {
+        self.executions.iter().map(|e| e.quantity).sum()
+    }
+}
+
+/// A fill reported inline with an order placement.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Execution {
+    #[serde(alias = "executionId", alias = "id", default)]
+    pub execution_id: Option<String>,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub price: Decimal,
+    #[serde(alias = "qty")]
+    pub quantity: i64,
+    #[serde(default, deserialize_with = "deserialize_optional_amount")]
+    pub fee: Option<Decimal>,
+}
+
+/// Amounts arrive either bare (`"0.52"`, `0.52`) or as a `Price` object
+/// (`{"value": "0.52", "currency": "USD"}`).
+fn deserialize_amount<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_optional_amount(deserializer)?
+        .ok_or_else(|| serde::de::Error::custom("missing amount"))
+}
+
+fn deserialize_optional_amount<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let raw = match &value {
+        serde_json::Value::Null => return Ok(None),
+        serde_json::Value::Object(obj) => obj.get("value").cloned().unwrap_or_default(),
+        other => other.clone(),
+    };
+    let text = match raw {
+        serde_json::Value::String(s) => s,
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::Null => return Ok(None),
+        other => return Err(serde::de::Error::custom(format!("invalid amount: {}", other))),
+    };
+    text.parse()
+        .map(Some)
+        .map_err(|e| serde::de::Error::custom(format!("invalid amount {:?}: {}", text, e)))
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -442,6 +673,9 @@ pub enum SignalAction {
     BuyNo,
     SellNo,
     CancelAll,
+    /// Cancel the single order named by the signal's `CANCEL_ORDER_ID_KEY`
+    /// metadata, leaving the market's other orders resting.
+    CancelOrder,
 }
 
 impl SignalAction {
@@ -454,16 +688,26 @@ impl SignalAction {
     }
 
     pub fn is_cancel(&self) -> bool {
-        matches!(self, Self::CancelAll)
+        matches!(self, Self::CancelAll | Self::CancelOrder)
     }
 
-    pub fn to_intent(&self) -> Option<OrderIntent> {
+    pub fn to_intent(self) -> Option<OrderIntent> {
         match self {
             Self::BuyYes => Some(OrderIntent::BuyLong),
             Self::SellYes => Some(OrderIntent::SellLong),
             Self::BuyNo => Some(OrderIntent::BuyShort),
             Self::SellNo => Some(OrderIntent::SellShort),
-            Self::CancelAll => None,
+            Self::CancelAll | Self::CancelOrder => None,
+        }
+    }
+
+    /// Position side this action reduces. Sells reduce their own side;
+    /// buys reduce the opposite side (buying YES nets against NO).
+    pub fn reduced_side(self) -> Option<Side> {
+        match self {
+            Self::SellYes | Self::BuyNo => Some(Side::Yes),
+            Self::SellNo | Self::BuyYes => Some(Side::No),
+            Self::CancelAll | Self::CancelOrder => None,
         }
     }
 }
@@ -476,6 +720,20 @@ pub enum Urgency {
     Critical,
 }
 
+/// Signal metadata flag (`true`) marking an order as a deliberate
+/// replacement of a resting one, exempt from the duplicate-order guard.
+pub const REPLACEMENT_KEY: &str = "replacement";
+
+/// Signal metadata key holding a quote's 1-based ladder level; orders at
+/// different levels are never duplicates of one another.
+pub const LADDER_LEVEL_KEY: &str = "level";
+
+/// Signal metadata key shared by the two legs of one completeness arb.
+pub const ARB_PAIR_KEY: &str = "arb_pair";
+
+/// Signal metadata key holding the order id a `CancelOrder` signal targets.
+pub const CANCEL_ORDER_ID_KEY: &str = "order_id";
+
 #[derive(Debug, Clone)]
 pub struct Signal {
     pub market_slug: String,
@@ -488,6 +746,12 @@ pub struct Signal {
     pub reason: String,
     pub metadata: std::collections::HashMap<String, serde_json::Value>,
     pub timestamp: DateTime<Utc>,
+    /// Only reduce an existing position: executors cap the quantity to what
+    /// is held on the reduced side and reject if nothing is held.
+    pub reduce_only: bool,
+    /// Rest no later than this time. Live orders are sent GoodTillDate;
+    /// paper resting orders are cancelled once it passes.
+    pub good_till: Option<DateTime<Utc>>,
 }
 
 impl Signal {
@@ -503,9 +767,41 @@ impl Signal {
         self.action.is_cancel()
     }
 
+    /// Closes held inventory rather than opening any: reduce-only, or a
+    /// sell (binaries cannot be shorted).
+    pub fn is_exit(&self) -> bool {
+        self.reduce_only || self.is_sell()
+    }
+
     pub fn notional(&self) -> Decimal {
         self.price * Decimal::from(self.quantity)
     }
+
+    /// Flagged with `REPLACEMENT_KEY`.
+    pub fn is_replacement(&self) -> bool {
+        self.metadata
+            .get(REPLACEMENT_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Arb pair from `ARB_PAIR_KEY`, if the signal is one leg of an arb.
+    pub fn arb_pair(&self) -> Option<&str> {
+        self.metadata.get(ARB_PAIR_KEY).and_then(|v| v.as_str())
+    }
+
+    /// Ladder level from `LADDER_LEVEL_KEY`, if the signal is a quote.
+    pub fn ladder_level(&self) -> Option<u64> {
+        self.metadata.get(LADDER_LEVEL_KEY).and_then(|v| v.as_u64())
+    }
+
+    /// Order a `CancelOrder` signal targets, from `CANCEL_ORDER_ID_KEY`.
+    pub fn cancel_order_id(&self) -> Option<&str> {
+        self.metadata
+            .get(CANCEL_ORDER_ID_KEY)
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.is_empty())
+    }
 }
 
 // =============================================================================
@@ -521,3 +817,74 @@ pub struct ExecutionResult {
     pub fee: Decimal,
     pub error: Option<String>,
 }
+
+// =============================================================================
+// Fill Events
+// =============================================================================
+
+/// A fill published by an executor so strategies can react to their own
+/// executions without waiting for a state reconcile.
+#[derive(Debug, Clone)]
+pub struct FillEvent {
+    pub order_id: String,
+    pub market_slug: String,
+    /// Strategy that produced the originating signal (empty if unknown).
+    pub strategy_name: String,
+    pub side: Side,
+    pub is_buy: bool,
+    pub price: Decimal,
+    pub quantity: i64,
+    pub fee: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn limit(intent: OrderIntent) -> OrderRequest {
+        OrderRequest::limit_order("nba-lal-bos".to_string(), intent, Decimal::new(55, 2), 10)
+    }
+
+    #[test]
+    fn test_good_till_cancel_order_omits_expiry() {
+        let json = serde_json::to_value(limit(OrderIntent::BuyLong)).unwrap();
+        assert_eq!(json["tif"], "TIME_IN_FORCE_GOOD_TILL_CANCEL");
+        assert!(json.get("expiresAt").is_none());
+    }
+
+    #[test]
+    fn test_good_till_date_order_serializes_expiry() {
+        let tip_off = Utc.with_ymd_and_hms(2026, 3, 1, 0, 30, 0).unwrap();
+        let order = limit(OrderIntent::BuyLong).good_till(tip_off);
+
+        let json = serde_json::to_value(&order).unwrap();
+        assert_eq!(json["tif"], "TIME_IN_FORCE_GOOD_TILL_DATE");
+        assert_eq!(json["expiresAt"], "2026-03-01T00:30:00Z");
+        assert_eq!(json["marketSlug"], "nba-lal-bos");
+    }
+
+    #[test]
+    fn test_create_order_response_parses_executions() {
+        let response: CreateOrderResponse = serde_json::from_str(
+            r#"{"id":"ord-1","executions":[
+                {"id":"ex-1","price":{"value":"0.48","currency":"USD"},"quantity":6,"fee":"0.03"},
+                {"executionId":"ex-2","price":"0.49","qty":4}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.order_id, "ord-1");
+        assert_eq!(response.filled_quantity(), 10);
+        let first = &response.executions[0];
+        assert_eq!(first.execution_id.as_deref(), Some("ex-1"));
+        assert_eq!((first.price, first.quantity), (Decimal::new(48, 2), 6));
+        assert_eq!(first.fee, Some(Decimal::new(3, 2)));
+        assert_eq!(response.executions[1].price, Decimal::new(49, 2));
+        assert_eq!(response.executions[1].fee, None);
+
+        let resting: CreateOrderResponse = serde_json::from_str(r#"{"orderId":"ord-2"}"#).unwrap();
+        assert_eq!(resting.filled_quantity(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Matching sportsbook odds events to Polymarket markets.
+//!
+//! Odds providers name games by home and away team ("Los Angeles Lakers",
+//! "Boston Celtics"); Polymarket titles them "Lakers vs. Celtics", "LA
+//! Lakers at Boston Celtics" or "Will the Lakers beat the Celtics?". A team
+//! matches a title side on its full name, its nickname (also as the side's
+//! last words, so "LA Lakers" counts) or, weakest, its city. Cities of more
+//! than one word are split off from a fixed list, otherwise "Oklahoma City
+//! Thunder" would have the nickname "city thunder".
+//!
+//! The YES team is the first-named side of the title. `match_report`
+//! explains every decision and depends only on its inputs, not their order:
+//! events come back sorted by id and ties between markets are reported as
+//! ambiguous instead of being broken by position, so the report can be
+//! snapshot-tested against a corpus of real titles.
+
+#![allow(dead_code)]
+
+use serde::Serialize;
+
+use crate::data::models::Market;
+
+/// Cities (and regions) of more than one word, normalized.
+const MULTI_WORD_CITIES: &[&str] = &[
+    "golden state",
+    "green bay",
+    "kansas city",
+    "las vegas",
+    "los angeles",
+    "new england",
+    "new jersey",
+    "new orleans",
+    "new york",
+    "oklahoma city",
+    "salt lake",
+    "san antonio",
+    "san diego",
+    "san francisco",
+    "san jose",
+    "st louis",
+    "tampa bay",
+];
+
+/// Words joining the two sides of a head-to-head title, tried in order.
+const SEPARATORS: &[&str] = &[" vs ", " v ", " beat ", " defeat ", " at "];
+
+/// One game from an odds provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddsEvent {
+    pub event_id: String,
+    pub home_team: String,
+    pub away_team: String,
+}
+
+/// Why an event was (or was not) matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchReason {
+    /// Both title sides name the teams in full.
+    FullName,
+    /// Weakest side matched on the nickname.
+    Nickname,
+    /// Weakest side matched on the city only.
+    City,
+    /// Several markets matched equally well.
+    Ambiguous,
+    /// No head-to-head market names both teams.
+    NoMarket,
+}
+
+/// Matching outcome for one event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventMatch {
+    pub event_id: String,
+    pub market_slug: Option<String>,
+    /// Team whose win resolves the market YES, as the provider names it.
+    pub yes_team: Option<String>,
+    pub reason: MatchReason,
+    /// Markets tied at the best match, sorted (empty without a match).
+    pub candidates: Vec<String>,
+}
+
+impl EventMatch {
+    /// Whether YES is the home team, if matched.
+    pub fn yes_is_home(&self, event: &OddsEvent) -> Option<bool> {
+        self.yes_team.as_ref().map(|team| *team == event.home_team)
+    }
+}
+
+/// Lowercase, with punctuation dropped and whitespace collapsed.
+fn normalize(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .replace('@', " at ")
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Normalized (city, nickname) of a team name. A name without a city, like
+/// "Lakers", has an empty city.
+pub fn split_team(name: &str) -> (String, String) {
+    let name = normalize(name);
+    for city in MULTI_WORD_CITIES {
+        if let Some(rest) = name.strip_prefix(city).and_then(|r| r.strip_prefix(' ')) {
+            return (city.to_string(), rest.to_string());
+        }
+    }
+    match name.split_once(' ') {
+        Some((city, nickname)) => (city.to_string(), nickname.to_string()),
+        None => (String::new(), name),
+    }
+}
+
+/// The two normalized sides of a head-to-head title, first-named first.
+/// `None` for titles that are not a game between two teams.
+pub fn title_sides(title: &str) -> Option<(String, String)> {
+    let title = normalize(title);
+    let title = title.strip_prefix("will ").unwrap_or(&title);
+    let (left, right) = SEPARATORS.iter().find_map(|sep| title.split_once(sep))?;
+    let strip = |side: &str| side.strip_prefix("the ").unwrap_or(side).to_string();
+    let (left, right) = (strip(left), strip(right));
+    (!left.is_empty() && !right.is_empty()).then_some((left, right))
+}
+
+/// How well `team` names a title side: 3 full name, 2 nickname, 1 city.
+fn side_score(side: &str, team: &str) -> u8 {
+    let full = normalize(team);
+    let (city, nickname) = split_team(team);
+    if side == full {
+        3
+    } else if side == nickname || side.ends_with(&format!(" {nickname}")) {
+        2
+    } else if !city.is_empty() && side == city {
+        1
+    } else {
+        0
+    }
+}
+
+/// Score of `event` against a market's sides and the YES team, or `None`
+/// when both teams are not named or the orientation is unclear.
+fn market_score<'a>(event: &'a OddsEvent, sides: &(String, String)) -> Option<(u8, &'a str)> {
+    let (home, away) = (event.home_team.as_str(), event.away_team.as_str());
+    let home_first = side_score(&sides.0, home).min(side_score(&sides.1, away));
+    let away_first = side_score(&sides.0, away).min(side_score(&sides.1, home));
+    match home_first.cmp(&away_first) {
+        std::cmp::Ordering::Greater => Some((home_first, home)),
+        std::cmp::Ordering::Less => Some((away_first, away)),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Match each event to the market naming both its teams best.
+pub fn match_event(event: &OddsEvent, markets: &[Market]) -> EventMatch {
+    let mut best = 0;
+    let mut hits: Vec<(&str, &str)> = Vec::new();
+    for market in markets {
+        let Some(sides) = title_sides(&market.title) else { continue };
+        let Some((score, yes_team)) = market_score(event, &sides) else { continue };
+        if score > best {
+            best = score;
+            hits.clear();
+        }
+        if score == best {
+            hits.push((market.slug.as_str(), yes_team));
+        }
+    }
+    hits.sort();
+    hits.dedup_by(|a, b| a.0 == b.0);
+
+    let candidates: Vec<String> = hits.iter().map(|(slug, _)| slug.to_string()).collect();
+    let (market_slug, yes_team, reason) = match (hits.as_slice(), best) {
+        ([], _) => (None, None, MatchReason::NoMarket),
+        ([(slug, team)], score) => {
+            let reason = match score {
+                3 => MatchReason::FullName,
+                2 => MatchReason::Nickname,
+                _ => MatchReason::City,
+            };
+            (Some(slug.to_string()), Some(team.to_string()), reason)
+        }
+        _ => (None, None, MatchReason::Ambiguous),
+    };
+    EventMatch {
+        event_id: event.event_id.clone(),
+        market_slug,
+        yes_team,
+        reason,
+        candidates,
+    }
+}
+
+/// Match a batch of events, sorted by event id.
+pub fn match_report(events: &[OddsEvent], markets: &[Market]) -> Vec<EventMatch> {
+    let mut report: Vec<EventMatch> = events.iter().map(|e| match_event(e, markets)).collect();
+    report.sort_by(|a, b| a.event_id.cmp(&b.event_id));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_multi_word_cities_and_parses_titles() {
+        let split = |name: &str| split_team(name);
+        assert_eq!(split("Oklahoma City Thunder"), ("oklahoma city".into(), "thunder".into()));
+        assert_eq!(split("Los Angeles Lakers"), ("los angeles".into(), "lakers".into()));
+        assert_eq!(split("Portland Trail Blazers"), ("portland".into(), "trail blazers".into()));
+        assert_eq!(split("St. Louis Blues"), ("st louis".into(), "blues".into()));
+        assert_eq!(split("Lakers"), (String::new(), "lakers".into()));
+
+        let sides = |title: &str| title_sides(title).map(|(a, b)| format!("{a}|{b}"));
+        assert_eq!(sides("Lakers vs. Celtics").as_deref(), Some("lakers|celtics"));
+        assert_eq!(
+            sides("Will the Knicks beat the Heat?").as_deref(),
+            Some("knicks|heat")
+        );
+        assert_eq!(sides("Chiefs @ Bills").as_deref(), Some("chiefs|bills"));
+        assert_eq!(sides("Will the Lakers win the title?"), None);
+    }
+}
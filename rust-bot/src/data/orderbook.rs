@@ -5,11 +5,17 @@
 
 #![allow(dead_code)]
 
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, RwLock};
+use tracing::{debug, info, warn};
 
-use super::models::{OrderBook, OrderBookSide, PriceLevel, Side};
+use super::models::{
+    complement_prices_consistent, OrderBook, OrderBookSide, PriceLevel,
+    Side, Signal,
+};
 
 /// Compact top-of-book snapshot for fast access.
 #[derive(Debug, Clone, Default)]
@@ -18,6 +24,11 @@ pub struct TopOfBook {
     pub yes_best_ask: Option<Decimal>,
     pub no_best_bid: Option<Decimal>,
     pub no_best_ask: Option<Decimal>,
+    /// Contracts available at each best level.
+    pub yes_best_bid_qty: i64,
+    pub yes_best_ask_qty: i64,
+    pub no_best_bid_qty: i64,
+    pub no_best_ask_qty: i64,
 }
 
 impl TopOfBook {
@@ -28,6 +39,13 @@ impl TopOfBook {
         }
     }
 
+    pub fn no_mid(&self) -> Option<Decimal> {
+        match (self.no_best_bid, self.no_best_ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+            _ => None,
+        }
+    }
+
     pub fn yes_spread(&self) -> Option<Decimal> {
         match (self.yes_best_bid, self.yes_best_ask) {
             (Some(bid), Some(ask)) => Some(ask - bid),
@@ -35,12 +53,115 @@ impl TopOfBook {
         }
     }
 
+    /// See `complement_prices_consistent`.
+    pub fn prices_consistent(&self, tolerance: Decimal) -> bool {
+        complement_prices_consistent(
+            self.yes_best_bid,
+            self.yes_best_ask,
+            self.no_best_bid,
+            self.no_best_ask,
+            tolerance,
+        )
+    }
+
     pub fn completeness_sum(&self) -> Option<Decimal> {
         match (self.yes_best_ask, self.no_best_ask) {
             (Some(yes_ask), Some(no_ask)) => Some(yes_ask + no_ask),
             _ => None,
         }
     }
+
+    /// YES mid weighted toward the thinner side: the price leans to the
+    /// ask when bids are deeper, since the ask is more likely to trade.
+    pub fn yes_microprice(&self) -> Option<Decimal> {
+        let (bid, ask) = (self.yes_best_bid?, self.yes_best_ask?);
+        let total = self.yes_best_bid_qty + self.yes_best_ask_qty;
+        if total <= 0 {
+            return self.yes_mid();
+        }
+        let bid_qty = Decimal::from(self.yes_best_bid_qty);
+        let ask_qty = Decimal::from(self.yes_best_ask_qty);
+        Some((bid * ask_qty + ask * bid_qty) / Decimal::from(total))
+    }
+
+    /// Top-of-book YES depth imbalance in [-1, 1]; positive when bids
+    /// outweigh asks.
+    pub fn yes_imbalance(&self) -> Option<Decimal> {
+        let total = self.yes_best_bid_qty + self.yes_best_ask_qty;
+        if total <= 0 {
+            return None;
+        }
+        Some(Decimal::from(self.yes_best_bid_qty - self.yes_best_ask_qty) / Decimal::from(total))
+    }
+}
+
+/// Health flags and a 0–1 score for one market's book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookQuality {
+    /// Best bid above best ask on either side.
+    pub crossed: bool,
+    /// YES bids or YES asks missing.
+    pub one_sided: bool,
+    /// YES spread above the tracker's wide-spread threshold.
+    pub wide_spread: bool,
+    pub spread: Option<Decimal>,
+    /// 0 for crossed or empty books, 0.25 for one-sided ones, otherwise
+    /// `1 - spread / (2 * threshold)` floored at 0 (so wide books score < 0.5).
+    pub score: Decimal,
+}
+
+impl BookQuality {
+    fn from_top(top: &TopOfBook, wide_spread_threshold: Decimal) -> Self {
+        let crossed_side = |bid: Option<Decimal>, ask: Option<Decimal>| {
+            matches!((bid, ask), (Some(b), Some(a)) if b > a)
+        };
+        let crossed = crossed_side(top.yes_best_bid, top.yes_best_ask)
+            || crossed_side(top.no_best_bid, top.no_best_ask);
+        let one_sided = top.yes_best_bid.is_none() || top.yes_best_ask.is_none();
+        let spread = top.yes_spread();
+        let wide_spread = wide_spread_threshold > Decimal::ZERO
+            && spread.is_some_and(|s| s > wide_spread_threshold);
+
+        let score = if crossed || (top.yes_best_bid.is_none() && top.yes_best_ask.is_none()) {
+            Decimal::ZERO
+        } else if one_sided {
+            Decimal::new(25, 2)
+        } else {
+            match spread {
+                Some(s) if wide_spread_threshold > Decimal::ZERO => {
+                    (Decimal::ONE - s / (Decimal::TWO * wide_spread_threshold))
+                        .max(Decimal::ZERO)
+                }
+                _ => Decimal::ONE,
+            }
+        };
+
+        Self {
+            crossed,
+            one_sided,
+            wide_spread,
+            spread,
+            score,
+        }
+    }
+}
+
+/// Thresholds for `OrderBookTracker::book_quality`.
+#[derive(Debug, Clone)]
+pub struct BookQualityConfig {
+    /// YES spread above which a book is flagged `wide_spread`.
+    pub wide_spread_threshold: Decimal,
+    /// Books scoring below this are skipped by strategies. Zero disables.
+    pub min_score: Decimal,
+}
+
+impl Default for BookQualityConfig {
+    fn default() -> Self {
+        Self {
+            wide_spread_threshold: Decimal::new(20, 2), // 0.20
+            min_score: Decimal::ZERO,
+        }
+    }
 }
 
 /// Thread-safe order book tracker for all markets.
@@ -53,6 +174,11 @@ pub struct OrderBookTracker {
 struct TrackerInner {
     books: HashMap<String, OrderBook>,
     tops: HashMap<String, TopOfBook>,
+    updated_at: HashMap<String, DateTime<Utc>>,
+    quality: BookQualityConfig,
+    /// Markets the arb scanner last skipped as crossed or implausible,
+    /// so a lasting glitch is warned about once rather than every scan.
+    arb_glitches: HashSet<String>,
 }
 
 impl OrderBookTracker {
@@ -67,6 +193,7 @@ impl OrderBookTracker {
         let mut inner = self.inner.write().unwrap();
         let top = Self::compute_top(&book);
         inner.tops.insert(book.market_slug.clone(), top);
+        inner.updated_at.insert(book.market_slug.clone(), Utc::now());
         inner.books.insert(book.market_slug.clone(), book);
     }
 
@@ -80,6 +207,7 @@ impl OrderBookTracker {
             }
             let top = Self::compute_top(book);
             inner.tops.insert(market_slug.to_string(), top);
+            inner.updated_at.insert(market_slug.to_string(), Utc::now());
         }
     }
 
@@ -101,30 +229,219 @@ impl OrderBookTracker {
         inner.books.keys().cloned().collect()
     }
 
+    pub fn set_quality_config(&self, config: BookQualityConfig) {
+        self.inner.write().unwrap().quality = config;
+    }
+
+    /// Health of the stored book for a market, if tracked.
+    pub fn book_quality(&self, market_slug: &str) -> Option<BookQuality> {
+        let inner = self.inner.read().unwrap();
+        let top = inner.tops.get(market_slug)?;
+        Some(BookQuality::from_top(top, inner.quality.wide_spread_threshold))
+    }
+
+    /// False when the market's book scores below the quality floor.
+    /// Untracked markets pass, since there is nothing to judge.
+    pub fn passes_quality_floor(&self, market_slug: &str) -> bool {
+        let inner = self.inner.read().unwrap();
+        Self::top_passes_floor(&inner, inner.tops.get(market_slug))
+    }
+
+    fn top_passes_floor(inner: &TrackerInner, top: Option<&TopOfBook>) -> bool {
+        let floor = inner.quality.min_score;
+        match top {
+            Some(top) if floor > Decimal::ZERO => {
+                BookQuality::from_top(top, inner.quality.wide_spread_threshold).score >= floor
+            }
+            _ => true,
+        }
+    }
+
+    /// Estimated fill when taking `quantity` contracts from one side of a
+    /// market's book: asks for a buy, bids for a sell, best level first.
+    /// Returns (fillable_quantity, vwap); the quantity falls short of
+    /// `quantity` when the book is shallower. `None` if the market is
+    /// untracked or that side has no levels.
+    pub fn vwap_for_qty(
+        &self,
+        market_slug: &str,
+        side: Side,
+        is_buy: bool,
+        quantity: i64,
+    ) -> Option<(i64, Decimal)> {
+        let inner = self.inner.read().unwrap();
+        let book = inner.books.get(market_slug)?;
+        let book_side = match side {
+            Side::Yes => &book.yes,
+            Side::No => &book.no,
+        };
+        let mut levels: Vec<&PriceLevel> = if is_buy {
+            book_side.asks.iter().collect()
+        } else {
+            book_side.bids.iter().collect()
+        };
+        if is_buy {
+            levels.sort_by_key(|l| l.price);
+        } else {
+            levels.sort_by_key(|l| std::cmp::Reverse(l.price));
+        }
+
+        let mut filled = 0i64;
+        let mut cost = Decimal::ZERO;
+        for level in levels {
+            if filled >= quantity {
+                break;
+            }
+            let take = level.quantity.min(quantity - filled);
+            cost += level.price * Decimal::from(take);
+            filled += take;
+        }
+        (filled > 0).then(|| (filled, cost / Decimal::from(filled)))
+    }
+
     /// Remove a market from tracking.
     pub fn remove(&self, market_slug: &str) {
         let mut inner = self.inner.write().unwrap();
         inner.books.remove(market_slug);
         inner.tops.remove(market_slug);
+        inner.updated_at.remove(market_slug);
+    }
+
+    /// Export the full book for one market as JSON, for debugging.
+    ///
+    /// The `book` field deserializes back into an `OrderBook`.
+    pub fn export_snapshot(&self, market_slug: &str) -> Option<Value> {
+        let inner = self.inner.read().unwrap();
+        let book = inner.books.get(market_slug)?;
+        Some(Self::snapshot_json(
+            book,
+            inner.updated_at.get(market_slug),
+            Utc::now(),
+        ))
+    }
+
+    /// Top-`depth` levels of both sides with derived YES prices, flagged
+    /// stale when the book has not updated within `stale_after`.
+    pub fn depth_snapshot(
+        &self,
+        market_slug: &str,
+        depth: usize,
+        stale_after: chrono::Duration,
+    ) -> Option<Value> {
+        let inner = self.inner.read().unwrap();
+        let book = inner.books.get(market_slug)?;
+        let top = inner.tops.get(market_slug).cloned().unwrap_or_default();
+        let updated_at = inner.updated_at.get(market_slug).copied();
+        let now = Utc::now();
+        let age_secs = updated_at.map(|t| (now - t).num_milliseconds() as f64 / 1000.0);
+        let stale = updated_at.is_none_or(|t| now - t > stale_after);
+
+        let side = |s: &OrderBookSide| {
+            json!({
+                "bids": s.top_bids(depth),
+                "asks": s.top_asks(depth),
+            })
+        };
+        Some(json!({
+            "market_slug": market_slug,
+            "captured_at": now.to_rfc3339(),
+            "updated_at": updated_at.map(|t| t.to_rfc3339()),
+            "age_secs": age_secs,
+            "stale": stale,
+            "depth": depth,
+            "yes": side(&book.yes),
+            "no": side(&book.no),
+            "mid": top.yes_mid(),
+            "microprice": top.yes_microprice(),
+            "imbalance": top.yes_imbalance(),
+            "completeness_sum": top.completeness_sum(),
+        }))
+    }
+
+    /// Export every tracked book as JSON, keyed by market slug.
+    pub fn export_all(&self) -> Value {
+        let inner = self.inner.read().unwrap();
+        let captured_at = Utc::now();
+        let markets: serde_json::Map<String, Value> = inner
+            .books
+            .iter()
+            .map(|(slug, book)| {
+                let snapshot =
+                    Self::snapshot_json(book, inner.updated_at.get(slug), captured_at);
+                (slug.clone(), snapshot)
+            })
+            .collect();
+        json!({
+            "captured_at": captured_at.to_rfc3339(),
+            "markets": markets,
+        })
+    }
+
+    fn snapshot_json(
+        book: &OrderBook,
+        updated_at: Option<&DateTime<Utc>>,
+        captured_at: DateTime<Utc>,
+    ) -> Value {
+        json!({
+            "market_slug": book.market_slug,
+            "captured_at": captured_at.to_rfc3339(),
+            "updated_at": updated_at.map(|t| t.to_rfc3339()),
+            "book": book,
+        })
     }
 
     /// Compute top-of-book from full order book.
-    fn compute_top(book: &OrderBook) -> TopOfBook {
+    pub fn compute_top(book: &OrderBook) -> TopOfBook {
         TopOfBook {
             yes_best_bid: book.yes.best_bid(),
             yes_best_ask: book.yes.best_ask(),
             no_best_bid: book.no.best_bid(),
             no_best_ask: book.no.best_ask(),
+            yes_best_bid_qty: book.yes.best_bid_depth(),
+            yes_best_ask_qty: book.yes.best_ask_depth(),
+            no_best_bid_qty: book.no.best_bid_depth(),
+            no_best_ask_qty: book.no.best_ask_depth(),
         }
     }
 
     /// Find completeness arbitrage opportunities.
-    /// Returns markets where YES ask + NO ask < $1.00.
-    pub fn scan_completeness_arb(&self, min_margin: Decimal) -> Vec<CompletenessArbSignal> {
+    /// Returns markets where YES ask + NO ask < $1.00 and both best asks
+    /// have at least `min_top_depth` contracts and the book clears the
+    /// quality floor. A positive `max_price_skew`
+    /// also skips books whose NO prices are inconsistent with YES.
+    ///
+    /// The margin net of both legs' entry fees must clear `min_margin`.
+    /// The pair is held to resolution, so no exit fee is charged.
+    ///
+    /// Books that are crossed on either side, or whose asks fall outside
+    /// [0.01, 0.99], are glitches rather than arbs and are skipped
+    /// regardless of the quality floor. A glitch is warned about when it
+    /// first appears and logged at debug while it persists.
+    pub fn scan_completeness_arb(
+        &self,
+        min_margin: Decimal,
+        min_top_depth: i64,
+        max_price_skew: Decimal,
+        fee_rate: Decimal,
+    ) -> Vec<CompletenessArbSignal> {
+        self.scan_completeness_arb_with_fees(min_margin, min_top_depth, max_price_skew, |_| {
+            fee_rate
+        })
+    }
+
+    /// `scan_completeness_arb` with each market's fee rate looked up by
+    /// slug, for markets whose fees differ from the default.
+    pub fn scan_completeness_arb_with_fees(
+        &self,
+        min_margin: Decimal,
+        min_top_depth: i64,
+        max_price_skew: Decimal,
+        fee_rate: impl Fn(&str) -> Decimal,
+    ) -> Vec<CompletenessArbSignal> {
+        let mut glitches: HashMap<String, &'static str> = HashMap::new();
         let inner = self.inner.read().unwrap();
-        let fee_rate = Decimal::new(1, 3); // 10 bps = 0.001
 
-        inner
+        let arbs = inner
             .tops
             .iter()
             .filter_map(|(slug, top)| {
@@ -132,14 +449,45 @@ impl OrderBookTracker {
                 if combined >= Decimal::ONE {
                     return None;
                 }
+                if inner
+                    .books
+                    .get(slug)
+                    .is_some_and(|book| book.yes.is_crossed() || book.no.is_crossed())
+                {
+                    glitches.insert(slug.clone(), "crossed book");
+                    return None;
+                }
+                let plausible = |ask: Option<Decimal>| {
+                    ask.is_some_and(|a| a >= Decimal::new(1, 2) && a <= Decimal::new(99, 2))
+                };
+                if !plausible(top.yes_best_ask) || !plausible(top.no_best_ask) {
+                    glitches.insert(slug.clone(), "implausible ask");
+                    return None;
+                }
+                if top.yes_best_ask_qty < min_top_depth || top.no_best_ask_qty < min_top_depth {
+                    return None;
+                }
+                if !Self::top_passes_floor(&inner, Some(top)) {
+                    debug!(market_slug = %slug, "Skipping arb on low-quality book");
+                    return None;
+                }
+                if max_price_skew > Decimal::ZERO && !top.prices_consistent(max_price_skew) {
+                    debug!(market_slug = %slug, "Skipping arb on inconsistent YES/NO prices");
+                    return None;
+                }
+                let yes_ask = top.yes_best_ask?;
+                let no_ask = top.no_best_ask?;
+                let fee_rate = fee_rate(slug);
                 let gross_margin = Decimal::ONE - combined;
                 let fee_cost = combined * fee_rate;
                 let net_margin = gross_margin - fee_cost;
                 if net_margin > min_margin {
                     Some(CompletenessArbSignal {
                         market_slug: slug.clone(),
-                        yes_ask: top.yes_best_ask?,
-                        no_ask: top.no_best_ask?,
+                        yes_ask,
+                        no_ask,
+                        yes_ask_qty: top.yes_best_ask_qty,
+                        no_ask_qty: top.no_best_ask_qty,
                         combined_cost: combined,
                         gross_margin,
                         net_margin,
@@ -148,7 +496,31 @@ impl OrderBookTracker {
                     None
                 }
             })
-            .collect()
+            .collect();
+        drop(inner);
+
+        self.note_arb_glitches(glitches);
+        arbs
+    }
+
+    /// Warn when a market's book starts being skipped as a glitch, log at
+    /// debug while it persists, and note when it clears.
+    fn note_arb_glitches(&self, glitches: HashMap<String, &'static str>) {
+        let mut inner = self.inner.write().unwrap();
+        for (slug, reason) in &glitches {
+            if inner.arb_glitches.insert(slug.clone()) {
+                warn!(market_slug = %slug, reason, "Skipping arb on glitched book");
+            } else {
+                debug!(market_slug = %slug, reason, "Still skipping arb on glitched book");
+            }
+        }
+        inner.arb_glitches.retain(|slug| {
+            let persists = glitches.contains_key(slug);
+            if !persists {
+                info!(market_slug = %slug, "Book glitch cleared, arb scanning resumed");
+            }
+            persists
+        });
     }
 }
 
@@ -158,12 +530,83 @@ impl Default for OrderBookTracker {
     }
 }
 
+/// Bounded log pairing each executed signal with the book it acted on.
+///
+/// Entries are also emitted at debug level under the `signal_log` target so
+/// they can be captured with `RUST_LOG=signal_log=debug`.
+#[derive(Debug)]
+pub struct SignalBookLog {
+    capacity: usize,
+    entries: VecDeque<Value>,
+}
+
+impl SignalBookLog {
+    /// A capacity of 0 disables the log.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Record a signal together with the current book for its market.
+    pub fn record(&mut self, signal: &Signal, tracker: &OrderBookTracker) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = json!({
+            "signal": {
+                "market_slug": signal.market_slug,
+                "action": format!("{:?}", signal.action),
+                "price": signal.price.to_string(),
+                "quantity": signal.quantity,
+                "urgency": format!("{:?}", signal.urgency),
+                "confidence": signal.confidence,
+                "strategy_name": signal.strategy_name,
+                "reason": signal.reason,
+                "reduce_only": signal.reduce_only,
+                "timestamp": signal.timestamp.to_rfc3339(),
+            },
+            "book": tracker.export_snapshot(&signal.market_slug),
+        });
+        debug!(target: "signal_log", entry = %entry, "Signal executed");
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Recorded entries, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::Array(self.entries.iter().cloned().collect())
+    }
+}
+
 /// Signal from completeness arbitrage scanner.
 #[derive(Debug, Clone)]
 pub struct CompletenessArbSignal {
     pub market_slug: String,
     pub yes_ask: Decimal,
     pub no_ask: Decimal,
+    pub yes_ask_qty: i64,
+    pub no_ask_qty: i64,
     pub combined_cost: Decimal,
     pub gross_margin: Decimal,
     pub net_margin: Decimal,
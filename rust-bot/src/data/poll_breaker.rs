@@ -0,0 +1,136 @@
+//! Backing off a polled feed whose endpoint keeps failing.
+//!
+//! A feed normally retries only through its next poll, so a dead or
+//! rate-limited endpoint is hit at full cadence forever, burning whatever
+//! request quota is left. After `failure_threshold` consecutive failed
+//! polls the breaker opens: each further failure doubles the poll interval
+//! up to `max_interval`, and the first successful poll restores the normal
+//! cadence.
+
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+/// When a feed's breaker opens and how far it backs off.
+#[derive(Debug, Clone)]
+pub struct PollBreakerConfig {
+    /// Consecutive failed polls that open the breaker. Zero disables.
+    pub failure_threshold: u32,
+    /// Longest interval while open.
+    pub max_interval: Duration,
+}
+
+impl Default for PollBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 0,
+            max_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// What a recorded poll changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerTransition {
+    /// This failure opened the breaker.
+    Opened,
+    /// This success closed an open breaker.
+    Closed,
+    Unchanged,
+}
+
+/// Consecutive-failure breaker for one polled feed.
+#[derive(Debug, Clone)]
+pub struct PollBreaker {
+    config: PollBreakerConfig,
+    consecutive_failures: u32,
+}
+
+impl PollBreaker {
+    pub fn new(config: PollBreakerConfig) -> Self {
+        Self {
+            config,
+            consecutive_failures: 0,
+        }
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.config.failure_threshold > 0
+            && self.consecutive_failures >= self.config.failure_threshold
+    }
+
+    pub fn record_failure(&mut self) -> BreakerTransition {
+        let was_open = self.is_open();
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if !was_open && self.is_open() {
+            BreakerTransition::Opened
+        } else {
+            BreakerTransition::Unchanged
+        }
+    }
+
+    pub fn record_success(&mut self) -> BreakerTransition {
+        let was_open = self.is_open();
+        self.consecutive_failures = 0;
+        if was_open {
+            BreakerTransition::Closed
+        } else {
+            BreakerTransition::Unchanged
+        }
+    }
+
+    /// Interval until the next poll: `base` while closed, otherwise `base`
+    /// doubled once per failure since opening, capped at `max_interval`
+    /// (but never below `base`).
+    pub fn interval(&self, base: Duration) -> Duration {
+        if !self.is_open() {
+            return base;
+        }
+        let doublings = self.consecutive_failures - self.config.failure_threshold + 1;
+        base.saturating_mul(2u32.saturating_pow(doublings))
+            .min(self.config.max_interval)
+            .max(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_failures_widen_interval_until_a_success() {
+        let base = Duration::from_secs(5);
+        let mut breaker = PollBreaker::new(PollBreakerConfig {
+            failure_threshold: 3,
+            max_interval: Duration::from_secs(60),
+        });
+
+        let mut intervals = Vec::new();
+        let mut transitions = Vec::new();
+        for _ in 0..6 {
+            transitions.push(breaker.record_failure());
+            intervals.push(breaker.interval(base).as_secs());
+        }
+        assert_eq!(intervals, vec![5, 5, 10, 20, 40, 60]);
+        assert_eq!(transitions[2], BreakerTransition::Opened);
+        assert_eq!(transitions.iter().filter(|t| **t == BreakerTransition::Opened).count(), 1);
+
+        assert_eq!(breaker.record_success(), BreakerTransition::Closed);
+        assert_eq!(breaker.interval(base), base);
+        assert_eq!(breaker.record_success(), BreakerTransition::Unchanged);
+    }
+
+    #[test]
+    fn test_zero_threshold_never_opens() {
+        let mut breaker = PollBreaker::new(PollBreakerConfig::default());
+        for _ in 0..100 {
+            assert_eq!(breaker.record_failure(), BreakerTransition::Unchanged);
+        }
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.interval(Duration::from_secs(5)), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,286 @@
+//! Startup retries for market discovery and the order book probe, and the
+//! live-mode auth preflight.
+//!
+//! A cold start hits the API several times in a row; one transient failure
+//! should not leave the bot with no markets or an unverified book
+//! endpoint. Both steps retry with exponential backoff: the probe degrades
+//! to a warning when it keeps failing, while discovery gives up (and the
+//! bot exits) if it never finds enough markets.
+//!
+//! The book probe is unauthenticated, so it cannot catch bad credentials
+//! or a skewed clock breaking request signatures. Live mode first fetches
+//! the balance, an authenticated endpoint, and refuses to start if the
+//! exchange rejects the credentials or stays unreachable.
+
+#![allow(dead_code)]
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::api::client::PolymarketClient;
+use crate::data::models::Balance;
+
+/// Longest wait between two startup attempts.
+const MAX_STARTUP_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retry policy for startup calls.
+#[derive(Debug, Clone)]
+pub struct StartupRetry {
+    /// Total attempts, including the first. Zero is treated as one.
+    pub attempts: u32,
+    /// Wait after the first failure; doubles after each further one.
+    pub backoff: Duration,
+}
+
+impl Default for StartupRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl StartupRetry {
+    fn attempts(&self) -> u32 {
+        self.attempts.max(1)
+    }
+
+    /// Wait after failed attempt `attempt` (0-based).
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_STARTUP_BACKOFF)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StartupError {
+    #[error("Discovered {found} markets after {attempts} attempts, need at least {min}")]
+    TooFewMarkets {
+        found: usize,
+        min: usize,
+        attempts: u32,
+    },
+
+    #[error(
+        "Authentication rejected during live preflight: {0}. Check PM_API_KEY_ID and \
+         PM_PRIVATE_KEY, and that the system clock is in sync (request signatures are \
+         timestamped)"
+    )]
+    AuthRejected(String),
+
+    #[error("Exchange unreachable during live preflight: {0}")]
+    PreflightFailed(String),
+}
+
+/// Run `op` until it succeeds or the attempts run out, returning the last
+/// error. `op` receives the 0-based attempt number.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    what: &str,
+    retry: &StartupRetry,
+    mut op: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let attempts = retry.attempts();
+    let mut attempt = 0;
+    loop {
+        match op(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt + 1 >= attempts => return Err(e),
+            Err(e) => {
+                let delay = retry.delay(attempt);
+                warn!(
+                    what,
+                    attempt = attempt + 1,
+                    attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Startup call failed, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Run `discover` until it returns at least `min_markets` markets. Errors
+/// with the last count if every attempt comes up short.
+pub async fn discover_with_retry<T, F, Fut>(
+    retry: &StartupRetry,
+    min_markets: usize,
+    mut discover: F,
+) -> Result<Vec<T>, StartupError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Vec<T>>,
+{
+    retry_with_backoff("market discovery", retry, |attempt| {
+        let found = discover();
+        async move {
+            let slugs = found.await;
+            if slugs.len() >= min_markets {
+                Ok(slugs)
+            } else {
+                Err(StartupError::TooFewMarkets {
+                    found: slugs.len(),
+                    min: min_markets,
+                    attempts: attempt + 1,
+                })
+            }
+        }
+    })
+    .await
+}
+
+/// Fetch the account balance before live trading. Auth failures abort at
+/// once (retrying the same credentials cannot help); other errors retry
+/// under `retry` and then abort as unreachable.
+pub async fn live_preflight(
+    client: &PolymarketClient,
+    retry: &StartupRetry,
+) -> Result<Balance, StartupError> {
+    // Auth errors come back as `Ok(Err(_))` so the retry loop stops on them.
+    let result = retry_with_backoff("auth preflight", retry, |_| async {
+        match client.get_balance().await {
+            Err(e) if e.is_auth_error() => Ok(Err(e)),
+            other => other.map(Ok),
+        }
+    })
+    .await;
+    match result {
+        Ok(Ok(balance)) => Ok(balance),
+        Ok(Err(e)) => Err(StartupError::AuthRejected(e.to_string())),
+        Err(e) => Err(StartupError::PreflightFailed(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock_exchange;
+    use rust_decimal_macros::dec;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn fast_retry(attempts: u32) -> StartupRetry {
+        StartupRetry {
+            attempts,
+            backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_retries_until_success_or_attempts_run_out() {
+        let calls = Cell::new(0);
+        let result = retry_with_backoff("probe", &fast_retry(3), |_| {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move { if n < 3 { Err("timeout") } else { Ok(n) } }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+
+        calls.set(0);
+        let result: Result<(), _> = retry_with_backoff("probe", &fast_retry(4), |_| {
+            calls.set(calls.get() + 1);
+            async { Err("connection refused") }
+        })
+        .await;
+        assert_eq!(result, Err("connection refused"));
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_discovery_retries_short_results_then_bails() {
+        let calls = Cell::new(0);
+        let found = discover_with_retry(&fast_retry(3), 2, || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move { (0..n).map(|i| format!("m{i}")).collect::<Vec<_>>() }
+        })
+        .await;
+        assert_eq!(found.unwrap().len(), 2);
+        assert_eq!(calls.get(), 2);
+
+        calls.set(0);
+        let err = discover_with_retry(&fast_retry(4), 2, || {
+            calls.set(calls.get() + 1);
+            async { vec!["only-one".to_string()] }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StartupError::TooFewMarkets {
+                found: 1,
+                min: 2,
+                attempts: 4
+            }
+        );
+        assert_eq!(calls.get(), 4);
+    }
+
+    /// Run the preflight against an exchange answering every request with
+    /// `status` and `body`; also returns how many requests it saw.
+    async fn preflight_against(
+        status: u16,
+        body: &'static str,
+    ) -> (Result<Balance, StartupError>, usize) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counted = hits.clone();
+        let (base_url, server) = mock_exchange::spawn(move |_, _| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            (status, body.to_string())
+        })
+        .await;
+        let result = live_preflight(&mock_exchange::client(&base_url), &fast_retry(3)).await;
+        server.abort();
+        (result, hits.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_live_preflight_aborts_on_rejected_credentials() {
+        let (result, hits) = preflight_against(
+            401,
+            r#"{"error":{"code":"UNAUTHORIZED","message":"invalid signature"}}"#,
+        )
+        .await;
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, StartupError::AuthRejected(_)), "{err:?}");
+        let message = err.to_string();
+        assert!(message.starts_with("Authentication rejected"), "{message}");
+        assert!(
+            message.contains("invalid signature") && message.contains("clock"),
+            "{message}"
+        );
+        // The same credentials will not start working: no startup retries.
+        assert_eq!(hits, 1);
+
+        let (result, _) = preflight_against(
+            200,
+            r#"{"balances":[{"currency":"USD","availableBalance":"250.50"}]}"#,
+        )
+        .await;
+        assert_eq!(result.unwrap().available_balance, dec!(250.50));
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let retry = StartupRetry::default();
+        assert_eq!(retry.delay(0), Duration::from_secs(2));
+        assert_eq!(retry.delay(2), Duration::from_secs(8));
+        assert_eq!(retry.delay(10), MAX_STARTUP_BACKOFF);
+        assert_eq!(fast_retry(0).attempts(), 1);
+    }
+}
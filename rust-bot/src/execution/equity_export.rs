@@ -0,0 +1,254 @@
+//! Equity curve export for offline analysis.
+//!
+//! A background task samples total equity, cash, position value and each
+//! strategy's realized P&L at a fixed interval and writes one timestamped
+//! CSV row per sample. Rows are buffered and flushed every `flush_every`
+//! samples and on shutdown. Strategy columns are fixed when the file is
+//! created; P&L booked under any other name (side closes, aging exits)
+//! is summed into `pnl_other`.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::execution::paper::StrategyPerformance;
+use crate::state::state_manager::StateManager;
+
+/// Samples buffered between flushes by default.
+pub const DEFAULT_FLUSH_EVERY: usize = 10;
+
+/// Realized P&L per strategy, published by the trading loop from the
+/// executor's performance so the sampler task can read it.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyPnlBoard {
+    pnl: Arc<RwLock<HashMap<String, Decimal>>>,
+}
+
+impl StrategyPnlBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn publish(&self, by_strategy: &HashMap<String, StrategyPerformance>) {
+        let mut pnl = self.pnl.write().unwrap();
+        pnl.clear();
+        pnl.extend(by_strategy.iter().map(|(name, perf)| (name.clone(), perf.realized_pnl)));
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, Decimal> {
+        self.pnl.read().unwrap().clone()
+    }
+}
+
+/// CSV writer for equity samples.
+pub struct EquityCsv<W: Write> {
+    out: W,
+    strategies: Vec<String>,
+    flush_every: usize,
+    unflushed: usize,
+}
+
+impl EquityCsv<BufWriter<File>> {
+    /// Create (or truncate) `path` and write the header.
+    pub fn create(path: impl AsRef<Path>, strategies: Vec<String>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), strategies)
+    }
+}
+
+impl<W: Write> EquityCsv<W> {
+    /// Wrap `out`, writing the header row first.
+    pub fn new(mut out: W, strategies: Vec<String>) -> io::Result<Self> {
+        let mut header = vec![
+            "timestamp".to_string(),
+            "total_equity".to_string(),
+            "cash".to_string(),
+            "position_value".to_string(),
+        ];
+        header.extend(strategies.iter().map(|s| format!("pnl_{s}")));
+        header.push("pnl_other".to_string());
+        writeln!(out, "{}", header.join(","))?;
+        Ok(Self {
+            out,
+            strategies,
+            flush_every: DEFAULT_FLUSH_EVERY,
+            unflushed: 0,
+        })
+    }
+
+    /// Flush after this many samples (at least 1).
+    pub fn with_flush_every(mut self, samples: usize) -> Self {
+        self.flush_every = samples.max(1);
+        self
+    }
+
+    /// Append one row for `state` and `pnl` at `at`.
+    pub fn write_sample(
+        &mut self,
+        at: DateTime<Utc>,
+        state: &StateManager,
+        pnl: &HashMap<String, Decimal>,
+    ) -> io::Result<()> {
+        let (cash, position_value) = state.get_balance_and_position_value();
+        let mut row = vec![
+            at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            (cash + position_value).round_dp(4).normalize().to_string(),
+            cash.round_dp(4).normalize().to_string(),
+            position_value.round_dp(4).normalize().to_string(),
+        ];
+        for strategy in &self.strategies {
+            let value = pnl.get(strategy).copied().unwrap_or(Decimal::ZERO);
+            row.push(value.round_dp(4).normalize().to_string());
+        }
+        let other: Decimal = pnl
+            .iter()
+            .filter(|(name, _)| !self.strategies.contains(name))
+            .map(|(_, value)| *value)
+            .sum();
+        row.push(other.round_dp(4).normalize().to_string());
+        writeln!(self.out, "{}", row.join(","))?;
+
+        self.unflushed += 1;
+        if self.unflushed >= self.flush_every {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.unflushed = 0;
+        self.out.flush()
+    }
+}
+
+/// Background task writing an `EquityCsv` row every interval.
+pub struct EquitySampler {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl EquitySampler {
+    /// Start sampling `state` and `pnl` every `interval` (the first sample
+    /// is taken immediately).
+    pub fn spawn<W: Write + Send + 'static>(
+        mut csv: EquityCsv<W>,
+        state: StateManager,
+        pnl: StrategyPnlBoard,
+        interval: Duration,
+    ) -> Self {
+        let (stop, mut stopped) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if let Err(e) = csv.write_sample(Utc::now(), &state, &pnl.snapshot()) {
+                            warn!(error = %e, "Equity export write failed");
+                        }
+                    }
+                    _ = &mut stopped => break,
+                }
+            }
+            // One last row so the curve ends at shutdown.
+            let closing = csv
+                .write_sample(Utc::now(), &state, &pnl.snapshot())
+                .and_then(|()| csv.flush());
+            if let Err(e) = closing {
+                warn!(error = %e, "Equity export flush failed");
+            }
+        });
+        Self { stop, task }
+    }
+
+    /// Take a final sample, flush the file and stop the task.
+    pub async fn close(self) {
+        let _ = self.stop.send(());
+        let _ = self.task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::Side;
+
+    #[test]
+    fn test_samples_write_header_and_formatted_rows() {
+        let strategies = vec!["market_maker".to_string(), "statistical_edge".to_string()];
+        let mut csv = EquityCsv::new(Vec::new(), strategies).unwrap();
+
+        let state = StateManager::new(Decimal::new(900, 0));
+        state.update_position("m", Side::Yes, 200, Decimal::new(5, 1));
+        let board = StrategyPnlBoard::new();
+        let mut by_strategy: HashMap<String, StrategyPerformance> = HashMap::new();
+        let mut close = |strategy: &str, pnl: Decimal| {
+            by_strategy
+                .entry(strategy.to_string())
+                .or_default()
+                .record_fill(Decimal::ONE, Decimal::ZERO, pnl, true);
+        };
+        close("market_maker", Decimal::new(125, 2));
+        close("close_side", Decimal::new(-5, 1));
+        board.publish(&by_strategy);
+
+        let start = Utc::now();
+        for n in 0..3 {
+            let at = start + chrono::Duration::seconds(n);
+            csv.write_sample(at, &state, &board.snapshot()).unwrap();
+        }
+        let contents = String::from_utf8(csv.out).unwrap();
+
+        let mut lines = contents.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "timestamp,total_equity,cash,position_value,\
+                 pnl_market_maker,pnl_statistical_edge,pnl_other"
+            )
+        );
+        let rows: Vec<Vec<&str>> = lines.map(|l| l.split(',').collect()).collect();
+        assert_eq!(rows.len(), 3, "{contents}");
+        for row in &rows {
+            assert!(DateTime::parse_from_rfc3339(row[0]).is_ok(), "{}", row[0]);
+            assert_eq!(&row[1..], ["1000", "900", "100", "1.25", "0", "-0.5"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sampler_writes_a_closing_row_and_flushes() {
+        let path = std::env::temp_dir().join(format!(
+            "equity-export-{}-{}.csv",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let csv = EquityCsv::create(&path, vec!["market_maker".to_string()])
+            .unwrap()
+            .with_flush_every(100);
+        let state = StateManager::new(Decimal::new(900, 0));
+
+        // Rows are still buffered when it stops; close must write them out.
+        let sampler = EquitySampler::spawn(
+            csv,
+            state,
+            StrategyPnlBoard::new(),
+            Duration::from_secs(3600),
+        );
+        sampler.close().await;
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let rows: Vec<&str> = contents.lines().skip(1).collect();
+        assert!(!rows.is_empty(), "{contents}");
+        assert!(rows.iter().all(|row| row.ends_with(",900,900,0,0,0")), "{contents}");
+    }
+}
@@ -0,0 +1,169 @@
+//! Rolling equity samples and the risk-adjusted returns they imply.
+//!
+//! Executors sample marked equity every N trading ticks into a bounded
+//! series; Sharpe and Sortino come from the simple returns between
+//! consecutive samples, scaled by the square root of the number of sample
+//! periods per year. Both assume a zero risk-free rate and are `None`
+//! until there is enough variation to divide by.
+
+#![allow(dead_code)]
+
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+use crate::util::dec::to_f64;
+
+/// Default number of equity samples retained.
+pub const DEFAULT_EQUITY_SAMPLE_CAPACITY: usize = 1_000;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Sample periods per year when sampling every `sample_every_ticks` ticks
+/// of `tick_interval_secs`.
+pub fn periods_per_year(tick_interval_secs: f64, sample_every_ticks: u64) -> f64 {
+    let period_secs = tick_interval_secs * sample_every_ticks.max(1) as f64;
+    if period_secs > 0.0 {
+        SECONDS_PER_YEAR / period_secs
+    } else {
+        0.0
+    }
+}
+
+/// Bounded series of equity samples taken at a fixed tick cadence.
+#[derive(Debug, Clone)]
+pub struct EquitySeries {
+    capacity: usize,
+    sample_every_ticks: u64,
+    periods_per_year: f64,
+    ticks: u64,
+    samples: VecDeque<f64>,
+}
+
+impl Default for EquitySeries {
+    /// Every tick of a one-second loop.
+    fn default() -> Self {
+        Self::new(DEFAULT_EQUITY_SAMPLE_CAPACITY, 1, periods_per_year(1.0, 1))
+    }
+}
+
+impl EquitySeries {
+    /// A capacity of 0 disables sampling; a cadence of 0 is treated as 1.
+    pub fn new(capacity: usize, sample_every_ticks: u64, periods_per_year: f64) -> Self {
+        Self {
+            capacity,
+            sample_every_ticks: sample_every_ticks.max(1),
+            periods_per_year,
+            ticks: 0,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Count one tick, sampling `equity` on every `sample_every_ticks`-th
+    /// (starting with the first). Returns whether a sample was taken.
+    pub fn on_tick(&mut self, equity: Decimal) -> bool {
+        let due = self.ticks.is_multiple_of(self.sample_every_ticks);
+        self.ticks += 1;
+        if due {
+            self.push(equity);
+        }
+        due
+    }
+
+    /// Record a sample, evicting the oldest when full.
+    pub fn push(&mut self, equity: Decimal) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(to_f64(equity));
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Simple returns between consecutive samples. Periods starting from
+    /// non-positive equity are skipped.
+    pub fn returns(&self) -> Vec<f64> {
+        self.samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .filter(|(prev, _)| **prev > 0.0)
+            .map(|(prev, next)| next / prev - 1.0)
+            .collect()
+    }
+
+    /// Annualized Sharpe ratio: mean return over its sample standard
+    /// deviation. `None` with fewer than two returns or no variation.
+    pub fn sharpe(&self) -> Option<f64> {
+        let returns = self.returns();
+        if returns.len() < 2 {
+            return None;
+        }
+        let mean = mean(&returns);
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+            / (returns.len() - 1) as f64;
+        let std_dev = variance.sqrt();
+        (std_dev > 0.0).then(|| mean / std_dev * self.periods_per_year.sqrt())
+    }
+
+    /// Annualized Sortino ratio: mean return over the downside deviation
+    /// (root mean square of negative returns, counting the others as zero).
+    /// `None` with fewer than two returns or no losing period.
+    pub fn sortino(&self) -> Option<f64> {
+        let returns = self.returns();
+        if returns.len() < 2 {
+            return None;
+        }
+        let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>()
+            / returns.len() as f64)
+            .sqrt();
+        (downside > 0.0).then(|| mean(&returns) / downside * self.periods_per_year.sqrt())
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharpe_and_sortino_match_hand_calculation() {
+        // Returns +10%, -10%, +10%, +10%: mean 0.05, sample std dev 0.1,
+        // downside deviation sqrt(0.01 / 4) = 0.05; four periods a year.
+        let mut series = EquitySeries::new(10, 1, 4.0);
+        for equity in [100_000, 110_000, 99_000, 108_900, 119_790] {
+            series.on_tick(Decimal::new(equity, 3));
+        }
+        assert_eq!(series.len(), 5);
+        assert!((series.sharpe().unwrap() - 1.0).abs() < 1e-9);
+        assert!((series.sortino().unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_samples_at_cadence_and_keeps_last_n() {
+        let mut series = EquitySeries::new(3, 2, 1.0);
+        let sampled: Vec<bool> = (0..8).map(|i| series.on_tick(Decimal::from(100 + i))).collect();
+        assert_eq!(sampled, vec![true, false, true, false, true, false, true, false]);
+        assert_eq!(series.len(), 3);
+        assert_eq!(series.returns().len(), 2);
+
+        let mut flat = EquitySeries::new(10, 1, 1.0);
+        for _ in 0..3 {
+            flat.on_tick(Decimal::ONE_HUNDRED);
+        }
+        assert_eq!(flat.sharpe(), None);
+        assert_eq!(flat.sortino(), None);
+        assert!(EquitySeries::new(0, 1, 1.0).returns().is_empty());
+        assert_eq!(periods_per_year(1.0, 60), 365.0 * 24.0 * 60.0);
+    }
+}
@@ -5,16 +5,27 @@
 
 #![allow(dead_code)]
 
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tracing::{info, warn};
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
 
 use crate::api::client::PolymarketClient;
 use crate::api::errors::ApiError;
+use crate::clock::{RealClock, SharedClock};
 use crate::data::models::*;
 use crate::data::orderbook::OrderBookTracker;
-use crate::state::state_manager::{OrderState, StateManager};
+use crate::execution::equity_series::EquitySeries;
+use crate::execution::paper::{
+    arb_buy_back, arb_leg_signal, strategy_performance_json, unpaired_sell, ArbHoldings,
+    ArbUnwindResult, StrategyPerformance, FILL_CHANNEL_CAPACITY,
+};
+use crate::execution::trade_log::{TradeLogHandle, TradeRecord};
+use crate::state::event_log::EventKind;
+use crate::state::state_manager::{OrderState, ReconciliationReport, StateManager};
+use crate::util::dec::{from_f64, pct, to_f64};
 
 /// Execution result returned to the strategy engine.
 #[derive(Debug, Clone)]
@@ -25,6 +36,41 @@ pub struct ExecResult {
     pub avg_fill_price: Option<Decimal>,
     pub fee: Decimal,
     pub error: Option<String>,
+    /// Why the order was rejected, when it was.
+    pub reject_reason: Option<RejectReason>,
+}
+
+/// Typed cause of a rejected order, so callers can tell a transient
+/// failure from one that retrying unchanged will never fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    /// Not enough cash for the order (and its fee).
+    InsufficientBalance,
+    /// The book cannot fill it, or only at a price that leaves no edge.
+    InsufficientLiquidity,
+    /// A local pre-trade check refused it: nothing held to sell, a
+    /// duplicate, or no free order slot.
+    RiskBlocked,
+    /// The exchange refused the request, or it was malformed.
+    ApiRejected,
+    /// Rate-limited, load-shed or briefly unreachable.
+    Throttled,
+}
+
+impl RejectReason {
+    /// Classify an API failure from order placement or cancellation.
+    pub fn from_api_error(error: &ApiError) -> Self {
+        match error {
+            ApiError::InsufficientBalance(_) => Self::InsufficientBalance,
+            e if e.is_retryable() => Self::Throttled,
+            _ => Self::ApiRejected,
+        }
+    }
+
+    /// Whether the same order may succeed later without changes.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::InsufficientLiquidity | Self::Throttled)
+    }
 }
 
 /// Live executor that places real orders via the API.
@@ -43,6 +89,49 @@ pub struct LiveExecutor {
     order_market: HashMap<String, String>,
     order_last_filled: HashMap<String, i64>,
     estimated_fees: HashMap<String, Decimal>,
+    order_strategy: HashMap<String, String>,
+    order_placed_at: HashMap<String, DateTime<Utc>>,
+    order_level: HashMap<String, u64>,
+
+    // Orders younger than this are not reconciled away when missing from
+    // the open-orders response; they may just not be visible yet.
+    orphan_grace: Duration,
+    clock: SharedClock,
+
+    // A new order matching an open one from the same strategy (market,
+    // side, price within `dedupe_tick`) placed within this window is
+    // rejected as a duplicate. Zero disables.
+    dedupe_window: Duration,
+    dedupe_tick: Decimal,
+
+    // Resting orders allowed across all markets (0 = no cap). At the cap a
+    // new order is refused, or with `evict_for_room` replaces the resting
+    // order furthest from mid when it would rest closer.
+    max_open_orders: usize,
+    evict_for_room: bool,
+
+    // Share of cash a buy may commit; larger buys are capped to fit.
+    cash_buffer_pct: Decimal,
+
+    // Equity sampled per tick for Sharpe and Sortino.
+    equity_series: EquitySeries,
+
+    // Per-strategy attribution of detected fills.
+    by_strategy: HashMap<String, StrategyPerformance>,
+
+    // Position drift (USD) above which reconciliation warns.
+    drift_alert_threshold: Decimal,
+
+    // Edge a buy must keep at its estimated book-walk VWAP; None skips the
+    // pre-trade impact check.
+    impact_min_edge: Option<Decimal>,
+
+    // Fill notifications for subscribers (e.g. the strategy engine).
+    fill_tx: broadcast::Sender<FillEvent>,
+
+    /// Contracts and open orders attributed to arb pairs.
+    arb_holdings: ArbHoldings,
+    trade_log: Option<TradeLogHandle>,
 }
 
 impl LiveExecutor {
@@ -63,9 +152,92 @@ impl LiveExecutor {
             order_market: HashMap::new(),
             order_last_filled: HashMap::new(),
             estimated_fees: HashMap::new(),
+            order_strategy: HashMap::new(),
+            order_placed_at: HashMap::new(),
+            order_level: HashMap::new(),
+            orphan_grace: Duration::zero(),
+            clock: RealClock::shared(),
+            dedupe_window: Duration::zero(),
+            dedupe_tick: Decimal::new(1, 2),
+            max_open_orders: 0,
+            evict_for_room: false,
+            cash_buffer_pct: DEFAULT_CASH_BUFFER_PCT,
+            equity_series: EquitySeries::default(),
+            by_strategy: HashMap::new(),
+            drift_alert_threshold: Decimal::ONE,
+            impact_min_edge: None,
+            fill_tx: broadcast::channel(FILL_CHANNEL_CAPACITY).0,
+            arb_holdings: ArbHoldings::default(),
+            trade_log: None,
         }
     }
 
+    /// Subscribe to fill notifications. Fills are detected during
+    /// `reconcile_state` from changes in each order's filled quantity.
+    pub fn subscribe_fills(&self) -> broadcast::Receiver<FillEvent> {
+        self.fill_tx.subscribe()
+    }
+
+    /// Append every detected fill to a structured trade log.
+    pub fn set_trade_log(&mut self, trade_log: TradeLogHandle) {
+        self.trade_log = Some(trade_log);
+    }
+
+    /// Set the notional drift (USD) above which reconciliation warns.
+    pub fn set_drift_alert_threshold(&mut self, threshold: Decimal) {
+        self.drift_alert_threshold = threshold;
+    }
+
+    /// Check buys carrying a `true_probability` against the book before
+    /// placing them: the estimated fill VWAP must leave at least `min_edge`,
+    /// otherwise the order is shrunk until it does (or rejected).
+    pub fn set_impact_min_edge(&mut self, min_edge: Decimal) {
+        self.impact_min_edge = Some(min_edge);
+    }
+
+    /// Keep orders placed within the last `secs` seconds through
+    /// reconciliation even if the API does not list them as open yet.
+    pub fn set_orphan_grace_secs(&mut self, secs: f64) {
+        self.orphan_grace = Duration::milliseconds((secs.max(0.0) * 1000.0) as i64);
+    }
+
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Reject orders duplicating one the same strategy placed in the last
+    /// `window_secs` at a price within `tick`, unless flagged as a
+    /// replacement. Guards against stacking quotes when signals outpace
+    /// reconciliation.
+    pub fn set_duplicate_guard(&mut self, window_secs: f64, tick: Decimal) {
+        self.dedupe_window = Duration::milliseconds((window_secs.max(0.0) * 1000.0) as i64);
+        self.dedupe_tick = tick;
+    }
+
+    /// Refuse new orders once `max_open_orders` are resting (0 disables).
+    /// With `evict`, an order that would rest closer to mid than the
+    /// furthest resting order cancels that one to make room.
+    pub fn set_open_order_cap(&mut self, max_open_orders: usize, evict: bool) {
+        self.max_open_orders = max_open_orders;
+        self.evict_for_room = evict;
+    }
+
+    /// Share of available cash a buy may commit (see
+    /// `max_affordable_quantity`).
+    pub fn set_cash_buffer_pct(&mut self, cash_buffer_pct: Decimal) {
+        self.cash_buffer_pct = cash_buffer_pct;
+    }
+
+    /// Sampling cadence and annualization for Sharpe and Sortino.
+    pub fn set_equity_series(&mut self, equity_series: EquitySeries) {
+        self.equity_series = equity_series;
+    }
+
+    /// Count one trading tick toward the equity sampling cadence.
+    pub fn sample_equity(&mut self) {
+        self.equity_series.on_tick(self.state.get_total_equity());
+    }
+
     /// Initial state sync from API (call before trading starts).
     pub async fn initialize(&mut self) -> Result<(), ApiError> {
         self.reconcile_state().await?;
@@ -78,10 +250,43 @@ impl LiveExecutor {
     /// Execute an approved signal.
     pub async fn execute_signal(&mut self, signal: &Signal) -> ExecResult {
         // Handle cancels.
+        if signal.action == SignalAction::CancelOrder {
+            return self.cancel_order(signal).await;
+        }
         if signal.is_cancel() {
             return self.cancel_all(&signal.market_slug).await;
         }
 
+        // Reduce-only: cap to the held quantity (position plus hedge) on the
+        // side being reduced.
+        // The exchange nets opposite-side buys, so the action is unchanged.
+        let mut capped;
+        let signal = if signal.reduce_only {
+            let held = signal
+                .action
+                .reduced_side()
+                .map_or(0, |side| self.held_quantity(&signal.market_slug, side));
+            if held <= 0 {
+                return ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!(
+                        "Reduce-only: no position in {} to reduce",
+                        signal.market_slug
+                    )),
+                    reject_reason: Some(RejectReason::RiskBlocked),
+                };
+            }
+            capped = signal.clone();
+            capped.quantity = signal.quantity.min(held);
+            &capped
+        } else {
+            signal
+        };
+
         let intent = match signal.action.to_intent() {
             Some(i) => i,
             None => {
@@ -92,20 +297,72 @@ impl LiveExecutor {
                     avg_fill_price: None,
                     fee: Decimal::ZERO,
                     error: Some("Invalid signal action".to_string()),
+                    reject_reason: Some(RejectReason::ApiRejected),
+                };
+            }
+        };
+
+        // Sells never exceed the held quantity (position plus hedge) on that
+        // side; binaries cannot be shorted.
+        let mut sell_capped;
+        let signal = if signal.is_sell() {
+            let side = intent.side();
+            let held = self.held_quantity(&signal.market_slug, side);
+            if held <= 0 {
+                return ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!(
+                        "No {} position in {} to sell",
+                        side, signal.market_slug
+                    )),
+                    reject_reason: Some(RejectReason::RiskBlocked),
                 };
             }
+            if signal.quantity > held {
+                warn!(
+                    market_slug = %signal.market_slug,
+                    side = %side,
+                    requested = signal.quantity,
+                    held,
+                    "Sell exceeds held quantity; capping to position"
+                );
+                sell_capped = signal.clone();
+                sell_capped.quantity = held;
+                &sell_capped
+            } else {
+                signal
+            }
+        } else {
+            signal
         };
 
-        // Balance pre-check for buys.
-        if signal.is_buy() && signal.price > Decimal::ZERO {
+        if let Some(existing) = self.find_duplicate(signal, intent) {
+            debug!(
+                market_slug = %signal.market_slug,
+                order_id = %existing,
+                price = %signal.price,
+                "Duplicate order suppressed"
+            );
+            return ExecResult {
+                order_id: String::new(),
+                status: OrderStatus::Rejected,
+                filled_quantity: 0,
+                avg_fill_price: None,
+                fee: Decimal::ZERO,
+                error: Some(format!("Duplicate of open order {}", existing)),
+                reject_reason: Some(RejectReason::RiskBlocked),
+            };
+        }
+
+        // Balance pre-check for buys: cap to what the cash buffer affords.
+        let mut affordable;
+        let signal = if signal.is_buy() && signal.price > Decimal::ZERO {
             let available = self.state.get_balance();
-            let buffer = Decimal::new(98, 2);
-            let max_affordable = (available * buffer) / signal.price;
-            let max_qty = max_affordable
-                .floor()
-                .to_string()
-                .parse::<i64>()
-                .unwrap_or(0);
+            let max_qty = max_affordable_quantity(available, signal.price, self.cash_buffer_pct);
 
             if max_qty <= 0 {
                 self.failed_trades.fetch_add(1, Ordering::Relaxed);
@@ -120,17 +377,82 @@ impl LiveExecutor {
                         signal.price * Decimal::from(signal.quantity),
                         available
                     )),
+                    reject_reason: Some(RejectReason::InsufficientBalance),
+                };
+            }
+            if signal.quantity > max_qty {
+                info!(
+                    market_slug = %signal.market_slug,
+                    requested = signal.quantity,
+                    resized = max_qty,
+                    "Buy capped to available cash"
+                );
+                affordable = signal.clone();
+                affordable.quantity = max_qty;
+                &affordable
+            } else {
+                signal
+            }
+        } else {
+            signal
+        };
+
+        // Pre-trade impact: size down until the book-walk VWAP keeps edge.
+        let mut impact_capped;
+        let signal = match self.impact_capped_quantity(signal) {
+            Some(qty) if qty == signal.quantity => signal,
+            Some(qty) if qty > 0 => {
+                info!(
+                    market_slug = %signal.market_slug,
+                    requested = signal.quantity,
+                    resized = qty,
+                    "Order resized to preserve edge after estimated impact"
+                );
+                impact_capped = signal.clone();
+                impact_capped.quantity = qty;
+                &impact_capped
+            }
+            Some(_) => {
+                self.failed_trades.fetch_add(1, Ordering::Relaxed);
+                return ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!(
+                        "Estimated impact leaves no edge in {}",
+                        signal.market_slug
+                    )),
+                    reject_reason: Some(RejectReason::InsufficientLiquidity),
                 };
             }
+            None => signal,
+        };
+
+        if let Err(error) = self.make_room(signal, intent).await {
+            self.failed_trades.fetch_add(1, Ordering::Relaxed);
+            return ExecResult {
+                order_id: String::new(),
+                status: OrderStatus::Rejected,
+                filled_quantity: 0,
+                avg_fill_price: None,
+                fee: Decimal::ZERO,
+                error: Some(error),
+                reject_reason: Some(RejectReason::RiskBlocked),
+            };
         }
 
         // Build API order request.
-        let order_req = OrderRequest::limit_order(
+        let mut order_req = OrderRequest::limit_order(
             signal.market_slug.clone(),
             intent,
             signal.price,
             signal.quantity,
         );
+        if let Some(expires_at) = signal.good_till {
+            order_req = order_req.good_till(expires_at);
+        }
 
         // Preview (best-effort).
         let estimated_fee = match self.client.preview_order(&order_req).await {
@@ -146,22 +468,68 @@ impl LiveExecutor {
                 self.successful_trades.fetch_add(1, Ordering::Relaxed);
 
                 // Track order.
+                let filled = response.filled_quantity();
                 self.order_market
                     .insert(order_id.clone(), signal.market_slug.clone());
-                self.order_last_filled.insert(order_id.clone(), 0);
+                self.order_last_filled.insert(order_id.clone(), filled);
                 self.estimated_fees
                     .insert(order_id.clone(), estimated_fee);
+                self.order_strategy
+                    .insert(order_id.clone(), signal.strategy_name.clone());
+                self.order_placed_at.insert(order_id.clone(), self.clock.now());
+                if let Some(level) = signal.ladder_level() {
+                    self.order_level.insert(order_id.clone(), level);
+                }
+                self.arb_holdings.track_order(&order_id, signal);
 
-                // Store in state.
-                self.state.add_order(OrderState {
-                    order_id: order_id.clone(),
-                    market_slug: signal.market_slug.clone(),
-                    intent,
-                    price: signal.price,
-                    quantity: signal.quantity,
-                    filled_quantity: 0,
-                    status: OrderStatus::Open,
-                });
+                // Apply immediate fills now rather than at the next reconcile.
+                let mut fill_cost = Decimal::ZERO;
+                let mut fill_fees = Decimal::ZERO;
+                for execution in response.executions.iter().filter(|e| e.quantity > 0) {
+                    let fee = execution.fee.unwrap_or_else(|| {
+                        self.prorated_fee(&order_id, execution.quantity, signal.quantity)
+                    });
+                    self.publish_fill(
+                        &order_id,
+                        &signal.market_slug,
+                        intent,
+                        execution.price,
+                        execution.quantity,
+                        fee,
+                    );
+                    self.state.apply_fill(
+                        &signal.market_slug,
+                        intent,
+                        execution.price,
+                        execution.quantity,
+                        fee,
+                    );
+                    fill_cost += execution.price * Decimal::from(execution.quantity);
+                    fill_fees += fee;
+                }
+
+                let status = if filled >= signal.quantity {
+                    OrderStatus::Filled
+                } else if filled > 0 {
+                    OrderStatus::PartiallyFilled
+                } else {
+                    OrderStatus::Open
+                };
+
+                // Store in state; a fully filled order has nothing left to track.
+                if status == OrderStatus::Filled {
+                    self.untrack_order(&order_id);
+                } else {
+                    self.state.add_order(OrderState {
+                        order_id: order_id.clone(),
+                        market_slug: signal.market_slug.clone(),
+                        intent,
+                        price: signal.price,
+                        quantity: signal.quantity,
+                        filled_quantity: filled,
+                        status,
+                    });
+                }
 
                 info!(
                     order_id = %order_id,
@@ -169,16 +537,18 @@ impl LiveExecutor {
                     action = ?signal.action,
                     price = %signal.price,
                     quantity = signal.quantity,
+                    filled,
                     "Order placed"
                 );
 
                 ExecResult {
                     order_id,
-                    status: OrderStatus::Open,
-                    filled_quantity: 0,
-                    avg_fill_price: None,
-                    fee: estimated_fee,
+                    status,
+                    filled_quantity: filled,
+                    avg_fill_price: (filled > 0).then(|| fill_cost / Decimal::from(filled)),
+                    fee: if filled > 0 { fill_fees } else { estimated_fee },
                     error: None,
+                    reject_reason: None,
                 }
             }
             Err(e) => {
@@ -193,11 +563,142 @@ impl LiveExecutor {
                     avg_fill_price: None,
                     fee: Decimal::ZERO,
                     error: Some(e.to_string()),
+                    reject_reason: Some(RejectReason::from_api_error(&e)),
                 }
             }
         }
     }
 
+    /// An open order from the same strategy on the same market, intent and
+    /// ladder level, priced within a tick of `signal`, still resting at
+    /// least `signal`'s quantity and placed inside the dedupe window. A
+    /// larger order adds size the resting one does not cover.
+    fn find_duplicate(&self, signal: &Signal, intent: OrderIntent) -> Option<String> {
+        if self.dedupe_window <= Duration::zero() || signal.is_replacement() {
+            return None;
+        }
+        let now = self.clock.now();
+        self.state
+            .get_open_orders(Some(&signal.market_slug))
+            .into_iter()
+            .filter(|o| o.intent == intent && o.quantity - o.filled_quantity >= signal.quantity)
+            .filter(|o| (o.price - signal.price).abs() <= self.dedupe_tick)
+            .filter(|o| self.order_strategy.get(&o.order_id) == Some(&signal.strategy_name))
+            .filter(|o| self.order_level.get(&o.order_id).copied() == signal.ladder_level())
+            .find(|o| {
+                self.order_placed_at
+                    .get(&o.order_id)
+                    .is_some_and(|placed| now - *placed < self.dedupe_window)
+            })
+            .map(|o| o.order_id)
+    }
+
+    /// Ensure a slot under the open-order cap, cancelling the resting order
+    /// furthest from mid if eviction is on and the new order would rest
+    /// closer. Exits are never held back by the cap. Errors with the reason
+    /// the order cannot be placed.
+    async fn make_room(&mut self, signal: &Signal, intent: OrderIntent) -> Result<(), String> {
+        if self.max_open_orders == 0 || signal.is_exit() {
+            return Ok(());
+        }
+        let open = self.state.get_open_orders(None);
+        if open.len() < self.max_open_orders {
+            return Ok(());
+        }
+        let full = format!("Open order cap reached ({})", self.max_open_orders);
+        if !self.evict_for_room {
+            return Err(full);
+        }
+
+        // Orders in markets without a mid are the least useful of all.
+        let distance = |slug: &str, intent: OrderIntent, price: Decimal| {
+            self.distance_from_mid(slug, intent, price).unwrap_or(Decimal::MAX)
+        };
+        let Some(furthest) = open
+            .iter()
+            .max_by_key(|o| distance(&o.market_slug, o.intent, o.price))
+        else {
+            return Err(full);
+        };
+        let furthest_distance = distance(&furthest.market_slug, furthest.intent, furthest.price);
+        let new_distance = distance(&signal.market_slug, intent, signal.price);
+        if new_distance >= furthest_distance {
+            return Err(full);
+        }
+
+        match self.client.cancel_order(&furthest.order_id).await {
+            Ok(_) => {
+                info!(
+                    evicted = %furthest.order_id,
+                    evicted_market = %furthest.market_slug,
+                    evicted_price = %furthest.price,
+                    market_slug = %signal.market_slug,
+                    price = %signal.price,
+                    "Open order cap reached; cancelled furthest-from-mid order"
+                );
+                self.state
+                    .update_order(&furthest.order_id, Some(OrderStatus::Cancelled), None);
+                self.state.remove_order(&furthest.order_id);
+                self.untrack_order(&furthest.order_id);
+                Ok(())
+            }
+            Err(e) => Err(format!("{full}; evicting {} failed: {e}", furthest.order_id)),
+        }
+    }
+
+    /// How far `price` sits from the mid of the intent's side of the book.
+    fn distance_from_mid(
+        &self,
+        slug: &str,
+        intent: OrderIntent,
+        price: Decimal,
+    ) -> Option<Decimal> {
+        let yes_mid = self.state.get_market(slug)?.yes_mid_price()?;
+        let mid = match intent.side() {
+            Side::Yes => yes_mid,
+            Side::No => Decimal::ONE - yes_mid,
+        };
+        Some((price - mid).abs())
+    }
+
+    /// Largest quantity up to `signal.quantity` whose estimated fill VWAP
+    /// stays at or below `true_probability - impact_min_edge`. `None` when
+    /// the check does not apply: disabled, not a buy, no probability
+    /// estimate, or no book to walk.
+    fn impact_capped_quantity(&self, signal: &Signal) -> Option<i64> {
+        let min_edge = self.impact_min_edge?;
+        if !signal.is_buy() {
+            return None;
+        }
+        let side = signal.action.to_intent()?.side();
+        let true_prob = signal
+            .metadata
+            .get("true_probability")
+            .and_then(|v| v.as_f64())
+            .and_then(from_f64)?;
+        let max_vwap = true_prob - min_edge;
+        let within = |qty: i64| {
+            self.orderbook
+                .vwap_for_qty(&signal.market_slug, side, true, qty)
+                .map(|(_, vwap)| vwap <= max_vwap)
+        };
+
+        if within(signal.quantity)? {
+            return Some(signal.quantity);
+        }
+        // VWAP only rises with size, so binary search the largest fit.
+        let (mut lo, mut hi) = (0, signal.quantity);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if within(mid).unwrap_or(false) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Some(lo)
+    }
+
     /// Cancel all orders for a market.
     async fn cancel_all(&mut self, market_slug: &str) -> ExecResult {
         match self.client.cancel_all_orders(Some(market_slug)).await {
@@ -217,6 +718,7 @@ impl LiveExecutor {
                     avg_fill_price: None,
                     fee: Decimal::ZERO,
                     error: None,
+                    reject_reason: None,
                 }
             }
             Err(e) => {
@@ -228,13 +730,310 @@ impl LiveExecutor {
                     avg_fill_price: None,
                     fee: Decimal::ZERO,
                     error: Some(e.to_string()),
+                    reject_reason: Some(RejectReason::from_api_error(&e)),
                 }
             }
         }
     }
 
-    /// Reconcile state from API (balance, positions, orders).
-    pub async fn reconcile_state(&mut self) -> Result<(), ApiError> {
+    /// Cancel the one order a `CancelOrder` signal names.
+    async fn cancel_order(&mut self, signal: &Signal) -> ExecResult {
+        let rejected = |reason: RejectReason, error: String| ExecResult {
+            order_id: String::new(),
+            status: OrderStatus::Rejected,
+            filled_quantity: 0,
+            avg_fill_price: None,
+            fee: Decimal::ZERO,
+            error: Some(error),
+            reject_reason: Some(reason),
+        };
+        let Some(order_id) = signal.cancel_order_id() else {
+            return rejected(
+                RejectReason::ApiRejected,
+                "Cancel order signal without an order id".to_string(),
+            );
+        };
+
+        match self.client.cancel_order(order_id).await {
+            Ok(_) => {
+                self.state
+                    .update_order(order_id, Some(OrderStatus::Cancelled), None);
+                self.state.remove_order(order_id);
+                self.untrack_order(order_id);
+
+                ExecResult {
+                    order_id: order_id.to_string(),
+                    status: OrderStatus::Cancelled,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: None,
+                    reject_reason: None,
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, order_id, market_slug = %signal.market_slug, "Cancel failed");
+                rejected(RejectReason::from_api_error(&e), e.to_string())
+            }
+        }
+    }
+
+    /// Close only the `side` holding in a market, counting any hedge on
+    /// that side. If the exchange does not support a side-specific close,
+    /// fall back to a reduce-only sell of the held quantity at the best bid.
+    pub async fn close_side(&mut self, market_slug: &str, side: Side) -> ExecResult {
+        let held = self.held_quantity(market_slug, side);
+        if held <= 0 {
+            return ExecResult {
+                order_id: String::new(),
+                status: OrderStatus::Rejected,
+                filled_quantity: 0,
+                avg_fill_price: None,
+                fee: Decimal::ZERO,
+                error: Some(format!("No {} position in {} to close", side, market_slug)),
+                reject_reason: Some(RejectReason::RiskBlocked),
+            };
+        }
+
+        match self.client.close_position(market_slug, Some(side)).await {
+            Ok(response) => {
+                let order_id = response
+                    .get("orderId")
+                    .or_else(|| response.get("id"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                info!(order_id = %order_id, market_slug, side = %side, "Close position sent");
+                ExecResult {
+                    order_id,
+                    status: OrderStatus::Pending,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: None,
+                    reject_reason: None,
+                }
+            }
+            // The endpoint or its `side` parameter is unsupported. Other
+            // rejections (including 429s) are reported, not worked around.
+            Err(ApiError::Http { status_code: 404 | 405 | 501, message, .. }) => {
+                warn!(
+                    market_slug,
+                    side = %side,
+                    error = %message,
+                    "Side close unsupported, selling held quantity instead"
+                );
+                let signal = self.close_side_signal(market_slug, side, held);
+                self.execute_signal(&signal).await
+            }
+            Err(e) => {
+                warn!(error = %e, market_slug, side = %side, "Close position failed");
+                ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(e.to_string()),
+                    reject_reason: Some(RejectReason::from_api_error(&e)),
+                }
+            }
+        }
+    }
+
+    /// Contracts held on `side` of a market, position and hedge together.
+    fn held_quantity(&self, market_slug: &str, side: Side) -> i64 {
+        self.state
+            .get_position(market_slug)
+            .into_iter()
+            .chain(self.state.get_hedge_position(market_slug))
+            .filter(|p| p.side == side)
+            .map(|p| p.quantity)
+            .sum()
+    }
+
+    /// Marketable reduce-only sell of `quantity` on `side`: priced at the
+    /// best bid, or the minimum price when the book has no bids.
+    fn close_side_signal(&self, market_slug: &str, side: Side, quantity: i64) -> Signal {
+        let top = self.orderbook.get_top(market_slug);
+        let (action, best_bid) = match side {
+            Side::Yes => (SignalAction::SellYes, top.and_then(|t| t.yes_best_bid)),
+            Side::No => (SignalAction::SellNo, top.and_then(|t| t.no_best_bid)),
+        };
+        Signal {
+            market_slug: market_slug.to_string(),
+            action,
+            price: best_bid.unwrap_or(Decimal::new(1, 2)),
+            quantity,
+            urgency: Urgency::Critical,
+            confidence: 1.0,
+            strategy_name: "close_side".to_string(),
+            reason: format!("Close {} position", side),
+            metadata: HashMap::new(),
+            timestamp: self.clock.now(),
+            reduce_only: true,
+            good_till: None,
+        }
+    }
+
+    /// Flatten the YES + NO holding an arb pair's legs bought (position and
+    /// hedge, capped at what the legs filled) like
+    /// `PaperExecutor::unwind_arb`. The pair's open leg orders are cancelled
+    /// first; if any cancel fails or is not confirmed, nothing is sold. Then
+    /// the leg held without a partner is sold, then the matched pairs on
+    /// both legs together, but only once the tracked book shows bids for
+    /// all of them on each leg. Excess one leg sold over the other is
+    /// bought back.
+    pub async fn unwind_arb(&mut self, market_slug: &str) -> ArbUnwindResult {
+        let mut result = ArbUnwindResult::default();
+        for order_id in self.arb_holdings.orders(market_slug) {
+            let Some(order) = self.state.get_order(&order_id) else {
+                self.arb_holdings.forget_order(&order_id);
+                continue;
+            };
+            if let Err(e) = self.cancel_arb_leg(&order).await {
+                error!(
+                    market_slug,
+                    order_id = %order_id,
+                    error = %e,
+                    "Arb unwind aborted: leg order not cancelled"
+                );
+                result.error = Some(format!(
+                    "Leg order {} not cancelled ({}); unwind aborted",
+                    order_id, e
+                ));
+                return result;
+            }
+            result.cancelled += 1;
+        }
+
+        let (yes_arb, no_arb) = self.arb_holdings.held(market_slug);
+        let yes_held = self.held_quantity(market_slug, Side::Yes).min(yes_arb);
+        let no_held = self.held_quantity(market_slug, Side::No).min(no_arb);
+        let matched = yes_held.min(no_held);
+        if yes_held.max(no_held) <= 0 {
+            result.error = Some(format!("No arb holding in {} to unwind", market_slug));
+            return result;
+        }
+
+        if let Some((action, quantity)) = unpaired_sell(yes_held, no_held) {
+            let now = self.clock.now();
+            let leg = arb_leg_signal(&self.orderbook, market_slug, action, quantity, now);
+            result.unpaired = self.send_arb_leg(&leg, &mut result).await;
+        }
+        if matched <= 0 {
+            return result;
+        }
+
+        let short_leg = [Side::Yes, Side::No].into_iter().find_map(|side| {
+            let fillable = self
+                .orderbook
+                .vwap_for_qty(market_slug, side, false, matched)
+                .map_or(0, |(quantity, _)| quantity);
+            (fillable < matched).then_some((side, fillable))
+        });
+        if let Some((side, fillable)) = short_leg {
+            error!(
+                market_slug,
+                side = %side,
+                matched,
+                fillable,
+                "Arb unwind aborted: leg cannot be filled"
+            );
+            result.error = Some(format!(
+                "{} leg can fill {} of {}; unwind aborted",
+                side, fillable, matched
+            ));
+            return result;
+        }
+
+        let now = self.clock.now();
+        let leg = |action| arb_leg_signal(&self.orderbook, market_slug, action, matched, now);
+        let (yes_leg, no_leg) = (leg(SignalAction::SellYes), leg(SignalAction::SellNo));
+        let yes_filled = self.send_arb_leg(&yes_leg, &mut result).await;
+        let no_filled = self.send_arb_leg(&no_leg, &mut result).await;
+        result.unwound = yes_filled.min(no_filled);
+
+        if let Some((action, excess)) = arb_buy_back(yes_filled, no_filled) {
+            let buy_back = arb_leg_signal(&self.orderbook, market_slug, action, excess, now);
+            result.rolled_back = self.send_arb_leg(&buy_back, &mut result).await;
+            error!(
+                market_slug,
+                yes_filled,
+                no_filled,
+                rolled_back = result.rolled_back,
+                excess,
+                "Arb unwind legs filled unevenly; bought back the excess"
+            );
+            result.error = Some(format!(
+                "Legs filled unevenly (YES {}, NO {}); bought back {} of {}",
+                yes_filled, no_filled, result.rolled_back, excess
+            ));
+        }
+        result
+    }
+
+    /// Send one `unwind_arb` leg, adding its cash to `result.proceeds`.
+    /// Returns the quantity filled on placement.
+    async fn send_arb_leg(&mut self, leg: &Signal, result: &mut ArbUnwindResult) -> i64 {
+        let exec = self.execute_signal(leg).await;
+        let price = exec.avg_fill_price.unwrap_or(leg.price);
+        let notional = price * Decimal::from(exec.filled_quantity);
+        let fee = if exec.filled_quantity > 0 { exec.fee } else { Decimal::ZERO };
+        result.proceeds += if leg.is_buy() { -notional } else { notional } - fee;
+        self.arb_holdings
+            .record_unwind(&leg.market_slug, leg.action, exec.filled_quantity);
+        exec.filled_quantity
+    }
+
+    /// Cancel an open arb leg order for `unwind_arb`, confirmed by the
+    /// cancel response. Whatever it filled since the last check is applied
+    /// first so the unwind sizes from it. Fails if the cancel errors or the
+    /// exchange still reports the order open.
+    async fn cancel_arb_leg(&mut self, order: &OrderState) -> Result<(), String> {
+        let id = order.order_id.as_str();
+        let remote = self.client.cancel_order(id).await.map_err(|e| e.to_string())?;
+        if remote.is_open() {
+            return Err(format!("still {}", remote.status));
+        }
+
+        let last_filled = self.order_last_filled.get(id).copied().unwrap_or(0);
+        if remote.filled_quantity > last_filled {
+            let quantity = remote.filled_quantity - last_filled;
+            let price = remote.avg_fill_price.or(remote.price).unwrap_or(order.price);
+            let fee = self.prorated_fee(id, quantity, order.quantity);
+            self.publish_fill(id, &order.market_slug, order.intent, price, quantity, fee);
+            self.state
+                .apply_fill(&order.market_slug, order.intent, price, quantity, fee);
+        }
+        self.state.update_order(id, Some(OrderStatus::Cancelled), None);
+        self.state.remove_order(id);
+        self.untrack_order(id);
+        Ok(())
+    }
+
+    /// Cancel every resting order across all markets in one call and mark
+    /// all locally tracked open orders cancelled. Returns how many local
+    /// orders were cleared.
+    pub async fn cancel_everything(&mut self) -> Result<usize, ApiError> {
+        self.client.cancel_all_orders(None).await?;
+
+        let open_orders = self.state.get_open_orders(None);
+        for order in &open_orders {
+            self.state
+                .update_order(&order.order_id, Some(OrderStatus::Cancelled), None);
+            self.state.remove_order(&order.order_id);
+            self.untrack_order(&order.order_id);
+        }
+        warn!(cancelled = open_orders.len(), "Cancelled all open orders across all markets");
+        Ok(open_orders.len())
+    }
+
+    /// Sync balance, positions and open orders from the API.
+    ///
+    /// Returns a report of local positions that disagreed with the API
+    /// before being overwritten; drift above the alert threshold is warned.
+    pub async fn reconcile_state(&mut self) -> Result<ReconciliationReport, ApiError> {
         // Balance.
         match self.client.get_balance().await {
             Ok(balance) => {
@@ -244,11 +1043,32 @@ impl LiveExecutor {
         }
 
         // Positions.
+        let mut report = ReconciliationReport::default();
         match self.client.get_positions().await {
             Ok(positions) => {
-                for p in positions {
-                    self.state
-                        .update_position(&p.market_slug, p.side, p.quantity, p.avg_price);
+                report = self.state.reconcile_positions(&positions);
+                for drift in report.drifts_above(self.drift_alert_threshold) {
+                    warn!(
+                        market_slug = %drift.market_slug,
+                        local_quantity = drift.local_quantity,
+                        api_quantity = drift.api_quantity,
+                        quantity_delta = drift.quantity_delta(),
+                        local_avg_price = %drift.local_avg_price,
+                        api_avg_price = %drift.api_avg_price,
+                        avg_price_delta = %drift.avg_price_delta(),
+                        notional_drift = %drift.notional_drift(),
+                        "POSITION DRIFT: local state diverged from exchange"
+                    );
+                    self.state.events().push(
+                        EventKind::ReconcileDrift,
+                        Some(&drift.market_slug),
+                        "Local position diverged from exchange",
+                        serde_json::json!({
+                            "local_quantity": drift.local_quantity,
+                            "api_quantity": drift.api_quantity,
+                            "notional_drift": drift.notional_drift().to_string(),
+                        }),
+                    );
                 }
             }
             Err(e) => warn!(error = %e, "Reconcile positions failed"),
@@ -261,6 +1081,25 @@ impl LiveExecutor {
                     orders.iter().map(|o| o.order_id.clone()).collect();
 
                 for o in &orders {
+                    // Only report fill deltas on orders placed by this
+                    // executor; pre-existing orders just set a baseline.
+                    let last_filled = self.order_last_filled.get(&o.order_id).copied().unwrap_or(0);
+                    if o.filled_quantity > last_filled && self.order_strategy.contains_key(&o.order_id) {
+                        let price = o.avg_fill_price.or(o.price).unwrap_or(Decimal::ZERO);
+                        let quantity = o.filled_quantity - last_filled;
+                        let fee = self.prorated_fee(&o.order_id, quantity, o.quantity);
+                        self.publish_fill(
+                            &o.order_id,
+                            &o.market_slug,
+                            parse_intent(&o.intent),
+                            price,
+                            quantity,
+                            fee,
+                        );
+                    }
+                    self.order_last_filled
+                        .insert(o.order_id.clone(), o.filled_quantity);
+
                     let status = match o.status.as_str() {
                         "PENDING" => OrderStatus::Pending,
                         "OPEN" => OrderStatus::Open,
@@ -275,13 +1114,7 @@ impl LiveExecutor {
                     self.state.add_order(OrderState {
                         order_id: o.order_id.clone(),
                         market_slug: o.market_slug.clone(),
-                        intent: match o.intent.as_str() {
-                            "ORDER_INTENT_BUY_LONG" => OrderIntent::BuyLong,
-                            "ORDER_INTENT_SELL_LONG" => OrderIntent::SellLong,
-                            "ORDER_INTENT_BUY_SHORT" => OrderIntent::BuyShort,
-                            "ORDER_INTENT_SELL_SHORT" => OrderIntent::SellShort,
-                            _ => OrderIntent::BuyLong,
-                        },
+                        intent: parse_intent(&o.intent),
                         price: o.price.unwrap_or(Decimal::ZERO),
                         quantity: o.quantity,
                         filled_quantity: o.filled_quantity,
@@ -289,24 +1122,154 @@ impl LiveExecutor {
                     });
                 }
 
-                // Clean up orders that are no longer open.
+                // Clean up orders that are no longer open, except ones
+                // placed too recently to show up in the response.
+                let now = self.clock.now();
                 let tracked: Vec<String> = self.order_market.keys().cloned().collect();
                 for id in tracked {
                     if !open_ids.contains(&id) {
+                        if let Some(placed_at) = self.order_placed_at.get(&id) {
+                            if now - *placed_at < self.orphan_grace {
+                                debug!(order_id = %id, "Unlisted order within grace; keeping");
+                                continue;
+                            }
+                        }
                         if let Some(order) = self.state.get_order(&id) {
                             if order.is_open() {
-                                self.state
-                                    .update_order(&id, Some(OrderStatus::Filled), None);
-                                self.state.remove_order(&id);
+                                match self.settle_unlisted_order(&order).await {
+                                    Some(status) => {
+                                        self.state.update_order(&id, Some(status), None);
+                                        self.state.remove_order(&id);
+                                    }
+                                    // Still open after all; check again next pass.
+                                    None => continue,
+                                }
                             }
                         }
+                        self.untrack_order(&id);
                     }
                 }
             }
             Err(e) => warn!(error = %e, "Reconcile orders failed"),
         }
 
-        Ok(())
+        Ok(report)
+    }
+
+    /// Look up the final state of a tracked order that dropped off the
+    /// open list, publishing whatever it filled since the last check.
+    /// Cancelled, expired and rejected orders publish only their real
+    /// fills; an order that cannot be fetched is treated as cancelled.
+    /// Returns `None` if the exchange still reports it open.
+    async fn settle_unlisted_order(&mut self, order: &OrderState) -> Option<OrderStatus> {
+        let id = order.order_id.as_str();
+        let remote = match self.client.get_order(id).await {
+            Ok(remote) => remote,
+            Err(e) => {
+                warn!(
+                    order_id = id,
+                    error = %e,
+                    "Unlisted order lookup failed; treating as cancelled"
+                );
+                return Some(OrderStatus::Cancelled);
+            }
+        };
+        if remote.is_open() {
+            return None;
+        }
+
+        let last_filled = self.order_last_filled.get(id).copied().unwrap_or(0);
+        if remote.filled_quantity > last_filled && self.order_strategy.contains_key(id) {
+            let quantity = remote.filled_quantity - last_filled;
+            let price = remote.avg_fill_price.or(remote.price).unwrap_or(order.price);
+            let fee = self.prorated_fee(id, quantity, order.quantity);
+            self.publish_fill(id, &order.market_slug, order.intent, price, quantity, fee);
+        }
+        Some(if remote.is_filled() {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::Cancelled
+        })
+    }
+
+    /// Forget the bookkeeping kept for an order that is no longer open.
+    fn untrack_order(&mut self, order_id: &str) {
+        self.order_market.remove(order_id);
+        self.order_last_filled.remove(order_id);
+        self.estimated_fees.remove(order_id);
+        self.order_strategy.remove(order_id);
+        self.order_placed_at.remove(order_id);
+        self.order_level.remove(order_id);
+        self.arb_holdings.forget_order(order_id);
+    }
+
+    /// The preview fee estimate pro-rated to `quantity` of `order_quantity`.
+    fn prorated_fee(&self, order_id: &str, quantity: i64, order_quantity: i64) -> Decimal {
+        match self.estimated_fees.get(order_id) {
+            Some(est) if order_quantity > 0 => {
+                *est * Decimal::from(quantity) / Decimal::from(order_quantity)
+            }
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Publish a fill of `quantity` contracts on `order_id`.
+    fn publish_fill(
+        &mut self,
+        order_id: &str,
+        market_slug: &str,
+        intent: OrderIntent,
+        price: Decimal,
+        quantity: i64,
+        fee: Decimal,
+    ) {
+        let strategy_name = self.order_strategy.get(order_id).cloned().unwrap_or_default();
+        self.arb_holdings
+            .record_fill(order_id, intent.side(), intent.is_buy(), quantity);
+
+        // Realized P&L on sells against the reconciled average entry price.
+        let realized_pnl = if intent.is_buy() {
+            Decimal::ZERO
+        } else {
+            self.state
+                .get_position(market_slug)
+                .filter(|p| p.side == intent.side())
+                .map(|p| (price - p.avg_price) * Decimal::from(quantity))
+                .unwrap_or(Decimal::ZERO)
+        };
+        self.by_strategy
+            .entry(strategy_name.clone())
+            .or_default()
+            .record_fill(price * Decimal::from(quantity), fee, realized_pnl, !intent.is_buy());
+
+        let event = FillEvent {
+            order_id: order_id.to_string(),
+            market_slug: market_slug.to_string(),
+            strategy_name,
+            side: intent.side(),
+            is_buy: intent.is_buy(),
+            price,
+            quantity,
+            fee,
+            timestamp: self.clock.now(),
+        };
+        self.state.record_trade(market_slug, event.side, price, event.timestamp);
+        info!(
+            order_id,
+            market_slug,
+            price = %price,
+            quantity,
+            "Fill detected"
+        );
+        if let Some(ref log) = self.trade_log {
+            log.record(TradeRecord::from_fill(&event, realized_pnl));
+        }
+        let _ = self.fill_tx.send(event);
+    }
+
+    /// Per-strategy attribution of detected fills.
+    pub fn strategy_performance(&self) -> &HashMap<String, StrategyPerformance> {
+        &self.by_strategy
     }
 
     /// Performance metrics.
@@ -315,14 +1278,7 @@ impl LiveExecutor {
         let cash = self.state.get_balance();
         let pos_value = self.state.get_total_position_value();
         let pnl = equity - self.initial_balance;
-        let pnl_pct = if self.initial_balance > Decimal::ZERO {
-            ((pnl / self.initial_balance) * Decimal::ONE_HUNDRED)
-                .to_string()
-                .parse::<f64>()
-                .unwrap_or(0.0)
-        } else {
-            0.0
-        };
+        let pnl_pct = to_f64(pct(pnl, self.initial_balance));
 
         let mut m = HashMap::new();
         m.insert("mode".to_string(), serde_json::json!("live"));
@@ -340,29 +1296,664 @@ impl LiveExecutor {
         );
         m.insert(
             "initial_balance".to_string(),
-            serde_json::json!(self.initial_balance.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(self.initial_balance)),
         );
         m.insert(
             "current_balance".to_string(),
-            serde_json::json!(cash.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(cash)),
         );
         m.insert(
             "position_value".to_string(),
-            serde_json::json!(pos_value.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(pos_value)),
         );
         m.insert(
             "total_equity".to_string(),
-            serde_json::json!(equity.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(equity)),
         );
         m.insert(
             "total_pnl".to_string(),
-            serde_json::json!(pnl.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(pnl)),
         );
         m.insert("pnl_percent".to_string(), serde_json::json!(pnl_pct));
+        m.insert(
+            "sharpe_ratio".to_string(),
+            serde_json::json!(self.equity_series.sharpe()),
+        );
+        m.insert(
+            "sortino_ratio".to_string(),
+            serde_json::json!(self.equity_series.sortino()),
+        );
         m.insert(
             "open_positions".to_string(),
             serde_json::json!(self.state.get_all_positions().len()),
         );
+        m.insert(
+            "by_strategy".to_string(),
+            strategy_performance_json(&self.by_strategy),
+        );
         m
     }
 }
+
+/// Parse an API intent string, defaulting to `BuyLong` for unknown values.
+fn parse_intent(intent: &str) -> OrderIntent {
+    match intent {
+        "ORDER_INTENT_BUY_LONG" => OrderIntent::BuyLong,
+        "ORDER_INTENT_SELL_LONG" => OrderIntent::SellLong,
+        "ORDER_INTENT_BUY_SHORT" => OrderIntent::BuyShort,
+        "ORDER_INTENT_SELL_SHORT" => OrderIntent::SellShort,
+        _ => OrderIntent::BuyLong,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock_exchange::{self, ok, Reply};
+    use crate::clock::{Clock, MockClock};
+    use crate::state::state_manager::MarketState;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Serve requests until aborted: order placement returns `order_body`,
+    /// and the account endpoints report no positions and no open orders.
+    async fn serve_exchange(listener: TcpListener, order_body: &'static str) {
+        serve_exchange_with_lookup(listener, order_body, "{}").await
+    }
+
+    /// `serve_exchange`, answering single-order lookups with `lookup_body`.
+    async fn serve_exchange_with_lookup(
+        listener: TcpListener,
+        order_body: &'static str,
+        lookup_body: &'static str,
+    ) {
+        mock_exchange::serve(listener, move |_, path| exchange(path, order_body, lookup_body))
+            .await
+    }
+
+    /// The `serve_exchange` routes.
+    fn exchange(path: &str, order_body: &str, lookup_body: &str) -> Reply {
+        match path {
+            "/v1/orders" => ok(order_body),
+            "/v1/account/balances" => {
+                ok(r#"{"balances":[{"currency":"USD","availableBalance":"100"}]}"#)
+            }
+            "/v1/portfolio/positions" => ok(r#"{"positions":[]}"#),
+            "/v1/orders/open" => ok(r#"{"orders":[]}"#),
+            p if p.starts_with("/v1/order/") && p.ends_with("/cancel") => ok(
+                r#"{"orderId":"cancelled","marketSlug":"mkt","intent":"ORDER_INTENT_BUY_LONG",
+                    "quantity":0,"status":"ORDER_STATE_CANCELED"}"#,
+            ),
+            p if p.starts_with("/v1/order/") => ok(lookup_body),
+            _ => ok("{}"),
+        }
+    }
+
+    fn buy_signal(market: &str) -> Signal {
+        Signal {
+            market_slug: market.to_string(),
+            action: SignalAction::BuyYes,
+            price: dec!(0.50),
+            quantity: 10,
+            urgency: Urgency::Low,
+            confidence: 0.8,
+            strategy_name: "test".to_string(),
+            reason: "test buy".to_string(),
+            metadata: HashMap::new(),
+            timestamp: Utc::now(),
+            reduce_only: false,
+            good_till: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fresh_order_survives_reconcile_within_orphan_grace() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange_with_lookup(
+            listener,
+            r#"{"orderId":"ord-1"}"#,
+            r#"{"orderId":"ord-1","marketSlug":"mkt","intent":"ORDER_INTENT_BUY_LONG",
+                "quantity":10,"filledQuantity":10,"avgFillPrice":"0.49","status":"FILLED"}"#,
+        ));
+
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let state = StateManager::new(dec!(100));
+        let clock = MockClock::default();
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_clock(Arc::new(clock.clone()));
+        exec.set_orphan_grace_secs(5.0);
+        let mut fills = exec.subscribe_fills();
+
+        let result = exec.execute_signal(&buy_signal("mkt")).await;
+        assert_eq!(result.order_id, "ord-1");
+
+        // Placed 1s ago and not yet listed as open: still tracked.
+        clock.advance(Duration::seconds(1));
+        exec.reconcile_state().await.unwrap();
+        assert!(state.get_order("ord-1").is_some_and(|o| o.is_open()));
+        assert!(fills.try_recv().is_err(), "No fill assumed inside the grace period");
+
+        // Past the grace period the order is looked up: it filled.
+        clock.advance(Duration::seconds(5));
+        exec.reconcile_state().await.unwrap();
+        assert!(state.get_order("ord-1").is_none());
+        let fill = fills.try_recv().unwrap();
+        assert_eq!((fill.quantity, fill.price), (10, dec!(0.49)));
+        assert_eq!(fill.timestamp, clock.now());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_cancelled_order_publishes_only_real_fills() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange_with_lookup(
+            listener,
+            r#"{"orderId":"ord-1"}"#,
+            r#"{"orderId":"ord-1","marketSlug":"mkt","intent":"ORDER_INTENT_BUY_LONG",
+                "quantity":10,"filledQuantity":4,"avgFillPrice":"0.50","status":"EXPIRED"}"#,
+        ));
+
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let state = StateManager::new(dec!(100));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_orphan_grace_secs(0.0);
+        let mut fills = exec.subscribe_fills();
+
+        exec.execute_signal(&buy_signal("mkt")).await;
+        exec.reconcile_state().await.unwrap();
+        assert!(state.get_order("ord-1").is_none());
+        assert_eq!(fills.try_recv().unwrap().quantity, 4);
+        assert!(fills.try_recv().is_err(), "The unfilled remainder is not a fill");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_order_that_cannot_be_fetched_is_not_filled() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let state = StateManager::new(dec!(100));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_orphan_grace_secs(0.0);
+        let mut fills = exec.subscribe_fills();
+
+        exec.execute_signal(&buy_signal("mkt")).await;
+        exec.reconcile_state().await.unwrap();
+        assert!(state.get_order("ord-1").is_none());
+        assert!(fills.try_recv().is_err());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_immediate_execution_updates_position_synchronously() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(
+            listener,
+            r#"{"orderId":"ord-2","executions":[
+                {"id":"ex-1","price":{"value":"0.48","currency":"USD"},"quantity":10,"fee":"0.05"}
+            ]}"#,
+        ));
+
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        let mut fills = exec.subscribe_fills();
+
+        let result = exec.execute_signal(&buy_signal("mkt")).await;
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.filled_quantity, 10);
+        assert_eq!(result.avg_fill_price, Some(dec!(0.48)));
+        assert_eq!(result.fee, dec!(0.05));
+
+        // Position and balance reflect the fill before any reconcile.
+        let pos = state.get_position("mkt").unwrap();
+        assert_eq!((pos.side, pos.quantity, pos.avg_price), (Side::Yes, 10, dec!(0.48)));
+        assert_eq!(state.get_balance(), dec!(95.15)); // 100 - 4.80 - 0.05
+        assert!(state.get_order("ord-2").is_none(), "Filled order is not left open");
+
+        let fill = fills.try_recv().unwrap();
+        assert_eq!((fill.quantity, fill.price, fill.fee), (10, dec!(0.48), dec!(0.05)));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_identical_buys_within_dedupe_window_place_one_order() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let clock = MockClock::default();
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_clock(Arc::new(clock.clone()));
+        exec.set_duplicate_guard(2.0, dec!(0.01));
+
+        let first = exec.execute_signal(&buy_signal("mkt")).await;
+        assert_eq!(first.order_id, "ord-1");
+
+        let second = exec.execute_signal(&buy_signal("mkt")).await;
+        assert_eq!(second.status, OrderStatus::Rejected);
+        assert!(second.error.unwrap().contains("Duplicate of open order ord-1"));
+        assert_eq!(state.get_open_orders(Some("mkt")).len(), 1);
+
+        // A larger order adds size the resting one does not cover.
+        let mut larger = buy_signal("mkt");
+        larger.quantity = 15;
+        assert_eq!(exec.execute_signal(&larger).await.order_id, "ord-1");
+
+        // A flagged replacement, or the same order after the window, goes out.
+        let mut replacement = buy_signal("mkt");
+        replacement
+            .metadata
+            .insert(REPLACEMENT_KEY.to_string(), serde_json::json!(true));
+        assert_eq!(exec.execute_signal(&replacement).await.order_id, "ord-1");
+        clock.advance(Duration::seconds(3));
+        assert_eq!(exec.execute_signal(&buy_signal("mkt")).await.order_id, "ord-1");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ladder_levels_a_tick_apart_are_not_duplicates() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_clock(Arc::new(MockClock::default()));
+        exec.set_duplicate_guard(2.0, dec!(0.01));
+
+        // A 3-level bid ladder stepped one dedupe tick apart.
+        let level = |level: u64, price: Decimal| {
+            let mut signal = buy_signal("mkt");
+            signal.price = price;
+            signal
+                .metadata
+                .insert(LADDER_LEVEL_KEY.to_string(), serde_json::json!(level));
+            signal
+        };
+        for (n, price) in [(1, dec!(0.48)), (2, dec!(0.47)), (3, dec!(0.46))] {
+            let result = exec.execute_signal(&level(n, price)).await;
+            assert_eq!(result.order_id, "ord-1", "level {n}: {:?}", result.error);
+        }
+
+        // Re-sending a level that is still resting is suppressed.
+        let again = exec.execute_signal(&level(3, dec!(0.46))).await;
+        assert_eq!(again.status, OrderStatus::Rejected);
+        assert!(again.error.unwrap().contains("Duplicate of open order"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_cash_buffer_caps_live_buy_like_risk_and_paper() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        // $100 cash, buffer 0.50, buy 500 at 0.50: floor(100 * 0.50 / 0.50)
+        // = 100, the quantity risk and paper cap to as well (math_tests 33).
+        let buffer = dec!(0.50);
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+        exec.set_cash_buffer_pct(buffer);
+        let mut signal = buy_signal("cash");
+        signal.quantity = 500;
+
+        let result = exec.execute_signal(&signal).await;
+        assert_eq!(result.order_id, "ord-1", "{:?}", result.error);
+        let expected = max_affordable_quantity(dec!(100), dec!(0.50), buffer);
+        assert_eq!(state.get_order("ord-1").unwrap().quantity, expected);
+        assert_eq!(expected, 100);
+
+        server.abort();
+    }
+
+    /// Executor holding only a NO hedge in `mkt`, against an exchange
+    /// whose side close answers `close_status`.
+    async fn hedged_executor(close_status: u16) -> (LiveExecutor, JoinHandle<()>) {
+        let (base_url, server) = mock_exchange::spawn(move |_, path| match path {
+            "/v1/order/close-position" => (close_status, r#"{"orderId":"close-1"}"#.to_string()),
+            p => exchange(p, r#"{"orderId":"ord-1"}"#, "{}"),
+        })
+        .await;
+        let state = StateManager::new(dec!(100));
+        state.update_hedge_position("mkt", Side::No, 8, dec!(0.40));
+        let client = mock_exchange::client(&base_url);
+        (LiveExecutor::new(client, state, OrderBookTracker::new()), server)
+    }
+
+    #[tokio::test]
+    async fn test_close_side_counts_hedge_and_reports_rate_limits() {
+        let (mut exec, server) = hedged_executor(200).await;
+        assert_eq!(exec.close_side("mkt", Side::No).await.order_id, "close-1");
+        assert_eq!(exec.close_side("mkt", Side::Yes).await.status, OrderStatus::Rejected);
+        server.abort();
+
+        // A 429 is not "side close unsupported": no fallback sell.
+        let (mut exec, server) = hedged_executor(429).await;
+        let limited = exec.close_side("mkt", Side::No).await;
+        assert_eq!(limited.status, OrderStatus::Rejected);
+        assert_eq!(limited.reject_reason, Some(RejectReason::Throttled));
+        assert!(exec.state.get_open_orders(None).is_empty());
+        server.abort();
+
+        // Nor is a plain rejection of the close.
+        let (mut exec, server) = hedged_executor(400).await;
+        let rejected = exec.close_side("mkt", Side::No).await;
+        assert_eq!(rejected.reject_reason, Some(RejectReason::ApiRejected));
+        assert!(exec.state.get_open_orders(None).is_empty());
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_side_close_fallback_sells_hedged_quantity() {
+        // Unsupported side close: sell the 8 NO held as a hedge instead.
+        let (mut exec, server) = hedged_executor(404).await;
+        let fallback = exec.close_side("mkt", Side::No).await;
+        assert_eq!(fallback.order_id, "ord-1", "{:?}", fallback.error);
+        let order = exec.state.get_order("ord-1").unwrap();
+        assert_eq!((order.intent, order.quantity), (OrderIntent::SellShort, 8));
+
+        // A sell above position plus hedge is capped to their sum.
+        exec.state.update_position("mkt", Side::No, 5, dec!(0.40));
+        exec.state.remove_order("ord-1");
+        let mut sell = buy_signal("mkt");
+        sell.action = SignalAction::SellNo;
+        sell.quantity = 20;
+        assert_eq!(exec.execute_signal(&sell).await.order_id, "ord-1");
+        assert_eq!(exec.state.get_order("ord-1").unwrap().quantity, 13);
+
+        server.abort();
+    }
+
+    /// YES bids 500 deep and NO bids `no_bid_depth` deep in "mkt".
+    fn arb_exit_book(no_bid_depth: i64) -> OrderBook {
+        OrderBook {
+            market_slug: "mkt".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: dec!(0.40), quantity: 500 }],
+                asks: vec![PriceLevel { price: dec!(0.42), quantity: 500 }],
+            },
+            no: OrderBookSide {
+                bids: vec![PriceLevel { price: dec!(0.56), quantity: no_bid_depth }],
+                asks: vec![PriceLevel { price: dec!(0.58), quantity: 500 }],
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unwind_arb_sells_unpaired_leg_then_pairs_when_both_bid() {
+        // Each order gets the next id and rests unfilled.
+        let next_id = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let (base_url, server) = mock_exchange::spawn(move |_, path| match path {
+            "/v1/orders" => {
+                let id = next_id.fetch_add(1, Ordering::Relaxed) + 1;
+                ok(&format!(r#"{{"orderId":"ord-{id}"}}"#))
+            }
+            p => exchange(p, "{}", "{}"),
+        })
+        .await;
+        // An arb entry whose NO leg filled 8 of 20, next to 5 YES another
+        // strategy holds.
+        let state = StateManager::new(dec!(100));
+        state.update_position("mkt", Side::Yes, 25, dec!(0.42));
+        state.update_hedge_position("mkt", Side::No, 8, dec!(0.58));
+        let ob = OrderBookTracker::new();
+        ob.update(arb_exit_book(5));
+        let client = mock_exchange::client(&base_url);
+        let mut exec = LiveExecutor::new(client, state.clone(), ob.clone());
+        let mut arb_leg = buy_signal("mkt");
+        arb_leg.metadata.insert(ARB_PAIR_KEY.to_string(), serde_json::json!("mkt"));
+        exec.arb_holdings.track_order("entry-yes", &arb_leg);
+        exec.arb_holdings.record_fill("entry-yes", Side::Yes, true, 20);
+        exec.arb_holdings.track_order("entry-no", &arb_leg);
+        exec.arb_holdings.record_fill("entry-no", Side::No, true, 8);
+        // A later leg still resting as ord-1.
+        arb_leg.action = SignalAction::BuyNo;
+        assert_eq!(exec.execute_signal(&arb_leg).await.status, OrderStatus::Open);
+        let orders = |state: &StateManager| {
+            let mut orders: Vec<_> = state
+                .get_open_orders(None)
+                .into_iter()
+                .map(|o| (o.order_id, o.intent, o.quantity))
+                .collect();
+            orders.sort_by(|a, b| a.0.cmp(&b.0));
+            orders
+        };
+
+        // The resting leg is cancelled first. Only 5 NO bid against 8 pairs:
+        // the 12 unpaired arb YES go, the pairs and the other 5 YES stay.
+        let result = exec.unwind_arb("mkt").await;
+        assert_eq!(result.cancelled, 1);
+        assert!(result.error.unwrap().contains("aborted"));
+        assert_eq!(orders(&state), vec![("ord-2".to_string(), OrderIntent::SellLong, 12)]);
+
+        // With the NO bids back, both pair legs go out together.
+        state.remove_order("ord-2");
+        ob.update(arb_exit_book(500));
+        let result = exec.unwind_arb("mkt").await;
+        assert!(result.error.is_none());
+        let sent: Vec<_> = orders(&state).into_iter().map(|(_, i, q)| (i, q)).collect();
+        assert_eq!(
+            sent,
+            vec![
+                (OrderIntent::SellLong, 12),
+                (OrderIntent::SellLong, 8),
+                (OrderIntent::SellShort, 8),
+            ]
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_large_buy_on_shallow_book_resized_to_preserve_edge() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let ob = OrderBookTracker::new();
+        ob.update(OrderBook {
+            market_slug: "mkt".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: dec!(0.45), quantity: 100 }],
+                asks: vec![
+                    PriceLevel { price: dec!(0.50), quantity: 10 },
+                    PriceLevel { price: dec!(0.55), quantity: 10 },
+                    PriceLevel { price: dec!(0.70), quantity: 50 },
+                ],
+            },
+            no: OrderBookSide::default(),
+        });
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), ob);
+        exec.set_impact_min_edge(dec!(0.05));
+
+        // Fair 0.60 allows a VWAP up to 0.55: 23 contracts average ~0.548,
+        // the full 40 would average 0.6125.
+        let mut signal = buy_signal("mkt");
+        signal.quantity = 40;
+        signal.metadata.insert("true_probability".to_string(), serde_json::json!(0.60));
+        let result = exec.execute_signal(&signal).await;
+        assert_eq!(result.order_id, "ord-1");
+        assert_eq!(state.get_order("ord-1").unwrap().quantity, 23);
+
+        // Fair 0.52 leaves no edge even at the best ask.
+        signal.metadata.insert("true_probability".to_string(), serde_json::json!(0.52));
+        let result = exec.execute_signal(&signal).await;
+        assert_eq!(result.status, OrderStatus::Rejected);
+        assert!(result.error.unwrap().contains("impact"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_open_order_cap_blocks_or_evicts_furthest_from_mid() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-new"}"#));
+
+        let state = StateManager::new(dec!(100));
+        for slug in ["mkt-a", "mkt-b"] {
+            state.update_market(MarketState {
+                market_slug: slug.to_string(),
+                title: slug.to_string(),
+                yes_bid: Some(dec!(0.48)),
+                yes_ask: Some(dec!(0.52)),
+                no_bid: Some(dec!(0.48)),
+                no_ask: Some(dec!(0.52)),
+                yes_bid_depth: None,
+                yes_ask_depth: None,
+                resolution_date: None,
+                tick_size: None,
+                min_order_size: None,
+                last_updated: Utc::now(),
+            });
+        }
+        // Mid 0.50: resting 0.01, 0.05 and 0.20 away.
+        for (id, market, price) in [
+            ("ord-1", "mkt-a", dec!(0.49)),
+            ("ord-2", "mkt-b", dec!(0.45)),
+            ("ord-3", "mkt-b", dec!(0.30)),
+        ] {
+            state.add_order(OrderState {
+                order_id: id.to_string(),
+                market_slug: market.to_string(),
+                intent: OrderIntent::BuyLong,
+                price,
+                quantity: 5,
+                filled_quantity: 0,
+                status: OrderStatus::Open,
+            });
+        }
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+
+        // At the cap without eviction: refused, nothing cancelled.
+        exec.set_open_order_cap(3, false);
+        let blocked = exec.execute_signal(&buy_signal("mkt-a")).await;
+        assert_eq!(blocked.status, OrderStatus::Rejected);
+        assert!(blocked.error.unwrap().contains("Open order cap reached (3)"));
+        assert_eq!(state.get_open_orders(None).len(), 3);
+
+        // With eviction, a 0.50 bid replaces the 0.30 one.
+        exec.set_open_order_cap(3, true);
+        let placed = exec.execute_signal(&buy_signal("mkt-a")).await;
+        assert_eq!(placed.order_id, "ord-new");
+        assert!(state.get_order("ord-3").is_none());
+        assert!(state.get_order("ord-2").is_some());
+        assert_eq!(state.get_open_orders(None).len(), 3);
+
+        // An order further out than everything resting is still refused.
+        let mut far = buy_signal("mkt-b");
+        far.price = dec!(0.20);
+        assert_eq!(exec.execute_signal(&far).await.status, OrderStatus::Rejected);
+        assert_eq!(state.get_open_orders(None).len(), 3);
+
+        // Selling held inventory is never blocked by the cap.
+        exec.set_open_order_cap(3, false);
+        state.update_position("mkt-a", Side::Yes, 10, dec!(0.40));
+        let exit = Signal {
+            action: SignalAction::SellYes,
+            ..buy_signal("mkt-a")
+        };
+        assert_eq!(exec.execute_signal(&exit).await.order_id, "ord-new");
+        assert_eq!(state.get_order("ord-new").unwrap().intent, OrderIntent::SellLong);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_everything_clears_open_orders_across_markets() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+
+        exec.execute_signal(&buy_signal("mkt-a")).await;
+        for (id, market) in [("ord-2", "mkt-b"), ("ord-3", "mkt-c")] {
+            state.add_order(OrderState {
+                order_id: id.to_string(),
+                market_slug: market.to_string(),
+                intent: OrderIntent::BuyLong,
+                price: dec!(0.40),
+                quantity: 5,
+                filled_quantity: 0,
+                status: OrderStatus::Open,
+            });
+        }
+        assert_eq!(state.get_open_orders(None).len(), 3);
+
+        let cancelled = exec.cancel_everything().await.unwrap();
+
+        assert_eq!(cancelled, 3);
+        assert!(state.get_open_orders(None).is_empty());
+        assert!(state.get_order("ord-1").is_none());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_signal_cancels_only_that_order() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_exchange(listener, r#"{"orderId":"ord-1"}"#));
+
+        let state = StateManager::new(dec!(100));
+        let client = mock_exchange::client(&format!("http://{}", addr));
+        let mut exec = LiveExecutor::new(client, state.clone(), OrderBookTracker::new());
+
+        exec.execute_signal(&buy_signal("mkt")).await;
+        state.add_order(OrderState {
+            order_id: "ord-2".to_string(),
+            market_slug: "mkt".to_string(),
+            intent: OrderIntent::SellLong,
+            price: dec!(0.60),
+            quantity: 5,
+            filled_quantity: 0,
+            status: OrderStatus::Open,
+        });
+        assert_eq!(state.get_open_orders(Some("mkt")).len(), 2);
+
+        let mut cancel = buy_signal("mkt");
+        cancel.action = SignalAction::CancelOrder;
+        cancel
+            .metadata
+            .insert(CANCEL_ORDER_ID_KEY.to_string(), serde_json::json!("ord-1"));
+        let result = exec.execute_signal(&cancel).await;
+
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(result.order_id, "ord-1");
+        assert!(state.get_order("ord-1").is_none());
+        let open = state.get_open_orders(Some("mkt"));
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].order_id, "ord-2");
+
+        cancel.metadata.clear();
+        assert_eq!(exec.execute_signal(&cancel).await.status, OrderStatus::Rejected);
+        assert_eq!(state.get_open_orders(Some("mkt")).len(), 1);
+
+        server.abort();
+    }
+}
@@ -1,2 +1,7 @@
+pub mod equity_export;
+pub mod equity_series;
 pub mod executor;
 pub mod paper;
+pub mod run_report;
+pub mod shadow;
+pub mod trade_log;
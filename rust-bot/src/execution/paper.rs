@@ -10,12 +10,18 @@
 use rust_decimal::Decimal;
 use chrono::Utc;
 use std::collections::HashMap;
-use tracing::{info, debug};
+use tokio::sync::broadcast;
+use tracing::{info, debug, error, warn};
 
+use crate::clock::{RealClock, SharedClock};
 use crate::data::models::*;
 use crate::data::orderbook::OrderBookTracker;
-use crate::execution::executor::ExecResult;
+use crate::execution::equity_series::EquitySeries;
+use crate::execution::executor::{ExecResult, RejectReason};
+use crate::execution::trade_log::{TradeLogHandle, TradeRecord};
 use crate::state::state_manager::{StateManager, OrderState};
+use crate::util::dec::{floor_i64, from_f64, pct, scale_bps, to_f64};
+use crate::util::rng::SplitMix64;
 
 // =============================================================================
 // Constants
@@ -24,8 +30,15 @@ use crate::state::state_manager::{StateManager, OrderState};
 /// Taker fee: 10 basis points (0.1%).
 const TAKER_FEE_RATE: &str = "0.001";
 
-/// Simulated slippage for market orders (5 bps beyond best price).
-const MARKET_ORDER_SLIPPAGE_BPS: &str = "0.0005";
+/// Simulated slippage for market orders, in bps beyond the best price.
+const MARKET_ORDER_SLIPPAGE_BPS: i64 = 5;
+
+/// Smoothing factor for the per-strategy EWMA of realized P&L per
+/// closing trade (higher = more weight on recent trades).
+const STRATEGY_PNL_EWMA_ALPHA: Decimal = Decimal::from_parts(1, 0, 0, false, 1);
+
+/// Capacity of the fill notification channel.
+pub const FILL_CHANNEL_CAPACITY: usize = 1024;
 
 // =============================================================================
 // Paper Fill
@@ -36,6 +49,7 @@ const MARKET_ORDER_SLIPPAGE_BPS: &str = "0.0005";
 pub struct PaperFill {
     pub order_id: String,
     pub market_slug: String,
+    pub strategy_name: String,
     pub side: Side,
     pub is_buy: bool,
     pub price: Decimal,
@@ -44,6 +58,22 @@ pub struct PaperFill {
     pub timestamp: chrono::DateTime<Utc>,
 }
 
+impl From<&PaperFill> for FillEvent {
+    fn from(fill: &PaperFill) -> Self {
+        Self {
+            order_id: fill.order_id.clone(),
+            market_slug: fill.market_slug.clone(),
+            strategy_name: fill.strategy_name.clone(),
+            side: fill.side,
+            is_buy: fill.is_buy,
+            price: fill.price,
+            quantity: fill.quantity,
+            fee: fill.fee,
+            timestamp: fill.timestamp,
+        }
+    }
+}
+
 // =============================================================================
 // Resting Order
 // =============================================================================
@@ -53,11 +83,21 @@ pub struct PaperFill {
 pub struct RestingOrder {
     pub order_id: String,
     pub market_slug: String,
+    pub strategy_name: String,
     pub intent: OrderIntent,
     pub price: Decimal,
     pub total_quantity: i64,
     pub filled_quantity: i64,
     pub created_at: chrono::DateTime<Utc>,
+    /// Cancelled by `check_resting_orders` once this time has passed.
+    pub expires_at: Option<chrono::DateTime<Utc>>,
+    /// Displayed volume still ahead of us at our price (queue model only).
+    pub queue_ahead: i64,
+    /// Displayed size at our price when last observed (queue model only).
+    pub level_size: i64,
+    /// Mid price last seen while the order rested without filling. Used as
+    /// the fill-time mid, since the crossing book itself is post-trade.
+    pub last_mid: Option<Decimal>,
 }
 
 impl RestingOrder {
@@ -66,6 +106,182 @@ impl RestingOrder {
     }
 }
 
+// =============================================================================
+// Arb Unwind
+// =============================================================================
+
+/// Outcome of `unwind_arb` on either executor.
+#[derive(Debug, Clone, Default)]
+pub struct ArbUnwindResult {
+    /// Resting arb leg orders cancelled before anything was sold.
+    pub cancelled: usize,
+    /// Contracts sold on the leg held without a partner.
+    pub unpaired: i64,
+    /// Matched YES/NO pairs sold on both legs.
+    pub unwound: i64,
+    /// Contracts bought back on the leg that sold more than the other.
+    pub rolled_back: i64,
+    /// Net cash received, after fees and any buy-back.
+    pub proceeds: Decimal,
+    /// Why the unwind aborted or only partly completed.
+    pub error: Option<String>,
+}
+
+/// Contracts bought by each arb pair's legs (orders carrying
+/// `ARB_PAIR_KEY`) and not yet unwound, so an unwind sells the arb's own
+/// inventory and leaves other strategies' holdings in the market alone.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ArbHoldings {
+    /// Pair -> (YES, NO) contracts.
+    held: HashMap<String, (i64, i64)>,
+    /// Leg order id -> pair, while the order may still fill.
+    orders: HashMap<String, String>,
+}
+
+impl ArbHoldings {
+    /// Attribute future fills of `order_id` to `signal`'s arb pair, if any.
+    pub(crate) fn track_order(&mut self, order_id: &str, signal: &Signal) {
+        if let Some(pair) = signal.arb_pair() {
+            self.orders.insert(order_id.to_string(), pair.to_string());
+        }
+    }
+
+    pub(crate) fn forget_order(&mut self, order_id: &str) {
+        self.orders.remove(order_id);
+    }
+
+    /// Tracked leg orders of `pair`.
+    pub(crate) fn orders(&self, pair: &str) -> Vec<String> {
+        self.orders
+            .iter()
+            .filter(|(_, p)| p.as_str() == pair)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Count a buy fill on a tracked leg order.
+    pub(crate) fn record_fill(&mut self, order_id: &str, side: Side, is_buy: bool, quantity: i64) {
+        let Some(pair) = self.orders.get(order_id).filter(|_| is_buy) else {
+            return;
+        };
+        let held = self.held.entry(pair.clone()).or_default();
+        match side {
+            Side::Yes => held.0 += quantity,
+            Side::No => held.1 += quantity,
+        }
+    }
+
+    /// Count `quantity` filled by an unwind leg of `pair`.
+    pub(crate) fn record_unwind(&mut self, pair: &str, action: SignalAction, quantity: i64) {
+        let held = self.held.entry(pair.to_string()).or_default();
+        match action {
+            SignalAction::SellYes => held.0 = (held.0 - quantity).max(0),
+            SignalAction::SellNo => held.1 = (held.1 - quantity).max(0),
+            SignalAction::BuyYes => held.0 += quantity,
+            SignalAction::BuyNo => held.1 += quantity,
+            _ => {}
+        }
+    }
+
+    /// (YES, NO) contracts attributed to `pair`.
+    pub(crate) fn held(&self, pair: &str) -> (i64, i64) {
+        self.held.get(pair).copied().unwrap_or_default()
+    }
+}
+
+/// Market order on one leg of an arb unwind, priced at the quote it
+/// crosses: the best bid for a sell, the best ask for a buy-back.
+pub(crate) fn arb_leg_signal(
+    orderbook: &OrderBookTracker,
+    market_slug: &str,
+    action: SignalAction,
+    quantity: i64,
+    now: chrono::DateTime<Utc>,
+) -> Signal {
+    let top = orderbook.get_top(market_slug);
+    let quote = top.and_then(|t| match action {
+        SignalAction::SellYes => t.yes_best_bid,
+        SignalAction::SellNo => t.no_best_bid,
+        SignalAction::BuyYes => t.yes_best_ask,
+        SignalAction::BuyNo => t.no_best_ask,
+        _ => None,
+    });
+    // Without a quote: the worst price in the direction of the order.
+    let fallback = if action.is_buy() {
+        Decimal::new(99, 2)
+    } else {
+        Decimal::new(1, 2)
+    };
+    Signal {
+        market_slug: market_slug.to_string(),
+        action,
+        price: quote.unwrap_or(fallback),
+        quantity,
+        urgency: Urgency::Critical,
+        confidence: 1.0,
+        strategy_name: "arb_unwind".to_string(),
+        reason: "Unwind completeness arb pair".to_string(),
+        metadata: HashMap::new(),
+        timestamp: now,
+        reduce_only: false,
+        good_till: None,
+    }
+}
+
+/// Sell on the leg holding more contracts for the excess over the other:
+/// `None` when the legs are even.
+pub(crate) fn unpaired_sell(yes_held: i64, no_held: i64) -> Option<(SignalAction, i64)> {
+    match yes_held - no_held {
+        0 => None,
+        d if d > 0 => Some((SignalAction::SellYes, d)),
+        d => Some((SignalAction::SellNo, -d)),
+    }
+}
+
+/// Buy-back that squares up legs sold unevenly: `None` when they matched.
+pub(crate) fn arb_buy_back(yes_sold: i64, no_sold: i64) -> Option<(SignalAction, i64)> {
+    match yes_sold - no_sold {
+        0 => None,
+        d if d > 0 => Some((SignalAction::BuyYes, d)),
+        d => Some((SignalAction::BuyNo, -d)),
+    }
+}
+
+// =============================================================================
+// Stress Mode
+// =============================================================================
+
+/// Deliberately bad execution for robustness testing. Applies to fills
+/// taken against the book when an order is placed; resting orders still
+/// fill at their limit price.
+#[derive(Debug, Clone)]
+pub struct StressConfig {
+    /// Multiplier on market-order slippage (1 = normal). Crossing limit
+    /// fills get the extra slippage too, but never past the limit.
+    pub slippage_multiplier: Decimal,
+    /// Chance that an immediate fill is cut to a random fraction.
+    pub partial_fill_prob: f64,
+    /// Chance that an order is rejected outright.
+    pub reject_prob: f64,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            slippage_multiplier: Decimal::ONE,
+            partial_fill_prob: 0.0,
+            reject_prob: 0.0,
+        }
+    }
+}
+
+/// Stress config plus the seeded generator driving it.
+#[derive(Debug, Clone)]
+struct FillStress {
+    config: StressConfig,
+    rng: SplitMix64,
+}
+
 // =============================================================================
 // Performance Metrics
 // =============================================================================
@@ -81,6 +297,12 @@ pub struct PaperPerformance {
     pub max_drawdown: Decimal,
     pub total_fees_paid: Decimal,
     pub total_volume: Decimal,
+    /// Realized P&L earned by filling better than the mid (entry and exit).
+    pub spread_pnl: Decimal,
+    /// Realized P&L from mid-price movement between entry and exit.
+    pub directional_pnl: Decimal,
+    /// Per-strategy attribution, keyed by `Signal::strategy_name`.
+    pub by_strategy: HashMap<String, StrategyPerformance>,
 }
 
 impl PaperPerformance {
@@ -94,6 +316,9 @@ impl PaperPerformance {
             max_drawdown: Decimal::ZERO,
             total_fees_paid: Decimal::ZERO,
             total_volume: Decimal::ZERO,
+            spread_pnl: Decimal::ZERO,
+            directional_pnl: Decimal::ZERO,
+            by_strategy: HashMap::new(),
         }
     }
 
@@ -116,6 +341,83 @@ impl PaperPerformance {
     }
 }
 
+/// Performance attributed to a single strategy's fills.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyPerformance {
+    pub total_trades: u64,
+    pub winning_trades: u64,
+    pub losing_trades: u64,
+    pub realized_pnl: Decimal,
+    pub fees_paid: Decimal,
+    pub volume: Decimal,
+    /// Exponentially-weighted realized P&L per closing trade.
+    pub ewma_pnl: Decimal,
+    closing_trades: u64,
+}
+
+impl StrategyPerformance {
+    /// Record one fill. `closes` marks fills that realize P&L (sells).
+    pub fn record_fill(
+        &mut self,
+        notional: Decimal,
+        fee: Decimal,
+        realized_pnl: Decimal,
+        closes: bool,
+    ) {
+        self.total_trades += 1;
+        self.fees_paid += fee;
+        self.volume += notional;
+        self.realized_pnl += realized_pnl;
+        if realized_pnl > Decimal::ZERO {
+            self.winning_trades += 1;
+        } else if realized_pnl < Decimal::ZERO {
+            self.losing_trades += 1;
+        }
+
+        if closes {
+            self.ewma_pnl = if self.closing_trades == 0 {
+                realized_pnl
+            } else {
+                STRATEGY_PNL_EWMA_ALPHA * realized_pnl
+                    + (Decimal::ONE - STRATEGY_PNL_EWMA_ALPHA) * self.ewma_pnl
+            };
+            self.closing_trades += 1;
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.total_trades == 0 {
+            return 0.0;
+        }
+        self.winning_trades as f64 / self.total_trades as f64
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "total_trades": self.total_trades,
+            "winning_trades": self.winning_trades,
+            "losing_trades": self.losing_trades,
+            "win_rate": self.win_rate(),
+            "realized_pnl": to_f64(self.realized_pnl),
+            "total_fees_paid": to_f64(self.fees_paid),
+            "total_volume": to_f64(self.volume),
+            "ewma_pnl": to_f64(self.ewma_pnl),
+        })
+    }
+}
+
+/// `by_strategy` entry for `get_performance`, shared by both executors.
+pub fn strategy_performance_json(
+    by_strategy: &HashMap<String, StrategyPerformance>,
+) -> serde_json::Value {
+    serde_json::Value::Object(
+        by_strategy
+            .iter()
+            .map(|(name, perf)| (name.clone(), perf.to_json()))
+            .collect(),
+    )
+}
+
 // =============================================================================
 // Paper Position (internal tracking with entry price for P&L)
 // =============================================================================
@@ -128,28 +430,45 @@ struct PaperPosition {
     quantity: i64,
     avg_price: Decimal,
     total_cost: Decimal,
+    /// Sum of the mid price at each entry fill, weighted by quantity.
+    total_entry_mid: Decimal,
+    /// Strategy whose fill opened the position; settlement P&L is booked
+    /// to it.
+    strategy_name: String,
 }
 
 impl PaperPosition {
-    fn new(side: Side, quantity: i64, price: Decimal) -> Self {
+    fn new(side: Side, quantity: i64, price: Decimal, strategy_name: &str) -> Self {
         Self {
             side,
             quantity,
             avg_price: price,
             total_cost: price * Decimal::from(quantity),
+            total_entry_mid: price * Decimal::from(quantity),
+            strategy_name: strategy_name.to_string(),
         }
     }
 
     /// Add to position (same direction). Updates weighted average price.
-    fn add(&mut self, quantity: i64, price: Decimal) {
+    fn add(&mut self, quantity: i64, price: Decimal, mid: Decimal) {
         let new_cost = price * Decimal::from(quantity);
         self.total_cost += new_cost;
+        self.total_entry_mid += mid * Decimal::from(quantity);
         self.quantity += quantity;
         if self.quantity > 0 {
             self.avg_price = self.total_cost / Decimal::from(self.quantity);
         }
     }
 
+    /// Quantity-weighted average mid price at entry.
+    fn avg_entry_mid(&self) -> Decimal {
+        if self.quantity > 0 {
+            self.total_entry_mid / Decimal::from(self.quantity)
+        } else {
+            Decimal::ZERO
+        }
+    }
+
     /// Reduce position. Returns realized P&L for the closed portion.
     fn reduce(&mut self, quantity: i64, exit_price: Decimal) -> Decimal {
         let close_qty = quantity.min(self.quantity);
@@ -160,14 +479,16 @@ impl PaperPosition {
         // Realized P&L = (exit_price - avg_entry) * quantity
         let pnl = (exit_price - self.avg_price) * Decimal::from(close_qty);
 
-        // Reduce total cost proportionally.
+        // Reduce total cost (and entry mids) proportionally.
         let cost_reduction = self.avg_price * Decimal::from(close_qty);
         self.total_cost -= cost_reduction;
+        self.total_entry_mid -= self.avg_entry_mid() * Decimal::from(close_qty);
         self.quantity -= close_qty;
 
         if self.quantity <= 0 {
             self.quantity = 0;
             self.total_cost = Decimal::ZERO;
+            self.total_entry_mid = Decimal::ZERO;
             self.avg_price = Decimal::ZERO;
         }
 
@@ -208,25 +529,55 @@ pub struct PaperExecutor {
 
     /// Slippage estimate for market orders.
     slippage_bps: Decimal,
+
+    /// Time source for fill timestamps and resting-order age.
+    clock: SharedClock,
+
+    /// Fill notifications for subscribers (e.g. the strategy engine).
+    fill_tx: broadcast::Sender<FillEvent>,
+
+    /// Make resting orders wait behind the displayed size at their price.
+    queue_model: bool,
+
+    /// Fill resting orders only on liquidity strictly better than their limit.
+    require_price_improvement_for_maker_fill: bool,
+
+    /// Simulate NO orders against the YES book's complement when the NO
+    /// side is empty.
+    synthesize_no_book: bool,
+
+    /// Structured trade log, if configured.
+    trade_log: Option<TradeLogHandle>,
+
+    /// Adverse-fill stress mode, if enabled.
+    stress: Option<FillStress>,
+
+    /// Share of cash a buy may commit; larger buys are capped to fit.
+    cash_buffer_pct: Decimal,
+
+    /// Equity sampled per tick for Sharpe and Sortino.
+    equity_series: EquitySeries,
+
+    /// Contracts and resting orders attributed to arb pairs.
+    arb_holdings: ArbHoldings,
 }
 
 impl PaperExecutor {
     pub fn new(state: StateManager, orderbook: OrderBookTracker) -> Self {
-        let initial_balance = state.get_balance();
+        Self::with_clock(state, orderbook, RealClock::shared())
+    }
+
+    /// Create with default fee/slippage and an explicit time source.
+    pub fn with_clock(
+        state: StateManager,
+        orderbook: OrderBookTracker,
+        clock: SharedClock,
+    ) -> Self {
         let fee_rate = TAKER_FEE_RATE.parse::<Decimal>().unwrap();
-        let slippage_bps = MARKET_ORDER_SLIPPAGE_BPS.parse::<Decimal>().unwrap();
-        Self {
-            state,
-            orderbook,
-            initial_balance,
-            positions: HashMap::new(),
-            resting_orders: HashMap::new(),
-            fill_history: Vec::new(),
-            performance: PaperPerformance::new(initial_balance),
-            next_order_id: 1,
-            fee_rate,
-            slippage_bps,
-        }
+        let slippage_bps = Decimal::from(MARKET_ORDER_SLIPPAGE_BPS);
+        let mut executor = Self::with_params(state, orderbook, fee_rate, slippage_bps);
+        executor.clock = clock;
+        executor
     }
 
     /// Create with a custom fee rate and slippage.
@@ -248,9 +599,79 @@ impl PaperExecutor {
             next_order_id: 1,
             fee_rate,
             slippage_bps,
+            clock: RealClock::shared(),
+            fill_tx: broadcast::channel(FILL_CHANNEL_CAPACITY).0,
+            queue_model: false,
+            require_price_improvement_for_maker_fill: false,
+            synthesize_no_book: false,
+            trade_log: None,
+            stress: None,
+            cash_buffer_pct: DEFAULT_CASH_BUFFER_PCT,
+            equity_series: EquitySeries::default(),
+            arb_holdings: ArbHoldings::default(),
         }
     }
 
+    /// Append every fill to a structured trade log.
+    pub fn set_trade_log(&mut self, trade_log: TradeLogHandle) {
+        self.trade_log = Some(trade_log);
+    }
+
+    /// Enable queue-position simulation for resting limit orders.
+    ///
+    /// A resting order joins behind the size displayed at its price and
+    /// only fills once that much volume has traded through: size reductions
+    /// at our level and crossing liquidity both consume the queue first.
+    pub fn with_queue_model(mut self, enabled: bool) -> Self {
+        self.queue_model = enabled;
+        self
+    }
+
+    /// Fill resting limit orders only against asks strictly below (bids
+    /// strictly above) their price, for more conservative results: at our
+    /// own price we are assumed never to reach the front of the queue.
+    /// Fills on placement still cross at the limit.
+    pub fn with_price_improvement_for_maker_fill(mut self, required: bool) -> Self {
+        self.require_price_improvement_for_maker_fill = required;
+        self
+    }
+
+    /// For markets without a separately quoted NO book, simulate NO orders
+    /// against the YES book's complement (`no_ask = 1 - yes_bid`,
+    /// `no_bid = 1 - yes_ask`) whenever the NO side is empty.
+    pub fn with_synthetic_no_book(mut self, enabled: bool) -> Self {
+        self.synthesize_no_book = enabled;
+        self
+    }
+
+    /// Share of available cash a buy may commit (see
+    /// `max_affordable_quantity`).
+    pub fn with_cash_buffer_pct(mut self, cash_buffer_pct: Decimal) -> Self {
+        self.cash_buffer_pct = cash_buffer_pct;
+        self
+    }
+
+    /// Sampling cadence and annualization for Sharpe and Sortino.
+    pub fn with_equity_series(mut self, equity_series: EquitySeries) -> Self {
+        self.equity_series = equity_series;
+        self
+    }
+
+    /// Count one trading tick toward the equity sampling cadence.
+    pub fn sample_equity(&mut self) {
+        self.equity_series.on_tick(self.state.get_total_equity());
+    }
+
+    /// Enable adverse-fill stress mode. The same seed and signal sequence
+    /// always produce the same fills.
+    pub fn with_stress(mut self, config: StressConfig, seed: u64) -> Self {
+        self.stress = Some(FillStress {
+            config,
+            rng: SplitMix64::new(seed),
+        });
+        self
+    }
+
     // =========================================================================
     // Public Interface (mirrors LiveExecutor)
     // =========================================================================
@@ -260,10 +681,21 @@ impl PaperExecutor {
     /// rest if the book cannot fill them.
     pub fn execute_signal(&mut self, signal: &Signal) -> ExecResult {
         // Handle cancel signals.
+        if signal.action == SignalAction::CancelOrder {
+            return self.cancel_order(signal);
+        }
         if signal.is_cancel() {
             return self.cancel_all(&signal.market_slug);
         }
 
+        // Reduce-only: re-issue as a plain order capped to the held size.
+        if signal.reduce_only {
+            return match self.reduce_only_signal(signal) {
+                Ok(reduced) => self.execute_signal(&reduced),
+                Err(rejected) => rejected,
+            };
+        }
+
         let intent = match signal.action.to_intent() {
             Some(i) => i,
             None => {
@@ -274,17 +706,54 @@ impl PaperExecutor {
                     avg_fill_price: None,
                     fee: Decimal::ZERO,
                     error: Some("Invalid signal action".to_string()),
+                    reject_reason: Some(RejectReason::ApiRejected),
                 };
             }
         };
 
         let is_buy = signal.is_buy();
 
-        // Balance pre-check for buys.
+        // Balance pre-check for buys: cap to what the cash buffer affords,
+        // then make sure the fee fits as well.
+        let mut affordable;
+        let signal = if is_buy && signal.price > Decimal::ZERO {
+            let available = self.state.get_balance();
+            let max_qty = max_affordable_quantity(available, signal.price, self.cash_buffer_pct);
+            if max_qty <= 0 {
+                return ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!(
+                        "Insufficient balance: need ${:.4}, have ${:.4}",
+                        signal.price * Decimal::from(signal.quantity),
+                        available
+                    )),
+                    reject_reason: Some(RejectReason::InsufficientBalance),
+                };
+            }
+            if signal.quantity > max_qty {
+                info!(
+                    market_slug = %signal.market_slug,
+                    requested = signal.quantity,
+                    resized = max_qty,
+                    "[PAPER] Buy capped to available cash"
+                );
+                affordable = signal.clone();
+                affordable.quantity = max_qty;
+                &affordable
+            } else {
+                signal
+            }
+        } else {
+            signal
+        };
         if is_buy && signal.price > Decimal::ZERO {
             let available = self.state.get_balance();
             let cost = signal.price * Decimal::from(signal.quantity);
-            let fee_estimate = cost * self.fee_rate;
+            let fee_estimate = cost * self.fee_rate_for(&signal.market_slug);
             if cost + fee_estimate > available {
                 return ExecResult {
                     order_id: String::new(),
@@ -296,12 +765,14 @@ impl PaperExecutor {
                         "Insufficient balance: need ${:.4} (+ ${:.4} fee), have ${:.4}",
                         cost, fee_estimate, available
                     )),
+                    reject_reason: Some(RejectReason::InsufficientBalance),
                 };
             }
         }
 
-        // Position pre-check for sells.
-        if signal.is_sell() {
+        // Position pre-check for sells: never sell more than is held.
+        let mut capped;
+        let signal = if signal.is_sell() {
             let side = intent.side();
             let pos_key = Self::position_key(&signal.market_slug, side);
             let held = self.positions.get(&pos_key).map(|p| p.quantity).unwrap_or(0);
@@ -316,19 +787,50 @@ impl PaperExecutor {
                         "No {} position in {} to sell",
                         side, signal.market_slug
                     )),
+                    reject_reason: Some(RejectReason::RiskBlocked),
                 };
             }
+            if signal.quantity > held {
+                warn!(
+                    market_slug = %signal.market_slug,
+                    side = %side,
+                    requested = signal.quantity,
+                    held,
+                    "[PAPER] Sell exceeds held quantity; capping to position"
+                );
+                capped = signal.clone();
+                capped.quantity = held;
+                &capped
+            } else {
+                signal
+            }
+        } else {
+            signal
+        };
+
+        if self.stress_reject() {
+            warn!(market_slug = %signal.market_slug, "[PAPER] Stress mode rejected order");
+            return ExecResult {
+                order_id: String::new(),
+                status: OrderStatus::Rejected,
+                filled_quantity: 0,
+                avg_fill_price: None,
+                fee: Decimal::ZERO,
+                error: Some("Stress mode: simulated reject".to_string()),
+                reject_reason: Some(RejectReason::ApiRejected),
+            };
         }
 
         let order_id = self.generate_order_id();
+        self.arb_holdings.track_order(&order_id, signal);
 
         // Determine order type by urgency: Critical/High => market, else limit.
         let is_market_order = matches!(signal.urgency, Urgency::Critical | Urgency::High);
 
         // Try to fill against the order book.
-        let book = self.orderbook.get_full(&signal.market_slug);
+        let book = self.fill_book(&signal.market_slug);
 
-        if is_market_order {
+        let result = if is_market_order {
             self.execute_market_order(
                 &order_id,
                 signal,
@@ -344,15 +846,315 @@ impl PaperExecutor {
                 is_buy,
                 book.as_ref(),
             )
+        };
+        if !self.resting_orders.contains_key(&order_id) {
+            self.arb_holdings.forget_order(&order_id);
+        }
+        result
+    }
+
+    /// Estimate what `execute_signal` would fill right now, mirroring the
+    /// live `/v1/order/preview` call. Uses the same book walk, slippage,
+    /// sell capping and fee as execution but changes no state. Only the
+    /// immediate fill is estimated: a limit order's resting remainder is
+    /// not. Signals that would be rejected preview as a zero fill.
+    pub fn preview(&self, signal: &Signal) -> OrderPreview {
+        if signal.is_cancel() {
+            return Self::empty_preview();
+        }
+        if signal.reduce_only {
+            return match self.reduce_only_signal(signal) {
+                Ok(reduced) => self.preview(&reduced),
+                Err(_) => Self::empty_preview(),
+            };
+        }
+        let Some(intent) = signal.action.to_intent() else {
+            return Self::empty_preview();
+        };
+
+        let is_buy = signal.is_buy();
+        let side = intent.side();
+        let mut signal = signal.clone();
+        if !is_buy {
+            let held = self
+                .positions
+                .get(&Self::position_key(&signal.market_slug, side))
+                .map(|p| p.quantity)
+                .unwrap_or(0);
+            if held <= 0 {
+                return Self::empty_preview();
+            }
+            signal.quantity = signal.quantity.min(held);
+        }
+
+        let book = self.fill_book(&signal.market_slug);
+        let book_side = book.as_ref().map(|b| match side {
+            Side::Yes => &b.yes,
+            Side::No => &b.no,
+        });
+        let (filled_qty, fill_price) =
+            if matches!(signal.urgency, Urgency::Critical | Urgency::High) {
+                self.simulate_market_fill(&signal, is_buy, book_side)
+            } else {
+                self.simulate_limit_fill(&signal, is_buy, book_side)
+            };
+        if filled_qty == 0 {
+            return Self::empty_preview();
+        }
+
+        let cost = fill_price * Decimal::from(filled_qty);
+        let fee = cost * self.fee_rate_for(&signal.market_slug);
+        OrderPreview {
+            estimated_fill_price: Some(fill_price),
+            estimated_fill_quantity: Some(filled_qty),
+            estimated_cost: Some(cost),
+            estimated_fee: Some(fee),
+            // Cash out for a buy, cash in for a sell.
+            estimated_total: Some(if is_buy { cost + fee } else { cost - fee }),
+        }
+    }
+
+    /// Taker fee rate for `slug`: the state's per-market override, or the
+    /// executor's default.
+    fn fee_rate_for(&self, slug: &str) -> Decimal {
+        self.state.fee_rate_for(slug, self.fee_rate)
+    }
+
+    fn empty_preview() -> OrderPreview {
+        OrderPreview {
+            estimated_fill_price: None,
+            estimated_fill_quantity: Some(0),
+            estimated_cost: Some(Decimal::ZERO),
+            estimated_fee: Some(Decimal::ZERO),
+            estimated_total: Some(Decimal::ZERO),
+        }
+    }
+
+    /// Close only the `side` holding in a market with a market sell of the
+    /// held quantity, mirroring `LiveExecutor::close_side`.
+    pub fn close_side(&mut self, market_slug: &str, side: Side) -> ExecResult {
+        let held = self
+            .positions
+            .get(&Self::position_key(market_slug, side))
+            .map(|p| (p.quantity, p.avg_price));
+        let (quantity, avg_price) = match held {
+            Some((q, p)) if q > 0 => (q, p),
+            _ => {
+                return ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!("No {} position in {} to close", side, market_slug)),
+                    reject_reason: Some(RejectReason::RiskBlocked),
+                };
+            }
+        };
+
+        // The price only matters without book data; the fill walks the bids.
+        let action = match side {
+            Side::Yes => SignalAction::SellYes,
+            Side::No => SignalAction::SellNo,
+        };
+        let signal = Signal {
+            market_slug: market_slug.to_string(),
+            action,
+            price: avg_price,
+            quantity,
+            urgency: Urgency::Critical,
+            confidence: 1.0,
+            strategy_name: "close_side".to_string(),
+            reason: format!("Close {} position", side),
+            metadata: HashMap::new(),
+            timestamp: self.clock.now(),
+            reduce_only: true,
+            good_till: None,
+        };
+        self.execute_signal(&signal)
+    }
+
+    /// Flatten the YES + NO holding an arb pair's legs bought in a market
+    /// (its `ARB_PAIR_KEY`), selling matched pairs on both legs together so
+    /// the exit never leaves a directional position behind. Run after an
+    /// arb entry whose legs filled unevenly. Other strategies' holdings in
+    /// the market are left alone.
+    ///
+    /// The pair's resting leg orders are cancelled first, so none can fill
+    /// afterwards and reopen the position. Contracts held on one leg without
+    /// a partner are sold next. Then two phases for the pairs. Prepare:
+    /// preview a market sell of the matched quantity on each leg; if either
+    /// leg's bids cannot absorb it, abort with no pair leg sent. Commit:
+    /// `sell_arb_pair`. Aborts and short fills are logged as errors.
+    pub fn unwind_arb(&mut self, market_slug: &str) -> ArbUnwindResult {
+        let mut result = ArbUnwindResult::default();
+        for order_id in self.arb_holdings.orders(market_slug) {
+            if self.resting_orders.remove(&order_id).is_some() {
+                self.state
+                    .update_order(&order_id, Some(OrderStatus::Cancelled), None);
+                self.state.remove_order(&order_id);
+                result.cancelled += 1;
+            }
+            self.arb_holdings.forget_order(&order_id);
+        }
+
+        let (yes_arb, no_arb) = self.arb_holdings.held(market_slug);
+        let held = |side| {
+            self.positions
+                .get(&Self::position_key(market_slug, side))
+                .map(|p| p.quantity)
+                .unwrap_or(0)
+        };
+        let (yes_held, no_held) = (held(Side::Yes).min(yes_arb), held(Side::No).min(no_arb));
+        let matched = yes_held.min(no_held);
+        if yes_held.max(no_held) <= 0 {
+            result.error = Some(format!("No arb holding in {} to unwind", market_slug));
+            return result;
+        }
+
+        let balance_before = self.state.get_balance();
+        if let Some((action, quantity)) = unpaired_sell(yes_held, no_held) {
+            let now = self.clock.now();
+            let leg = arb_leg_signal(&self.orderbook, market_slug, action, quantity, now);
+            result.unpaired = self.send_arb_leg(&leg);
+        }
+
+        if matched > 0 {
+            // Phase 1: both legs must be fillable in full before either is sent.
+            let now = self.clock.now();
+            let yes_leg =
+                arb_leg_signal(&self.orderbook, market_slug, SignalAction::SellYes, matched, now);
+            let no_leg =
+                arb_leg_signal(&self.orderbook, market_slug, SignalAction::SellNo, matched, now);
+            let short_leg = [&yes_leg, &no_leg].into_iter().find_map(|leg| {
+                let fillable = self.preview(leg).estimated_fill_quantity.unwrap_or(0);
+                (fillable < matched).then_some((leg.action, fillable))
+            });
+            match short_leg {
+                Some((action, fillable)) => {
+                    error!(
+                        market_slug,
+                        action = ?action,
+                        matched,
+                        fillable,
+                        "[PAPER] Arb unwind aborted: leg cannot be filled"
+                    );
+                    result.error = Some(format!(
+                        "{:?} leg can fill {} of {}; unwind aborted",
+                        action, fillable, matched
+                    ));
+                }
+                // Phase 2.
+                None => self.sell_arb_pair(&yes_leg, &no_leg, &mut result),
+            }
+        }
+
+        result.proceeds = self.state.get_balance() - balance_before;
+        info!(
+            market_slug,
+            cancelled = result.cancelled,
+            unpaired = result.unpaired,
+            unwound = result.unwound,
+            proceeds = %result.proceeds,
+            "[PAPER] Arb unwind complete"
+        );
+        result
+    }
+
+    /// Commit phase of `unwind_arb`: sell both pair legs, then buy back any
+    /// excess one leg sold over the other so the remaining holding is
+    /// again a matched pair.
+    fn sell_arb_pair(&mut self, yes_leg: &Signal, no_leg: &Signal, result: &mut ArbUnwindResult) {
+        let market_slug = yes_leg.market_slug.as_str();
+        let yes_filled = self.send_arb_leg(yes_leg);
+        let no_filled = self.send_arb_leg(no_leg);
+        result.unwound = yes_filled.min(no_filled);
+
+        if let Some((action, excess)) = arb_buy_back(yes_filled, no_filled) {
+            let buy_back =
+                arb_leg_signal(&self.orderbook, market_slug, action, excess, self.clock.now());
+            result.rolled_back = self.send_arb_leg(&buy_back);
+            error!(
+                market_slug,
+                yes_filled,
+                no_filled,
+                rolled_back = result.rolled_back,
+                excess,
+                "[PAPER] Arb unwind legs filled unevenly; bought back the excess"
+            );
+            result.error = Some(format!(
+                "Legs filled unevenly (YES {}, NO {}); bought back {} of {}",
+                yes_filled, no_filled, result.rolled_back, excess
+            ));
+        }
+    }
+
+    /// Execute one `unwind_arb` leg against the pair's attributed holding.
+    /// Returns the quantity filled.
+    fn send_arb_leg(&mut self, leg: &Signal) -> i64 {
+        let filled = self.execute_signal(leg).filled_quantity;
+        self.arb_holdings.record_unwind(&leg.market_slug, leg.action, filled);
+        filled
+    }
+
+    /// Settle a resolved market at par: the winning side pays 1.00 per
+    /// contract and the losing side 0.00, with no fee, instead of leaving
+    /// the positions marked at their last price. Resting orders in the
+    /// market are cancelled first. Returns the settlement fills.
+    pub fn settle_resolved(&mut self, market_slug: &str, yes_won: bool) -> Vec<PaperFill> {
+        self.settle_market(market_slug, if yes_won { Side::Yes } else { Side::No })
+    }
+
+    /// `settle_resolved` by winning side: each held position is realized at
+    /// 1.00 if on `winning_side` and 0.00 otherwise, then removed. The P&L
+    /// is booked to the strategy that opened the position.
+    pub fn settle_market(&mut self, market_slug: &str, winning_side: Side) -> Vec<PaperFill> {
+        self.cancel_all(market_slug);
+
+        let mut fills = Vec::new();
+        for side in [Side::Yes, Side::No] {
+            let Some((quantity, strategy_name)) = self
+                .positions
+                .get(&Self::position_key(market_slug, side))
+                .filter(|p| p.quantity > 0)
+                .map(|p| (p.quantity, p.strategy_name.clone()))
+            else {
+                continue;
+            };
+            let price = if side == winning_side { Decimal::ONE } else { Decimal::ZERO };
+            let order_id = self.generate_order_id();
+            let fill = self.record_fill(
+                &order_id,
+                market_slug,
+                &strategy_name,
+                side,
+                false,
+                price,
+                quantity,
+                Some(price),
+                Decimal::ZERO,
+            );
+            info!(
+                market_slug,
+                side = %side,
+                quantity,
+                price = %price,
+                "[PAPER] Position settled on resolution"
+            );
+            fills.push(fill);
         }
+        fills
     }
 
     /// Check all resting limit orders against the current order book.
-    /// Fills any that now have sufficient depth at their limit price.
+    /// Cancels orders past their `good_till`, then fills any that now have
+    /// sufficient depth at their limit price.
     /// Returns a list of fills that occurred.
     pub fn check_resting_orders(&mut self) -> Vec<PaperFill> {
         let mut fills = Vec::new();
 
+        self.cancel_expired_orders();
+
         // Collect order IDs to avoid borrow conflicts.
         let order_ids: Vec<String> = self.resting_orders.keys().cloned().collect();
 
@@ -367,7 +1169,7 @@ impl PaperExecutor {
                 continue;
             }
 
-            let book = match self.orderbook.get_full(&order.market_slug) {
+            let book = match self.fill_book(&order.market_slug) {
                 Some(b) => b,
                 None => continue,
             };
@@ -383,22 +1185,61 @@ impl PaperExecutor {
                 (false, Side::No) => &book.no,
             };
 
-            let (fill_qty, fill_price) = if is_buy {
-                // Buy limit: fills if asks exist at or below limit price.
-                self.simulate_limit_buy_fill(book_side, order.price, order.remaining())
+            // Queue model: size that left our level traded (or cancelled)
+            // ahead of us.
+            let mut queue_ahead = 0;
+            if self.queue_model {
+                let shown = displayed_at_price(book_side, is_buy, order.price);
+                queue_ahead = (order.queue_ahead - (order.level_size - shown).max(0)).max(0);
+                if let Some(resting) = self.resting_orders.get_mut(&order_id) {
+                    resting.level_size = shown;
+                    resting.queue_ahead = queue_ahead;
+                }
+            }
+
+            let wanted = queue_ahead + order.remaining();
+            let strict = self.require_price_improvement_for_maker_fill;
+            let (crossing_qty, fill_price) = if is_buy {
+                // Buy limit: fills if asks exist at (or below) the limit price.
+                self.simulate_limit_buy_fill(book_side, order.price, wanted, strict)
+            } else {
+                // Sell limit: fills if bids exist at (or above) the limit price.
+                self.simulate_limit_sell_fill(book_side, order.price, wanted, strict)
+            };
+
+            // Crossing volume fills the queue ahead of us before our order.
+            // Those trades also take size off our level, so expect it gone
+            // rather than counting the same volume again when it drops.
+            let fill_qty = if queue_ahead > 0 {
+                let consumed = queue_ahead.min(crossing_qty);
+                if let Some(resting) = self.resting_orders.get_mut(&order_id) {
+                    resting.queue_ahead -= consumed;
+                    resting.level_size = (resting.level_size - consumed).max(0);
+                }
+                crossing_qty - consumed
             } else {
-                // Sell limit: fills if bids exist at or above limit price.
-                self.simulate_limit_sell_fill(book_side, order.price, order.remaining())
+                crossing_qty
             };
 
+            if fill_qty == 0 {
+                if let (Some(mid), Some(resting)) =
+                    (book_mid(book_side), self.resting_orders.get_mut(&order_id))
+                {
+                    resting.last_mid = Some(mid);
+                }
+            }
+
             if fill_qty > 0 {
                 let fill = self.record_fill(
                     &order_id,
                     &order.market_slug,
+                    &order.strategy_name,
                     side,
                     is_buy,
                     fill_price,
                     fill_qty,
+                    order.last_mid.or_else(|| book_mid(book_side)),
+                    self.fee_rate_for(&order.market_slug),
                 );
                 fills.push(fill);
 
@@ -428,6 +1269,11 @@ impl PaperExecutor {
         fills
     }
 
+    /// Subscribe to fill notifications. Every recorded fill is published.
+    pub fn subscribe_fills(&self) -> broadcast::Receiver<FillEvent> {
+        self.fill_tx.subscribe()
+    }
+
     /// Get all resting (unfilled/partially filled) orders.
     pub fn get_resting_orders(&self) -> Vec<RestingOrder> {
         self.resting_orders.values().cloned().collect()
@@ -449,14 +1295,7 @@ impl PaperExecutor {
         let cash = self.state.get_balance();
         let pos_value = self.state.get_total_position_value();
         let pnl = equity - self.initial_balance;
-        let pnl_pct = if self.initial_balance > Decimal::ZERO {
-            ((pnl / self.initial_balance) * Decimal::ONE_HUNDRED)
-                .to_string()
-                .parse::<f64>()
-                .unwrap_or(0.0)
-        } else {
-            0.0
-        };
+        let pnl_pct = to_f64(pct(pnl, self.initial_balance));
 
         let mut m = HashMap::new();
         m.insert("mode".to_string(), serde_json::json!("paper"));
@@ -478,40 +1317,56 @@ impl PaperExecutor {
         );
         m.insert(
             "initial_balance".to_string(),
-            serde_json::json!(decimal_to_f64(self.initial_balance)),
+            serde_json::json!(to_f64(self.initial_balance)),
         );
         m.insert(
             "current_balance".to_string(),
-            serde_json::json!(decimal_to_f64(cash)),
+            serde_json::json!(to_f64(cash)),
         );
         m.insert(
             "position_value".to_string(),
-            serde_json::json!(decimal_to_f64(pos_value)),
+            serde_json::json!(to_f64(pos_value)),
         );
         m.insert(
             "total_equity".to_string(),
-            serde_json::json!(decimal_to_f64(equity)),
+            serde_json::json!(to_f64(equity)),
         );
         m.insert(
             "total_pnl".to_string(),
-            serde_json::json!(decimal_to_f64(pnl)),
+            serde_json::json!(to_f64(pnl)),
         );
         m.insert("pnl_percent".to_string(), serde_json::json!(pnl_pct));
         m.insert(
             "realized_pnl".to_string(),
-            serde_json::json!(decimal_to_f64(self.performance.total_pnl)),
+            serde_json::json!(to_f64(self.performance.total_pnl)),
+        );
+        m.insert(
+            "spread_pnl".to_string(),
+            serde_json::json!(to_f64(self.performance.spread_pnl)),
+        );
+        m.insert(
+            "directional_pnl".to_string(),
+            serde_json::json!(to_f64(self.performance.directional_pnl)),
         );
         m.insert(
             "max_drawdown".to_string(),
-            serde_json::json!(decimal_to_f64(self.performance.max_drawdown)),
+            serde_json::json!(to_f64(self.performance.max_drawdown)),
         );
         m.insert(
             "total_fees_paid".to_string(),
-            serde_json::json!(decimal_to_f64(self.performance.total_fees_paid)),
+            serde_json::json!(to_f64(self.performance.total_fees_paid)),
         );
         m.insert(
             "total_volume".to_string(),
-            serde_json::json!(decimal_to_f64(self.performance.total_volume)),
+            serde_json::json!(to_f64(self.performance.total_volume)),
+        );
+        m.insert(
+            "sharpe_ratio".to_string(),
+            serde_json::json!(self.equity_series.sharpe()),
+        );
+        m.insert(
+            "sortino_ratio".to_string(),
+            serde_json::json!(self.equity_series.sortino()),
         );
         m.insert(
             "open_positions".to_string(),
@@ -521,6 +1376,10 @@ impl PaperExecutor {
             "resting_orders".to_string(),
             serde_json::json!(self.resting_orders.len()),
         );
+        m.insert(
+            "by_strategy".to_string(),
+            strategy_performance_json(&self.performance.by_strategy),
+        );
         m
     }
 
@@ -555,24 +1414,8 @@ impl PaperExecutor {
         };
 
         // Simulate fill by walking the book.
-        let (filled_qty, avg_price) = match book_side {
-            Some(bs) => {
-                if is_buy {
-                    self.walk_asks_with_slippage(bs, signal.quantity)
-                } else {
-                    self.walk_bids_with_slippage(bs, signal.quantity)
-                }
-            }
-            None => {
-                // No book data: fill at signal price + slippage as fallback.
-                let slipped = if is_buy {
-                    signal.price * (Decimal::ONE + self.slippage_bps)
-                } else {
-                    signal.price * (Decimal::ONE - self.slippage_bps)
-                };
-                (signal.quantity, slipped)
-            }
-        };
+        let (filled_qty, avg_price) = self.simulate_market_fill(signal, is_buy, book_side);
+        let (filled_qty, avg_price) = self.stress_fill(is_buy, filled_qty, avg_price, None);
 
         if filled_qty == 0 {
             return ExecResult {
@@ -582,6 +1425,7 @@ impl PaperExecutor {
                 avg_fill_price: None,
                 fee: Decimal::ZERO,
                 error: Some("No liquidity available in order book".to_string()),
+                reject_reason: Some(RejectReason::InsufficientLiquidity),
             };
         }
 
@@ -589,10 +1433,13 @@ impl PaperExecutor {
         let fill = self.record_fill(
             order_id,
             &signal.market_slug,
+            &signal.strategy_name,
             side,
             is_buy,
             avg_price,
             filled_qty,
+            book_side.and_then(book_mid),
+            self.fee_rate_for(&signal.market_slug),
         );
 
         // Store order in StateManager.
@@ -630,6 +1477,7 @@ impl PaperExecutor {
             avg_fill_price: Some(avg_price),
             fee: fill.fee,
             error: None,
+            reject_reason: None,
         }
     }
 
@@ -660,16 +1508,9 @@ impl PaperExecutor {
         };
 
         // Try immediate fill against crossing levels.
-        let (immediate_fill, fill_price) = match book_side {
-            Some(bs) => {
-                if is_buy {
-                    self.simulate_limit_buy_fill(bs, signal.price, signal.quantity)
-                } else {
-                    self.simulate_limit_sell_fill(bs, signal.price, signal.quantity)
-                }
-            }
-            None => (0, signal.price),
-        };
+        let (immediate_fill, fill_price) = self.simulate_limit_fill(signal, is_buy, book_side);
+        let (immediate_fill, fill_price) =
+            self.stress_fill(is_buy, immediate_fill, fill_price, Some(signal.price));
 
         let remaining = signal.quantity - immediate_fill;
         let mut total_fee = Decimal::ZERO;
@@ -679,10 +1520,13 @@ impl PaperExecutor {
             let fill = self.record_fill(
                 order_id,
                 &signal.market_slug,
+                &signal.strategy_name,
                 side,
                 is_buy,
                 fill_price,
                 immediate_fill,
+                book_side.and_then(book_mid),
+                self.fee_rate_for(&signal.market_slug),
             );
             total_fee = fill.fee;
 
@@ -719,15 +1563,24 @@ impl PaperExecutor {
 
         // If there's a remainder, add as a resting order.
         if remaining > 0 {
-            let resting = RestingOrder {
+            let mut resting = RestingOrder {
                 order_id: order_id.to_string(),
                 market_slug: signal.market_slug.clone(),
+                strategy_name: signal.strategy_name.clone(),
                 intent,
                 price: signal.price,
                 total_quantity: signal.quantity,
                 filled_quantity: immediate_fill,
-                created_at: Utc::now(),
+                created_at: self.clock.now(),
+                expires_at: signal.good_till,
+                queue_ahead: 0,
+                level_size: 0,
+                last_mid: book_side.and_then(book_mid),
             };
+            if let (true, Some(bs)) = (self.queue_model, book_side) {
+                resting.level_size = displayed_at_price(bs, is_buy, signal.price);
+                resting.queue_ahead = resting.level_size;
+            }
             self.resting_orders.insert(order_id.to_string(), resting);
 
             debug!(
@@ -750,6 +1603,7 @@ impl PaperExecutor {
             },
             fee: total_fee,
             error: None,
+            reject_reason: None,
         }
     }
 
@@ -757,6 +1611,65 @@ impl PaperExecutor {
     // Book Walking / Fill Simulation
     // =========================================================================
 
+    /// The book orders in `market_slug` simulate against, with the NO side
+    /// synthesized from YES if configured and missing.
+    fn fill_book(&self, market_slug: &str) -> Option<OrderBook> {
+        let mut book = self.orderbook.get_full(market_slug)?;
+        if self.synthesize_no_book && book.no.is_empty() {
+            book.no = book.yes.complement();
+        }
+        Some(book)
+    }
+
+    /// Market order fill: walk the book with slippage, or fill in full at
+    /// the signal price plus slippage when there is no book.
+    /// Returns (filled_quantity, avg_fill_price).
+    fn simulate_market_fill(
+        &self,
+        signal: &Signal,
+        is_buy: bool,
+        book_side: Option<&OrderBookSide>,
+    ) -> (i64, Decimal) {
+        match book_side {
+            Some(bs) => {
+                if is_buy {
+                    self.walk_asks_with_slippage(bs, signal.quantity)
+                } else {
+                    self.walk_bids_with_slippage(bs, signal.quantity)
+                }
+            }
+            None => {
+                // No book data: fill at signal price + slippage as fallback.
+                let slipped = if is_buy {
+                    signal.price + scale_bps(signal.price, self.slippage_bps)
+                } else {
+                    signal.price - scale_bps(signal.price, self.slippage_bps)
+                };
+                (signal.quantity, slipped)
+            }
+        }
+    }
+
+    /// Limit order immediate fill against crossing levels only; nothing
+    /// fills without a book. Returns (filled_quantity, avg_fill_price).
+    fn simulate_limit_fill(
+        &self,
+        signal: &Signal,
+        is_buy: bool,
+        book_side: Option<&OrderBookSide>,
+    ) -> (i64, Decimal) {
+        match book_side {
+            Some(bs) => {
+                if is_buy {
+                    self.simulate_limit_buy_fill(bs, signal.price, signal.quantity, false)
+                } else {
+                    self.simulate_limit_sell_fill(bs, signal.price, signal.quantity, false)
+                }
+            }
+            None => (0, signal.price),
+        }
+    }
+
     /// Walk the ask side of the book for a market buy order.
     /// Returns (filled_quantity, volume_weighted_avg_price).
     /// Applies slippage on top of the VWAP.
@@ -767,14 +1680,14 @@ impl PaperExecutor {
     ) -> (i64, Decimal) {
         let mut asks = book_side.asks.clone();
         // Sort asks ascending by price (best ask first).
-        asks.sort_by(|a, b| a.price.cmp(&b.price));
+        asks.sort_by_key(|a| a.price);
 
         let (qty, vwap) = Self::walk_levels(&asks, requested_qty);
         if qty == 0 {
             return (0, Decimal::ZERO);
         }
         // Apply slippage: buyer pays slightly more.
-        let slipped_price = vwap * (Decimal::ONE + self.slippage_bps);
+        let slipped_price = vwap + scale_bps(vwap, self.slippage_bps);
         (qty, slipped_price)
     }
 
@@ -788,31 +1701,33 @@ impl PaperExecutor {
     ) -> (i64, Decimal) {
         let mut bids = book_side.bids.clone();
         // Sort bids descending by price (best bid first).
-        bids.sort_by(|a, b| b.price.cmp(&a.price));
+        bids.sort_by_key(|b| std::cmp::Reverse(b.price));
 
         let (qty, vwap) = Self::walk_levels(&bids, requested_qty);
         if qty == 0 {
             return (0, Decimal::ZERO);
         }
         // Apply slippage: seller receives slightly less.
-        let slipped_price = vwap * (Decimal::ONE - self.slippage_bps);
+        let slipped_price = vwap - scale_bps(vwap, self.slippage_bps);
         (qty, slipped_price)
     }
 
-    /// Simulate a limit buy fill: match against asks at or below the limit price.
+    /// Simulate a limit buy fill: match against asks at or below the limit price
+    /// (strictly below with `strict`).
     /// Returns (filled_quantity, volume_weighted_avg_price).
     fn simulate_limit_buy_fill(
         &self,
         book_side: &OrderBookSide,
         limit_price: Decimal,
         requested_qty: i64,
+        strict: bool,
     ) -> (i64, Decimal) {
         let mut asks: Vec<&PriceLevel> = book_side
             .asks
             .iter()
-            .filter(|a| a.price <= limit_price)
+            .filter(|a| a.price < limit_price || (!strict && a.price == limit_price))
             .collect();
-        asks.sort_by(|a, b| a.price.cmp(&b.price));
+        asks.sort_by_key(|a| a.price);
 
         let mut filled = 0i64;
         let mut cost = Decimal::ZERO;
@@ -833,20 +1748,22 @@ impl PaperExecutor {
         (filled, vwap)
     }
 
-    /// Simulate a limit sell fill: match against bids at or above the limit price.
+    /// Simulate a limit sell fill: match against bids at or above the limit price
+    /// (strictly above with `strict`).
     /// Returns (filled_quantity, volume_weighted_avg_price).
     fn simulate_limit_sell_fill(
         &self,
         book_side: &OrderBookSide,
         limit_price: Decimal,
         requested_qty: i64,
+        strict: bool,
     ) -> (i64, Decimal) {
         let mut bids: Vec<&PriceLevel> = book_side
             .bids
             .iter()
-            .filter(|b| b.price >= limit_price)
+            .filter(|b| b.price > limit_price || (!strict && b.price == limit_price))
             .collect();
-        bids.sort_by(|a, b| b.price.cmp(&a.price));
+        bids.sort_by_key(|b| std::cmp::Reverse(b.price));
 
         let mut filled = 0i64;
         let mut proceeds = Decimal::ZERO;
@@ -867,6 +1784,46 @@ impl PaperExecutor {
         (filled, vwap)
     }
 
+    /// Stress mode: whether to reject the next order.
+    fn stress_reject(&mut self) -> bool {
+        match self.stress {
+            Some(ref mut stress) => stress.rng.chance(stress.config.reject_prob),
+            None => false,
+        }
+    }
+
+    /// Stress mode: worsen an immediate fill with extra slippage (capped
+    /// at `limit` when given) and maybe cut it to a random fraction.
+    fn stress_fill(
+        &mut self,
+        is_buy: bool,
+        qty: i64,
+        price: Decimal,
+        limit: Option<Decimal>,
+    ) -> (i64, Decimal) {
+        let slippage_bps = self.slippage_bps;
+        let Some(ref mut stress) = self.stress else {
+            return (qty, price);
+        };
+        if qty <= 0 {
+            return (qty, price);
+        }
+
+        let extra_bps = slippage_bps * (stress.config.slippage_multiplier - Decimal::ONE);
+        let extra = scale_bps(price, extra_bps.max(Decimal::ZERO));
+        let mut price = if is_buy { price + extra } else { price - extra };
+        if let Some(limit) = limit {
+            price = if is_buy { price.min(limit) } else { price.max(limit) };
+        }
+
+        let mut qty = qty;
+        if stress.rng.chance(stress.config.partial_fill_prob) {
+            let fraction = from_f64(stress.rng.next_f64()).unwrap_or(Decimal::ZERO);
+            qty = floor_i64(Decimal::from(qty) * fraction).max(1);
+        }
+        (qty, price)
+    }
+
     /// Walk sorted price levels, filling up to `requested_qty`.
     /// Returns (filled_quantity, volume_weighted_avg_price).
     fn walk_levels(levels: &[PriceLevel], requested_qty: i64) -> (i64, Decimal) {
@@ -895,17 +1852,25 @@ impl PaperExecutor {
     // =========================================================================
 
     /// Record a fill: update balance, position, metrics, and fill history.
+    /// `fee_rate` is the taker fee for trades and zero for settlements.
+    #[allow(clippy::too_many_arguments)]
     fn record_fill(
         &mut self,
         order_id: &str,
         market_slug: &str,
+        strategy_name: &str,
         side: Side,
         is_buy: bool,
         fill_price: Decimal,
         fill_qty: i64,
+        mid: Option<Decimal>,
+        fee_rate: Decimal,
     ) -> PaperFill {
+        // Without a two-sided book, treat the fill as at-mid (no spread edge).
+        let mid = mid.unwrap_or(fill_price);
         let notional = fill_price * Decimal::from(fill_qty);
-        let fee = notional * self.fee_rate;
+        let fee = notional * fee_rate;
+        self.arb_holdings.record_fill(order_id, side, is_buy, fill_qty);
 
         // Update balance.
         let current_balance = self.state.get_balance();
@@ -926,17 +1891,23 @@ impl PaperExecutor {
             let pos = self
                 .positions
                 .entry(pos_key.clone())
-                .or_insert_with(|| PaperPosition::new(side, 0, Decimal::ZERO));
-            pos.add(fill_qty, fill_price);
+                .or_insert_with(|| PaperPosition::new(side, 0, Decimal::ZERO, strategy_name));
+            pos.add(fill_qty, fill_price, mid);
             Decimal::ZERO // No realized P&L on buys.
         } else {
             // Closing or reducing a position.
             match self.positions.get_mut(&pos_key) {
                 Some(pos) => {
+                    // Spread edge: entry mid over entry price plus exit price
+                    // over exit mid. The rest is mid movement.
+                    let closed = Decimal::from(fill_qty.min(pos.quantity));
+                    let spread = (pos.avg_entry_mid() - pos.avg_price + fill_price - mid) * closed;
                     let pnl = pos.reduce(fill_qty, fill_price);
                     if pos.quantity <= 0 {
                         self.positions.remove(&pos_key);
                     }
+                    self.performance.spread_pnl += spread;
+                    self.performance.directional_pnl += pnl - spread;
                     pnl
                 }
                 None => Decimal::ZERO,
@@ -944,11 +1915,26 @@ impl PaperExecutor {
         };
 
         // Sync position to StateManager.
-        if let Some(pos) = self.positions.get(&pos_key) {
-            self.state
-                .update_position(market_slug, side, pos.quantity, pos.avg_price);
-        } else {
-            self.state.remove_position(market_slug);
+        // The side just filled is the position and the other side, if also
+        // held, its hedge. A closed side hands the market to the other one.
+        let opposite_key = Self::position_key(market_slug, side.opposite());
+        match (self.positions.get(&pos_key), self.positions.get(&opposite_key)) {
+            (Some(pos), hedge) => {
+                self.state
+                    .update_position(market_slug, side, pos.quantity, pos.avg_price);
+                match hedge {
+                    Some(h) => self
+                        .state
+                        .update_hedge_position(market_slug, h.side, h.quantity, h.avg_price),
+                    None => self.state.update_hedge_position(market_slug, side, 0, Decimal::ZERO),
+                }
+            }
+            (None, Some(pos)) => {
+                self.state
+                    .update_position(market_slug, pos.side, pos.quantity, pos.avg_price);
+                self.state.update_hedge_position(market_slug, side, 0, Decimal::ZERO);
+            }
+            (None, None) => self.state.remove_position(market_slug),
         }
 
         // Update performance metrics.
@@ -961,6 +1947,11 @@ impl PaperExecutor {
             self.performance.losing_trades += 1;
         }
         self.performance.total_pnl += realized_pnl;
+        self.performance
+            .by_strategy
+            .entry(strategy_name.to_string())
+            .or_default()
+            .record_fill(notional, fee, realized_pnl, !is_buy);
 
         // Update drawdown tracking.
         let equity = self.state.get_total_equity();
@@ -970,15 +1961,23 @@ impl PaperExecutor {
         let fill = PaperFill {
             order_id: order_id.to_string(),
             market_slug: market_slug.to_string(),
+            strategy_name: strategy_name.to_string(),
             side,
             is_buy,
             price: fill_price,
             quantity: fill_qty,
             fee,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
+        self.state.record_trade(market_slug, side, fill_price, fill.timestamp);
         self.fill_history.push(fill.clone());
+        let event = FillEvent::from(&fill);
+        if let Some(ref log) = self.trade_log {
+            log.record(TradeRecord::from_fill(&event, realized_pnl));
+        }
+        // No subscribers is fine; the send error is ignored.
+        let _ = self.fill_tx.send(event);
 
         debug!(
             order_id = %order_id,
@@ -1032,6 +2031,72 @@ impl PaperExecutor {
             avg_fill_price: None,
             fee: Decimal::ZERO,
             error: None,
+            reject_reason: None,
+        }
+    }
+
+    /// Cancel the one resting order a `CancelOrder` signal names.
+    fn cancel_order(&mut self, signal: &Signal) -> ExecResult {
+        let rejected = |reason: RejectReason, error: String| ExecResult {
+            order_id: String::new(),
+            status: OrderStatus::Rejected,
+            filled_quantity: 0,
+            avg_fill_price: None,
+            fee: Decimal::ZERO,
+            error: Some(error),
+            reject_reason: Some(reason),
+        };
+        let Some(order_id) = signal.cancel_order_id() else {
+            return rejected(
+                RejectReason::ApiRejected,
+                "Cancel order signal without an order id".to_string(),
+            );
+        };
+        if self.resting_orders.remove(order_id).is_none() {
+            return rejected(RejectReason::ApiRejected, format!("Order {order_id} is not resting"));
+        }
+        self.state
+            .update_order(order_id, Some(OrderStatus::Cancelled), None);
+        self.state.remove_order(order_id);
+        info!(
+            market_slug = %signal.market_slug,
+            order_id,
+            "[PAPER] Order cancelled"
+        );
+
+        ExecResult {
+            order_id: order_id.to_string(),
+            status: OrderStatus::Cancelled,
+            filled_quantity: 0,
+            avg_fill_price: None,
+            fee: Decimal::ZERO,
+            error: None,
+            reject_reason: None,
+        }
+    }
+
+    /// Cancel resting orders whose GoodTillDate expiry has passed.
+    fn cancel_expired_orders(&mut self) {
+        let now = self.clock.now();
+        let expired: Vec<String> = self
+            .resting_orders
+            .iter()
+            .filter(|(_, o)| o.expires_at.is_some_and(|at| at <= now))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in &expired {
+            if let Some(order) = self.resting_orders.remove(id) {
+                info!(
+                    order_id = %id,
+                    market_slug = %order.market_slug,
+                    remaining = order.remaining(),
+                    "[PAPER] Resting order expired"
+                );
+            }
+            self.state
+                .update_order(id, Some(OrderStatus::Cancelled), None);
+            self.state.remove_order(id);
         }
     }
 
@@ -1046,6 +2111,48 @@ impl PaperExecutor {
         id
     }
 
+    /// Rewrite a reduce-only signal as a plain sell of the held side, capped
+    /// to the held quantity. A buy of the opposite side (e.g. BuyYes to exit
+    /// NO) is simulated as the equivalent sell at the complementary price,
+    /// since paper positions are not netted across sides.
+    fn reduce_only_signal(&self, signal: &Signal) -> Result<Signal, ExecResult> {
+        let side = signal.action.reduced_side();
+        let held = side
+            .and_then(|s| self.positions.get(&Self::position_key(&signal.market_slug, s)))
+            .map(|p| p.quantity)
+            .unwrap_or(0);
+
+        let side = match side {
+            Some(s) if held > 0 => s,
+            _ => {
+                return Err(ExecResult {
+                    order_id: String::new(),
+                    status: OrderStatus::Rejected,
+                    filled_quantity: 0,
+                    avg_fill_price: None,
+                    fee: Decimal::ZERO,
+                    error: Some(format!(
+                        "Reduce-only: no position in {} to reduce",
+                        signal.market_slug
+                    )),
+                    reject_reason: Some(RejectReason::RiskBlocked),
+                });
+            }
+        };
+
+        let mut reduced = signal.clone();
+        reduced.reduce_only = false;
+        reduced.quantity = signal.quantity.min(held);
+        if signal.is_buy() {
+            reduced.action = match side {
+                Side::Yes => SignalAction::SellYes,
+                Side::No => SignalAction::SellNo,
+            };
+            reduced.price = Decimal::ONE - signal.price;
+        }
+        Ok(reduced)
+    }
+
     /// Position key: "{market_slug}:{side}" to distinguish YES vs NO
     /// positions in the same market.
     fn position_key(market_slug: &str, side: Side) -> String {
@@ -1053,12 +2160,27 @@ impl PaperExecutor {
     }
 }
 
-// =============================================================================
-// Utility
-// =============================================================================
+/// Midpoint of the best bid and ask on one side of the book.
+fn book_mid(book_side: &OrderBookSide) -> Option<Decimal> {
+    match (book_side.best_bid(), book_side.best_ask()) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+        _ => None,
+    }
+}
 
-fn decimal_to_f64(d: Decimal) -> f64 {
-    d.to_string().parse::<f64>().unwrap_or(0.0)
+/// Displayed size resting on our own side of the book at `price`: bids for
+/// a buy order, asks for a sell order.
+fn displayed_at_price(book_side: &OrderBookSide, is_buy: bool, price: Decimal) -> i64 {
+    let levels = if is_buy {
+        &book_side.bids
+    } else {
+        &book_side.asks
+    };
+    levels
+        .iter()
+        .filter(|l| l.price == price)
+        .map(|l| l.quantity)
+        .sum()
 }
 
 // =============================================================================
@@ -1105,6 +2227,8 @@ mod tests {
             reason: "test buy".to_string(),
             metadata: HashMap::new(),
             timestamp: Utc::now(),
+            reduce_only: false,
+            good_till: None,
         }
     }
 
@@ -1120,6 +2244,8 @@ mod tests {
             reason: "test sell".to_string(),
             metadata: HashMap::new(),
             timestamp: Utc::now(),
+            reduce_only: false,
+            good_till: None,
         }
     }
 
@@ -1137,6 +2263,90 @@ mod tests {
         assert!(result.fee > Decimal::ZERO);
     }
 
+    #[test]
+    fn test_preview_matches_actual_fill_without_mutating_state() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state.clone(), ob);
+
+        // Walks two ask levels: 100 @ 0.52 + 50 @ 0.53, plus slippage.
+        let buy = buy_signal("test-market", Decimal::new(53, 2), 150, Urgency::Critical);
+        let preview = executor.preview(&buy);
+        assert_eq!(state.get_balance(), Decimal::new(10000, 2));
+        assert!(executor.positions.is_empty());
+        assert!(executor.fill_history.is_empty());
+
+        let result = executor.execute_signal(&buy);
+        assert_eq!(preview.estimated_fill_quantity, Some(result.filled_quantity));
+        assert_eq!(preview.estimated_fill_price, result.avg_fill_price);
+        assert_eq!(preview.estimated_fee, Some(result.fee));
+        assert_eq!(
+            preview.estimated_total,
+            Some(Decimal::new(10000, 2) - state.get_balance())
+        );
+
+        // Sells are capped to the held 150, as in execution.
+        let sell = sell_signal("test-market", Decimal::new(49, 2), 500, Urgency::Critical);
+        let preview = executor.preview(&sell);
+        let balance_before = state.get_balance();
+        let result = executor.execute_signal(&sell);
+        assert_eq!(preview.estimated_fill_quantity, Some(150));
+        assert_eq!(preview.estimated_fill_price, result.avg_fill_price);
+        assert_eq!(preview.estimated_fee, Some(result.fee));
+        assert_eq!(preview.estimated_total, Some(state.get_balance() - balance_before));
+
+        // Nothing left to sell: a zero fill.
+        assert_eq!(executor.preview(&sell).estimated_fill_quantity, Some(0));
+    }
+
+    fn stressed_run(seed: u64) -> Vec<(OrderStatus, i64, Option<Decimal>)> {
+        let (_, ob) = setup();
+        let state = StateManager::new(Decimal::new(100000, 2)); // $1000.00
+        let config = StressConfig {
+            slippage_multiplier: Decimal::from(3),
+            partial_fill_prob: 0.3,
+            reject_prob: 0.2,
+        };
+        let mut executor = PaperExecutor::new(state, ob).with_stress(config, seed);
+        (0..20)
+            .map(|i| {
+                let signal = if i % 2 == 0 {
+                    buy_signal("test-market", Decimal::new(53, 2), 40, Urgency::Critical)
+                } else {
+                    sell_signal("test-market", Decimal::new(49, 2), 40, Urgency::Critical)
+                };
+                let result = executor.execute_signal(&signal);
+                (result.status, result.filled_quantity, result.avg_fill_price)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stress_mode_is_reproducible_per_seed() {
+        let run = stressed_run(42);
+        assert_eq!(run, stressed_run(42));
+        assert_ne!(run, stressed_run(7));
+
+        assert!(run.iter().any(|(status, ..)| *status == OrderStatus::Rejected));
+        assert!(run.iter().any(|(status, ..)| *status == OrderStatus::PartiallyFilled));
+    }
+
+    #[test]
+    fn test_stress_mode_multiplies_slippage() {
+        let (state, ob) = setup();
+        let config = StressConfig {
+            slippage_multiplier: Decimal::from(3),
+            ..StressConfig::default()
+        };
+        let mut executor = PaperExecutor::new(state, ob).with_stress(config, 1);
+
+        // Normal: 0.52 + 5 bps = 0.52026. Stress adds the other 2 * 5 bps
+        // on top: 0.52026 + 0.00052026 = 0.52078026.
+        let buy = buy_signal("test-market", Decimal::new(52, 2), 50, Urgency::Critical);
+        let result = executor.execute_signal(&buy);
+        assert_eq!(result.filled_quantity, 50);
+        assert_eq!(result.avg_fill_price, Some(Decimal::new(52078026, 8)));
+    }
+
     #[test]
     fn test_market_buy_partial_fill_low_depth() {
         // Use a large balance so the pre-check doesn't reject.
@@ -1206,6 +2416,382 @@ mod tests {
         assert!(result.error.is_some());
     }
 
+    #[test]
+    fn test_reduce_only_exit_without_position_is_rejected() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state.clone(), ob);
+
+        // Stale NO-side stop-loss exit (BuyYes) after the NO position closed.
+        let mut exit = buy_signal("test-market", Decimal::new(52, 2), 10, Urgency::High);
+        exit.reduce_only = true;
+        let result = executor.execute_signal(&exit);
+
+        assert_eq!(result.status, OrderStatus::Rejected);
+        assert!(result.error.unwrap().contains("Reduce-only"));
+        assert!(executor.positions.is_empty(), "Must not open a YES position");
+        assert_eq!(state.get_balance(), Decimal::new(10000, 2));
+
+        let mut sell = sell_signal("test-market", Decimal::new(50, 2), 10, Urgency::High);
+        sell.reduce_only = true;
+        assert_eq!(executor.execute_signal(&sell).status, OrderStatus::Rejected);
+    }
+
+    #[test]
+    fn test_reduce_only_opposite_buy_closes_held_side_capped() {
+        let state = StateManager::new(Decimal::new(10000, 2));
+        let ob = OrderBookTracker::new();
+        ob.update(OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(40, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(42, 2), quantity: 500 }],
+            },
+            no: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(58, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(60, 2), quantity: 500 }],
+            },
+        });
+        let mut executor = PaperExecutor::new(state, ob);
+
+        let mut buy_no = buy_signal("test-market", Decimal::new(60, 2), 30, Urgency::Critical);
+        buy_no.action = SignalAction::BuyNo;
+        assert_eq!(executor.execute_signal(&buy_no).filled_quantity, 30);
+
+        // Exit 100 via BuyYes while holding only 30 NO: capped to 30 and
+        // closes the NO position instead of opening YES.
+        let mut exit = buy_signal("test-market", Decimal::new(42, 2), 100, Urgency::High);
+        exit.reduce_only = true;
+        let result = executor.execute_signal(&exit);
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.filled_quantity, 30);
+        assert!(executor.positions.is_empty());
+    }
+
+    #[test]
+    fn test_sell_larger_than_position_is_capped_to_held() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state.clone(), ob);
+
+        let buy = buy_signal("test-market", Decimal::new(52, 2), 40, Urgency::Critical);
+        assert_eq!(executor.execute_signal(&buy).filled_quantity, 40);
+
+        // Bids hold 300 contracts, but only the 40 held may be sold.
+        let sell = sell_signal("test-market", Decimal::new(50, 2), 100, Urgency::Critical);
+        let result = executor.execute_signal(&sell);
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.filled_quantity, 40);
+        assert!(executor.positions.values().all(|p| p.quantity >= 0));
+        assert!(executor.positions.is_empty());
+        assert!(state.get_position("test-market").is_none());
+    }
+
+    #[test]
+    fn test_close_side_closes_yes_and_leaves_no_intact() {
+        let state = StateManager::new(Decimal::new(10000, 2));
+        let ob = OrderBookTracker::new();
+        ob.update(OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(40, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(42, 2), quantity: 500 }],
+            },
+            no: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(56, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(58, 2), quantity: 500 }],
+            },
+        });
+        let mut executor = PaperExecutor::new(state.clone(), ob);
+
+        let buy_yes = buy_signal("test-market", Decimal::new(42, 2), 20, Urgency::Critical);
+        assert_eq!(executor.execute_signal(&buy_yes).filled_quantity, 20);
+        let mut buy_no = buy_signal("test-market", Decimal::new(58, 2), 30, Urgency::Critical);
+        buy_no.action = SignalAction::BuyNo;
+        assert_eq!(executor.execute_signal(&buy_no).filled_quantity, 30);
+
+        // Both legs reach the state: NO as the position, YES as its hedge.
+        let hedge = state.get_hedge_position("test-market").unwrap();
+        assert_eq!((hedge.side, hedge.quantity), (Side::Yes, 20));
+        // Fills carry 5 bps slippage: 20 @ 0.42021 + 30 @ 0.58029.
+        assert_eq!(state.market_exposure("test-market"), Decimal::new(2581290, 5));
+        // 20 matched; the 10 unmatched NO remain directional.
+        assert_eq!(state.net_market_exposure("test-market"), Decimal::new(580290, 5));
+
+        let result = executor.close_side("test-market", Side::Yes);
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.filled_quantity, 20);
+        assert!(!executor.positions.contains_key("test-market:YES"));
+        assert_eq!(executor.positions["test-market:NO"].quantity, 30);
+        let pos = state.get_position("test-market").unwrap();
+        assert_eq!((pos.side, pos.quantity), (Side::No, 30));
+        assert!(state.get_hedge_position("test-market").is_none());
+
+        // Nothing left to close on YES.
+        let again = executor.close_side("test-market", Side::Yes);
+        assert_eq!(again.status, OrderStatus::Rejected);
+    }
+
+    /// YES bids 500 deep; NO bids `no_bid_depth` deep. Asks are deep on both.
+    fn arb_exit_book(no_bid_depth: i64) -> OrderBook {
+        OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(40, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(42, 2), quantity: 500 }],
+            },
+            no: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(56, 2), quantity: no_bid_depth }],
+                asks: vec![PriceLevel { price: Decimal::new(58, 2), quantity: 500 }],
+            },
+        }
+    }
+
+    /// Executor holding 20 YES + 20 NO in "test-market".
+    fn arb_pair_executor(no_bid_depth: i64) -> (PaperExecutor, StateManager, OrderBookTracker) {
+        let state = StateManager::new(Decimal::new(10000, 2));
+        let ob = OrderBookTracker::new();
+        ob.update(arb_exit_book(500));
+        let mut executor = PaperExecutor::new(state.clone(), ob.clone());
+        let buy_yes = arb_leg("test", SignalAction::BuyYes, Decimal::new(42, 2), Urgency::Critical);
+        assert_eq!(executor.execute_signal(&buy_yes).filled_quantity, 20);
+        let no_price = Decimal::new(58, 2);
+        let buy_no = arb_leg("completeness_arb", SignalAction::BuyNo, no_price, Urgency::Critical);
+        assert_eq!(executor.execute_signal(&buy_no).filled_quantity, 20);
+        ob.update(arb_exit_book(no_bid_depth));
+        (executor, state, ob)
+    }
+
+    /// Buy of 20 on one leg of the "test-market" arb pair.
+    fn arb_leg(strategy: &str, action: SignalAction, price: Decimal, urgency: Urgency) -> Signal {
+        let mut signal = buy_signal("test-market", price, 20, urgency);
+        signal.action = action;
+        signal.strategy_name = strategy.to_string();
+        signal
+            .metadata
+            .insert(ARB_PAIR_KEY.to_string(), serde_json::json!("test-market"));
+        signal
+    }
+
+    #[test]
+    fn test_unwind_arb_aborts_when_one_leg_lacks_liquidity() {
+        // Only 5 NO contracts bid against 20 held.
+        let (mut executor, state, ob) = arb_pair_executor(5);
+        let balance = state.get_balance();
+
+        let result = executor.unwind_arb("test-market");
+
+        assert_eq!(result.unwound, 0);
+        assert!(result.error.unwrap().contains("aborted"));
+        // Nothing was sent: both legs and the balance are untouched.
+        assert_eq!(executor.positions["test-market:YES"].quantity, 20);
+        assert_eq!(executor.positions["test-market:NO"].quantity, 20);
+        assert_eq!(state.get_balance(), balance);
+        assert_eq!(executor.get_fill_history().len(), 2);
+
+        // Once the NO bids refill, both legs go out together.
+        ob.update(arb_exit_book(500));
+        let result = executor.unwind_arb("test-market");
+        assert_eq!(result.unwound, 20);
+        assert_eq!(result.rolled_back, 0);
+        assert!(result.error.is_none());
+        // 20 @ 0.3998 + 20 @ 0.55972 = 19.1904, less the 0.1% fee = 19.1712096.
+        assert_eq!(result.proceeds, Decimal::new(191712096, 7));
+        assert!(executor.positions.is_empty());
+        assert!(state.get_position("test-market").is_none());
+    }
+
+    #[test]
+    fn test_unwind_arb_rolls_back_when_a_leg_fails() {
+        let (mut executor, state, ob) = arb_pair_executor(500);
+        let now = executor.clock.now();
+        let yes_leg = arb_leg_signal(&ob, "test-market", SignalAction::SellYes, 20, now);
+        let no_leg = arb_leg_signal(&ob, "test-market", SignalAction::SellNo, 20, now);
+        // Both legs passed the prepare phase; the NO bids vanish before the
+        // NO sell goes out, so it fills nothing and the YES sell is bought
+        // back.
+        ob.update(arb_exit_book(0));
+        let mut result = ArbUnwindResult::default();
+        let balance = state.get_balance();
+
+        executor.sell_arb_pair(&yes_leg, &no_leg, &mut result);
+        result.proceeds = state.get_balance() - balance;
+
+        assert_eq!(result.unwound, 0);
+        assert_eq!(result.rolled_back, 20);
+        assert!(result.error.is_some());
+        // Still a matched pair: no directional exposure left behind.
+        assert_eq!(executor.positions["test-market:YES"].quantity, 20);
+        assert_eq!(executor.positions["test-market:NO"].quantity, 20);
+        assert_eq!(state.net_market_exposure("test-market"), Decimal::ZERO);
+        // The round trip cost the spread plus fees.
+        assert!(result.proceeds < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_unwind_arb_sells_the_unpaired_leg_of_a_failed_entry() {
+        // An entry whose NO leg filled only 8 of 20.
+        let (mut executor, state, _ob) = arb_pair_executor(500);
+        let mut sell_no = sell_signal("test-market", Decimal::new(56, 2), 12, Urgency::Critical);
+        sell_no.action = SignalAction::SellNo;
+        assert_eq!(executor.execute_signal(&sell_no).filled_quantity, 12);
+
+        let result = executor.unwind_arb("test-market");
+
+        assert_eq!(result.unpaired, 12);
+        assert_eq!(result.unwound, 8);
+        assert_eq!(result.rolled_back, 0);
+        assert!(result.error.is_none());
+        assert!(executor.positions.is_empty());
+        assert!(state.get_position("test-market").is_none());
+    }
+
+    #[test]
+    fn test_unwind_arb_cancels_resting_legs_and_leaves_other_inventory() {
+        let (mut executor, state, _ob) = arb_pair_executor(500);
+        // Another strategy holds 5 more YES in the same market.
+        let other = buy_signal("test-market", Decimal::new(42, 2), 5, Urgency::Critical);
+        assert_eq!(executor.execute_signal(&other).filled_quantity, 5);
+        // A second arb leg still resting below the book.
+        let resting =
+            arb_leg("completeness_arb", SignalAction::BuyNo, Decimal::new(30, 2), Urgency::Low);
+        let order_id = executor.execute_signal(&resting).order_id;
+
+        let result = executor.unwind_arb("test-market");
+
+        assert_eq!(result.cancelled, 1);
+        assert_eq!(result.unwound, 20);
+        assert!(result.error.is_none());
+        assert!(executor.get_resting_orders().is_empty());
+        assert!(state.get_order(&order_id).is_none());
+        // Only the arb's 20 pairs were sold; the other 5 YES remain.
+        assert_eq!(executor.positions["test-market:YES"].quantity, 5);
+        assert!(!executor.positions.contains_key("test-market:NO"));
+
+        let result = executor.unwind_arb("test-market");
+        assert!(result.error.unwrap().contains("No arb holding"));
+        assert_eq!(executor.positions["test-market:YES"].quantity, 5);
+    }
+
+    #[test]
+    fn test_settle_resolved_pays_yes_at_par_and_no_at_zero() {
+        let (mut executor, state, _ob) = arb_pair_executor(500);
+        let balance = state.get_balance();
+        let resting = buy_signal("test-market", Decimal::new(30, 2), 10, Urgency::Low);
+        assert_eq!(executor.execute_signal(&resting).status, OrderStatus::Open);
+
+        let fills = executor.settle_resolved("test-market", true);
+
+        // 20 YES pay 1.00 each, 20 NO pay nothing, no fee either way.
+        assert_eq!(fills.len(), 2);
+        assert_eq!((fills[0].side, fills[0].price), (Side::Yes, Decimal::ONE));
+        assert_eq!((fills[1].side, fills[1].price), (Side::No, Decimal::ZERO));
+        assert!(fills.iter().all(|f| f.fee.is_zero()));
+        assert_eq!(state.get_balance(), balance + Decimal::new(20, 0));
+        // Each leg's P&L goes to the strategy that bought it:
+        // YES 20 × (1 - 0.42021) = 11.5958, NO 20 × (0 - 0.58029) = -11.6058.
+        let perf = executor.get_performance_snapshot();
+        assert_eq!(perf.by_strategy["test"].realized_pnl, Decimal::new(115958, 4));
+        assert_eq!(
+            perf.by_strategy["completeness_arb"].realized_pnl,
+            Decimal::new(-116058, 4)
+        );
+        assert!(!perf.by_strategy.contains_key("settlement"));
+        assert_eq!(perf.total_pnl, Decimal::new(-1, 2));
+        assert!(executor.positions.is_empty());
+        assert!(executor.get_resting_orders().is_empty());
+        assert!(state.get_position("test-market").is_none());
+
+        // Nothing left to settle.
+        assert!(executor.settle_resolved("test-market", true).is_empty());
+    }
+
+    #[test]
+    fn test_settle_market_realizes_held_yes_at_par_or_zero() {
+        let settle = |winning_side: Side| {
+            let state = StateManager::new(Decimal::new(100, 0));
+            let ob = OrderBookTracker::new();
+            ob.update(OrderBook {
+                market_slug: "test-market".to_string(),
+                yes: OrderBookSide {
+                    bids: vec![PriceLevel { price: Decimal::new(58, 2), quantity: 100 }],
+                    asks: vec![PriceLevel { price: Decimal::new(60, 2), quantity: 100 }],
+                },
+                no: OrderBookSide::default(),
+            });
+            let mut executor =
+                PaperExecutor::with_params(state.clone(), ob, Decimal::ZERO, Decimal::ZERO);
+            let buy = buy_signal("test-market", Decimal::new(60, 2), 100, Urgency::Critical);
+            assert_eq!(executor.execute_signal(&buy).avg_fill_price, Some(Decimal::new(60, 2)));
+            // $100 - 100 × 0.60 = $40 left.
+            assert_eq!(state.get_balance(), Decimal::new(40, 0));
+
+            let fills = executor.settle_market("test-market", winning_side);
+            assert_eq!(fills.len(), 1);
+            assert!(executor.positions.is_empty());
+            assert!(state.get_position("test-market").is_none());
+            (executor.get_performance_snapshot().total_pnl, state.get_balance())
+        };
+
+        // YES wins: 100 × (1.00 - 0.60) = +$40; the payout is $100.
+        assert_eq!(settle(Side::Yes), (Decimal::new(40, 0), Decimal::new(140, 0)));
+        // NO wins: 100 × (0.00 - 0.60) = -$60; nothing is paid out.
+        assert_eq!(settle(Side::No), (Decimal::new(-60, 0), Decimal::new(40, 0)));
+    }
+
+    #[test]
+    fn test_pnl_attributed_per_strategy_sums_to_totals() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob);
+
+        // Market maker: buy 50 then sell 50 (round trip at a loss).
+        let mut mm_buy = buy_signal("test-market", Decimal::new(52, 2), 50, Urgency::Critical);
+        mm_buy.strategy_name = "market_maker".to_string();
+        let mut mm_sell = sell_signal("test-market", Decimal::new(50, 2), 50, Urgency::Critical);
+        mm_sell.strategy_name = "market_maker".to_string();
+        // Arb: buy 20 only (no realized P&L).
+        let mut arb_buy = buy_signal("test-market", Decimal::new(52, 2), 20, Urgency::Critical);
+        arb_buy.strategy_name = "live_arbitrage".to_string();
+
+        executor.execute_signal(&mm_buy);
+        executor.execute_signal(&mm_sell);
+        executor.execute_signal(&arb_buy);
+
+        let perf = executor.get_performance_snapshot();
+        let mm = &perf.by_strategy["market_maker"];
+        let arb = &perf.by_strategy["live_arbitrage"];
+
+        assert_eq!(mm.total_trades, 2);
+        assert_eq!(arb.total_trades, 1);
+        assert!(mm.realized_pnl < Decimal::ZERO);
+        assert_eq!(mm.losing_trades, 1);
+        assert_eq!(mm.ewma_pnl, mm.realized_pnl, "First closing trade seeds the EWMA");
+        assert_eq!(arb.realized_pnl, Decimal::ZERO);
+        assert_eq!(arb.win_rate(), 0.0);
+
+        assert_eq!(mm.total_trades + arb.total_trades, perf.total_trades);
+        assert_eq!(mm.realized_pnl + arb.realized_pnl, perf.total_pnl);
+        assert_eq!(mm.fees_paid + arb.fees_paid, perf.total_fees_paid);
+        assert_eq!(mm.volume + arb.volume, perf.total_volume);
+
+        let json = executor.get_performance();
+        assert_eq!(json["by_strategy"]["market_maker"]["total_trades"], 2);
+        assert_eq!(json["by_strategy"]["live_arbitrage"]["total_trades"], 1);
+    }
+
+    #[test]
+    fn test_strategy_ewma_pnl_weights_recent_trades() {
+        // alpha = 0.1: seed 10, then -10 => 0.1 * -10 + 0.9 * 10 = 8
+        let mut perf = StrategyPerformance::default();
+        perf.record_fill(Decimal::ONE, Decimal::ZERO, Decimal::new(10, 0), true);
+        perf.record_fill(Decimal::ONE, Decimal::ZERO, Decimal::ZERO, false);
+        perf.record_fill(Decimal::ONE, Decimal::ZERO, Decimal::new(-10, 0), true);
+        assert_eq!(perf.ewma_pnl, Decimal::new(8, 0));
+        assert_eq!(perf.realized_pnl, Decimal::ZERO);
+        assert_eq!(perf.total_trades, 3);
+    }
+
     #[test]
     fn test_buy_then_sell_tracks_pnl() {
         let (state, ob) = setup();
@@ -1283,8 +2869,297 @@ mod tests {
             reason: "cancel".to_string(),
             metadata: HashMap::new(),
             timestamp: Utc::now(),
+            reduce_only: false,
+            good_till: None,
         };
         executor.execute_signal(&cancel);
         assert_eq!(executor.get_resting_orders().len(), 0);
     }
+
+    #[test]
+    fn test_cancel_order_removes_only_the_named_order() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state.clone(), ob);
+
+        let bid = executor.execute_signal(&buy_signal(
+            "test-market",
+            Decimal::new(45, 2),
+            50,
+            Urgency::Low,
+        ));
+        let mut no_bid = buy_signal("test-market", Decimal::new(40, 2), 20, Urgency::Low);
+        no_bid.action = SignalAction::BuyNo;
+        let kept = executor.execute_signal(&no_bid);
+        assert_eq!(executor.get_resting_orders().len(), 2);
+
+        let mut cancel = buy_signal("test-market", Decimal::ZERO, 0, Urgency::Low);
+        cancel.action = SignalAction::CancelOrder;
+        cancel
+            .metadata
+            .insert(CANCEL_ORDER_ID_KEY.to_string(), serde_json::json!(bid.order_id));
+        let result = executor.execute_signal(&cancel);
+
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(result.order_id, bid.order_id);
+        let resting = executor.get_resting_orders();
+        assert_eq!(resting.len(), 1);
+        assert_eq!(resting[0].order_id, kept.order_id);
+        assert!(state.get_order(&bid.order_id).is_none());
+        assert!(state.get_order(&kept.order_id).is_some());
+
+        // Cancelling it again, or without an id, is rejected.
+        assert_eq!(executor.execute_signal(&cancel).status, OrderStatus::Rejected);
+        cancel.metadata.clear();
+        assert_eq!(executor.execute_signal(&cancel).status, OrderStatus::Rejected);
+        assert_eq!(executor.get_resting_orders().len(), 1);
+    }
+
+    #[test]
+    fn test_resting_order_uses_injected_clock() {
+        use crate::clock::{Clock, MockClock};
+        use std::sync::Arc;
+
+        let (state, ob) = setup();
+        let clock = MockClock::default();
+        let start = clock.now();
+        let mut executor = PaperExecutor::with_clock(state, ob, Arc::new(clock.clone()));
+
+        let signal = buy_signal("test-market", Decimal::new(48, 2), 50, Urgency::Low);
+        executor.execute_signal(&signal);
+
+        clock.advance(chrono::Duration::seconds(90));
+        let resting = executor.get_resting_orders();
+        assert_eq!(resting.len(), 1);
+        assert_eq!(resting[0].created_at, start);
+        assert_eq!((clock.now() - resting[0].created_at).num_seconds(), 90);
+    }
+
+    #[test]
+    fn test_good_till_order_expires_on_resting_check() {
+        use crate::clock::{Clock, MockClock};
+        use std::sync::Arc;
+
+        let (state, ob) = setup();
+        let clock = MockClock::default();
+        let mut executor =
+            PaperExecutor::with_clock(state.clone(), ob, Arc::new(clock.clone()));
+
+        let mut expiring = buy_signal("test-market", Decimal::new(48, 2), 50, Urgency::Low);
+        expiring.good_till = Some(clock.now() + chrono::Duration::seconds(60));
+        let expiring_id = executor.execute_signal(&expiring).order_id;
+        let open_ended = buy_signal("test-market", Decimal::new(47, 2), 50, Urgency::Low);
+        executor.execute_signal(&open_ended);
+        assert_eq!(executor.get_resting_orders().len(), 2);
+
+        // Before the expiry both orders keep resting.
+        clock.advance(chrono::Duration::seconds(59));
+        executor.check_resting_orders();
+        assert_eq!(executor.get_resting_orders().len(), 2);
+
+        // At the expiry only the GoodTillDate order is cancelled.
+        clock.advance(chrono::Duration::seconds(1));
+        let fills = executor.check_resting_orders();
+        assert!(fills.is_empty());
+        let resting = executor.get_resting_orders();
+        assert_eq!(resting.len(), 1);
+        assert!(resting[0].expires_at.is_none());
+        assert!(state.get_order(&expiring_id).is_none());
+    }
+
+    /// YES book with `bid_at_50` contracts bid at 0.50 and the given asks.
+    fn queue_book(bid_at_50: i64, asks: Vec<PriceLevel>) -> OrderBook {
+        OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![
+                    PriceLevel { price: Decimal::new(50, 2), quantity: bid_at_50 },
+                    PriceLevel { price: Decimal::new(49, 2), quantity: 200 },
+                ],
+                asks,
+            },
+            no: OrderBookSide::default(),
+        }
+    }
+
+    #[test]
+    fn test_queue_model_waits_for_volume_ahead() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob.clone()).with_queue_model(true);
+
+        // Join the 0.50 bid behind 100 displayed contracts.
+        let signal = buy_signal("test-market", Decimal::new(50, 2), 50, Urgency::Low);
+        executor.execute_signal(&signal);
+        assert_eq!(executor.get_resting_orders()[0].queue_ahead, 100);
+
+        // 30 contracts offered at our price trade with the queue ahead: 70 left.
+        let crossing = || vec![PriceLevel { price: Decimal::new(50, 2), quantity: 30 }];
+        ob.update(queue_book(100, crossing()));
+        assert!(executor.check_resting_orders().is_empty());
+        assert_eq!(executor.get_resting_orders()[0].queue_ahead, 70);
+
+        // The book catches up with those trades (100 -> 70): not counted twice.
+        ob.update(queue_book(70, vec![]));
+        assert!(executor.check_resting_orders().is_empty());
+        assert_eq!(executor.get_resting_orders()[0].queue_ahead, 70);
+
+        // Displayed bid size drops 70 -> 0: the rest of the queue is gone,
+        // so the 30 crossing contracts now fill us.
+        ob.update(queue_book(0, crossing()));
+        let fills = executor.check_resting_orders();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 30);
+        let resting = executor.get_resting_orders();
+        assert_eq!(resting[0].queue_ahead, 0);
+        assert_eq!(resting[0].remaining(), 20);
+    }
+
+    #[test]
+    fn test_without_queue_model_crossing_liquidity_fills_immediately() {
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob.clone());
+
+        let signal = buy_signal("test-market", Decimal::new(50, 2), 50, Urgency::Low);
+        executor.execute_signal(&signal);
+
+        ob.update(queue_book(
+            100,
+            vec![PriceLevel { price: Decimal::new(50, 2), quantity: 30 }],
+        ));
+        let fills = executor.check_resting_orders();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 30);
+    }
+
+    #[test]
+    fn test_price_improvement_required_for_resting_fill_at_limit() {
+        let at_limit = || vec![PriceLevel { price: Decimal::new(50, 2), quantity: 30 }];
+        let improved = || vec![PriceLevel { price: Decimal::new(49, 2), quantity: 30 }];
+
+        // Default: an ask at exactly our resting bid fills it.
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob.clone());
+        let signal = buy_signal("test-market", Decimal::new(50, 2), 50, Urgency::Low);
+        executor.execute_signal(&signal);
+        ob.update(queue_book(100, at_limit()));
+        assert_eq!(executor.check_resting_orders()[0].quantity, 30);
+
+        // Strict: the same ask leaves us resting; only a better one fills.
+        let (state, ob) = setup();
+        let mut executor =
+            PaperExecutor::new(state, ob.clone()).with_price_improvement_for_maker_fill(true);
+        executor.execute_signal(&signal);
+        ob.update(queue_book(100, at_limit()));
+        assert!(executor.check_resting_orders().is_empty());
+        assert_eq!(executor.get_resting_orders()[0].remaining(), 50);
+
+        ob.update(queue_book(100, improved()));
+        let fills = executor.check_resting_orders();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 30);
+        assert_eq!(fills[0].price, Decimal::new(49, 2));
+    }
+
+    #[test]
+    fn test_no_buy_fills_against_synthesized_no_book() {
+        // The seeded book quotes YES 0.50 / 0.52 and no NO side.
+        let mut signal = buy_signal("test-market", Decimal::new(50, 2), 40, Urgency::Low);
+        signal.action = SignalAction::BuyNo;
+
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob);
+        let result = executor.execute_signal(&signal);
+        assert_eq!(result.filled_quantity, 0);
+        assert_eq!(result.status, OrderStatus::Open);
+
+        // Synthesized: the 0.50 YES bid is a NO ask at 0.50.
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob).with_synthetic_no_book(true);
+        let result = executor.execute_signal(&signal);
+        assert_eq!(result.filled_quantity, 40);
+        assert_eq!(result.avg_fill_price, Some(Decimal::new(50, 2)));
+        assert_eq!(executor.get_fill_history()[0].side, Side::No);
+
+        let no = setup().1.get_full("test-market").unwrap().yes.complement();
+        assert_eq!(no.best_bid(), Some(Decimal::new(48, 2)));
+        assert_eq!(no.best_ask(), Some(Decimal::new(50, 2)));
+        assert_eq!(no.total_ask_depth(), 300);
+    }
+
+    #[test]
+    fn test_fill_appends_parseable_trade_log_line() {
+        use crate::execution::trade_log::TradeLog;
+
+        let path = std::env::temp_dir().join(format!(
+            "paper-trade-log-{}-{}.jsonl",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let log = TradeLog::open(&path).unwrap();
+
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob);
+        executor.set_trade_log(log.handle());
+        let mut signal = buy_signal("test-market", Decimal::new(52, 2), 10, Urgency::Low);
+        signal.strategy_name = "market_maker".to_string();
+        let result = executor.execute_signal(&signal);
+        assert_eq!(result.filled_quantity, 10);
+
+        // Closing flushes the buffered writer.
+        log.close();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let record: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(record["order_id"], result.order_id.as_str());
+        assert_eq!(record["market_slug"], "test-market");
+        assert_eq!(record["side"], "YES");
+        assert_eq!(record["direction"], "BUY");
+        assert_eq!(record["price"], "0.52");
+        assert_eq!(record["quantity"], 10);
+        assert_eq!(record["fee"], "0.00520");
+        assert_eq!(record["realized_pnl"], "0");
+        assert_eq!(record["strategy"], "market_maker");
+        assert!(record["timestamp"].is_string());
+    }
+
+    #[test]
+    fn test_round_trip_at_quotes_attributes_pnl_to_spread() {
+        // YES book 0.50 / 0.52 => mid 0.51 throughout.
+        let (state, ob) = setup();
+        let mut executor = PaperExecutor::new(state, ob.clone());
+        let level = |price: i64, quantity: i64| PriceLevel {
+            price: Decimal::new(price, 2),
+            quantity,
+        };
+        let book = |bids: Vec<PriceLevel>, asks: Vec<PriceLevel>| OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide { bids, asks },
+            no: OrderBookSide::default(),
+        };
+
+        // Bid 10 @ 0.50 rests; a seller crosses down to it.
+        executor.execute_signal(&buy_signal("test-market", Decimal::new(50, 2), 10, Urgency::Low));
+        ob.update(book(vec![level(50, 100)], vec![level(50, 10), level(52, 100)]));
+        assert_eq!(executor.check_resting_orders()[0].price, Decimal::new(50, 2));
+
+        // Offer 10 @ 0.52 rests in the restored book; a buyer lifts it.
+        ob.update(book(vec![level(50, 100)], vec![level(52, 100)]));
+        executor.execute_signal(&sell_signal("test-market", Decimal::new(52, 2), 10, Urgency::Low));
+        ob.update(book(vec![level(52, 10), level(50, 100)], vec![level(52, 100)]));
+        assert_eq!(executor.check_resting_orders()[0].price, Decimal::new(52, 2));
+
+        // Realized = (0.52 - 0.50) * 10 = 0.20
+        // Spread   = (0.51 - 0.50) * 10 + (0.52 - 0.51) * 10 = 0.20
+        // Directional = mid 0.51 -> 0.51 = 0
+        let perf = executor.get_performance_snapshot();
+        assert_eq!(perf.total_pnl, Decimal::new(20, 2));
+        assert_eq!(perf.spread_pnl, Decimal::new(20, 2));
+        assert_eq!(perf.directional_pnl, Decimal::ZERO);
+
+        let reported = executor.get_performance();
+        assert_eq!(reported["spread_pnl"], serde_json::json!(0.2));
+        assert_eq!(reported["directional_pnl"], serde_json::json!(0.0));
+    }
 }
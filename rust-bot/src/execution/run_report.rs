@@ -0,0 +1,136 @@
+//! Machine-readable summary of one bot run, written as JSON at shutdown.
+//!
+//! Wraps an executor's `get_performance` map with run metadata (start and
+//! end time, tick and feed cycle counts) so CI and post-run analysis can
+//! read a single artifact instead of scraping the final log lines.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// One run's results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    /// Executor mode: "paper", "live" or "shadow" (empty without one).
+    pub mode: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub runtime_secs: f64,
+    /// Trading loop iterations.
+    pub tick_count: u64,
+    /// Market feed poll cycles.
+    pub feed_cycles: u64,
+    /// Executor metrics from `get_performance`, less `mode` and `by_strategy`.
+    pub performance: BTreeMap<String, Value>,
+    /// Per-strategy metrics, keyed by strategy name.
+    pub by_strategy: BTreeMap<String, Value>,
+}
+
+impl RunReport {
+    /// Build from an executor's `get_performance` map.
+    pub fn new(
+        mut performance: HashMap<String, Value>,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        tick_count: u64,
+        feed_cycles: u64,
+    ) -> Self {
+        let mode = performance
+            .remove("mode")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let by_strategy = match performance.remove("by_strategy") {
+            Some(Value::Object(map)) => map.into_iter().collect(),
+            _ => BTreeMap::new(),
+        };
+        Self {
+            mode,
+            started_at,
+            ended_at,
+            runtime_secs: (ended_at - started_at).num_milliseconds() as f64 / 1000.0,
+            tick_count,
+            feed_cycles,
+            performance: performance.into_iter().collect(),
+            by_strategy,
+        }
+    }
+
+    /// Write the report to `path` as pretty-printed JSON, replacing any
+    /// existing file.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        out.write_all(b"\n")?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::{
+        OrderBook, OrderBookSide, PriceLevel, Signal, SignalAction, Urgency,
+    };
+    use crate::data::orderbook::OrderBookTracker;
+    use crate::execution::paper::PaperExecutor;
+    use crate::state::state_manager::StateManager;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_report_from_paper_run_round_trips_through_json() {
+        let state = StateManager::new(Decimal::new(100, 0));
+        let ob = OrderBookTracker::new();
+        ob.update(OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(48, 2), quantity: 500 }],
+                asks: vec![PriceLevel { price: Decimal::new(52, 2), quantity: 500 }],
+            },
+            no: OrderBookSide::default(),
+        });
+        let mut executor = PaperExecutor::new(state, ob);
+        let signal = Signal {
+            market_slug: "test-market".to_string(),
+            action: SignalAction::BuyYes,
+            price: Decimal::new(52, 2),
+            quantity: 10,
+            urgency: Urgency::Critical,
+            confidence: 1.0,
+            strategy_name: "market_maker".to_string(),
+            reason: "test".to_string(),
+            metadata: HashMap::new(),
+            timestamp: Utc::now(),
+            reduce_only: false,
+            good_till: None,
+        };
+        assert_eq!(executor.execute_signal(&signal).filled_quantity, 10);
+
+        let started_at = Utc::now();
+        let ended_at = started_at + chrono::Duration::milliseconds(90_500);
+        let report = RunReport::new(executor.get_performance(), started_at, ended_at, 181, 18);
+
+        assert_eq!(report.mode, "paper");
+        assert_eq!(report.runtime_secs, 90.5);
+        assert_eq!(report.performance["total_trades"], 1);
+        assert!(!report.performance.contains_key("by_strategy"));
+        assert_eq!(report.by_strategy["market_maker"]["total_trades"], 1);
+
+        let path = std::env::temp_dir().join(format!(
+            "run-report-{}-{}.json",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        report.write(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let parsed: RunReport = serde_json::from_str(&contents).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
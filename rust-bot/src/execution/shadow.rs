@@ -0,0 +1,86 @@
+//! Shadow executor: logs each approved signal as the order it would send,
+//! without placing it or simulating a fill.
+//!
+//! Used to vet a strategy config against live data before going live.
+//! Nothing here touches `StateManager`, so balance, positions and open
+//! orders stay exactly as the feed and initial balance left them.
+
+#![allow(dead_code)]
+
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+use crate::data::models::{OrderStatus, Signal};
+use crate::execution::executor::ExecResult;
+
+/// Counts of would-be orders, overall and broken down.
+#[derive(Debug, Clone, Default)]
+pub struct ShadowExecutor {
+    total_orders: u64,
+    by_action: BTreeMap<String, u64>,
+    by_strategy: BTreeMap<String, u64>,
+}
+
+impl ShadowExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log `signal` as a would-be order and count it. Never fills.
+    pub fn execute_signal(&mut self, signal: &Signal) -> ExecResult {
+        self.total_orders += 1;
+        let action = format!("{:?}", signal.action);
+        *self.by_action.entry(action.clone()).or_insert(0) += 1;
+        *self
+            .by_strategy
+            .entry(signal.strategy_name.clone())
+            .or_insert(0) += 1;
+
+        info!(
+            market_slug = %signal.market_slug,
+            action = %action,
+            price = %signal.price,
+            quantity = signal.quantity,
+            urgency = ?signal.urgency,
+            reduce_only = signal.reduce_only,
+            good_till = ?signal.good_till,
+            strategy = %signal.strategy_name,
+            reason = %signal.reason,
+            "[SHADOW] Would-be order"
+        );
+
+        ExecResult {
+            order_id: format!("shadow-{}", self.total_orders),
+            status: OrderStatus::Pending,
+            filled_quantity: 0,
+            avg_fill_price: None,
+            fee: Decimal::ZERO,
+            error: None,
+            reject_reason: None,
+        }
+    }
+
+    pub fn total_orders(&self) -> u64 {
+        self.total_orders
+    }
+
+    /// Would-be order counts (same map format as the other executors).
+    pub fn get_performance(&self) -> HashMap<String, serde_json::Value> {
+        let mut m = HashMap::new();
+        m.insert("mode".to_string(), serde_json::json!("shadow"));
+        m.insert(
+            "would_be_orders".to_string(),
+            serde_json::json!(self.total_orders),
+        );
+        m.insert(
+            "would_be_by_action".to_string(),
+            serde_json::json!(self.by_action),
+        );
+        m.insert(
+            "would_be_by_strategy".to_string(),
+            serde_json::json!(self.by_strategy),
+        );
+        m
+    }
+}
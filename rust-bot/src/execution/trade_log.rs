@@ -0,0 +1,146 @@
+//! Newline-delimited JSON trade log for post-analysis.
+//!
+//! Executors hand each fill to a `TradeLogHandle`, which queues it for a
+//! background writer thread so execution never waits on disk I/O. The file
+//! is independent of the console logger; each line is one `TradeRecord`.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::data::models::{FillEvent, Side};
+
+/// How long the writer waits for new records before flushing the buffer.
+const IDLE_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// One fill, as written to the trade log.
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeRecord {
+    pub order_id: String,
+    pub market_slug: String,
+    pub side: Side,
+    /// "BUY" or "SELL".
+    pub direction: &'static str,
+    pub price: Decimal,
+    pub quantity: i64,
+    pub fee: Decimal,
+    pub realized_pnl: Decimal,
+    pub strategy: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TradeRecord {
+    pub fn from_fill(fill: &FillEvent, realized_pnl: Decimal) -> Self {
+        Self {
+            order_id: fill.order_id.clone(),
+            market_slug: fill.market_slug.clone(),
+            side: fill.side,
+            direction: if fill.is_buy { "BUY" } else { "SELL" },
+            price: fill.price,
+            quantity: fill.quantity,
+            fee: fill.fee,
+            realized_pnl,
+            strategy: fill.strategy_name.clone(),
+            timestamp: fill.timestamp,
+        }
+    }
+}
+
+enum Command {
+    Record(TradeRecord),
+    Shutdown,
+}
+
+/// Cheap, cloneable sender used by the executors.
+#[derive(Debug, Clone)]
+pub struct TradeLogHandle {
+    tx: mpsc::Sender<Command>,
+}
+
+impl TradeLogHandle {
+    /// Queue a record for writing. Never blocks.
+    pub fn record(&self, record: TradeRecord) {
+        // The writer only goes away after shutdown; late records are dropped.
+        let _ = self.tx.send(Command::Record(record));
+    }
+}
+
+/// Owner of the background writer. Dropping (or `close`) flushes the file.
+pub struct TradeLog {
+    tx: mpsc::Sender<Command>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl TradeLog {
+    /// Open `path` for appending and start the writer thread.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (tx, rx) = mpsc::channel();
+        let worker = std::thread::Builder::new()
+            .name("trade-log".to_string())
+            .spawn(move || run_writer(BufWriter::new(file), rx))?;
+        Ok(Self {
+            tx,
+            worker: Some(worker),
+        })
+    }
+
+    pub fn handle(&self) -> TradeLogHandle {
+        TradeLogHandle {
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Write out everything queued so far and stop the writer.
+    pub fn close(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            let _ = self.tx.send(Command::Shutdown);
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for TradeLog {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run_writer<W: Write>(mut out: W, rx: mpsc::Receiver<Command>) {
+    loop {
+        match rx.recv_timeout(IDLE_FLUSH_INTERVAL) {
+            Ok(Command::Record(record)) => {
+                if let Err(e) = write_record(&mut out, &record) {
+                    warn!(error = %e, order_id = %record.order_id, "Trade log write failed");
+                }
+            }
+            Ok(Command::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                if let Err(e) = out.flush() {
+                    warn!(error = %e, "Trade log flush failed");
+                }
+            }
+        }
+    }
+    if let Err(e) = out.flush() {
+        warn!(error = %e, "Trade log flush failed");
+    }
+}
+
+fn write_record<W: Write>(out: &mut W, record: &TradeRecord) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, record)?;
+    out.write_all(b"\n")
+}
@@ -4,9 +4,12 @@
 
 pub mod api;
 pub mod auth;
+pub mod clock;
 pub mod config;
 pub mod data;
 pub mod execution;
 pub mod risk;
 pub mod state;
+pub mod status_server;
 pub mod strategies;
+pub mod util;
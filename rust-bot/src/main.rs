@@ -13,35 +13,60 @@
 
 mod api;
 mod auth;
+mod clock;
 mod config;
 mod data;
 mod execution;
 mod risk;
 mod state;
+mod status_server;
 mod strategies;
+mod util;
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::signal;
 use tokio::sync::Notify;
 use tracing::{error, info, warn};
 
+use api::client::ConnectionConfig;
 use auth::PolymarketAuth;
 use config::{Settings, TradingMode};
+use data::feed_scheduler::{FeedScheduler, FeedSchedulerConfig};
+use data::feed_watchdog::{FeedHealth, FeedWatchdog};
 use data::market_feed::{MarketFeed, MarketFeedConfig};
-use data::orderbook::OrderBookTracker;
-use execution::executor::LiveExecutor;
-use execution::paper::PaperExecutor;
-use risk::risk_manager::{RiskConfig, RiskManager};
-use state::state_manager::StateManager;
-use strategies::engine::StrategyEngine;
+use data::poll_breaker::PollBreakerConfig;
+use data::models::{parse_fee_override, FeeSchedule, OrderStatus, Signal};
+use data::orderbook::{BookQualityConfig, OrderBookTracker, SignalBookLog};
+use data::startup::{discover_with_retry, live_preflight, retry_with_backoff, StartupRetry};
+use execution::equity_export::{EquityCsv, EquitySampler, StrategyPnlBoard};
+use execution::equity_series::{periods_per_year, EquitySeries};
+use execution::executor::{ExecResult, LiveExecutor};
+use execution::paper::{PaperExecutor, StressConfig};
+use execution::run_report::RunReport;
+use execution::shadow::ShadowExecutor;
+use execution::trade_log::TradeLog;
+use risk::position_manager::{PositionAgingConfig, PositionManager};
+use risk::risk_manager::{parse_confidence_floor, RiskConfig, RiskManager};
+use risk::size_ramp::parse_size_ramp;
+use state::event_log::{EventKind, EventLog};
+use state::state_manager::{PriceBandConfig, StateManager, WarmupConfig};
+use status_server::{StatusServer, StatusServerConfig};
+use strategies::completeness_arb::{CompletenessArbConfig, CompletenessArbStrategy};
+use strategies::engine::{ConflictPolicy, StrategyEngine, BOOK_CONTEXT_KEYS, STRATEGY_NAMES};
 use strategies::live_arbitrage::{LiveArbitrageConfig, LiveArbitrageStrategy};
 use strategies::market_maker::{MarketMakerConfig, MarketMakerStrategy};
-use strategies::statistical_edge::{StatisticalEdgeConfig, StatisticalEdgeStrategy};
+use strategies::net_edge::{NetEdgeConfig, NetEdgeGate};
+use strategies::statistical_edge::{
+    OddsAggregation, PairConfig, StatisticalEdgeConfig, StatisticalEdgeStrategy,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let started_at = chrono::Utc::now();
+
     // Load configuration.
     let settings = Settings::from_env();
 
@@ -64,19 +89,77 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // Initialize auth.
-    let auth = PolymarketAuth::new(&settings.pm_api_key_id, &settings.pm_private_key)?;
-    info!(public_key = %auth.public_key_base64(), "Authentication initialized");
+    let auth_pool = build_auth_pool(&settings)?;
+    info!(
+        public_key = %auth_pool[0].public_key_base64(),
+        api_keys = auth_pool.len(),
+        "Authentication initialized"
+    );
+
+    // Size poll concurrency and the connection pool together. Polls beyond
+    // the client's per-second quota only queue at the rate limiter, holding
+    // pooled connections idle, so concurrency is capped at the quota and the
+    // pool defaults to one idle connection per concurrent poll.
+    let request_quota = settings.api_rate_limit as usize * auth_pool.len();
+    let poll_concurrency = settings.rest_orderbook_concurrency.min(request_quota);
+    if poll_concurrency < settings.rest_orderbook_concurrency {
+        info!(
+            configured = settings.rest_orderbook_concurrency,
+            request_quota,
+            "Order book poll concurrency capped at the request quota"
+        );
+    }
+    let connection_config = ConnectionConfig {
+        pool_max_idle_per_host: match settings.http_pool_max_idle_per_host {
+            0 => poll_concurrency,
+            n => n,
+        },
+        pool_idle_timeout: Duration::from_secs_f64(settings.http_pool_idle_timeout_secs),
+        tcp_keepalive: (settings.http_tcp_keepalive_secs > 0.0)
+            .then(|| Duration::from_secs_f64(settings.http_tcp_keepalive_secs)),
+    };
 
     // Initialize API client (shared across feed + executor).
     let client = Arc::new(
-        api::client::PolymarketClient::with_defaults(auth, &settings.pm_base_url)?,
+        api::client::PolymarketClient::with_key_pool(
+            auth_pool,
+            &settings.pm_base_url,
+            settings.api_rate_limit,
+            3,
+            30,
+        )?
+        .with_connection_config(&connection_config)?
+        .with_body_logging(settings.log_api_bodies),
     );
 
     // Initialize state manager.
-    let state = StateManager::new(settings.initial_balance);
+    let state = StateManager::new(settings.initial_balance)
+        .with_event_log(EventLog::new(settings.event_log_capacity));
+    state.set_warmup_config(WarmupConfig {
+        min_updates: settings.warmup_min_updates,
+        warmup_secs: settings.warmup_secs,
+        max_gap_secs: settings.warmup_max_gap_secs,
+    });
+    state.set_price_band_config(PriceBandConfig {
+        max_jump_pct: settings.price_band_max_jump_pct,
+        confirm_polls: settings.price_band_confirm_polls,
+    });
+    state.set_mark_last_trade(settings.mark_last_trade);
+    state.set_fee_overrides(
+        settings
+            .fee_overrides
+            .iter()
+            .filter_map(|e| parse_fee_override(e))
+            .collect(),
+    );
 
     // Initialize order book tracker.
     let orderbook = OrderBookTracker::new();
+    orderbook.set_quality_config(BookQualityConfig {
+        wide_spread_threshold: settings.book_wide_spread,
+        min_score: settings.book_quality_floor,
+    });
+    let mut signal_log = SignalBookLog::new(settings.signal_log_capacity);
 
     // Shutdown signal using AtomicBool (reliable across select! loops).
     let shutdown_flag = Arc::new(AtomicBool::new(false));
@@ -95,69 +178,41 @@ async fn main() -> anyhow::Result<()> {
     // =========================================================================
     // Discover markets to trade
     // =========================================================================
-    let market_slugs = if settings.market_slugs.is_empty() {
-        info!("No MARKET_SLUGS configured, discovering open markets from API...");
-        // Pass closed=false to only get open (non-closed) markets,
-        // matching the Python bot's discover_markets() approach.
-        let mut all_markets = Vec::new();
-        for offset in (0..500).step_by(100) {
-            match client.get_markets(None, None, 100, offset, Some("false")).await {
-                Ok(batch) => {
-                    if batch.is_empty() {
-                        break;
-                    }
-                    all_markets.extend(batch);
-                }
-                Err(e) => {
-                    warn!(error = %e, offset, "Market fetch failed at offset");
-                    break;
-                }
-            }
-        }
-        info!(total_fetched = all_markets.len(), "Fetched open markets from API");
-
-        // Additional client-side filter: active, future game date.
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let tradeable: Vec<&data::models::Market> = all_markets
-            .iter()
-            .filter(|m| m.active)
-            .filter(|m| {
-                // Extract date from slug: aec-nfl-lac-ten-YYYY-MM-DD
-                let parts: Vec<&str> = m.slug.split('-').collect();
-                if parts.len() >= 7 {
-                    let date_str = format!("{}-{}-{}", parts[4], parts[5], parts[6]);
-                    date_str >= today
-                } else {
-                    // Keep markets with unknown date format.
-                    true
-                }
-            })
-            .collect();
-
-        let slugs: Vec<String> = tradeable.iter().map(|m| m.slug.clone()).collect();
+    let startup_retry = StartupRetry {
+        attempts: settings.startup_retry_attempts,
+        backoff: Duration::from_secs_f64(settings.startup_retry_backoff_secs),
+    };
+    // Live orders need working credentials; find out before trading starts.
+    if settings.trading_mode == TradingMode::Live {
+        let balance = live_preflight(&client, &startup_retry).await?;
         info!(
-            total_fetched = all_markets.len(),
-            tradeable = tradeable.len(),
-            "Discovered tradeable markets"
+            available = %balance.available_balance,
+            currency = %balance.currency,
+            "Live preflight OK: credentials accepted"
         );
-        for (i, m) in tradeable.iter().enumerate().take(10) {
-            info!(
-                slug = %m.slug,
-                title = %m.title,
-                yes_bid = ?m.yes_bid,
-                yes_ask = ?m.yes_ask,
-                no_bid = ?m.no_bid,
-                no_ask = ?m.no_ask,
-                "  [{}] Market", i + 1
-            );
-        }
-        if slugs.len() > 10 {
-            info!("  ... and {} more", slugs.len() - 10);
-        }
+    }
+    // Scheduled resolution and order constraints per discovered market
+    // (configured slugs learn theirs from the feed's status check).
+    let mut resolution_dates = HashMap::new();
+    let mut market_metadata = HashMap::new();
+    let market_slugs = if settings.market_slugs.is_empty() {
+        info!("No MARKET_SLUGS configured, discovering open markets from API...");
+        let markets = discover_with_retry(&startup_retry, settings.min_discovered_markets, || {
+            discover_markets(&client, &settings)
+        })
+        .await?;
+        let slugs: Vec<String> = markets.iter().map(|m| m.slug.clone()).collect();
+        market_metadata.extend(markets.iter().map(|m| (m.slug.clone(), m.metadata())));
+        resolution_dates.extend(markets.into_iter().map(|m| (m.slug, m.resolution_date)));
 
-        // Verify book endpoint parses correctly.
+        // Verify book endpoint parses correctly. A probe that keeps failing
+        // is not fatal: the feed retries every cycle anyway.
         if let Some(first_slug) = slugs.first() {
-            match client.get_market_book(first_slug).await {
+            let probe = retry_with_backoff("order book probe", &startup_retry, |_| {
+                client.get_market_book(first_slug)
+            })
+            .await;
+            match probe {
                 Ok(book) => {
                     info!(
                         slug = %first_slug,
@@ -170,7 +225,11 @@ async fn main() -> anyhow::Result<()> {
                         "Order book probe OK"
                     );
                 }
-                Err(e) => warn!(slug = %first_slug, error = %e, "Order book probe failed"),
+                Err(e) => warn!(
+                    slug = %first_slug,
+                    error = %e,
+                    "Order book probe failed after retries, starting anyway"
+                ),
             }
         }
 
@@ -189,46 +248,152 @@ async fn main() -> anyhow::Result<()> {
             yes_ask: None,
             no_bid: None,
             no_ask: None,
+            yes_bid_depth: None,
+            yes_ask_depth: None,
+            resolution_date: resolution_dates.get(slug).copied().flatten(),
+            tick_size: None,
+            min_order_size: None,
             last_updated: chrono::Utc::now(),
         });
+        if let Some(&metadata) = market_metadata.get(slug) {
+            state.set_market_metadata(slug, metadata);
+        }
     }
 
     // =========================================================================
     // Start market data feed (background task)
     // =========================================================================
+    let staleness_threshold = Duration::from_secs(30);
     let feed_config = MarketFeedConfig {
         poll_interval: Duration::from_secs_f64(
             settings.rest_orderbook_poll_interval_seconds,
         ),
-        max_concurrency: settings.rest_orderbook_concurrency,
-        staleness_threshold: Duration::from_secs(30),
+        max_concurrency: poll_concurrency,
+        staleness_threshold,
+        status_check_interval: Duration::from_secs_f64(settings.market_status_check_secs),
+        breaker: PollBreakerConfig {
+            failure_threshold: settings.feed_breaker_failures,
+            max_interval: Duration::from_secs_f64(settings.feed_breaker_max_interval_secs),
+        },
     };
-    let feed = MarketFeed::new(
-        client.clone(),
-        orderbook.clone(),
-        state.clone(),
-        feed_config,
-        shutdown_notify.clone(),
-    );
-    let feed_handle = feed.spawn();
+    let scheduler_config = if settings.feed_priority_scheduling {
+        let scheduler_config = FeedSchedulerConfig {
+            min_interval: chrono::Duration::milliseconds(
+                (settings.rest_orderbook_poll_interval_seconds * 1000.0) as i64,
+            ),
+            max_interval: chrono::Duration::milliseconds(
+                (settings.feed_max_poll_interval_seconds * 1000.0) as i64,
+            ),
+            wide_spread: settings.book_wide_spread,
+            max_polls_per_cycle: settings.feed_max_polls_per_cycle,
+            ..FeedSchedulerConfig::default()
+        };
+        info!(
+            max_interval_s = settings.feed_max_poll_interval_seconds,
+            max_polls_per_cycle = settings.feed_max_polls_per_cycle,
+            "Feed priority scheduling ENABLED"
+        );
+        Some(scheduler_config)
+    } else {
+        None
+    };
+    // Shared with every (re)started feed task: the watchdog's heartbeat.
+    let feed_cycles = Arc::new(AtomicU64::new(0));
+    let spawn_feed = || {
+        let mut feed = MarketFeed::new(
+            client.clone(),
+            orderbook.clone(),
+            state.clone(),
+            feed_config.clone(),
+            shutdown_notify.clone(),
+        )
+        .with_cycle_counter(feed_cycles.clone());
+        if let Some(ref config) = scheduler_config {
+            feed = feed.with_scheduler(FeedScheduler::new(config.clone(), state.clone()));
+        }
+        feed.spawn()
+    };
+    let mut feed_handle = spawn_feed();
     info!("Market data feed started");
 
+    // =========================================================================
+    // Status server (background task)
+    // =========================================================================
+    let status_addr = format!("{}:{}", settings.health_host, settings.health_port);
+    let (toggle_tx, toggle_rx) = tokio::sync::mpsc::unbounded_channel();
+    let status_handle = match tokio::net::TcpListener::bind(&status_addr).await {
+        Ok(listener) => {
+            let mut server = StatusServer::new(
+                StatusServerConfig {
+                    book_depth: settings.status_book_depth,
+                    book_stale_after: chrono::Duration::from_std(staleness_threshold)?,
+                },
+                orderbook.clone(),
+            );
+            if settings.event_log_capacity > 0 {
+                server = server.with_events(state.events().clone());
+            }
+            if settings.status_control_enabled {
+                server = server
+                    .with_control(toggle_tx, &settings.status_control_token)
+                    .with_markets(state.clone());
+                info!("Strategy and market control routes enabled on the status server");
+            }
+            let server = Arc::new(server);
+            info!(addr = %status_addr, "Status server listening");
+            Some(tokio::spawn(server.serve(listener)))
+        }
+        Err(e) => {
+            warn!(addr = %status_addr, error = %e, "Status server failed to bind");
+            None
+        }
+    };
+
     // =========================================================================
     // Initialize risk manager
     // =========================================================================
     let risk_config = RiskConfig {
         kelly_fraction: settings.kelly_fraction,
         min_edge: settings.min_edge,
+        fee_rate: settings.fee_rate,
         max_position_per_market: settings.max_position_per_market,
+        max_position_pct_of_equity: settings.max_position_pct_of_equity,
+        max_contracts_per_market: settings.max_contracts_per_market,
         max_portfolio_exposure: settings.max_portfolio_exposure,
         max_portfolio_exposure_pct: settings.max_portfolio_exposure_pct,
         max_correlated_exposure: settings.max_correlated_exposure,
         max_positions: settings.max_positions,
+        net_hedged_exposure: settings.net_hedged_exposure,
         max_daily_loss: settings.max_daily_loss,
         max_drawdown_pct: settings.max_drawdown_pct,
         max_total_pnl_drawdown_pct_for_new_buys: settings
             .max_total_pnl_drawdown_pct_for_new_buys,
         min_trade_size: settings.min_trade_size,
+        max_var: settings.max_var,
+        var_confidence: settings.var_confidence,
+        max_orders_per_sec: settings.max_orders_per_sec,
+        max_orders_per_sec_per_market: settings.max_orders_per_sec_per_market,
+        warning_threshold_pct: settings.warning_threshold_pct,
+        warning_size_scale: settings.warning_size_scale,
+        panic_liquidate_drawdown_pct: settings.panic_liquidate_drawdown_pct,
+        conservative_marks: settings.conservative_marks,
+        min_resolution_horizon: resolution_horizon(&settings),
+        min_confidence: settings
+            .min_confidence
+            .iter()
+            .filter_map(|e| parse_confidence_floor(e))
+            .collect(),
+        cash_buffer_pct: settings.cash_buffer_pct,
+        size_ramp: settings
+            .size_ramp
+            .iter()
+            .filter_map(|e| parse_size_ramp(e))
+            .collect(),
+        drawdown_scaled_kelly: settings.drawdown_scaled_kelly,
+        min_order_interval: chrono::Duration::milliseconds(
+            (settings.min_order_interval_secs * 1000.0) as i64,
+        ),
+        flip_protection: settings.flip_protection,
     };
     let mut risk_manager = RiskManager::new(risk_config, state.clone());
 
@@ -237,11 +402,31 @@ async fn main() -> anyhow::Result<()> {
     // =========================================================================
     let market_maker = if settings.enable_market_maker {
         info!("Market maker strategy ENABLED");
-        Some(MarketMakerStrategy::new(MarketMakerConfig {
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
             spread: settings.market_maker_spread,
+            max_spread: settings.market_maker_max_spread,
             order_size: settings.market_maker_order_size,
+            inventory_target: settings.market_maker_inventory_target,
+            fees: FeeSchedule {
+                maker_fee_rate: settings.maker_fee_rate,
+                taker_fee_rate: settings.fee_rate,
+            },
+            spread_safety_margin: settings.market_maker_spread_safety_margin,
+            min_top_depth: settings.min_top_depth,
+            max_depth_fraction: settings.market_maker_max_depth_fraction,
+            mid_ewma_alpha: settings.market_maker_mid_ewma_alpha,
+            refresh_jitter_pct: settings.market_maker_refresh_jitter_pct,
+            imbalance_size_factor: settings.market_maker_imbalance_size_factor,
+            refresh_dwell_secs: settings.market_maker_refresh_dwell_secs,
+            refresh_hysteresis: settings.market_maker_refresh_hysteresis,
+            num_levels: settings.market_maker_num_levels,
+            level_spacing: settings.market_maker_level_spacing,
+            level_size_decay: settings.market_maker_level_size_decay,
+            tick_size: settings.tick_size,
             ..MarketMakerConfig::default()
-        }))
+        });
+        mm.set_orderbook(orderbook.clone());
+        Some(mm)
     } else {
         None
     };
@@ -252,6 +437,9 @@ async fn main() -> anyhow::Result<()> {
             min_edge: settings.live_arb_min_edge,
             order_size: settings.live_arb_order_size,
             cooldown_seconds: settings.live_arb_cooldown_seconds,
+            lag_window_seconds: settings.live_arb_lag_window_seconds,
+            lag_logit_per_point: settings.live_arb_lag_logit_per_point,
+            taker_margin_threshold: settings.live_arb_taker_margin,
             ..LiveArbitrageConfig::default()
         }))
     } else {
@@ -264,33 +452,155 @@ async fn main() -> anyhow::Result<()> {
             min_edge: settings.stat_edge_min_edge,
             order_size: settings.stat_edge_order_size,
             cooldown_seconds: settings.stat_edge_cooldown_seconds,
+            confidence_half_life_seconds: settings.stat_edge_confidence_half_life_seconds,
+            pairs: settings
+                .stat_edge_pairs
+                .iter()
+                .filter_map(|p| PairConfig::parse(p))
+                .collect(),
+            pair_entry_z: settings.stat_edge_pair_entry_z,
+            pair_exit_z: settings.stat_edge_pair_exit_z,
+            pair_lookback: settings.stat_edge_pair_lookback,
+            aggregation: if settings.stat_edge_line_shopping {
+                OddsAggregation::BestLine
+            } else {
+                OddsAggregation::Consensus
+            },
             ..StatisticalEdgeConfig::default()
         }))
     } else {
         None
     };
 
-    let mut engine = StrategyEngine::new(state.clone(), market_maker, live_arb, stat_edge);
+    let completeness_arb = if settings.enable_completeness_arb {
+        info!("Completeness arbitrage strategy ENABLED");
+        let mut arb = CompletenessArbStrategy::new(
+            CompletenessArbConfig {
+                min_margin: settings.min_edge,
+                min_top_depth: settings.min_top_depth,
+                max_price_skew: settings.arb_max_price_skew,
+                fee_rate: settings.fee_rate,
+                max_contracts: settings.completeness_arb_max_contracts,
+                cooldown_seconds: settings.completeness_arb_cooldown_seconds,
+            },
+            orderbook.clone(),
+        );
+        arb.set_state(state.clone());
+        Some(arb)
+    } else {
+        None
+    };
+
+    let mut engine = StrategyEngine::new(
+        state.clone(),
+        market_maker,
+        live_arb,
+        stat_edge,
+        completeness_arb,
+    );
+    if !settings.resolve_signal_conflicts {
+        engine.set_conflict_policy(ConflictPolicy::KeepAll);
+    }
+    if settings.net_edge_gate {
+        info!(
+            min_net_edge = %settings.net_edge_min,
+            adverse_haircut = %settings.net_edge_adverse_haircut,
+            "Net edge gate enabled"
+        );
+        engine.set_net_edge_gate(
+            NetEdgeGate::new(NetEdgeConfig {
+                min_net_edge: settings.net_edge_min,
+                fees: FeeSchedule {
+                    maker_fee_rate: settings.maker_fee_rate,
+                    taker_fee_rate: settings.fee_rate,
+                },
+                adverse_selection_haircut: settings.net_edge_adverse_haircut,
+            })
+            .with_orderbook(orderbook.clone()),
+        );
+    }
+    let position_manager = PositionManager::new(
+        PositionAgingConfig {
+            max_position_age_secs: settings.max_position_age_secs,
+            exit_retry_secs: settings.position_exit_retry_secs,
+        },
+        state.clone(),
+    );
+    if position_manager.is_enabled() {
+        info!(
+            max_age_secs = settings.max_position_age_secs,
+            "Position aging enabled"
+        );
+        engine.set_position_manager(position_manager);
+    }
+    if settings.status_control_enabled {
+        engine.subscribe_toggles(toggle_rx);
+    }
 
     // =========================================================================
-    // Initialize executor (paper or live)
+    // Initialize executor (paper, live or shadow)
     // =========================================================================
     // We use an enum-like approach to avoid trait objects.
+    let equity_series = EquitySeries::new(
+        settings.perf_sample_capacity,
+        settings.perf_sample_every_ticks,
+        if settings.perf_annualization_factor > 0.0 {
+            settings.perf_annualization_factor
+        } else {
+            periods_per_year(settings.tick_interval_secs, settings.perf_sample_every_ticks)
+        },
+    );
     let mut paper_executor = if settings.trading_mode == TradingMode::Paper {
         info!("Paper executor initialized (NO real orders will be placed)");
-        Some(PaperExecutor::new(state.clone(), orderbook.clone()))
+        let mut paper = PaperExecutor::new(state.clone(), orderbook.clone())
+            .with_queue_model(settings.paper_queue_model)
+            .with_price_improvement_for_maker_fill(settings.paper_require_price_improvement)
+            .with_synthetic_no_book(settings.paper_synthesize_no_book)
+            .with_cash_buffer_pct(settings.cash_buffer_pct)
+            .with_equity_series(equity_series.clone());
+        if settings.paper_stress {
+            warn!(
+                seed = settings.paper_stress_seed,
+                slippage_multiplier = %settings.paper_stress_slippage_multiplier,
+                partial_fill_prob = settings.paper_stress_partial_fill_prob,
+                reject_prob = settings.paper_stress_reject_prob,
+                "Paper stress mode ENABLED: fills are deliberately worsened"
+            );
+            paper = paper.with_stress(
+                StressConfig {
+                    slippage_multiplier: settings.paper_stress_slippage_multiplier,
+                    partial_fill_prob: settings.paper_stress_partial_fill_prob,
+                    reject_prob: settings.paper_stress_reject_prob,
+                },
+                settings.paper_stress_seed,
+            );
+        }
+        Some(paper)
     } else {
         None
     };
 
     let mut live_executor = if settings.trading_mode == TradingMode::Live {
         // Live executor needs its own client instance (feed holds the Arc'd one).
-        let live_auth = PolymarketAuth::new(&settings.pm_api_key_id, &settings.pm_private_key)?;
-        let live_client = api::client::PolymarketClient::with_defaults(
-            live_auth,
+        let live_client = api::client::PolymarketClient::with_key_pool(
+            build_auth_pool(&settings)?,
             &settings.pm_base_url,
-        )?;
+            settings.api_rate_limit,
+            3,
+            30,
+        )?
+        .with_connection_config(&connection_config)?
+        .with_body_logging(settings.log_api_bodies);
         let mut exec = LiveExecutor::new(live_client, state.clone(), orderbook.clone());
+        exec.set_drift_alert_threshold(settings.reconcile_drift_alert_usd);
+        exec.set_orphan_grace_secs(settings.orphan_grace_secs);
+        exec.set_duplicate_guard(settings.order_dedupe_window_secs, settings.tick_size);
+        exec.set_open_order_cap(settings.max_open_orders, settings.open_order_eviction);
+        exec.set_cash_buffer_pct(settings.cash_buffer_pct);
+        exec.set_equity_series(equity_series);
+        if settings.live_impact_check {
+            exec.set_impact_min_edge(settings.min_edge);
+        }
         info!("Syncing initial state from API...");
         if let Err(e) = exec.initialize().await {
             warn!(error = %e, "Initial state sync failed (continuing with defaults)");
@@ -300,6 +610,56 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
+    let mut shadow_executor = if settings.trading_mode == TradingMode::Shadow {
+        info!("Shadow mode: approved signals are logged, NOT executed or simulated");
+        Some(ShadowExecutor::new())
+    } else {
+        None
+    };
+
+    // Structured trade log (independent of the console logger).
+    let trade_log = if settings.trade_log_path.is_empty() {
+        None
+    } else {
+        let log = TradeLog::open(&settings.trade_log_path)?;
+        info!(path = %settings.trade_log_path, "Trade log enabled");
+        if let Some(ref mut paper) = paper_executor {
+            paper.set_trade_log(log.handle());
+        } else if let Some(ref mut live) = live_executor {
+            live.set_trade_log(log.handle());
+        }
+        Some(log)
+    };
+
+    // Equity curve CSV, sampled off the trading loop.
+    let strategy_pnl = StrategyPnlBoard::new();
+    let equity_sampler = if settings.equity_export_path.is_empty()
+        || settings.trading_mode == TradingMode::Shadow
+    {
+        None
+    } else {
+        let strategies = STRATEGY_NAMES
+            .into_iter()
+            .filter(|name| engine.is_strategy_enabled(name).is_some())
+            .map(str::to_string)
+            .collect();
+        let csv = EquityCsv::create(&settings.equity_export_path, strategies)?;
+        info!(path = %settings.equity_export_path, "Equity export enabled");
+        Some(EquitySampler::spawn(
+            csv,
+            state.clone(),
+            strategy_pnl.clone(),
+            Duration::from_secs_f64(settings.equity_export_interval_secs),
+        ))
+    };
+
+    // Route executor fills back to the strategies.
+    if let Some(ref paper) = paper_executor {
+        engine.subscribe_fills(paper.subscribe_fills());
+    } else if let Some(ref live) = live_executor {
+        engine.subscribe_fills(live.subscribe_fills());
+    }
+
     // =========================================================================
     // Main trading loop
     // =========================================================================
@@ -312,10 +672,13 @@ async fn main() -> anyhow::Result<()> {
 
     let tick_duration = Duration::from_secs_f64(settings.tick_interval_secs);
     let mut tick_count: u64 = 0;
+    let mut halted = false;
 
     // Let market feed run a few cycles before we start trading.
     info!("Waiting 10s for initial market data...");
     tokio::time::sleep(Duration::from_secs(10)).await;
+    let mut feed_watchdog =
+        FeedWatchdog::new(Duration::from_secs_f64(settings.feed_stall_secs), Instant::now());
 
     loop {
         if shutdown_flag.load(Ordering::SeqCst) {
@@ -331,6 +694,49 @@ async fn main() -> anyhow::Result<()> {
             break;
         }
 
+        // A panicked or hung feed leaves us trading on stale books: alert,
+        // and restart it if configured.
+        let cycles = feed_cycles.load(Ordering::Relaxed);
+        if let FeedHealth::Stalled { stalled_for, first } =
+            feed_watchdog.check(cycles, Instant::now())
+        {
+            let dead = feed_handle.is_finished();
+            if first {
+                error!(
+                    stalled_secs = stalled_for.as_secs(),
+                    cycles,
+                    dead,
+                    "Market feed stalled: no completed poll cycle"
+                );
+                state.events().push(
+                    EventKind::FeedGap,
+                    None,
+                    format!("Market feed stalled for {}s", stalled_for.as_secs()),
+                    serde_json::json!({ "cycles": cycles, "dead": dead }),
+                );
+            }
+            if settings.feed_restart_on_stall {
+                feed_handle.abort();
+                feed_handle = spawn_feed();
+                feed_watchdog.reset(Instant::now());
+                warn!(cycles, "Market feed restarted");
+                state.events().push(
+                    EventKind::FeedGap,
+                    None,
+                    "Market feed restarted",
+                    serde_json::json!({ "cycles": cycles }),
+                );
+            }
+        }
+
+        // Settle resolved markets at par. Live positions settle on the
+        // exchange and come back through reconciliation.
+        for (slug, yes_won) in state.take_unsettled_resolutions() {
+            if let Some(ref mut paper) = paper_executor {
+                paper.settle_resolved(&slug, yes_won);
+            }
+        }
+
         // Check resting orders in paper mode.
         if let Some(ref mut paper) = paper_executor {
             let fills = paper.check_resting_orders();
@@ -344,11 +750,22 @@ async fn main() -> anyhow::Result<()> {
                     fee = %fill.fee,
                     "[PAPER] Resting order filled"
                 );
+                state.events().push(
+                    EventKind::Fill,
+                    Some(&fill.market_slug),
+                    format!("Resting {} filled {} @ {}", fill.side, fill.quantity, fill.price),
+                    serde_json::json!({ "order_id": fill.order_id }),
+                );
             }
         }
 
         // Scan for completeness arbitrage opportunities.
-        let arb_signals = orderbook.scan_completeness_arb(settings.min_edge);
+        let arb_signals = orderbook.scan_completeness_arb_with_fees(
+            settings.min_edge,
+            settings.min_top_depth,
+            settings.arb_max_price_skew,
+            |slug| state.fee_rate_for(slug, settings.fee_rate),
+        );
         if !arb_signals.is_empty() {
             for arb in &arb_signals {
                 info!(
@@ -365,32 +782,123 @@ async fn main() -> anyhow::Result<()> {
         // Run strategy engine.
         let output = engine.on_tick(&mut risk_manager);
 
+        // Emergency stop: pull every resting order once when the breaker trips,
+        // before any exits from this tick (panic liquidation) are sent.
+        if risk_manager.is_halted() && !halted {
+            halted = true;
+            state.events().push(
+                EventKind::BreakerTrip,
+                None,
+                risk_manager.halt_reason().unwrap_or("tripped"),
+                serde_json::Value::Null,
+            );
+            if let Some(ref mut live) = live_executor {
+                match live.cancel_everything().await {
+                    Ok(count) => warn!(count, "Circuit breaker halted trading; orders cancelled"),
+                    Err(e) => warn!(error = %e, "Emergency cancel failed"),
+                }
+            }
+        } else if !risk_manager.is_halted() {
+            halted = false;
+        }
+
         // Execute approved signals.
+        let mut arb_fills: HashMap<String, (i64, i64)> = HashMap::new();
         for signal in &output.approved_signals {
+            signal_log.record(signal, &orderbook);
             if let Some(ref mut paper) = paper_executor {
                 let result = paper.execute_signal(signal);
                 if let Some(ref err) = result.error {
                     warn!(
                         market_slug = %signal.market_slug,
                         error = %err,
+                        reject_reason = ?result.reject_reason,
                         "[PAPER] Execution failed"
                     );
                 }
+                record_execution(state.events(), signal, &result);
+                record_arb_leg(&mut arb_fills, signal, &result);
             } else if let Some(ref mut live) = live_executor {
                 let result = live.execute_signal(signal).await;
                 if let Some(ref err) = result.error {
                     warn!(
                         market_slug = %signal.market_slug,
                         error = %err,
+                        reject_reason = ?result.reject_reason,
                         "Execution failed"
                     );
                 }
+                record_execution(state.events(), signal, &result);
+                record_arb_leg(&mut arb_fills, signal, &result);
+            } else if let Some(ref mut shadow) = shadow_executor {
+                shadow.execute_signal(signal);
             }
         }
 
+        // An arb whose legs filled unevenly holds a directional position,
+        // not a locked margin: cancel its resting legs and unwind what the
+        // legs bought.
+        for (market_slug, (yes_filled, no_filled)) in arb_fills {
+            if yes_filled == no_filled {
+                continue;
+            }
+            let result = if let Some(ref mut paper) = paper_executor {
+                paper.unwind_arb(&market_slug)
+            } else if let Some(ref mut live) = live_executor {
+                live.unwind_arb(&market_slug).await
+            } else {
+                continue;
+            };
+            warn!(
+                market_slug = %market_slug,
+                yes_filled,
+                no_filled,
+                cancelled = result.cancelled,
+                unpaired = result.unpaired,
+                unwound = result.unwound,
+                error = ?result.error,
+                "Arb legs filled unevenly, unwound the arb legs"
+            );
+            state.events().push(
+                EventKind::Reject,
+                Some(&market_slug),
+                format!("Arb legs filled unevenly (YES {yes_filled}, NO {no_filled}); unwound"),
+                serde_json::json!({
+                    "cancelled": result.cancelled,
+                    "unpaired": result.unpaired,
+                    "unwound": result.unwound,
+                    "proceeds": result.proceeds.to_string(),
+                    "error": result.error,
+                }),
+            );
+        }
+
+        // Advance the size ramps with each strategy's profitable closes,
+        // and hand the strategies' P&L to the equity export.
+        let by_strategy = if let Some(ref paper) = paper_executor {
+            Some(&paper.get_performance_snapshot().by_strategy)
+        } else {
+            live_executor.as_ref().map(|live| live.strategy_performance())
+        };
+        if let Some(by_strategy) = by_strategy {
+            for (name, perf) in by_strategy {
+                risk_manager.set_profitable_trades(name, perf.winning_trades);
+            }
+            if equity_sampler.is_some() {
+                strategy_pnl.publish(by_strategy);
+            }
+        }
+
+        // Sample equity for the Sharpe and Sortino ratios.
+        if let Some(ref mut paper) = paper_executor {
+            paper.sample_equity();
+        } else if let Some(ref mut live) = live_executor {
+            live.sample_equity();
+        }
+
         // Live executor periodic reconciliation.
         if let Some(ref mut live) = live_executor {
-            if tick_count % 10 == 0 {
+            if tick_count.is_multiple_of(10) {
                 if let Err(e) = live.reconcile_state().await {
                     warn!(error = %e, "Reconciliation failed");
                 }
@@ -398,17 +906,26 @@ async fn main() -> anyhow::Result<()> {
         }
 
         // Periodic performance logging.
-        if tick_count % 30 == 0 {
+        if tick_count.is_multiple_of(30) {
             let perf = if let Some(ref paper) = paper_executor {
                 paper.get_performance()
             } else if let Some(ref live) = live_executor {
                 live.get_performance()
+            } else if let Some(ref shadow) = shadow_executor {
+                shadow.get_performance()
             } else {
                 std::collections::HashMap::new()
             };
 
             let markets_with_data = state.get_all_markets().len();
-            let arb_count = orderbook.scan_completeness_arb(settings.min_edge).len();
+            let arb_count = orderbook
+                .scan_completeness_arb_with_fees(
+                    settings.min_edge,
+                    settings.min_top_depth,
+                    settings.arb_max_price_skew,
+                    |slug| state.fee_rate_for(slug, settings.fee_rate),
+                )
+                .len();
 
             info!(
                 tick = tick_count,
@@ -422,8 +939,24 @@ async fn main() -> anyhow::Result<()> {
                 positions = ?perf.get("open_positions"),
                 fees_paid = ?perf.get("total_fees_paid"),
                 max_drawdown = ?perf.get("max_drawdown"),
+                sharpe = ?perf.get("sharpe_ratio"),
+                sortino = ?perf.get("sortino_ratio"),
+                would_be_orders = ?perf.get("would_be_orders"),
                 "Performance update"
             );
+
+            if let Some(ref pm) = engine.position_manager {
+                let ages = pm.report();
+                if let Some(oldest) = ages.first() {
+                    info!(
+                        positions = ages.len(),
+                        aged = ages.iter().filter(|a| a.aged).count(),
+                        oldest_market = %oldest.market_slug,
+                        oldest_age_secs = oldest.age_secs,
+                        "Position aging report"
+                    );
+                }
+            }
         }
     }
 
@@ -448,22 +981,9 @@ async fn main() -> anyhow::Result<()> {
     // Cancel open orders in live mode.
     if let Some(ref mut live) = live_executor {
         info!("Cancelling all open orders...");
-        let markets = state.get_all_markets();
-        for market in &markets {
-            let _ = live
-                .execute_signal(&data::models::Signal {
-                    market_slug: market.market_slug.clone(),
-                    action: data::models::SignalAction::CancelAll,
-                    price: rust_decimal::Decimal::ZERO,
-                    quantity: 0,
-                    urgency: data::models::Urgency::Critical,
-                    confidence: 1.0,
-                    strategy_name: "shutdown".to_string(),
-                    reason: "Graceful shutdown".to_string(),
-                    metadata: std::collections::HashMap::new(),
-                    timestamp: chrono::Utc::now(),
-                })
-                .await;
+        match live.cancel_everything().await {
+            Ok(count) => info!(count, "Open orders cancelled"),
+            Err(e) => warn!(error = %e, "Cancel all on shutdown failed"),
         }
     }
 
@@ -472,6 +992,8 @@ async fn main() -> anyhow::Result<()> {
         paper.get_performance()
     } else if let Some(ref live) = live_executor {
         live.get_performance()
+    } else if let Some(ref shadow) = shadow_executor {
+        shadow.get_performance()
     } else {
         std::collections::HashMap::new()
     };
@@ -482,11 +1004,221 @@ async fn main() -> anyhow::Result<()> {
         info!("  {}: {}", k, v);
     }
     info!("========================================");
+    let connections = client.connection_stats();
+    info!(
+        responses = connections.responses,
+        peak_in_flight = connections.peak_in_flight,
+        reuse_rate = format!("{:.3}", connections.reuse_rate()),
+        "Feed client connection stats"
+    );
+
+    if !settings.run_report_path.is_empty() {
+        let report = RunReport::new(
+            perf,
+            started_at,
+            chrono::Utc::now(),
+            tick_count,
+            feed_cycles.load(Ordering::Relaxed),
+        );
+        match report.write(&settings.run_report_path) {
+            Ok(()) => info!(path = %settings.run_report_path, "Run report written"),
+            Err(e) => {
+                warn!(error = %e, path = %settings.run_report_path, "Run report write failed")
+            }
+        }
+    }
+
+    if let Some(handle) = status_handle {
+        handle.abort();
+    }
+
+    // Flush the trade log and equity export before exit.
+    if let Some(log) = trade_log {
+        log.close();
+    }
+    if let Some(sampler) = equity_sampler {
+        sampler.close().await;
+    }
     info!("Bot shutdown complete.");
 
     Ok(())
 }
 
+/// Log an executed signal as a fill, a resting order or a reject.
+fn record_execution(events: &EventLog, signal: &Signal, result: &ExecResult) {
+    let kind = if result.status == OrderStatus::Rejected || result.error.is_some() {
+        EventKind::Reject
+    } else if result.filled_quantity > 0 {
+        EventKind::Fill
+    } else {
+        EventKind::OrderPlaced
+    };
+    let message = match (&result.error, kind) {
+        (Some(err), _) => err.clone(),
+        (None, EventKind::Fill) => format!(
+            "{:?} filled {}/{} @ {}",
+            signal.action,
+            result.filled_quantity,
+            signal.quantity,
+            result.avg_fill_price.unwrap_or(signal.price)
+        ),
+        _ => format!("{:?} {} @ {}", signal.action, signal.quantity, signal.price),
+    };
+    let mut detail = serde_json::json!({
+        "order_id": result.order_id,
+        "strategy": signal.strategy_name,
+        "status": format!("{:?}", result.status),
+    });
+    if let Some(reason) = result.reject_reason {
+        detail["reject_reason"] = serde_json::json!(format!("{:?}", reason));
+    }
+    for key in BOOK_CONTEXT_KEYS {
+        if let Some(value) = signal.metadata.get(key) {
+            detail[key] = value.clone();
+        }
+    }
+    events.push(kind, Some(&signal.market_slug), message, detail);
+}
+
+/// Add an arb leg's fill to its pair's (YES, NO) filled quantities.
+fn record_arb_leg(
+    arb_fills: &mut HashMap<String, (i64, i64)>,
+    signal: &Signal,
+    result: &ExecResult,
+) {
+    let (Some(pair), Some(intent)) = (signal.arb_pair(), signal.action.to_intent()) else {
+        return;
+    };
+    let filled = arb_fills.entry(pair.to_string()).or_default();
+    match intent.side() {
+        data::models::Side::Yes => filled.0 += result.filled_quantity,
+        data::models::Side::No => filled.1 += result.filled_quantity,
+    }
+}
+
+/// `RISK_MIN_RESOLUTION_HORIZON_SECS` as a duration (zero disables).
+fn resolution_horizon(settings: &Settings) -> chrono::Duration {
+    chrono::Duration::milliseconds((settings.min_resolution_horizon_secs * 1000.0) as i64)
+}
+
+/// Fetch open markets and select the tradeable ones (active, not yet
+/// played, not about to resolve, liquid enough). A failed page ends the
+/// fetch early.
+async fn discover_markets(
+    client: &api::client::PolymarketClient,
+    settings: &Settings,
+) -> Vec<data::models::Market> {
+    // Pass closed=false to only get open (non-closed) markets,
+    // matching the Python bot's discover_markets() approach.
+    let mut all_markets = Vec::new();
+    for offset in (0..500).step_by(100) {
+        match client.get_markets(None, None, 100, offset, Some("false")).await {
+            Ok(batch) => {
+                if batch.is_empty() {
+                    break;
+                }
+                all_markets.extend(batch);
+            }
+            Err(e) => {
+                warn!(error = %e, offset, "Market fetch failed at offset");
+                break;
+            }
+        }
+    }
+    info!(total_fetched = all_markets.len(), "Fetched open markets from API");
+
+    // Additional client-side filter: active, future game date, not about
+    // to resolve.
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let horizon = resolution_horizon(settings);
+    let resolves_soon = |m: &data::models::Market| {
+        horizon > chrono::Duration::zero()
+            && m.resolution_date.is_some_and(|at| at - chrono::Utc::now() < horizon)
+    };
+    let near_resolution = all_markets.iter().filter(|m| resolves_soon(m)).count();
+    if near_resolution > 0 {
+        info!(
+            near_resolution,
+            min_resolution_horizon_secs = settings.min_resolution_horizon_secs,
+            "Skipping markets about to resolve"
+        );
+    }
+    let tradeable: Vec<&data::models::Market> = all_markets
+        .iter()
+        .filter(|m| m.active && !resolves_soon(m))
+        .filter(|m| {
+            // Extract date from slug: aec-nfl-lac-ten-YYYY-MM-DD
+            let parts: Vec<&str> = m.slug.split('-').collect();
+            if parts.len() >= 7 {
+                let date_str = format!("{}-{}-{}", parts[4], parts[5], parts[6]);
+                date_str >= today
+            } else {
+                // Keep markets with unknown date format.
+                true
+            }
+        })
+        .collect();
+
+    // Markets of interest: drop thin books, keep the most liquid.
+    let selection = data::market_filter::select_markets(
+        tradeable.iter().copied(),
+        &data::market_filter::MarketFilterConfig {
+            min_volume_24h: settings.min_volume_24h,
+            min_liquidity: settings.min_liquidity,
+            max_markets: settings.max_markets,
+        },
+    );
+    if selection.filtered_out() > 0 {
+        info!(
+            below_volume = selection.below_volume,
+            below_liquidity = selection.below_liquidity,
+            over_limit = selection.over_limit,
+            min_volume_24h = %settings.min_volume_24h,
+            min_liquidity = %settings.min_liquidity,
+            max_markets = settings.max_markets,
+            "Filtered markets at discovery"
+        );
+    }
+    let tradeable = selection.selected;
+
+    info!(
+        total_fetched = all_markets.len(),
+        tradeable = tradeable.len(),
+        "Discovered tradeable markets"
+    );
+    for (i, m) in tradeable.iter().enumerate().take(10) {
+        info!(
+            slug = %m.slug,
+            title = %m.title,
+            yes_bid = ?m.yes_bid,
+            yes_ask = ?m.yes_ask,
+            no_bid = ?m.no_bid,
+            no_ask = ?m.no_ask,
+            "  [{}] Market", i + 1
+        );
+    }
+    if tradeable.len() > 10 {
+        info!("  ... and {} more", tradeable.len() - 10);
+    }
+
+    tradeable.into_iter().cloned().collect()
+}
+
+/// Primary credentials followed by any `PM_EXTRA_API_KEYS` entries.
+fn build_auth_pool(settings: &Settings) -> anyhow::Result<Vec<PolymarketAuth>> {
+    let mut pool = vec![PolymarketAuth::new(
+        &settings.pm_api_key_id,
+        &settings.pm_private_key,
+    )?];
+    for entry in &settings.pm_extra_api_keys {
+        let (key_id, private_key) = entry
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("PM_EXTRA_API_KEYS entries must be key_id:private_key"))?;
+        pool.push(PolymarketAuth::new(key_id, private_key)?);
+    }
+    Ok(pool)
+}
+
 fn init_logging(settings: &Settings) {
     use tracing_subscriber::EnvFilter;
 
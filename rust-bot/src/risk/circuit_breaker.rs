@@ -1,7 +1,8 @@
 //! Circuit breaker for emergency stop conditions.
 //!
 //! Monitors daily loss and drawdown to halt trading when risk thresholds
-//! are exceeded.
+//! are exceeded. An optional warning tier scales position sizing down
+//! before the hard trip.
 
 #![allow(dead_code)]
 
@@ -14,6 +15,11 @@ pub struct CircuitBreaker {
     max_drawdown_pct: Decimal,
     starting_equity: Decimal,
     peak_equity: Decimal,
+    current_equity: Decimal,
+    /// Fraction of either limit at which the warning tier starts. Zero disables.
+    warning_threshold_pct: Decimal,
+    /// Sizing multiplier applied while in the warning tier.
+    warning_scale: Decimal,
     tripped: bool,
     trip_reason: Option<String>,
 }
@@ -25,15 +31,27 @@ impl CircuitBreaker {
             max_drawdown_pct,
             starting_equity: Decimal::ZERO,
             peak_equity: Decimal::ZERO,
+            current_equity: Decimal::ZERO,
+            warning_threshold_pct: Decimal::ZERO,
+            warning_scale: Decimal::ONE,
             tripped: false,
             trip_reason: None,
         }
     }
 
+    /// Enable the warning tier: once daily loss or drawdown reaches
+    /// `threshold_pct` of its limit, `risk_scale` returns `scale`.
+    pub fn with_warning_tier(mut self, threshold_pct: Decimal, scale: Decimal) -> Self {
+        self.warning_threshold_pct = threshold_pct;
+        self.warning_scale = scale;
+        self
+    }
+
     /// Initialize with starting equity (call on startup).
     pub fn initialize(&mut self, equity: Decimal) {
         self.starting_equity = equity;
         self.peak_equity = equity;
+        self.current_equity = equity;
         self.tripped = false;
         self.trip_reason = None;
     }
@@ -43,6 +61,7 @@ impl CircuitBreaker {
         if self.tripped {
             return; // Already tripped
         }
+        self.current_equity = current_equity;
 
         // Track peak equity for drawdown
         if current_equity > self.peak_equity {
@@ -81,6 +100,56 @@ impl CircuitBreaker {
         }
     }
 
+    /// Sizing multiplier for new exposure: 1 normally, the warning scale
+    /// once loss or drawdown enters the warning band, 0 when tripped.
+    pub fn risk_scale(&self) -> Decimal {
+        if self.tripped {
+            return Decimal::ZERO;
+        }
+        if self.in_warning_band() {
+            self.warning_scale
+        } else {
+            Decimal::ONE
+        }
+    }
+
+    /// True when loss or drawdown is past the warning threshold but the
+    /// breaker has not tripped.
+    pub fn in_warning_band(&self) -> bool {
+        if self.tripped || self.warning_threshold_pct <= Decimal::ZERO {
+            return false;
+        }
+        let daily_loss = self.starting_equity - self.current_equity;
+        if daily_loss >= self.daily_loss_limit * self.warning_threshold_pct {
+            return true;
+        }
+        if self.peak_equity > Decimal::ZERO {
+            let drawdown_pct = (self.peak_equity - self.current_equity) / self.peak_equity;
+            if drawdown_pct >= self.max_drawdown_pct * self.warning_threshold_pct {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Current drawdown from peak equity as a fraction of the peak.
+    pub fn drawdown_pct(&self) -> Decimal {
+        if self.peak_equity <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        ((self.peak_equity - self.current_equity) / self.peak_equity).max(Decimal::ZERO)
+    }
+
+    /// Kelly fraction multiplier that falls linearly from 1 at the peak to
+    /// 0 at `max_drawdown_pct`.
+    pub fn drawdown_kelly_scale(&self) -> Decimal {
+        if self.max_drawdown_pct <= Decimal::ZERO {
+            return Decimal::ONE;
+        }
+        (Decimal::ONE - self.drawdown_pct() / self.max_drawdown_pct)
+            .clamp(Decimal::ZERO, Decimal::ONE)
+    }
+
     /// Emergency stop — immediately halt all trading.
     pub fn emergency_stop(&mut self, reason: &str) {
         self.trip(reason);
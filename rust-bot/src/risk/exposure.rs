@@ -11,9 +11,20 @@ use crate::state::state_manager::StateManager;
 #[derive(Debug, Clone)]
 pub struct ExposureConfig {
     pub max_position_per_market: Decimal,
+    /// Per-market limit as a fraction of total equity, so concentration
+    /// scales with the book; the tighter of this and the dollar limit
+    /// applies. Zero disables.
+    pub max_position_pct_of_equity: Decimal,
+    /// Contracts held in one market, alongside the dollar limit: on a
+    /// cheap market the dollar limit alone allows a huge quantity. Zero
+    /// disables.
+    pub max_contracts_per_market: i64,
     pub max_portfolio_exposure: Decimal,
     pub max_correlated_exposure: Decimal,
     pub max_positions: usize,
+    /// Count only unmatched YES/NO quantity toward the per-market and
+    /// correlation limits. The portfolio limit always uses gross capital.
+    pub net_hedged_exposure: bool,
 }
 
 /// Result of an exposure check.
@@ -44,23 +55,55 @@ impl ExposureMonitor {
             .insert(group_name.to_string(), markets);
     }
 
-    /// Check if additional exposure can be added.
+    /// Configured correlation groups, keyed by group name.
+    pub fn correlation_groups(&self) -> &HashMap<String, Vec<String>> {
+        &self.correlation_groups
+    }
+
+    /// Check if additional exposure, bought at `price`, can be added.
+    /// `price` converts it to contracts for the per-market contract limit.
     pub fn can_add_exposure(
         &self,
         state: &StateManager,
         market_slug: &str,
         additional_exposure: Decimal,
+        price: Decimal,
     ) -> ExposureCheck {
         // Per-market limit
-        let current_market = state.market_exposure(market_slug);
-        let market_headroom = self.config.max_position_per_market - current_market;
+        let market_limit = self.market_limit(state);
+        let current_market = self.directional_exposure(state, market_slug);
+        let mut market_headroom = market_limit - current_market;
+
+        // Per-market contract limit; its headroom in dollars tightens the
+        // dollar headroom so callers size down to whichever binds first.
+        if self.config.max_contracts_per_market > 0 && price > Decimal::ZERO {
+            let held = self.directional_contracts(state, market_slug);
+            let contract_headroom = (self.config.max_contracts_per_market - held).max(0);
+            market_headroom = market_headroom.min(Decimal::from(contract_headroom) * price);
+
+            let additional_contracts = additional_exposure / price;
+            if Decimal::from(held) + additional_contracts
+                > Decimal::from(self.config.max_contracts_per_market)
+            {
+                return ExposureCheck {
+                    allowed: false,
+                    reason: format!(
+                        "Per-market contract limit: held {} + {} > {}",
+                        held,
+                        additional_contracts.normalize(),
+                        self.config.max_contracts_per_market
+                    ),
+                    max_additional_exposure: market_headroom.max(Decimal::ZERO),
+                };
+            }
+        }
 
-        if current_market + additional_exposure > self.config.max_position_per_market {
+        if current_market + additional_exposure > market_limit {
             return ExposureCheck {
                 allowed: false,
                 reason: format!(
                     "Per-market limit: current ${:.2} + ${:.2} > ${:.2}",
-                    current_market, additional_exposure, self.config.max_position_per_market
+                    current_market, additional_exposure, market_limit
                 ),
                 max_additional_exposure: market_headroom.max(Decimal::ZERO),
             };
@@ -98,11 +141,11 @@ impl ExposureMonitor {
         }
 
         // Correlation group limit
-        for (_group, group_markets) in &self.correlation_groups {
+        for group_markets in self.correlation_groups.values() {
             if group_markets.contains(&market_slug.to_string()) {
                 let group_exposure: Decimal = group_markets
                     .iter()
-                    .map(|m| state.market_exposure(m))
+                    .map(|m| self.directional_exposure(state, m))
                     .sum();
                 let corr_headroom = self.config.max_correlated_exposure - group_exposure;
 
@@ -128,6 +171,41 @@ impl ExposureMonitor {
         }
     }
 
+    /// Dollar limit per market: the tighter of the absolute limit and the
+    /// share of equity, when set.
+    pub fn market_limit(&self, state: &StateManager) -> Decimal {
+        let limit = self.config.max_position_per_market;
+        if self.config.max_position_pct_of_equity > Decimal::ZERO {
+            limit.min(state.get_total_equity() * self.config.max_position_pct_of_equity)
+        } else {
+            limit
+        }
+    }
+
+    /// Exposure in one market that counts toward directional limits.
+    pub fn directional_exposure(&self, state: &StateManager, market_slug: &str) -> Decimal {
+        if self.config.net_hedged_exposure {
+            state.net_market_exposure(market_slug)
+        } else {
+            state.market_exposure(market_slug)
+        }
+    }
+
+    /// Contracts in one market that count toward the contract limit:
+    /// both legs, or only the unmatched quantity when netting.
+    pub fn directional_contracts(&self, state: &StateManager, market_slug: &str) -> i64 {
+        let held = state.get_position(market_slug).map(|p| p.quantity).unwrap_or(0);
+        let hedge = state
+            .get_hedge_position(market_slug)
+            .map(|h| h.quantity)
+            .unwrap_or(0);
+        if self.config.net_hedged_exposure {
+            (held - hedge).abs()
+        } else {
+            held + hedge
+        }
+    }
+
     /// Total exposure across all positions.
     pub fn total_exposure(&self, state: &StateManager) -> Decimal {
         state.get_total_position_value()
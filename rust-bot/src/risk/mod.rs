@@ -2,3 +2,5 @@ pub mod position_sizer;
 pub mod circuit_breaker;
 pub mod exposure;
 pub mod risk_manager;
+pub mod position_manager;
+pub mod size_ramp;
@@ -0,0 +1,165 @@
+//! Position aging: reports how long positions have been held and exits
+//! any that outlive a global maximum age, whichever strategy opened them.
+//!
+//! The market maker has its own underwater hold limit; arb and stat-edge
+//! positions do not, and can sit indefinitely once a market goes quiet.
+//! The sweep emits a reduce-only sell at the best bid for each aged
+//! position, re-sending it only after `exit_retry_secs` if the position is
+//! still open.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::clock::{RealClock, SharedClock};
+use crate::data::models::{Side, Signal, SignalAction, Urgency};
+use crate::state::state_manager::{PositionState, StateManager};
+
+/// Position aging configuration. A zero max age disables the sweep.
+#[derive(Debug, Clone)]
+pub struct PositionAgingConfig {
+    pub max_position_age_secs: i64,
+    /// Minimum time before re-sending an exit for a position still held.
+    pub exit_retry_secs: i64,
+}
+
+impl Default for PositionAgingConfig {
+    fn default() -> Self {
+        Self {
+            max_position_age_secs: 0,
+            exit_retry_secs: 30,
+        }
+    }
+}
+
+/// Age of one open position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionAge {
+    pub market_slug: String,
+    pub side: Side,
+    pub quantity: i64,
+    pub age_secs: i64,
+    pub aged: bool,
+}
+
+/// Tracks position ages and produces exits for aged positions.
+pub struct PositionManager {
+    config: PositionAgingConfig,
+    state: StateManager,
+    clock: SharedClock,
+    /// When an aging exit was last sent, keyed by "slug:SIDE".
+    exits_sent: HashMap<String, DateTime<Utc>>,
+}
+
+impl PositionManager {
+    pub fn new(config: PositionAgingConfig, state: StateManager) -> Self {
+        Self {
+            config,
+            state,
+            clock: RealClock::shared(),
+            exits_sent: HashMap::new(),
+        }
+    }
+
+    /// Replace the time source (tests inject a `MockClock`).
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.max_position_age_secs > 0
+    }
+
+    /// Ages of all open positions, oldest first.
+    pub fn report(&self) -> Vec<PositionAge> {
+        let now = self.clock.now();
+        let mut ages: Vec<PositionAge> = self
+            .state
+            .get_all_positions()
+            .into_iter()
+            .filter(|p| p.quantity > 0)
+            .map(|p| {
+                let age_secs = (now - p.created_at).num_seconds();
+                PositionAge {
+                    aged: self.is_enabled() && age_secs >= self.config.max_position_age_secs,
+                    market_slug: p.market_slug,
+                    side: p.side,
+                    quantity: p.quantity,
+                    age_secs,
+                }
+            })
+            .collect();
+        ages.sort_by_key(|a| std::cmp::Reverse(a.age_secs));
+        ages
+    }
+
+    /// Reduce-only exits for positions older than the max age.
+    pub fn sweep(&mut self) -> Vec<Signal> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+
+        let now = self.clock.now();
+        let positions = self.state.get_all_positions();
+        let held: Vec<String> = positions.iter().map(position_key).collect();
+        self.exits_sent.retain(|key, _| held.contains(key));
+
+        let mut signals = Vec::new();
+        for position in positions.iter().filter(|p| p.quantity > 0) {
+            let age_secs = (now - position.created_at).num_seconds();
+            if age_secs < self.config.max_position_age_secs {
+                continue;
+            }
+
+            let key = position_key(position);
+            if let Some(sent) = self.exits_sent.get(&key) {
+                if (now - *sent).num_seconds() < self.config.exit_retry_secs {
+                    continue;
+                }
+            }
+
+            warn!(
+                market_slug = %position.market_slug,
+                side = %position.side,
+                quantity = position.quantity,
+                age_secs,
+                "Position exceeded max age, exiting"
+            );
+            self.exits_sent.insert(key, now);
+            signals.push(self.exit_signal(position, age_secs));
+        }
+        signals
+    }
+
+    fn exit_signal(&self, position: &PositionState, age_secs: i64) -> Signal {
+        let market = self.state.get_market(&position.market_slug);
+        let (action, best_bid) = match position.side {
+            Side::Yes => (SignalAction::SellYes, market.and_then(|m| m.yes_bid)),
+            Side::No => (SignalAction::SellNo, market.and_then(|m| m.no_bid)),
+        };
+        Signal {
+            market_slug: position.market_slug.clone(),
+            action,
+            price: best_bid.unwrap_or(Decimal::new(1, 2)),
+            quantity: position.quantity,
+            urgency: Urgency::Critical,
+            confidence: 1.0,
+            strategy_name: "position_aging".to_string(),
+            reason: format!(
+                "Position aged {}s (max {}s)",
+                age_secs, self.config.max_position_age_secs
+            ),
+            metadata: HashMap::new(),
+            timestamp: self.clock.now(),
+            reduce_only: true,
+            good_till: None,
+        }
+    }
+}
+
+fn position_key(position: &PositionState) -> String {
+    format!("{}:{}", position.market_slug, position.side)
+}
@@ -1,4 +1,4 @@
-//! Kelly Criterion position sizing for binary prediction markets.
+//! Kelly Criterion position sizing for binary and multi-outcome prediction markets.
 //!
 //! For a contract priced at P in (0, 1), if the outcome occurs, the payout is $1,
 //! so the net-odds ratio is:
@@ -7,16 +7,32 @@
 //! Full Kelly fraction:
 //!     f* = (p*b - q) / b
 //!
+//! For markets with more than two mutually-exclusive outcomes (e.g. soccer
+//! home/draw/away), the optimal simultaneous bets follow the generalized
+//! Kelly rule. With outcome prices P_i and true probabilities p_i, sort by
+//! expected return p_i / P_i and admit outcomes into the bet set S while
+//!     p_k / P_k > R(S),  R(S) = (1 - sum_S p_i) / (1 - sum_S P_i)
+//! starting from R = 1. Each admitted outcome gets
+//!     f_i = p_i - R(S) * P_i
+//! For two outcomes this reduces to the binary formula above.
+//!
 //! We apply:
 //! - Fractional Kelly (e.g., 0.25 for quarter Kelly)
 //! - Confidence multiplier in [0, 1]
 //! - Clamp to [0, max_position_pct]
+//!
+//! Repeat signals on a market should size the increment toward the target,
+//! not a fresh target each time; `calculate_incremental_position_size`
+//! subtracts what is already held.
 
 #![allow(dead_code)]
 
 use rust_decimal::Decimal;
 use tracing::debug;
 
+use crate::data::models::fee_adjusted_min_edge;
+use crate::util::dec::from_f64;
+
 /// Estimated edge for a trade.
 #[derive(Debug, Clone)]
 pub struct EdgeEstimate {
@@ -37,7 +53,7 @@ impl EdgeEstimate {
     pub fn from_confidence(probability: Decimal, confidence: f64) -> Self {
         Self {
             probability,
-            confidence: Decimal::from_f64_retain(confidence).unwrap_or(Decimal::ONE),
+            confidence: from_f64(confidence).unwrap_or(Decimal::ONE),
         }
     }
 }
@@ -62,6 +78,8 @@ pub struct KellyPositionSizer {
     pub kelly_fraction: Decimal,
     pub max_position_pct: Decimal,
     pub min_edge: Decimal,
+    /// Taker fee rate used to raise `min_edge` at prices where fees bite.
+    pub fee_rate: Decimal,
 }
 
 impl KellyPositionSizer {
@@ -74,9 +92,20 @@ impl KellyPositionSizer {
             kelly_fraction,
             max_position_pct,
             min_edge,
+            fee_rate: Decimal::ZERO,
         }
     }
 
+    pub fn with_fee_rate(mut self, fee_rate: Decimal) -> Self {
+        self.fee_rate = fee_rate;
+        self
+    }
+
+    /// Edge required at `price`: the larger of `min_edge` and the fee floor.
+    pub fn effective_min_edge(&self, price: Decimal) -> Decimal {
+        self.min_edge.max(fee_adjusted_min_edge(price, self.fee_rate))
+    }
+
     /// Calculate position sizing for a bet.
     ///
     /// Returns None if the trade should be skipped (no edge / too small).
@@ -86,39 +115,100 @@ impl KellyPositionSizer {
         market_price: Decimal,
         edge: &EdgeEstimate,
     ) -> Option<PositionSizeResult> {
-        if bankroll <= Decimal::ZERO {
+        if market_price <= Decimal::ZERO || market_price >= Decimal::ONE {
             return None;
         }
-        if market_price <= Decimal::ZERO || market_price >= Decimal::ONE {
+
+        // Binary market: YES at P with probability p, NO at 1-P with 1-p.
+        let outcomes = [
+            (market_price, edge.probability),
+            (Decimal::ONE - market_price, Decimal::ONE - edge.probability),
+        ];
+        self.calculate_position_size_multi(bankroll, &outcomes, 0, edge.confidence)
+    }
+
+    /// Size the order that tops the current holding up to the Kelly target:
+    /// `current_notional` is the dollars already held in this outcome, and
+    /// only the remainder is returned. None when already at or above the
+    /// target (or when there is no target at all).
+    pub fn calculate_incremental_position_size(
+        &self,
+        bankroll: Decimal,
+        market_price: Decimal,
+        edge: &EdgeEstimate,
+        current_notional: Decimal,
+    ) -> Option<PositionSizeResult> {
+        let target = self.calculate_position_size(bankroll, market_price, edge)?;
+        let notional = target.notional - current_notional.max(Decimal::ZERO);
+        if notional <= Decimal::ZERO {
+            debug!(
+                target = %target.notional,
+                current = %current_notional,
+                "Already at Kelly target"
+            );
             return None;
         }
 
-        // Edge = true probability - market price
-        let implied_edge = edge.probability - market_price;
+        let contracts = (notional / market_price)
+            .floor()
+            .to_string()
+            .parse::<i64>()
+            .unwrap_or(0);
+        if contracts <= 0 {
+            return None;
+        }
 
-        // Minimum edge threshold
-        if implied_edge.abs() < self.min_edge {
-            debug!(edge = %implied_edge, min_edge = %self.min_edge, "Below min edge");
+        Some(PositionSizeResult {
+            notional,
+            contracts,
+            ..target
+        })
+    }
+
+    /// Calculate position sizing for `chosen` among mutually-exclusive
+    /// outcomes given as `(price, true_probability)` pairs.
+    ///
+    /// Uses the generalized Kelly fraction for simultaneous bets, so the
+    /// size accounts for the other outcomes worth backing. Returns None if
+    /// the chosen outcome is not in the optimal bet set or is too small.
+    pub fn calculate_position_size_multi(
+        &self,
+        bankroll: Decimal,
+        outcomes: &[(Decimal, Decimal)],
+        chosen: usize,
+        confidence: Decimal,
+    ) -> Option<PositionSizeResult> {
+        if bankroll <= Decimal::ZERO {
+            return None;
+        }
+        let &(market_price, probability) = outcomes.get(chosen)?;
+        if outcomes
+            .iter()
+            .any(|&(price, _)| price <= Decimal::ZERO || price >= Decimal::ONE)
+        {
             return None;
         }
 
-        let p = edge.probability;
-        let q = Decimal::ONE - p;
+        // Edge = true probability - market price
+        let implied_edge = probability - market_price;
 
-        // Net odds ratio for binary payout: b = (1 - P) / P
-        let b = (Decimal::ONE - market_price) / market_price;
-        if b <= Decimal::ZERO {
+        // Minimum edge threshold
+        let min_edge = self.effective_min_edge(market_price);
+        if implied_edge.abs() < min_edge {
+            debug!(edge = %implied_edge, min_edge = %min_edge, "Below min edge");
             return None;
         }
 
-        // Full Kelly: f* = (p*b - q) / b
-        let kelly_full = (p * b - q) / b;
+        let reserve = kelly_reserve_rate(outcomes)?;
+
+        // Full Kelly: f* = p - R * P (zero or negative if not in the bet set)
+        let kelly_full = probability - reserve * market_price;
         if kelly_full <= Decimal::ZERO {
             return None;
         }
 
         // Apply fractional Kelly and confidence
-        let kelly_adjusted = (kelly_full * self.kelly_fraction * edge.confidence)
+        let kelly_adjusted = (kelly_full * self.kelly_fraction * confidence)
             .max(Decimal::ZERO)
             .min(self.max_position_pct);
 
@@ -146,3 +236,33 @@ impl KellyPositionSizer {
         })
     }
 }
+
+/// Reserve rate R(S) of the optimal generalized-Kelly bet set.
+///
+/// Outcomes are admitted in order of expected return p/P while it exceeds
+/// the current R. Returns None if no outcome has positive expectation.
+fn kelly_reserve_rate(outcomes: &[(Decimal, Decimal)]) -> Option<Decimal> {
+    let mut by_return: Vec<(Decimal, Decimal)> = outcomes.to_vec();
+    by_return.sort_by_key(|&(price, prob)| std::cmp::Reverse(prob / price));
+
+    let mut reserve = Decimal::ONE;
+    let mut prob_sum = Decimal::ZERO;
+    let mut price_sum = Decimal::ZERO;
+    let mut admitted = 0;
+
+    for (price, prob) in by_return {
+        if prob / price <= reserve {
+            break;
+        }
+        let denom = Decimal::ONE - (price_sum + price);
+        if denom <= Decimal::ZERO {
+            break;
+        }
+        prob_sum += prob;
+        price_sum += price;
+        reserve = (Decimal::ONE - prob_sum) / denom;
+        admitted += 1;
+    }
+
+    (admitted > 0).then_some(reserve)
+}
@@ -1,36 +1,108 @@
 //! Complete risk management system.
 //!
 //! Combines:
-//! - Position sizing (Kelly)
+//! - Position sizing (Kelly, then each strategy's size ramp)
 //! - Exposure monitoring (per-market / portfolio / correlation)
 //! - Circuit breaker (daily loss / drawdown / emergency stop)
+//! - Portfolio tail risk (binary-payoff VaR / expected shortfall)
+//! - Order-rate limiting (global and per-market token buckets, plus a
+//!   minimum interval between new orders in each market)
+//! - Panic liquidation (marked-equity drawdown exits everything)
+//!
+//! Given the same state, clock and signals, evaluation is deterministic, so
+//! recorded decisions can be replayed as golden-file tests.
 
 #![allow(dead_code)]
 
 use rust_decimal::Decimal;
-use tracing::info;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::{error, info, warn};
 
-use crate::data::models::Signal;
-use crate::state::state_manager::StateManager;
+use crate::clock::{RealClock, SharedClock};
+use crate::data::models::{
+    max_affordable_quantity, OrderIntent, Side, Signal, SignalAction, Urgency, ARB_PAIR_KEY,
+};
+use crate::state::state_manager::{MarkMode, PositionState, StateManager};
+use crate::util::dec::{floor_i64, from_f64};
 
 use super::circuit_breaker::CircuitBreaker;
 use super::exposure::{ExposureConfig, ExposureMonitor};
 use super::position_sizer::{EdgeEstimate, KellyPositionSizer};
+use super::size_ramp::SizeRamp;
 
 /// Risk configuration.
 #[derive(Debug, Clone)]
 pub struct RiskConfig {
     pub kelly_fraction: Decimal,
     pub min_edge: Decimal,
+    /// Taker fee rate; raises the Kelly min edge via `fee_adjusted_min_edge`.
+    pub fee_rate: Decimal,
     pub max_position_per_market: Decimal,
+    /// Per-market limit as a fraction of equity; the tighter of this and
+    /// `max_position_per_market` applies. Zero disables.
+    pub max_position_pct_of_equity: Decimal,
+    /// Contracts held per market, on top of the dollar limit. Zero disables.
+    pub max_contracts_per_market: i64,
     pub max_portfolio_exposure: Decimal,
     pub max_portfolio_exposure_pct: Decimal,
     pub max_correlated_exposure: Decimal,
     pub max_positions: usize,
+    /// Treat matched YES/NO holdings in a market as hedged for the
+    /// per-market and correlation limits.
+    pub net_hedged_exposure: bool,
     pub max_daily_loss: Decimal,
     pub max_drawdown_pct: Decimal,
     pub max_total_pnl_drawdown_pct_for_new_buys: Decimal,
     pub min_trade_size: Decimal,
+    /// Maximum portfolio VaR (dollars) allowed after a new buy. Zero disables.
+    pub max_var: Decimal,
+    /// Confidence level used for the `max_var` check (e.g. 0.95).
+    pub var_confidence: Decimal,
+    /// Global cap on approved buy/sell signals per second. Zero disables.
+    pub max_orders_per_sec: u32,
+    /// Per-market cap on approved buy/sell signals per second. Zero disables.
+    pub max_orders_per_sec_per_market: u32,
+    /// Fraction of the daily-loss / drawdown limits at which buy sizing is
+    /// scaled down. Zero disables the warning tier.
+    pub warning_threshold_pct: Decimal,
+    /// Buy sizing multiplier while in the warning tier (e.g. 0.5).
+    pub warning_size_scale: Decimal,
+    /// Drawdown of marked equity from its peak (e.g. 0.15) at which every
+    /// position is exited at the bid and the breaker tripped. Zero disables.
+    pub panic_liquidate_drawdown_pct: Decimal,
+    /// Value positions at the less favorable of their two exits (see
+    /// `MarkMode::Conservative`) for marked equity and panic liquidation.
+    pub conservative_marks: bool,
+    /// Block new buys in markets resolving sooner than this; exits are
+    /// still allowed. Zero disables.
+    pub min_resolution_horizon: chrono::Duration,
+    /// Lowest `signal.confidence` at which each strategy (by name) may open
+    /// or add to a position. Strategies without an entry have no floor.
+    pub min_confidence: HashMap<String, f64>,
+    /// Share of cash a buy may commit (see `max_affordable_quantity`).
+    pub cash_buffer_pct: Decimal,
+    /// Buy size ramps for newly deployed strategies, by name. Strategies
+    /// without an entry trade at full size.
+    pub size_ramp: HashMap<String, SizeRamp>,
+    /// Scale the Kelly fraction down linearly with drawdown from peak
+    /// equity, reaching zero at `max_drawdown_pct`.
+    pub drawdown_scaled_kelly: bool,
+    /// Minimum time between approved orders in one market, across all
+    /// strategies; cancels and reduce-only exits are exempt, and orders
+    /// approved in the same engine cycle count once. Zero disables.
+    pub min_order_interval: chrono::Duration,
+    /// Block buying the opposite side of a market already held, unless the
+    /// signal is reduce-only or a completeness-arb leg.
+    pub flip_protection: bool,
+}
+
+/// Parse a `strategy:floor` confidence floor entry, with the floor in
+/// [0, 1].
+pub fn parse_confidence_floor(entry: &str) -> Option<(String, f64)> {
+    let (strategy, floor) = entry.trim().split_once(':')?;
+    let floor: f64 = floor.trim().parse().ok()?;
+    let strategy = strategy.trim();
+    (!strategy.is_empty() && (0.0..=1.0).contains(&floor)).then(|| (strategy.to_string(), floor))
 }
 
 /// Decision from risk evaluation.
@@ -41,6 +113,42 @@ pub struct RiskDecision {
     pub reason: String,
 }
 
+/// A single binary exposure used for tail-risk estimation.
+#[derive(Debug, Clone)]
+struct BinaryExposure {
+    market_slug: String,
+    /// Probability that the position resolves worthless.
+    loss_probability: Decimal,
+    /// Mark value lost if the position resolves worthless.
+    value: Decimal,
+}
+
+/// Token bucket holding up to one second of orders at `rate` per second,
+/// refilled continuously.
+#[derive(Debug, Clone)]
+struct OrderRateBucket {
+    tokens: f64,
+    refilled_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl OrderRateBucket {
+    fn full(rate: u32, now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            tokens: f64::from(rate),
+            refilled_at: now,
+        }
+    }
+
+    /// Refill for the time since the last refill; true if a token is left.
+    fn refill(&mut self, rate: u32, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let elapsed = (now - self.refilled_at).num_microseconds().unwrap_or(i64::MAX).max(0);
+        let refill = elapsed as f64 / 1_000_000.0 * f64::from(rate);
+        self.tokens = (self.tokens + refill).min(f64::from(rate));
+        self.refilled_at = now;
+        self.tokens >= 1.0
+    }
+}
+
 /// Complete risk management system.
 pub struct RiskManager {
     config: RiskConfig,
@@ -49,6 +157,24 @@ pub struct RiskManager {
     exposure_monitor: ExposureMonitor,
     circuit_breaker: CircuitBreaker,
     starting_equity: Decimal,
+    /// Global order-rate bucket, created on the first order.
+    order_rate_bucket: Option<OrderRateBucket>,
+    market_order_rate_buckets: HashMap<String, OrderRateBucket>,
+    /// Highest marked equity seen, for the panic liquidation drawdown.
+    peak_marked_equity: Decimal,
+    panic_liquidated: bool,
+    clock: SharedClock,
+    /// Start of the time-based size ramps.
+    ramp_started_at: chrono::DateTime<chrono::Utc>,
+    /// Profitable closed trades per strategy, for the size ramps.
+    profitable_trades: HashMap<String, u64>,
+    /// When each market last had an order approved, for
+    /// `min_order_interval`.
+    last_order_at: HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// Markets with an order approved since `begin_cycle`; further orders
+    /// in the same batch (arb legs, two-sided quotes, ladder levels) are
+    /// not held to `min_order_interval`.
+    cycle_markets: HashSet<String>,
 }
 
 impl RiskManager {
@@ -59,17 +185,22 @@ impl RiskManager {
             config.kelly_fraction,
             Decimal::ONE, // max_position_pct (clamped again by exposure monitor)
             config.min_edge,
-        );
+        )
+        .with_fee_rate(config.fee_rate);
 
         let exposure_monitor = ExposureMonitor::new(ExposureConfig {
             max_position_per_market: config.max_position_per_market,
+            max_position_pct_of_equity: config.max_position_pct_of_equity,
+            max_contracts_per_market: config.max_contracts_per_market,
             max_portfolio_exposure: config.max_portfolio_exposure,
             max_correlated_exposure: config.max_correlated_exposure,
             max_positions: config.max_positions,
+            net_hedged_exposure: config.net_hedged_exposure,
         });
 
         let mut circuit_breaker =
-            CircuitBreaker::new(config.max_daily_loss, config.max_drawdown_pct);
+            CircuitBreaker::new(config.max_daily_loss, config.max_drawdown_pct)
+                .with_warning_tier(config.warning_threshold_pct, config.warning_size_scale);
         circuit_breaker.initialize(starting_equity);
 
         info!(
@@ -88,6 +219,49 @@ impl RiskManager {
             exposure_monitor,
             circuit_breaker,
             starting_equity,
+            order_rate_bucket: None,
+            market_order_rate_buckets: HashMap::new(),
+            peak_marked_equity: starting_equity,
+            panic_liquidated: false,
+            clock: RealClock::shared(),
+            ramp_started_at: chrono::Utc::now(),
+            profitable_trades: HashMap::new(),
+            last_order_at: HashMap::new(),
+            cycle_markets: HashSet::new(),
+        }
+    }
+
+    /// Replace the time source (tests inject a `MockClock`). The
+    /// time-based size ramps restart from the new clock's present.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.ramp_started_at = clock.now();
+        self.clock = clock;
+    }
+
+    /// Report a strategy's profitable closed trades so far (e.g. the
+    /// executor's `winning_trades`), advancing its size ramp.
+    pub fn set_profitable_trades(&mut self, strategy: &str, count: u64) {
+        self.profitable_trades.insert(strategy.to_string(), count);
+    }
+
+    /// Current size ramp multiplier for `strategy` (1 without a ramp).
+    pub fn size_ramp_scale(&self, strategy: &str) -> Decimal {
+        match self.config.size_ramp.get(strategy) {
+            Some(ramp) => ramp.scale(
+                self.profitable_trades.get(strategy).copied().unwrap_or(0),
+                self.clock.now() - self.ramp_started_at,
+            ),
+            None => Decimal::ONE,
+        }
+    }
+
+    /// Kelly fraction used for sizing: the configured fraction, scaled down
+    /// with drawdown when `drawdown_scaled_kelly` is set.
+    pub fn effective_kelly_fraction(&self) -> Decimal {
+        if self.config.drawdown_scaled_kelly {
+            self.config.kelly_fraction * self.circuit_breaker.drawdown_kelly_scale()
+        } else {
+            self.config.kelly_fraction
         }
     }
 
@@ -97,13 +271,37 @@ impl RiskManager {
         self.circuit_breaker.update(equity);
     }
 
+    /// Whether the circuit breaker has halted new risk.
+    pub fn is_halted(&self) -> bool {
+        self.circuit_breaker.is_tripped()
+    }
+
+    /// Why the circuit breaker tripped, while halted.
+    pub fn halt_reason(&self) -> Option<&str> {
+        self.circuit_breaker.can_trade().1
+    }
+
+    /// Halt all new risk immediately; exits are still allowed.
+    pub fn emergency_stop(&mut self, reason: &str) {
+        self.circuit_breaker.emergency_stop(reason);
+    }
+
     /// Reset starting equity (e.g., after initial API sync).
     pub fn reset_starting_equity(&mut self) {
         self.starting_equity = self.state.get_total_equity();
         self.circuit_breaker.initialize(self.starting_equity);
+        self.peak_marked_equity = self.starting_equity;
+        self.panic_liquidated = false;
         info!(starting_equity = %self.starting_equity, "Starting equity reset");
     }
 
+    /// Start a new batch of signals. Orders approved in one batch count
+    /// once toward `min_order_interval`, so the engine calls this before
+    /// each cycle's signals.
+    pub fn begin_cycle(&mut self) {
+        self.cycle_markets.clear();
+    }
+
     /// Evaluate a signal through all risk checks.
     pub fn evaluate_signal(&mut self, signal: Signal) -> RiskDecision {
         // Always allow cancels.
@@ -122,6 +320,13 @@ impl RiskManager {
         let (can_trade, reason) = self.circuit_breaker.can_trade();
         if !can_trade {
             if signal.is_sell() {
+                if let Some(reason) = self.check_order_rate(&signal.market_slug) {
+                    return RiskDecision {
+                        approved: false,
+                        signal: None,
+                        reason,
+                    };
+                }
                 return RiskDecision {
                     approved: true,
                     signal: Some(signal),
@@ -149,16 +354,58 @@ impl RiskManager {
             };
         }
 
+        // Buying the other side of a held binary is a flip in all but
+        // name, and usually two strategies disagreeing.
+        if let Some(held) = self.flip_blocked_by(&signal) {
+            return RiskDecision {
+                approved: false,
+                signal: None,
+                reason: format!(
+                    "Rejected: flip protection, holding {} in {}",
+                    held, signal.market_slug
+                ),
+            };
+        }
+
+        // Low-conviction entries only churn fees, however small Kelly
+        // sizes them.
+        if signal.is_buy() {
+            if let Some(&floor) = self.config.min_confidence.get(&signal.strategy_name) {
+                if signal.confidence < floor {
+                    return RiskDecision {
+                        approved: false,
+                        signal: None,
+                        reason: format!(
+                            "Rejected: confidence {:.2} below {} floor {:.2}",
+                            signal.confidence, signal.strategy_name, floor
+                        ),
+                    };
+                }
+            }
+        }
+
+        // Too close to resolution to react: exits only.
+        if signal.is_buy()
+            && self.config.min_resolution_horizon > chrono::Duration::zero()
+            && self
+                .state
+                .resolves_within(&signal.market_slug, self.config.min_resolution_horizon)
+        {
+            return RiskDecision {
+                approved: false,
+                signal: None,
+                reason: format!(
+                    "Rejected: resolves within {}s",
+                    self.config.min_resolution_horizon.num_seconds()
+                ),
+            };
+        }
+
         // Cash check for buys.
         if signal.is_buy() && price > Decimal::ZERO {
             let available_cash = self.state.get_balance();
-            let cash_buffer = Decimal::new(98, 2); // 0.98
-            let max_affordable = (available_cash * cash_buffer) / price;
-            let max_affordable_qty = max_affordable
-                .floor()
-                .to_string()
-                .parse::<i64>()
-                .unwrap_or(0);
+            let max_affordable_qty =
+                max_affordable_quantity(available_cash, price, self.config.cash_buffer_pct);
 
             if max_affordable_qty <= 0 {
                 return RiskDecision {
@@ -181,19 +428,49 @@ impl RiskManager {
                 .metadata
                 .get("true_probability")
                 .and_then(|v| v.as_f64())
-                .and_then(|f| Decimal::from_f64_retain(f))
+                .and_then(from_f64)
             {
                 let edge = EdgeEstimate::from_confidence(true_prob, signal.confidence);
-                if let Some(result) =
-                    self.position_sizer
-                        .calculate_position_size(self.state.get_total_equity(), price, &edge)
-                {
+                let held = self.held_notional(&signal.market_slug, signal.action.to_intent());
+                self.position_sizer.kelly_fraction = self.effective_kelly_fraction();
+                if let Some(result) = self.position_sizer.calculate_incremental_position_size(
+                    self.state.get_total_equity(),
+                    price,
+                    &edge,
+                    held,
+                ) {
                     qty = qty.min(result.contracts);
                 } else {
                     return RiskDecision {
                         approved: false,
                         signal: None,
-                        reason: "Rejected: insufficient edge/confidence".to_string(),
+                        reason: "Rejected: insufficient edge/confidence or at Kelly target"
+                            .to_string(),
+                    };
+                }
+            }
+        }
+
+        // Unproven strategies start small, but always with at least one
+        // contract so they can book the trades that ramp them up.
+        if signal.is_buy() {
+            let scale = self.size_ramp_scale(&signal.strategy_name);
+            if scale < Decimal::ONE {
+                let ramped = floor_i64(Decimal::from(qty) * scale);
+                qty = ramped.max(1).min(qty);
+            }
+        }
+
+        // Circuit breaker warning tier scales new exposure down.
+        if signal.is_buy() {
+            let scale = self.circuit_breaker.risk_scale();
+            if scale < Decimal::ONE {
+                qty = floor_i64(Decimal::from(qty) * scale);
+                if qty <= 0 {
+                    return RiskDecision {
+                        approved: false,
+                        signal: None,
+                        reason: "Rejected: circuit breaker warning tier".to_string(),
                     };
                 }
             }
@@ -224,6 +501,7 @@ impl RiskManager {
                 &self.state,
                 &signal.market_slug,
                 notional,
+                price,
             );
 
             // Portfolio exposure % check.
@@ -274,6 +552,69 @@ impl RiskManager {
                     reason: format!("Rejected: below min trade size ${:.2}", final_notional),
                 };
             }
+
+            // Portfolio tail-risk check.
+            if self.config.max_var > Decimal::ZERO {
+                let side = match signal.action {
+                    SignalAction::BuyNo => Side::No,
+                    _ => Side::Yes,
+                };
+                let exposures =
+                    self.binary_exposures(Some((&signal.market_slug, side, qty, price)));
+                let var = value_at_risk(
+                    &self.loss_distribution(&exposures),
+                    self.config.var_confidence,
+                );
+                if var > self.config.max_var {
+                    return RiskDecision {
+                        approved: false,
+                        signal: None,
+                        reason: format!(
+                            "Rejected: portfolio VaR ${:.2} > ${:.2}",
+                            var, self.config.max_var
+                        ),
+                    };
+                }
+            }
+        }
+
+        // Exchange minimum order size, when the market reports one.
+        let min_order_size = self
+            .state
+            .get_market(&signal.market_slug)
+            .and_then(|m| m.min_order_size);
+        if let Some(min) = min_order_size.filter(|min| qty < *min) {
+            return RiskDecision {
+                approved: false,
+                signal: None,
+                reason: format!("Rejected: {} contracts below market minimum {}", qty, min),
+            };
+        }
+
+        // Per-market throttle across strategies, independent of their own
+        // cooldowns.
+        if !signal.reduce_only {
+            if let Some(reason) = self.check_order_interval(&signal.market_slug) {
+                return RiskDecision {
+                    approved: false,
+                    signal: None,
+                    reason,
+                };
+            }
+        }
+
+        // Order-rate limits (last line of defense against order storms).
+        if let Some(reason) = self.check_order_rate(&signal.market_slug) {
+            return RiskDecision {
+                approved: false,
+                signal: None,
+                reason,
+            };
+        }
+
+        if !signal.reduce_only && self.config.min_order_interval > chrono::Duration::zero() {
+            self.last_order_at.insert(signal.market_slug.clone(), self.clock.now());
+            self.cycle_markets.insert(signal.market_slug.clone());
         }
 
         // Produce (possibly resized) signal.
@@ -287,6 +628,100 @@ impl RiskManager {
         }
     }
 
+    /// The held side an opening buy of the other side would flip, when
+    /// flip protection applies.
+    fn flip_blocked_by(&self, signal: &Signal) -> Option<Side> {
+        if !self.config.flip_protection
+            || !signal.is_buy()
+            || signal.reduce_only
+            || signal.metadata.contains_key(ARB_PAIR_KEY)
+        {
+            return None;
+        }
+        let side = signal.action.to_intent()?.side();
+        self.state
+            .get_position(&signal.market_slug)
+            .into_iter()
+            .chain(self.state.get_hedge_position(&signal.market_slug))
+            .find(|p| p.quantity > 0 && p.side != side)
+            .map(|p| p.side)
+    }
+
+    /// Reject a new order in `market_slug` if the market's last approved
+    /// order, from an earlier batch, is within `min_order_interval`.
+    fn check_order_interval(&self, market_slug: &str) -> Option<String> {
+        let interval = self.config.min_order_interval;
+        if interval <= chrono::Duration::zero() || self.cycle_markets.contains(market_slug) {
+            return None;
+        }
+        let last = self.last_order_at.get(market_slug)?;
+        let elapsed = self.clock.now() - *last;
+        if elapsed >= interval {
+            return None;
+        }
+        Some(format!(
+            "Rejected: {:.1}s since last order in market, minimum {:.1}s",
+            elapsed.num_milliseconds() as f64 / 1000.0,
+            interval.num_milliseconds() as f64 / 1000.0
+        ))
+    }
+
+    /// Consume one order token for `market_slug`. Returns a rejection reason
+    /// if either the per-market or the global bucket is empty.
+    fn check_order_rate(&mut self, market_slug: &str) -> Option<String> {
+        let now = self.clock.now();
+        let global = self.config.max_orders_per_sec;
+        let per_market = self.config.max_orders_per_sec_per_market;
+
+        // Both buckets must have a token before either is spent, so an
+        // order held back by one limit does not use up the other.
+        if per_market > 0 {
+            let bucket = self
+                .market_order_rate_buckets
+                .entry(market_slug.to_string())
+                .or_insert_with(|| OrderRateBucket::full(per_market, now));
+            if !bucket.refill(per_market, now) {
+                warn!(market_slug, limit = per_market, "Per-market order rate limit hit");
+                return Some(format!(
+                    "Rejected: per-market order rate limit ({}/s)",
+                    per_market
+                ));
+            }
+        }
+        if global > 0 {
+            let bucket = self
+                .order_rate_bucket
+                .get_or_insert_with(|| OrderRateBucket::full(global, now));
+            if !bucket.refill(global, now) {
+                warn!(market_slug, limit = global, "Global order rate limit hit");
+                return Some(format!("Rejected: global order rate limit ({}/s)", global));
+            }
+        }
+
+        if let Some(bucket) = self.market_order_rate_buckets.get_mut(market_slug) {
+            bucket.tokens -= 1.0;
+        }
+        if let Some(ref mut bucket) = self.order_rate_bucket {
+            bucket.tokens -= 1.0;
+        }
+        None
+    }
+
+    /// Cost basis already held on the side `intent` buys, counting both
+    /// the position and any hedge in the market.
+    fn held_notional(&self, market_slug: &str, intent: Option<OrderIntent>) -> Decimal {
+        let Some(side) = intent.map(|i| i.side()) else {
+            return Decimal::ZERO;
+        };
+        self.state
+            .get_position(market_slug)
+            .into_iter()
+            .chain(self.state.get_hedge_position(market_slug))
+            .filter(|p| p.side == side)
+            .map(|p| p.cost_basis())
+            .sum()
+    }
+
     fn is_new_buy_blocked_by_drawdown(&self) -> bool {
         if self.config.max_total_pnl_drawdown_pct_for_new_buys <= Decimal::ZERO {
             return false;
@@ -299,8 +734,261 @@ impl RiskManager {
         drawdown_pct >= self.config.max_total_pnl_drawdown_pct_for_new_buys
     }
 
+    // =========================================================================
+    // Panic liquidation
+    // =========================================================================
+
+    /// Cash plus every position (and hedge) at its mark: the price it could
+    /// actually be sold at, or the worse of its two exits with
+    /// `conservative_marks`. Falls back to cost when neither book quotes it.
+    pub fn marked_equity(&self) -> Decimal {
+        let mode = if self.config.conservative_marks {
+            MarkMode::Conservative
+        } else {
+            MarkMode::Liquidation
+        };
+        self.state.get_balance() + self.state.mark_to_market(mode)
+    }
+
+    /// Reduce-only exits for every open position once marked equity has
+    /// fallen `panic_liquidate_drawdown_pct` from its peak; also trips the
+    /// breaker so nothing new is opened. Fires once until
+    /// `reset_starting_equity`.
+    pub fn check_panic_liquidation(&mut self) -> Vec<Signal> {
+        let threshold = self.config.panic_liquidate_drawdown_pct;
+        if threshold <= Decimal::ZERO || self.panic_liquidated {
+            return Vec::new();
+        }
+
+        let marked = self.marked_equity();
+        self.peak_marked_equity = self.peak_marked_equity.max(marked);
+        if self.peak_marked_equity <= Decimal::ZERO {
+            return Vec::new();
+        }
+        let drawdown = (self.peak_marked_equity - marked) / self.peak_marked_equity;
+        if drawdown < threshold {
+            return Vec::new();
+        }
+
+        self.panic_liquidated = true;
+        let positions = self.open_positions_and_hedges();
+        error!(
+            marked_equity = %marked,
+            peak_marked_equity = %self.peak_marked_equity,
+            drawdown = %drawdown,
+            threshold = %threshold,
+            positions = positions.len(),
+            "Panic liquidation triggered"
+        );
+        self.emergency_stop(&format!(
+            "Panic liquidation: marked equity down {:.4} from peak",
+            drawdown
+        ));
+
+        positions
+            .iter()
+            .map(|p| Signal {
+                market_slug: p.market_slug.clone(),
+                action: match p.side {
+                    Side::Yes => SignalAction::SellYes,
+                    Side::No => SignalAction::SellNo,
+                },
+                price: self.best_bid(p).unwrap_or(Decimal::new(1, 2)),
+                quantity: p.quantity,
+                urgency: Urgency::Critical,
+                confidence: 1.0,
+                strategy_name: "panic_liquidation".to_string(),
+                reason: format!("Panic liquidation at {:.4} drawdown", drawdown),
+                metadata: HashMap::new(),
+                timestamp: self.clock.now(),
+                reduce_only: true,
+                good_till: None,
+            })
+            .collect()
+    }
+
+    fn open_positions_and_hedges(&self) -> Vec<PositionState> {
+        let mut positions = Vec::new();
+        for position in self.state.get_all_positions() {
+            let hedge = self.state.get_hedge_position(&position.market_slug);
+            positions.push(position);
+            positions.extend(hedge);
+        }
+        positions.retain(|p| p.quantity > 0);
+        positions
+    }
+
+    fn best_bid(&self, position: &PositionState) -> Option<Decimal> {
+        let market = self.state.get_market(&position.market_slug)?;
+        match position.side {
+            Side::Yes => market.yes_bid,
+            Side::No => market.no_bid,
+        }
+    }
+
     pub fn set_correlation_group(&mut self, group_name: &str, markets: Vec<String>) {
         self.exposure_monitor
             .set_correlation_group(group_name, markets);
     }
+
+    // =========================================================================
+    // Tail risk
+    // =========================================================================
+
+    /// Portfolio value-at-risk at `confidence` (e.g. 0.95): the loss that is
+    /// not exceeded with that probability.
+    ///
+    /// Each position is a binary payoff that loses its full mark value with
+    /// probability `1 - mid`. Markets in the same correlation group are
+    /// assumed to resolve together (worst case), losing with the highest
+    /// loss probability among their members. This is a deterministic
+    /// approximation, not a Monte Carlo simulation.
+    pub fn portfolio_var(&self, confidence: Decimal) -> Decimal {
+        let exposures = self.binary_exposures(None);
+        value_at_risk(&self.loss_distribution(&exposures), confidence)
+    }
+
+    /// Expected shortfall at `confidence`: the average loss in the worst
+    /// `1 - confidence` tail of the same distribution as `portfolio_var`.
+    pub fn portfolio_expected_shortfall(&self, confidence: Decimal) -> Decimal {
+        let exposures = self.binary_exposures(None);
+        expected_shortfall(&self.loss_distribution(&exposures), confidence)
+    }
+
+    /// Current positions and hedge legs (plus an optional hypothetical buy)
+    /// as binary exposures priced at the market mid.
+    fn binary_exposures(
+        &self,
+        extra: Option<(&str, Side, i64, Decimal)>,
+    ) -> Vec<BinaryExposure> {
+        let mut exposures: Vec<BinaryExposure> = self
+            .open_positions_and_hedges()
+            .iter()
+            .map(|p| self.binary_exposure(&p.market_slug, p.side, p.quantity, p.avg_price))
+            .collect();
+        if let Some((market_slug, side, quantity, price)) = extra {
+            exposures.push(self.binary_exposure(market_slug, side, quantity, price));
+        }
+        exposures
+    }
+
+    fn binary_exposure(
+        &self,
+        market_slug: &str,
+        side: Side,
+        quantity: i64,
+        fallback_price: Decimal,
+    ) -> BinaryExposure {
+        let win_probability = self
+            .state
+            .get_market(market_slug)
+            .and_then(|m| m.yes_mid_price())
+            .map(|mid| match side {
+                Side::Yes => mid,
+                Side::No => Decimal::ONE - mid,
+            })
+            .unwrap_or(fallback_price)
+            .max(Decimal::ZERO)
+            .min(Decimal::ONE);
+
+        BinaryExposure {
+            market_slug: market_slug.to_string(),
+            loss_probability: Decimal::ONE - win_probability,
+            value: win_probability * Decimal::from(quantity),
+        }
+    }
+
+    /// Discrete loss distribution (loss -> probability), ascending by loss.
+    ///
+    /// Exposures are first merged into independent units: one per
+    /// correlation group, and one per remaining market. Each unit can
+    /// double the number of distinct losses, so past `MAX_LOSS_POINTS`
+    /// the distribution is coarsened (see `coarsen_losses`) to keep the
+    /// cost linear in the number of positions.
+    fn loss_distribution(&self, exposures: &[BinaryExposure]) -> BTreeMap<Decimal, Decimal> {
+        let mut groups: Vec<(&String, &Vec<String>)> =
+            self.exposure_monitor.correlation_groups().iter().collect();
+        groups.sort_by(|a, b| a.0.cmp(b.0));
+
+        // unit key -> (value at risk, loss probability)
+        let mut units: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+        for exposure in exposures {
+            let key = groups
+                .iter()
+                .find(|(_, markets)| markets.contains(&exposure.market_slug))
+                .map(|(name, _)| format!("group:{}", name))
+                .unwrap_or_else(|| format!("market:{}", exposure.market_slug));
+            let unit = units.entry(key).or_insert((Decimal::ZERO, Decimal::ZERO));
+            unit.0 += exposure.value;
+            unit.1 = unit.1.max(exposure.loss_probability);
+        }
+
+        let mut dist: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+        dist.insert(Decimal::ZERO, Decimal::ONE);
+        for (value, loss_probability) in units.values() {
+            let mut next: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+            for (loss, prob) in &dist {
+                *next.entry(*loss).or_insert(Decimal::ZERO) +=
+                    *prob * (Decimal::ONE - *loss_probability);
+                *next.entry(*loss + *value).or_insert(Decimal::ZERO) += *prob * *loss_probability;
+            }
+            dist = next;
+            if dist.len() > MAX_LOSS_POINTS {
+                dist = coarsen_losses(&dist);
+            }
+        }
+        dist
+    }
+}
+
+/// Distinct losses kept in a VaR loss distribution before coarsening.
+const MAX_LOSS_POINTS: usize = 1024;
+
+/// Merge losses into `MAX_LOSS_POINTS / 2` equal buckets up to the largest
+/// loss, each loss rounded up to its bucket's upper edge so VaR and
+/// expected shortfall can only err high.
+fn coarsen_losses(dist: &BTreeMap<Decimal, Decimal>) -> BTreeMap<Decimal, Decimal> {
+    let max_loss = dist.keys().next_back().copied().unwrap_or(Decimal::ZERO);
+    let width = max_loss / Decimal::from(MAX_LOSS_POINTS / 2);
+    if width <= Decimal::ZERO {
+        return dist.clone();
+    }
+    let mut coarse: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+    for (loss, prob) in dist {
+        let bucket = ((*loss / width).ceil() * width).min(max_loss);
+        *coarse.entry(bucket).or_insert(Decimal::ZERO) += *prob;
+    }
+    coarse
+}
+
+/// Smallest loss whose cumulative probability reaches `confidence`.
+fn value_at_risk(dist: &BTreeMap<Decimal, Decimal>, confidence: Decimal) -> Decimal {
+    let mut cumulative = Decimal::ZERO;
+    for (loss, prob) in dist {
+        cumulative += *prob;
+        if cumulative >= confidence {
+            return *loss;
+        }
+    }
+    dist.keys().next_back().copied().unwrap_or(Decimal::ZERO)
+}
+
+/// Probability-weighted average loss over the worst `1 - confidence` tail.
+fn expected_shortfall(dist: &BTreeMap<Decimal, Decimal>, confidence: Decimal) -> Decimal {
+    let tail = Decimal::ONE - confidence;
+    if tail <= Decimal::ZERO {
+        return dist.keys().next_back().copied().unwrap_or(Decimal::ZERO);
+    }
+
+    let mut remaining = tail;
+    let mut weighted = Decimal::ZERO;
+    for (loss, prob) in dist.iter().rev() {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = (*prob).min(remaining);
+        weighted += *loss * take;
+        remaining -= take;
+    }
+    weighted / tail
 }
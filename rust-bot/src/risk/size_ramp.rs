@@ -0,0 +1,115 @@
+//! Position-size ramp for newly deployed strategies.
+//!
+//! A freshly tuned strategy should not size straight to full Kelly before
+//! it has proven itself live. Its buys are scaled by a factor that starts
+//! at `initial_scale` and grows exponentially to 1.0 as it books profitable
+//! closed trades or as time passes since startup, whichever is further
+//! along: halfway there, the scale is `sqrt(initial_scale)`.
+
+#![allow(dead_code)]
+
+use rust_decimal::Decimal;
+
+use crate::util::dec::{from_f64, to_f64};
+
+/// Ramp for one strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeRamp {
+    /// Scale of the first trades, in (0, 1].
+    pub initial_scale: Decimal,
+    /// Profitable closed trades to reach full size. Zero ignores trades.
+    pub trades_to_full: u64,
+    /// Time since startup to reach full size. Zero ignores time.
+    pub time_to_full: chrono::Duration,
+}
+
+impl SizeRamp {
+    /// Fraction of the way to full size, in [0, 1]. A ramp with neither
+    /// target is already complete.
+    pub fn progress(&self, profitable_trades: u64, elapsed: chrono::Duration) -> f64 {
+        let by_trades = (self.trades_to_full > 0)
+            .then(|| profitable_trades as f64 / self.trades_to_full as f64);
+        let by_time = (self.time_to_full > chrono::Duration::zero()).then(|| {
+            elapsed.num_milliseconds() as f64 / self.time_to_full.num_milliseconds() as f64
+        });
+        match (by_trades, by_time) {
+            (None, None) => 1.0,
+            (a, b) => a.unwrap_or(0.0).max(b.unwrap_or(0.0)).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Size multiplier: `initial_scale^(1 - progress)`.
+    pub fn scale(&self, profitable_trades: u64, elapsed: chrono::Duration) -> Decimal {
+        let progress = self.progress(profitable_trades, elapsed);
+        if progress >= 1.0 {
+            return Decimal::ONE;
+        }
+        from_f64(to_f64(self.initial_scale).powf(1.0 - progress))
+            .unwrap_or(self.initial_scale)
+            .min(Decimal::ONE)
+    }
+}
+
+/// Parse a `strategy:initial_scale:trades_to_full[:secs_to_full]` ramp
+/// entry, with the scale in (0, 1].
+pub fn parse_size_ramp(entry: &str) -> Option<(String, SizeRamp)> {
+    let mut parts = entry.trim().split(':').map(str::trim);
+    let strategy = parts.next().filter(|s| !s.is_empty())?;
+    let initial_scale: Decimal = parts.next()?.parse().ok()?;
+    let trades_to_full: u64 = parts.next()?.parse().ok()?;
+    let secs_to_full: i64 = match parts.next() {
+        Some(secs) => secs.parse().ok().filter(|s| *s >= 0)?,
+        None => 0,
+    };
+    if parts.next().is_some() || initial_scale <= Decimal::ZERO || initial_scale > Decimal::ONE {
+        return None;
+    }
+    Some((
+        strategy.to_string(),
+        SizeRamp {
+            initial_scale,
+            trades_to_full,
+            time_to_full: chrono::Duration::seconds(secs_to_full),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_grows_geometrically_by_trades_or_time() {
+        let ramp = SizeRamp {
+            initial_scale: Decimal::new(25, 2),
+            trades_to_full: 10,
+            time_to_full: chrono::Duration::hours(4),
+        };
+        let zero = chrono::Duration::zero();
+        assert_eq!(ramp.scale(0, zero), Decimal::new(25, 2));
+        assert_eq!(ramp.scale(5, zero), Decimal::new(5, 1));
+        assert_eq!(ramp.scale(0, chrono::Duration::hours(2)), Decimal::new(5, 1));
+        // Whichever is further along wins.
+        assert_eq!(ramp.scale(5, chrono::Duration::hours(4)), Decimal::ONE);
+        assert_eq!(ramp.scale(12, zero), Decimal::ONE);
+
+        let untimed = SizeRamp { trades_to_full: 0, ..ramp.clone() };
+        assert_eq!(untimed.scale(100, zero), Decimal::new(25, 2));
+        let no_targets = SizeRamp { time_to_full: zero, ..untimed };
+        assert_eq!(no_targets.scale(0, zero), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_parses_ramp_entries() {
+        let (name, ramp) = parse_size_ramp(" statistical_edge:0.2:20:3600 ").unwrap();
+        assert_eq!(name, "statistical_edge");
+        assert_eq!(ramp.initial_scale, Decimal::new(2, 1));
+        assert_eq!(ramp.trades_to_full, 20);
+        assert_eq!(ramp.time_to_full, chrono::Duration::hours(1));
+        assert_eq!(parse_size_ramp("mm:0.5:10").unwrap().1.time_to_full.num_seconds(), 0);
+
+        for bad in ["mm:0:10", "mm:1.5:10", ":0.5:10", "mm:0.5", "mm:0.5:10:-1", "mm:0.5:1:2:3"] {
+            assert!(parse_size_ramp(bad).is_none(), "{bad}");
+        }
+    }
+}
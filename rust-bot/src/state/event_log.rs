@@ -0,0 +1,166 @@
+//! Bounded in-memory log of recent operational events.
+//!
+//! Keeps the last N order placements, fills, rejects, breaker trips,
+//! reconcile drifts and feed gaps so an operator can see what just happened
+//! (via the status server's `/events`) without running at debug level.
+//! `EventLog` is a cheap clone handle; a push builds the event outside the
+//! lock and holds it only to append (and evict the oldest at capacity).
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Default number of events retained.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    OrderPlaced,
+    Fill,
+    Reject,
+    BreakerTrip,
+    ReconcileDrift,
+    FeedGap,
+}
+
+/// One logged event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Event {
+    /// Increases by one per push, so gaps show evicted events.
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_slug: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub detail: Value,
+}
+
+#[derive(Debug)]
+struct Shared {
+    capacity: usize,
+    next_seq: AtomicU64,
+    events: Mutex<VecDeque<Event>>,
+}
+
+/// Shared handle to the ring buffer.
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    shared: Arc<Shared>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+impl EventLog {
+    /// A capacity of 0 disables the log.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                capacity,
+                next_seq: AtomicU64::new(0),
+                events: Mutex::new(VecDeque::with_capacity(capacity)),
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Record an event, evicting the oldest when full. `detail` may be
+    /// `Value::Null`.
+    pub fn push(
+        &self,
+        kind: EventKind,
+        market_slug: Option<&str>,
+        message: impl Into<String>,
+        detail: Value,
+    ) {
+        if self.shared.capacity == 0 {
+            return;
+        }
+        let event = Event {
+            seq: self.shared.next_seq.fetch_add(1, Ordering::Relaxed),
+            at: Utc::now(),
+            kind,
+            market_slug: market_slug.map(str::to_string),
+            message: message.into(),
+            detail,
+        };
+        let mut events = self.shared.events.lock().unwrap();
+        if events.len() == self.shared.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.events.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Up to `limit` retained events, newest first (0 = all).
+    pub fn recent(&self, limit: usize) -> Vec<Event> {
+        let events = self.shared.events.lock().unwrap();
+        let limit = if limit == 0 { events.len() } else { limit };
+        events.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Retained events as a JSON array, newest first.
+    pub fn to_json(&self, limit: usize) -> Value {
+        serde_json::to_value(self.recent(limit)).unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_keeps_last_n_events_newest_first() {
+        let log = EventLog::new(3);
+        for i in 0..5 {
+            log.push(EventKind::Fill, Some("m"), format!("fill {i}"), json!({ "qty": i }));
+        }
+        assert_eq!(log.len(), 3);
+
+        let json = log.to_json(0);
+        let events = json.as_array().unwrap();
+        let seqs: Vec<u64> = events.iter().map(|e| e["seq"].as_u64().unwrap()).collect();
+        assert_eq!(seqs, vec![4, 3, 2]);
+        assert_eq!(events[0]["kind"], "fill");
+        assert_eq!(events[0]["message"], "fill 4");
+        assert_eq!(events[0]["market_slug"], "m");
+        assert_eq!(events[0]["detail"]["qty"], 4);
+
+        log.push(EventKind::BreakerTrip, None, "tripped", Value::Null);
+        let newest = &log.to_json(1)[0];
+        assert_eq!(newest["kind"], "breaker_trip");
+        assert!(newest.get("market_slug").is_none());
+        assert!(newest.get("detail").is_none());
+        assert_eq!(log.recent(2).len(), 2);
+    }
+
+    #[test]
+    fn test_zero_capacity_records_nothing() {
+        let log = EventLog::new(0);
+        log.push(EventKind::Reject, Some("m"), "rejected", Value::Null);
+        assert!(log.is_empty());
+        assert_eq!(log.to_json(0), json!([]));
+    }
+}
@@ -1 +1,2 @@
+pub mod event_log;
 pub mod state_manager;
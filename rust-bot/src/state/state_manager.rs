@@ -7,10 +7,14 @@
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
-use crate::data::models::{OrderIntent, OrderStatus, Side};
+use crate::clock::{RealClock, SharedClock};
+use crate::state::event_log::EventLog;
+use crate::data::models::{
+    complement_prices_consistent, MarketMetadata, OrderIntent, OrderStatus, Position, Side,
+};
 
 // =============================================================================
 // State Types
@@ -24,6 +28,15 @@ pub struct MarketState {
     pub yes_ask: Option<Decimal>,
     pub no_bid: Option<Decimal>,
     pub no_ask: Option<Decimal>,
+    /// Contracts at the best YES bid/ask, if known from the order book.
+    pub yes_bid_depth: Option<i64>,
+    pub yes_ask_depth: Option<i64>,
+    /// When the market is scheduled to resolve, if the API reports it.
+    pub resolution_date: Option<DateTime<Utc>>,
+    /// Exchange tick size and minimum order size (contracts), filled from
+    /// the metadata cache when left `None`.
+    pub tick_size: Option<Decimal>,
+    pub min_order_size: Option<i64>,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -41,6 +54,65 @@ impl MarketState {
             (Some(bid), Some(ask)) if bid > Decimal::ZERO && ask > Decimal::ZERO && bid < ask
         )
     }
+
+    /// Price a held `side` is marked at under `mode`; `None` when neither
+    /// book quotes it. See `MarkMode` for the convention.
+    pub fn mark_price(&self, side: Side, mode: MarkMode) -> Option<Decimal> {
+        let (own_bid, other_ask) = match side {
+            Side::Yes => (self.yes_bid, self.no_ask),
+            Side::No => (self.no_bid, self.yes_ask),
+        };
+        let complement = other_ask.map(|ask| Decimal::ONE - ask);
+        match (mode, own_bid, complement) {
+            (MarkMode::Conservative, Some(bid), Some(c)) => Some(bid.min(c)),
+            (_, Some(bid), _) => Some(bid),
+            (_, None, c) => c,
+        }
+    }
+
+    /// False when the NO prices stray more than `tolerance` from the YES
+    /// complement, i.e. `|yes_bid + no_ask - 1|` or `|yes_ask + no_bid - 1|`.
+    pub fn prices_consistent(&self, tolerance: Decimal) -> bool {
+        complement_prices_consistent(
+            self.yes_bid,
+            self.yes_ask,
+            self.no_bid,
+            self.no_ask,
+            tolerance,
+        )
+    }
+}
+
+/// How open positions are valued.
+///
+/// A held side can be exited two ways: sold on its own book at its bid, or
+/// closed against the other book by buying the opposite side at its ask
+/// (worth `1 - ask`; this is how the market maker prices NO exits).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkMode {
+    /// The side's own bid, falling back to the opposite ask's complement.
+    #[default]
+    Liquidation,
+    /// The less favorable of the two exits, so risk checks never value a
+    /// position above what both books support.
+    Conservative,
+}
+
+/// Most recent trade seen in a market, kept as the YES price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastTrade {
+    pub yes_price: Decimal,
+    pub at: DateTime<Utc>,
+}
+
+impl LastTrade {
+    /// The trade's price for `side` (NO trades at the YES complement).
+    pub fn price(&self, side: Side) -> Decimal {
+        match side {
+            Side::Yes => self.yes_price,
+            Side::No => Decimal::ONE - self.yes_price,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -83,18 +155,177 @@ impl OrderState {
 // State Manager
 // =============================================================================
 
+/// A market where the local position disagreed with the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionDrift {
+    pub market_slug: String,
+    /// `None` when there was no local position.
+    pub local_side: Option<Side>,
+    /// `None` when the exchange reported no position.
+    pub api_side: Option<Side>,
+    pub local_quantity: i64,
+    pub api_quantity: i64,
+    pub local_avg_price: Decimal,
+    pub api_avg_price: Decimal,
+}
+
+impl PositionDrift {
+    /// API quantity minus local quantity.
+    pub fn quantity_delta(&self) -> i64 {
+        self.api_quantity - self.local_quantity
+    }
+
+    /// API average price minus local average price.
+    pub fn avg_price_delta(&self) -> Decimal {
+        self.api_avg_price - self.local_avg_price
+    }
+
+    /// Absolute cost-basis difference in USD. A side mismatch counts both
+    /// positions in full since neither offsets the other.
+    pub fn notional_drift(&self) -> Decimal {
+        let local = self.local_avg_price * Decimal::from(self.local_quantity);
+        let api = self.api_avg_price * Decimal::from(self.api_quantity);
+        let sides_differ = matches!(
+            (self.local_side, self.api_side),
+            (Some(l), Some(a)) if l != a
+        );
+        if sides_differ {
+            local + api
+        } else {
+            (api - local).abs()
+        }
+    }
+}
+
+/// Result of reconciling local positions against the exchange.
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationReport {
+    /// Number of distinct markets compared (local and API combined).
+    pub markets_checked: usize,
+    pub drifts: Vec<PositionDrift>,
+}
+
+impl ReconciliationReport {
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+
+    /// Drifts whose notional exceeds `threshold` USD.
+    pub fn drifts_above(&self, threshold: Decimal) -> Vec<&PositionDrift> {
+        self.drifts
+            .iter()
+            .filter(|d| d.notional_drift() > threshold)
+            .collect()
+    }
+}
+
+/// Per-market warmup: a market is tradeable once it has received
+/// `min_updates` priced updates spanning at least `warmup_secs`. A gap
+/// longer than `max_gap_secs` between updates restarts the warmup.
+#[derive(Debug, Clone, Default)]
+pub struct WarmupConfig {
+    pub min_updates: u64,
+    pub warmup_secs: f64,
+    /// Zero never restarts the warmup.
+    pub max_gap_secs: f64,
+}
+
+/// Priced updates seen since the market last (re)started warming up.
+#[derive(Debug, Clone)]
+struct WarmupProgress {
+    updates: u64,
+    since: DateTime<Utc>,
+    last: DateTime<Utc>,
+}
+
+/// Price-band sanity filter for feed updates: a YES or NO mid more than
+/// `max_jump_pct` from that side's last accepted mid is held back until it
+/// persists for `confirm_polls` consecutive polls. A zero `max_jump_pct`
+/// (the default) disables it.
+#[derive(Debug, Clone, Default)]
+pub struct PriceBandConfig {
+    pub max_jump_pct: Decimal,
+    pub confirm_polls: u32,
+}
+
+/// Outcome of screening one update against the price band.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceBandCheck {
+    Accepted,
+    /// Keep the previous market state; this was anomalous poll `anomalies`
+    /// on `side`.
+    Rejected {
+        side: Side,
+        last_good_mid: Decimal,
+        mid: Decimal,
+        anomalies: u32,
+    },
+}
+
+/// Last accepted mid and the run of out-of-band mids since.
+#[derive(Debug, Clone)]
+struct PriceBand {
+    last_good_mid: Decimal,
+    /// Most recent out-of-band mid; a run only counts while it holds steady.
+    pending_mid: Option<Decimal>,
+    anomalies: u32,
+}
+
 #[derive(Debug)]
 struct Inner {
     balance: Decimal,
     markets: HashMap<String, MarketState>,
+    /// Markets switched off at runtime; strategies skip them.
+    disabled_markets: HashSet<String>,
+    /// Markets no longer trading, with the outcome once resolved
+    /// (`Some(true)` = YES won).
+    closed_markets: HashMap<String, Option<bool>>,
+    /// Exchange order constraints per market, from `get_market`.
+    market_metadata: HashMap<String, MarketMetadata>,
+    /// Taker fee rates for markets that differ from the default schedule.
+    fee_overrides: HashMap<String, Decimal>,
+    /// Last trade per market, from our fills.
+    last_trades: HashMap<String, LastTrade>,
+    /// When each market's best prices last changed (polls that repeat the
+    /// same quotes do not count).
+    quotes_changed_at: HashMap<String, DateTime<Utc>>,
+    /// Mark positions at the last trade when it is newer than the quotes.
+    mark_last_trade: bool,
+    /// Resolutions not yet settled by the executor.
+    unsettled_resolutions: Vec<(String, bool)>,
+    warmup_config: WarmupConfig,
+    warmup: HashMap<String, WarmupProgress>,
+    price_band_config: PriceBandConfig,
+    price_bands: HashMap<(String, Side), PriceBand>,
     positions: HashMap<String, PositionState>,
+    /// Opposite-side holdings kept alongside `positions` rather than netted
+    /// (the paper executor holds both legs of a completeness arb).
+    hedges: HashMap<String, PositionState>,
     orders: HashMap<String, OrderState>,
+    /// Time source for position open timestamps.
+    clock: SharedClock,
+}
+
+impl Inner {
+    /// The market's last trade, if last-trade marks are on and it is newer
+    /// than the market's last quote change.
+    fn fresh_last_trade(&self, slug: &str) -> Option<&LastTrade> {
+        if !self.mark_last_trade {
+            return None;
+        }
+        let trade = self.last_trades.get(slug)?;
+        let quoted_at = self.quotes_changed_at.get(slug);
+        quoted_at.is_none_or(|at| trade.at > *at).then_some(trade)
+    }
 }
 
 /// Thread-safe centralized state container.
 #[derive(Debug, Clone)]
 pub struct StateManager {
     inner: Arc<RwLock<Inner>>,
+    /// Recent operational events; outside `inner` so pushes never wait on
+    /// the state lock.
+    events: EventLog,
 }
 
 impl StateManager {
@@ -103,12 +334,38 @@ impl StateManager {
             inner: Arc::new(RwLock::new(Inner {
                 balance: initial_balance,
                 markets: HashMap::new(),
+                disabled_markets: HashSet::new(),
+                closed_markets: HashMap::new(),
+                market_metadata: HashMap::new(),
+                fee_overrides: HashMap::new(),
+                last_trades: HashMap::new(),
+                quotes_changed_at: HashMap::new(),
+                mark_last_trade: false,
+                unsettled_resolutions: Vec::new(),
+                warmup_config: WarmupConfig::default(),
+                warmup: HashMap::new(),
+                price_band_config: PriceBandConfig::default(),
+                price_bands: HashMap::new(),
                 positions: HashMap::new(),
+                hedges: HashMap::new(),
                 orders: HashMap::new(),
+                clock: RealClock::shared(),
             })),
+            events: EventLog::default(),
         }
     }
 
+    /// Replace the event log (call before cloning the manager).
+    pub fn with_event_log(mut self, events: EventLog) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Shared event log that components push operational events to.
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
     // =========================================================================
     // Balance
     // =========================================================================
@@ -122,29 +379,224 @@ impl StateManager {
     }
 
     pub fn get_total_equity(&self) -> Decimal {
+        let (balance, position_value) = self.get_balance_and_position_value();
+        balance + position_value
+    }
+
+    /// Cash and open position value at cost, read under one lock so the
+    /// two always add up to the same equity.
+    pub fn get_balance_and_position_value(&self) -> (Decimal, Decimal) {
         let inner = self.inner.read().unwrap();
         let position_value: Decimal = inner
             .positions
             .values()
+            .chain(inner.hedges.values())
             .map(|p| p.cost_basis())
             .sum();
-        inner.balance + position_value
+        (inner.balance, position_value)
+    }
+
+    /// Open positions (and hedges) valued at `mode` marks, at cost where
+    /// neither book quotes the held side. With last-trade marks enabled, a
+    /// trade newer than the market's last quote change is used instead; a
+    /// `Conservative` mark takes the worse of that trade and the books.
+    pub fn mark_to_market(&self, mode: MarkMode) -> Decimal {
+        let inner = self.inner.read().unwrap();
+        inner
+            .positions
+            .values()
+            .chain(inner.hedges.values())
+            .filter(|p| p.quantity > 0)
+            .map(|p| {
+                let book = inner
+                    .markets
+                    .get(&p.market_slug)
+                    .and_then(|m| m.mark_price(p.side, mode));
+                let trade = inner.fresh_last_trade(&p.market_slug).map(|t| t.price(p.side));
+                let mark = match (mode, trade, book) {
+                    (MarkMode::Conservative, Some(trade), Some(book)) => trade.min(book),
+                    (_, Some(trade), _) => trade,
+                    (_, None, book) => book.unwrap_or(p.avg_price),
+                };
+                p.notional_value(mark)
+            })
+            .sum()
+    }
+
+    /// Prefer a market's last trade over its quotes for marks when the
+    /// trade is newer than the last quote change (stale books).
+    pub fn set_mark_last_trade(&self, enabled: bool) {
+        self.inner.write().unwrap().mark_last_trade = enabled;
+    }
+
+    /// Per-market taker fee rates, replacing any set before.
+    pub fn set_fee_overrides(&self, overrides: HashMap<String, Decimal>) {
+        self.inner.write().unwrap().fee_overrides = overrides;
+    }
+
+    /// Fee rate for `slug`: its override, or `default` without one.
+    pub fn fee_rate_for(&self, slug: &str, default: Decimal) -> Decimal {
+        self.inner
+            .read()
+            .unwrap()
+            .fee_overrides
+            .get(slug)
+            .copied()
+            .unwrap_or(default)
+    }
+
+    /// Record a trade at `price` on `side` of `slug`.
+    pub fn record_trade(&self, slug: &str, side: Side, price: Decimal, at: DateTime<Utc>) {
+        let yes_price = match side {
+            Side::Yes => price,
+            Side::No => Decimal::ONE - price,
+        };
+        self.inner
+            .write()
+            .unwrap()
+            .last_trades
+            .insert(slug.to_string(), LastTrade { yes_price, at });
+    }
+
+    pub fn last_trade(&self, slug: &str) -> Option<LastTrade> {
+        self.inner.read().unwrap().last_trades.get(slug).copied()
     }
 
     pub fn get_total_position_value(&self) -> Decimal {
         let inner = self.inner.read().unwrap();
-        inner.positions.values().map(|p| p.cost_basis()).sum()
+        inner
+            .positions
+            .values()
+            .chain(inner.hedges.values())
+            .map(|p| p.cost_basis())
+            .sum()
     }
 
     // =========================================================================
     // Markets
     // =========================================================================
 
-    pub fn update_market(&self, market: MarketState) {
+    pub fn update_market(&self, mut market: MarketState) {
         let mut inner = self.inner.write().unwrap();
+        if let Some(meta) = inner.market_metadata.get(&market.market_slug) {
+            market.tick_size = market.tick_size.or(meta.tick_size);
+            market.min_order_size = market.min_order_size.or(meta.min_order_size);
+        }
+        if market.yes_bid.is_some() || market.yes_ask.is_some() {
+            let max_gap = inner.warmup_config.max_gap_secs;
+            let at = market.last_updated;
+            let progress = inner
+                .warmup
+                .entry(market.market_slug.clone())
+                .or_insert(WarmupProgress { updates: 0, since: at, last: at });
+            let gap = (at - progress.last).num_milliseconds() as f64 / 1000.0;
+            if max_gap > 0.0 && gap > max_gap {
+                progress.updates = 0;
+                progress.since = at;
+            }
+            progress.updates += 1;
+            progress.last = at;
+        }
+        let quotes_changed = inner.markets.get(&market.market_slug).is_none_or(|prev| {
+            (prev.yes_bid, prev.yes_ask, prev.no_bid, prev.no_ask)
+                != (market.yes_bid, market.yes_ask, market.no_bid, market.no_ask)
+        });
+        if quotes_changed {
+            inner
+                .quotes_changed_at
+                .insert(market.market_slug.clone(), market.last_updated);
+        }
         inner.markets.insert(market.market_slug.clone(), market);
     }
 
+    /// Replace the time source (tests inject a `MockClock`).
+    pub fn set_clock(&self, clock: SharedClock) {
+        self.inner.write().unwrap().clock = clock;
+    }
+
+    pub fn set_warmup_config(&self, config: WarmupConfig) {
+        self.inner.write().unwrap().warmup_config = config;
+    }
+
+    pub fn set_price_band_config(&self, config: PriceBandConfig) {
+        self.inner.write().unwrap().price_band_config = config;
+    }
+
+    /// Screen a new `side` mid for `slug` before it is applied; each side
+    /// has its own band. An in-band mid is accepted and becomes the last
+    /// good mid. An out-of-band one is rejected unless it is the
+    /// `confirm_polls`-th consecutive poll near the same level, which is
+    /// taken as a real move.
+    pub fn check_price_band(&self, slug: &str, side: Side, mid: Option<Decimal>) -> PriceBandCheck {
+        let mut inner = self.inner.write().unwrap();
+        let max_jump = inner.price_band_config.max_jump_pct;
+        let confirm_polls = inner.price_band_config.confirm_polls;
+        let Some(mid) = mid else {
+            return PriceBandCheck::Accepted;
+        };
+        if max_jump <= Decimal::ZERO {
+            return PriceBandCheck::Accepted;
+        }
+
+        let within = |from: Decimal, to: Decimal| {
+            from > Decimal::ZERO && ((to - from) / from).abs() <= max_jump
+        };
+        let band = inner
+            .price_bands
+            .entry((slug.to_string(), side))
+            .or_insert(PriceBand {
+                last_good_mid: mid,
+                pending_mid: None,
+                anomalies: 0,
+            });
+        if within(band.last_good_mid, mid) {
+            band.last_good_mid = mid;
+            band.pending_mid = None;
+            band.anomalies = 0;
+            return PriceBandCheck::Accepted;
+        }
+
+        band.anomalies = match band.pending_mid {
+            Some(pending) if within(pending, mid) => band.anomalies + 1,
+            _ => 1,
+        };
+        band.pending_mid = Some(mid);
+        if band.anomalies >= confirm_polls {
+            band.last_good_mid = mid;
+            band.pending_mid = None;
+            band.anomalies = 0;
+            return PriceBandCheck::Accepted;
+        }
+        PriceBandCheck::Rejected {
+            side,
+            last_good_mid: band.last_good_mid,
+            mid,
+            anomalies: band.anomalies,
+        }
+    }
+
+    /// Priced updates received since the market's warmup (re)started.
+    pub fn update_count(&self, slug: &str) -> u64 {
+        let inner = self.inner.read().unwrap();
+        inner.warmup.get(slug).map(|p| p.updates).unwrap_or(0)
+    }
+
+    /// Whether `slug` has enough fresh updates to be traded.
+    pub fn is_market_warm(&self, slug: &str) -> bool {
+        let inner = self.inner.read().unwrap();
+        let config = &inner.warmup_config;
+        if config.min_updates == 0 && config.warmup_secs <= 0.0 {
+            return true;
+        }
+        match inner.warmup.get(slug) {
+            Some(p) => {
+                let fresh_for = (p.last - p.since).num_milliseconds() as f64 / 1000.0;
+                p.updates >= config.min_updates && fresh_for >= config.warmup_secs
+            }
+            None => false,
+        }
+    }
+
     pub fn get_market(&self, slug: &str) -> Option<MarketState> {
         self.inner.read().unwrap().markets.get(slug).cloned()
     }
@@ -153,6 +605,92 @@ impl StateManager {
         self.inner.read().unwrap().markets.values().cloned().collect()
     }
 
+    /// Cache a market's order constraints, keeping known values the new
+    /// metadata leaves out, and apply them to its current state.
+    pub fn set_market_metadata(&self, slug: &str, metadata: MarketMetadata) {
+        let mut inner = self.inner.write().unwrap();
+        let cached = inner.market_metadata.entry(slug.to_string()).or_default();
+        cached.tick_size = metadata.tick_size.or(cached.tick_size);
+        cached.min_order_size = metadata.min_order_size.or(cached.min_order_size);
+        let cached = *cached;
+        if let Some(market) = inner.markets.get_mut(slug) {
+            market.tick_size = cached.tick_size;
+            market.min_order_size = cached.min_order_size;
+        }
+    }
+
+    /// Cached order constraints for `slug`, if any were reported.
+    pub fn market_metadata(&self, slug: &str) -> Option<MarketMetadata> {
+        self.inner.read().unwrap().market_metadata.get(slug).copied()
+    }
+
+    /// Record a market's scheduled resolution time.
+    pub fn set_resolution_date(&self, slug: &str, resolution_date: Option<DateTime<Utc>>) {
+        let mut inner = self.inner.write().unwrap();
+        if let Some(market) = inner.markets.get_mut(slug) {
+            market.resolution_date = resolution_date;
+        }
+    }
+
+    /// Whether `slug` is scheduled to resolve less than `horizon` from now
+    /// (or already past its resolution date). False when the date is unknown.
+    pub fn resolves_within(&self, slug: &str, horizon: chrono::Duration) -> bool {
+        let inner = self.inner.read().unwrap();
+        inner
+            .markets
+            .get(slug)
+            .and_then(|m| m.resolution_date)
+            .is_some_and(|at| at - inner.clock.now() < horizon)
+    }
+
+    /// Enable or disable trading in a market at runtime. The strategy
+    /// engine skips disabled markets and cancels their resting orders.
+    pub fn set_market_enabled(&self, slug: &str, enabled: bool) {
+        let mut inner = self.inner.write().unwrap();
+        if enabled {
+            inner.disabled_markets.remove(slug);
+        } else {
+            inner.disabled_markets.insert(slug.to_string());
+        }
+    }
+
+    pub fn is_market_enabled(&self, slug: &str) -> bool {
+        !self.inner.read().unwrap().disabled_markets.contains(slug)
+    }
+
+    pub fn get_disabled_markets(&self) -> Vec<String> {
+        self.inner.read().unwrap().disabled_markets.iter().cloned().collect()
+    }
+
+    /// Record that a market stopped trading, disabling it (which also
+    /// cancels its resting orders). A resolution, recorded once, is queued
+    /// for `take_unsettled_resolutions`.
+    pub fn mark_market_closed(&self, slug: &str, yes_won: Option<bool>) {
+        let mut inner = self.inner.write().unwrap();
+        inner.disabled_markets.insert(slug.to_string());
+        let recorded = inner.closed_markets.entry(slug.to_string()).or_insert(None);
+        if recorded.is_none() {
+            *recorded = yes_won;
+            if let Some(yes_won) = yes_won {
+                inner.unsettled_resolutions.push((slug.to_string(), yes_won));
+            }
+        }
+    }
+
+    pub fn is_market_closed(&self, slug: &str) -> bool {
+        self.inner.read().unwrap().closed_markets.contains_key(slug)
+    }
+
+    /// `Some(true)` if the market resolved YES, `Some(false)` if NO.
+    pub fn market_resolution(&self, slug: &str) -> Option<bool> {
+        self.inner.read().unwrap().closed_markets.get(slug).copied().flatten()
+    }
+
+    /// Resolutions recorded since the last call, each returned once.
+    pub fn take_unsettled_resolutions(&self) -> Vec<(String, bool)> {
+        std::mem::take(&mut self.inner.write().unwrap().unsettled_resolutions)
+    }
+
     // =========================================================================
     // Positions
     // =========================================================================
@@ -167,7 +705,9 @@ impl StateManager {
         let mut inner = self.inner.write().unwrap();
         if quantity <= 0 {
             inner.positions.remove(market_slug);
+            inner.hedges.remove(market_slug);
         } else {
+            let now = inner.clock.now();
             let entry = inner
                 .positions
                 .entry(market_slug.to_string())
@@ -176,7 +716,7 @@ impl StateManager {
                     side,
                     quantity,
                     avg_price,
-                    created_at: Utc::now(),
+                    created_at: now,
                 });
             entry.side = side;
             entry.quantity = quantity;
@@ -199,7 +739,143 @@ impl StateManager {
     }
 
     pub fn remove_position(&self, market_slug: &str) {
-        self.inner.write().unwrap().positions.remove(market_slug);
+        let mut inner = self.inner.write().unwrap();
+        inner.positions.remove(market_slug);
+        inner.hedges.remove(market_slug);
+    }
+
+    /// Record the opposite-side holding in a market whose position is
+    /// tracked separately. A non-positive quantity clears it.
+    pub fn update_hedge_position(
+        &self,
+        market_slug: &str,
+        side: Side,
+        quantity: i64,
+        avg_price: Decimal,
+    ) {
+        let mut inner = self.inner.write().unwrap();
+        if quantity <= 0 {
+            inner.hedges.remove(market_slug);
+            return;
+        }
+        let now = inner.clock.now();
+        let entry = inner
+            .hedges
+            .entry(market_slug.to_string())
+            .or_insert_with(|| PositionState {
+                market_slug: market_slug.to_string(),
+                side,
+                quantity,
+                avg_price,
+                created_at: now,
+            });
+        entry.side = side;
+        entry.quantity = quantity;
+        entry.avg_price = avg_price;
+    }
+
+    pub fn get_hedge_position(&self, market_slug: &str) -> Option<PositionState> {
+        self.inner.read().unwrap().hedges.get(market_slug).cloned()
+    }
+
+    /// Apply an execution to balance and position. Buys add to the side
+    /// bought, netting against an opposite holding first; sells reduce the
+    /// side sold.
+    pub fn apply_fill(
+        &self,
+        market_slug: &str,
+        intent: OrderIntent,
+        price: Decimal,
+        quantity: i64,
+        fee: Decimal,
+    ) {
+        let notional = price * Decimal::from(quantity);
+        let side = intent.side();
+        let current = {
+            let mut inner = self.inner.write().unwrap();
+            if intent.is_buy() {
+                inner.balance -= notional + fee;
+            } else {
+                inner.balance += notional - fee;
+            }
+            inner.positions.get(market_slug).cloned()
+        };
+
+        let (side, quantity, avg_price) = match current {
+            Some(p) if p.side == side && intent.is_buy() => {
+                let total = p.quantity + quantity;
+                let avg = (p.cost_basis() + notional) / Decimal::from(total);
+                (side, total, avg)
+            }
+            Some(p) if p.side == side => (side, p.quantity - quantity, p.avg_price),
+            Some(p) if intent.is_buy() && quantity <= p.quantity => {
+                (p.side, p.quantity - quantity, p.avg_price)
+            }
+            Some(p) if intent.is_buy() => (side, quantity - p.quantity, price),
+            // Selling a side not held leaves the position alone.
+            Some(_) => return,
+            None if intent.is_buy() => (side, quantity, price),
+            None => return,
+        };
+        self.update_position(market_slug, side, quantity, avg_price);
+    }
+
+    /// Overwrite local positions with exchange truth, reporting every
+    /// market where they differed beforehand.
+    ///
+    /// Local positions absent from `api_positions` are reported but kept,
+    /// matching the previous overwrite-only behavior.
+    pub fn reconcile_positions(&self, api_positions: &[Position]) -> ReconciliationReport {
+        let inner = self.inner.read().unwrap();
+        let mut report = ReconciliationReport::default();
+        let mut seen = std::collections::HashSet::new();
+
+        for p in api_positions {
+            seen.insert(p.market_slug.clone());
+            let api_side = (p.quantity > 0).then_some(p.side);
+            let api_avg_price = api_side.map(|_| p.avg_price).unwrap_or(Decimal::ZERO);
+            let local = inner.positions.get(&p.market_slug);
+            let matches = match local {
+                Some(l) => {
+                    l.side == p.side && l.quantity == p.quantity && l.avg_price == p.avg_price
+                }
+                None => p.quantity <= 0,
+            };
+            if !matches {
+                report.drifts.push(PositionDrift {
+                    market_slug: p.market_slug.clone(),
+                    local_side: local.map(|l| l.side),
+                    api_side,
+                    local_quantity: local.map(|l| l.quantity).unwrap_or(0),
+                    api_quantity: p.quantity.max(0),
+                    local_avg_price: local.map(|l| l.avg_price).unwrap_or(Decimal::ZERO),
+                    api_avg_price,
+                });
+            }
+        }
+
+        for (slug, l) in inner.positions.iter() {
+            if !seen.contains(slug) {
+                seen.insert(slug.clone());
+                report.drifts.push(PositionDrift {
+                    market_slug: slug.clone(),
+                    local_side: Some(l.side),
+                    api_side: None,
+                    local_quantity: l.quantity,
+                    api_quantity: 0,
+                    local_avg_price: l.avg_price,
+                    api_avg_price: Decimal::ZERO,
+                });
+            }
+        }
+        report.markets_checked = seen.len();
+        report.drifts.sort_by(|a, b| a.market_slug.cmp(&b.market_slug));
+        drop(inner);
+
+        for p in api_positions {
+            self.update_position(&p.market_slug, p.side, p.quantity, p.avg_price);
+        }
+        report
     }
 
     // =========================================================================
@@ -256,14 +932,31 @@ impl StateManager {
         self.inner.read().unwrap().positions.len()
     }
 
-    /// Total exposure for a specific market.
+    /// Gross cost basis held in a market, both sides counted.
     pub fn market_exposure(&self, market_slug: &str) -> Decimal {
-        self.inner
-            .read()
-            .unwrap()
-            .positions
-            .get(market_slug)
+        let inner = self.inner.read().unwrap();
+        [inner.positions.get(market_slug), inner.hedges.get(market_slug)]
+            .into_iter()
+            .flatten()
             .map(|p| p.cost_basis())
-            .unwrap_or(Decimal::ZERO)
+            .sum()
+    }
+
+    /// Directional exposure in a market: matched YES/NO quantity pays a
+    /// locked $1 and is treated as hedged, so only the cost basis of the
+    /// unmatched remainder counts.
+    pub fn net_market_exposure(&self, market_slug: &str) -> Decimal {
+        let inner = self.inner.read().unwrap();
+        let Some(position) = inner.positions.get(market_slug) else {
+            return Decimal::ZERO;
+        };
+        match inner.hedges.get(market_slug) {
+            Some(hedge) if hedge.side != position.side => {
+                let larger = if position.quantity >= hedge.quantity { position } else { hedge };
+                let matched = position.quantity.min(hedge.quantity);
+                larger.avg_price * Decimal::from(larger.quantity - matched)
+            }
+            _ => position.cost_basis(),
+        }
     }
 }
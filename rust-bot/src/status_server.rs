@@ -0,0 +1,300 @@
+//! HTTP status server for operators.
+//!
+//! A deliberately small HTTP/1.1 responder on a raw `TcpListener`: one
+//! request per connection, JSON bodies. Routes:
+//!
+//! - `GET /health` — liveness.
+//! - `GET /book/{slug}` — top-N depth of both sides plus mid, microprice,
+//!   imbalance and completeness sum; 404 for untracked markets.
+//! - `GET /events` — recent operational events, newest first; `?limit=N`
+//!   caps the count. 404 unless an event log is attached.
+//! - `POST /strategies/{name}/enable` and `.../disable` — only when a
+//!   control channel is attached, and only with the control token as
+//!   `Authorization: Bearer <token>` (401 otherwise); queues a toggle that
+//!   the strategy engine applies on its next tick.
+//! - `POST /markets/{slug}/enable` and `.../disable` — only when market
+//!   state is attached, behind the same token; a disabled market is
+//!   skipped by every strategy and its resting orders are cancelled.
+
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::data::orderbook::OrderBookTracker;
+use crate::state::event_log::EventLog;
+use crate::state::state_manager::StateManager;
+use crate::strategies::engine::{StrategyToggle, STRATEGY_NAMES};
+
+/// Largest request head accepted before the connection is dropped.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Status server configuration.
+#[derive(Debug, Clone)]
+pub struct StatusServerConfig {
+    /// Price levels per side returned by `/book/{slug}`.
+    pub book_depth: usize,
+    /// Books older than this are reported as stale.
+    pub book_stale_after: chrono::Duration,
+}
+
+impl Default for StatusServerConfig {
+    fn default() -> Self {
+        Self {
+            book_depth: 5,
+            book_stale_after: chrono::Duration::seconds(30),
+        }
+    }
+}
+
+/// Status code and JSON body for one request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl StatusResponse {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn accepted(body: Value) -> Self {
+        Self { status: 202, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+}
+
+/// Serves bot status from shared handles.
+pub struct StatusServer {
+    config: StatusServerConfig,
+    orderbook: OrderBookTracker,
+    /// Strategy toggles for the engine; `None` keeps the server read-only.
+    control: Option<mpsc::UnboundedSender<StrategyToggle>>,
+    /// Market enable/disable target; `None` leaves the route off.
+    markets: Option<StateManager>,
+    /// Bearer token the control routes require.
+    control_token: String,
+    events: Option<EventLog>,
+}
+
+impl StatusServer {
+    pub fn new(config: StatusServerConfig, orderbook: OrderBookTracker) -> Self {
+        Self {
+            config,
+            orderbook,
+            control: None,
+            markets: None,
+            control_token: String::new(),
+            events: None,
+        }
+    }
+
+    /// Serve `events` at `GET /events`.
+    pub fn with_events(mut self, events: EventLog) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Enable the strategy control routes for requests bearing `token`,
+    /// sending toggles to `control`. An empty token authorizes nothing.
+    pub fn with_control(
+        mut self,
+        control: mpsc::UnboundedSender<StrategyToggle>,
+        token: &str,
+    ) -> Self {
+        self.control = Some(control);
+        self.control_token = token.trim().to_string();
+        self
+    }
+
+    /// Enable the market control routes against `state`. They share the
+    /// token set by `with_control`.
+    pub fn with_markets(mut self, state: StateManager) -> Self {
+        self.markets = Some(state);
+        self
+    }
+
+    /// Route one request without credentials.
+    pub fn handle(&self, method: &str, path: &str) -> StatusResponse {
+        self.handle_with_token(method, path, None)
+    }
+
+    /// Route one request carrying bearer token `token`. Separate from the
+    /// socket loop so it can be exercised directly.
+    pub fn handle_with_token(
+        &self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+    ) -> StatusResponse {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["health"]) => StatusResponse::ok(json!({ "status": "ok" })),
+            ("GET", ["book", slug]) if !slug.is_empty() => self.book(slug),
+            ("GET", ["events"]) if self.events.is_some() => self.events(query),
+            ("GET", _) => StatusResponse::error(404, "not found"),
+            ("POST", ["strategies" | "markets", ..])
+                if (self.control.is_some() || self.markets.is_some())
+                    && !self.authorized(token) =>
+            {
+                StatusResponse::error(401, "unauthorized")
+            }
+            ("POST", ["strategies", name, action]) if self.control.is_some() => {
+                self.toggle_strategy(name, action)
+            }
+            ("POST", ["markets", slug, action]) if self.markets.is_some() && !slug.is_empty() => {
+                self.toggle_market(slug, action)
+            }
+            _ => StatusResponse::error(405, "method not allowed"),
+        }
+    }
+
+    fn authorized(&self, token: Option<&str>) -> bool {
+        !self.control_token.is_empty() && token == Some(self.control_token.as_str())
+    }
+
+    fn toggle_strategy(&self, name: &str, action: &str) -> StatusResponse {
+        let enabled = match action {
+            "enable" => true,
+            "disable" => false,
+            _ => return StatusResponse::error(404, "not found"),
+        };
+        if !STRATEGY_NAMES.contains(&name) {
+            return StatusResponse::error(404, &format!("unknown strategy: {name}"));
+        }
+        let toggle = StrategyToggle {
+            strategy: name.to_string(),
+            enabled,
+        };
+        match self.control.as_ref().map(|tx| tx.send(toggle)) {
+            Some(Ok(())) => StatusResponse::accepted(json!({
+                "strategy": name,
+                "enabled": enabled,
+            })),
+            _ => StatusResponse::error(503, "strategy engine not running"),
+        }
+    }
+
+    fn toggle_market(&self, slug: &str, action: &str) -> StatusResponse {
+        let enabled = match action {
+            "enable" => true,
+            "disable" => false,
+            _ => return StatusResponse::error(404, "not found"),
+        };
+        let Some(state) = self.markets.as_ref() else {
+            return StatusResponse::error(404, "not found");
+        };
+        state.set_market_enabled(slug, enabled);
+        StatusResponse::accepted(json!({
+            "market": slug,
+            "enabled": enabled,
+        }))
+    }
+
+    fn events(&self, query: &str) -> StatusResponse {
+        let Some(events) = self.events.as_ref() else {
+            return StatusResponse::error(404, "not found");
+        };
+        let limit = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("limit="))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        StatusResponse::ok(json!({
+            "capacity": events.capacity(),
+            "events": events.to_json(limit),
+        }))
+    }
+
+    fn book(&self, slug: &str) -> StatusResponse {
+        match self.orderbook.depth_snapshot(
+            slug,
+            self.config.book_depth,
+            self.config.book_stale_after,
+        ) {
+            Some(snapshot) => StatusResponse::ok(snapshot),
+            None => StatusResponse::error(404, &format!("unknown market: {slug}")),
+        }
+    }
+
+    /// Accept connections until the task is aborted.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) {
+        loop {
+            match listener.accept().await {
+                Ok((socket, _)) => {
+                    let server = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = server.respond(socket).await {
+                            debug!(error = %e, "Status request failed");
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "Status server accept failed"),
+            }
+        }
+    }
+
+    async fn respond(&self, mut socket: TcpStream) -> std::io::Result<()> {
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..n]);
+            if head.len() > MAX_REQUEST_HEAD {
+                return Ok(());
+            }
+        }
+
+        let head = String::from_utf8_lossy(&head);
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or("").split_whitespace();
+        let method = request_line.next().unwrap_or("");
+        let path = request_line.next().unwrap_or("");
+        let token = lines.find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            let value = value.trim();
+            name.trim().eq_ignore_ascii_case("authorization").then_some(value)?;
+            value.strip_prefix("Bearer ").map(str::trim)
+        });
+        let response = self.handle_with_token(method, path, token);
+
+        let body = response.body.to_string();
+        let reply = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            reason_phrase(response.status),
+            body.len(),
+            body
+        );
+        socket.write_all(reply.as_bytes()).await?;
+        socket.shutdown().await
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
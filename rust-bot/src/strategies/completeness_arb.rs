@@ -0,0 +1,148 @@
+//! Completeness arbitrage strategy.
+//!
+//! Turns each `CompletenessArbSignal` from the order book scanner into a
+//! pair of taker buys (YES and NO at their best asks). Holding both sides
+//! pays $1 at resolution, so the pair locks in the margin. Both legs carry
+//! the same `arb_pair` metadata tag so the engine can keep them the same
+//! size after risk checks.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::data::models::{Signal, SignalAction, Urgency, ARB_PAIR_KEY};
+use crate::data::orderbook::{CompletenessArbSignal, OrderBookTracker};
+use crate::state::state_manager::StateManager;
+
+/// Configuration for completeness arbitrage.
+#[derive(Debug, Clone)]
+pub struct CompletenessArbConfig {
+    pub min_margin: Decimal,
+    pub min_top_depth: i64,
+    pub max_price_skew: Decimal,
+    pub fee_rate: Decimal,
+    /// Cap on contracts per leg. Zero sizes to the available depth only.
+    pub max_contracts: i64,
+    /// Minimum time between arbs on the same market.
+    pub cooldown_seconds: f64,
+}
+
+impl Default for CompletenessArbConfig {
+    fn default() -> Self {
+        Self {
+            min_margin: Decimal::new(1, 2),      // 0.01
+            min_top_depth: 5,
+            max_price_skew: Decimal::new(10, 2), // 0.10
+            fee_rate: Decimal::new(1, 3),        // 10 bps
+            max_contracts: 0,
+            cooldown_seconds: 30.0,
+        }
+    }
+}
+
+/// Completeness arbitrage strategy.
+pub struct CompletenessArbStrategy {
+    config: CompletenessArbConfig,
+    enabled: bool,
+    orderbook: OrderBookTracker,
+    last_signal_at: HashMap<String, DateTime<Utc>>,
+    /// Source of per-market fee overrides; `fee_rate` applies without one.
+    state: Option<StateManager>,
+}
+
+impl CompletenessArbStrategy {
+    pub fn new(config: CompletenessArbConfig, orderbook: OrderBookTracker) -> Self {
+        Self {
+            config,
+            enabled: true,
+            orderbook,
+            last_signal_at: HashMap::new(),
+            state: None,
+        }
+    }
+
+    /// Price each market's arb with its fee override from `state`.
+    pub fn set_state(&mut self, state: StateManager) {
+        self.state = Some(state);
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Scan the tracked books and emit a BuyYes + BuyNo pair per arb.
+    pub fn on_tick(&mut self) -> Vec<Signal> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let now = Utc::now();
+        let mut signals = Vec::new();
+
+        let default_fee = self.config.fee_rate;
+        let state = self.state.as_ref();
+        let arbs = self.orderbook.scan_completeness_arb_with_fees(
+            self.config.min_margin,
+            self.config.min_top_depth,
+            self.config.max_price_skew,
+            |slug| state.map_or(default_fee, |s| s.fee_rate_for(slug, default_fee)),
+        );
+
+        for arb in arbs {
+            // Cooldown check.
+            if let Some(last) = self.last_signal_at.get(&arb.market_slug) {
+                let elapsed = (now - *last).num_milliseconds() as f64 / 1000.0;
+                if elapsed < self.config.cooldown_seconds {
+                    continue;
+                }
+            }
+
+            let mut quantity = arb.yes_ask_qty.min(arb.no_ask_qty);
+            if self.config.max_contracts > 0 {
+                quantity = quantity.min(self.config.max_contracts);
+            }
+            if quantity <= 0 {
+                continue;
+            }
+
+            self.last_signal_at.insert(arb.market_slug.clone(), now);
+            signals.push(leg(&arb, SignalAction::BuyYes, arb.yes_ask, quantity));
+            signals.push(leg(&arb, SignalAction::BuyNo, arb.no_ask, quantity));
+        }
+
+        signals
+    }
+}
+
+fn leg(arb: &CompletenessArbSignal, action: SignalAction, price: Decimal, quantity: i64) -> Signal {
+    let mut metadata = HashMap::new();
+    metadata.insert(ARB_PAIR_KEY.to_string(), serde_json::json!(arb.market_slug));
+    metadata.insert(
+        "net_margin".to_string(),
+        serde_json::json!(arb.net_margin.to_string()),
+    );
+
+    Signal {
+        market_slug: arb.market_slug.clone(),
+        action,
+        price,
+        quantity,
+        urgency: Urgency::Critical,
+        confidence: 1.0,
+        strategy_name: "completeness_arb".to_string(),
+        reason: format!(
+            "Completeness arb: {} + {} = {}, net {:.4}",
+            arb.yes_ask, arb.no_ask, arb.combined_cost, arb.net_margin
+        ),
+        metadata,
+        timestamp: Utc::now(),
+        reduce_only: false,
+        good_till: None,
+    }
+}
@@ -1,16 +1,30 @@
 //! Strategy engine: aggregates signals from all strategies, applies risk
 //! management, and routes approved signals to the execution engine.
+//!
+//! Every order signal leaves with the book it fired on in its metadata
+//! (`BOOK_CONTEXT_KEYS`), so the event stream shows the exact conditions.
+//! With a `NetEdgeGate` set, entries whose edge does not survive fees,
+//! impact and adverse selection are dropped before risk checks.
 
 #![allow(dead_code)]
 
-use tracing::debug;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
 
-use crate::data::models::Signal;
+use crate::clock::{RealClock, SharedClock};
+use crate::data::models::{FillEvent, Side, Signal, SignalAction, Urgency};
+use crate::risk::position_manager::PositionManager;
 use crate::risk::risk_manager::RiskManager;
 use crate::state::state_manager::{MarketState, StateManager};
+use crate::util::dec::to_f64;
 
+use super::completeness_arb::CompletenessArbStrategy;
 use super::live_arbitrage::{GameState, LiveArbitrageStrategy};
 use super::market_maker::MarketMakerStrategy;
+use super::net_edge::NetEdgeGate;
 use super::statistical_edge::{OddsSnapshot, StatisticalEdgeStrategy};
 
 /// Aggregated signals ready for execution.
@@ -18,6 +32,45 @@ use super::statistical_edge::{OddsSnapshot, StatisticalEdgeStrategy};
 pub struct EngineOutput {
     pub approved_signals: Vec<Signal>,
     pub rejected_count: usize,
+    /// Signals dropped because a higher-priority strategy acted on the
+    /// same market-side this cycle.
+    pub conflicted_count: usize,
+    /// Entries dropped by the net edge gate this cycle.
+    pub edge_gated_count: usize,
+}
+
+/// Signal metadata keys describing the market's YES book when the signal
+/// fired: quote age in milliseconds, spread, mid and top-of-book depth
+/// imbalance in [-1, 1] (positive when bids outweigh asks). Null when the
+/// book does not quote them.
+pub const BOOK_CONTEXT_KEYS: [&str; 4] = ["book_age_ms", "spread", "mid", "imbalance"];
+
+/// Names accepted by `StrategyEngine::set_strategy_enabled`, matching
+/// each strategy's `strategy_name` on its signals.
+pub const STRATEGY_NAMES: [&str; 4] = [
+    "market_maker",
+    "live_arbitrage",
+    "statistical_edge",
+    "completeness_arb",
+];
+
+/// Request to switch a strategy on or off at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyToggle {
+    pub strategy: String,
+    pub enabled: bool,
+}
+
+/// How signals from different strategies on the same market-side are
+/// reconciled before risk checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Emit every signal, even if strategies oppose each other.
+    KeepAll,
+    /// Keep only the highest-priority strategy's signals per market-side.
+    /// Exits beat entries, then higher urgency, then risk-reducing.
+    #[default]
+    Priority,
 }
 
 /// The strategy engine orchestrates all trading strategies.
@@ -25,7 +78,19 @@ pub struct StrategyEngine {
     pub market_maker: Option<MarketMakerStrategy>,
     pub live_arbitrage: Option<LiveArbitrageStrategy>,
     pub statistical_edge: Option<StatisticalEdgeStrategy>,
+    pub completeness_arb: Option<CompletenessArbStrategy>,
+    /// Exits positions that outlive the max position age.
+    pub position_manager: Option<PositionManager>,
     state: StateManager,
+    fill_rx: Option<broadcast::Receiver<FillEvent>>,
+    toggle_rx: Option<mpsc::UnboundedReceiver<StrategyToggle>>,
+    /// Disabled markets whose resting orders have already been cancelled.
+    cancelled_disabled: HashSet<String>,
+    /// Cancels queued by strategy toggles, sent with the next signals.
+    toggle_cancels: Vec<Signal>,
+    conflict_policy: ConflictPolicy,
+    net_edge_gate: Option<NetEdgeGate>,
+    clock: SharedClock,
 }
 
 impl StrategyEngine {
@@ -34,12 +99,142 @@ impl StrategyEngine {
         market_maker: Option<MarketMakerStrategy>,
         live_arbitrage: Option<LiveArbitrageStrategy>,
         statistical_edge: Option<StatisticalEdgeStrategy>,
+        completeness_arb: Option<CompletenessArbStrategy>,
     ) -> Self {
         Self {
             market_maker,
             live_arbitrage,
             statistical_edge,
+            completeness_arb,
+            position_manager: None,
             state,
+            fill_rx: None,
+            toggle_rx: None,
+            cancelled_disabled: HashSet::new(),
+            toggle_cancels: Vec::new(),
+            conflict_policy: ConflictPolicy::default(),
+            net_edge_gate: None,
+            clock: RealClock::shared(),
+        }
+    }
+
+    /// Replace the time source (tests inject a `MockClock`).
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    pub fn set_position_manager(&mut self, position_manager: PositionManager) {
+        self.position_manager = Some(position_manager);
+    }
+
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    pub fn set_net_edge_gate(&mut self, gate: NetEdgeGate) {
+        self.net_edge_gate = Some(gate);
+    }
+
+    /// Subscribe to an executor's fill notifications.
+    pub fn subscribe_fills(&mut self, rx: broadcast::Receiver<FillEvent>) {
+        self.fill_rx = Some(rx);
+    }
+
+    /// Receive strategy toggles (e.g. from the status server's control
+    /// route); they are applied at the start of each tick.
+    pub fn subscribe_toggles(&mut self, rx: mpsc::UnboundedReceiver<StrategyToggle>) {
+        self.toggle_rx = Some(rx);
+    }
+
+    /// Switch a strategy on or off by name. A disabled strategy emits no
+    /// signals but keeps its state (cooldowns, ingested odds and game
+    /// states), so re-enabling resumes where it left off. Disabling the
+    /// market maker also cancels the orders resting in the markets it was
+    /// quoting. Returns false if no strategy by that name is configured.
+    pub fn set_strategy_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        if name == "market_maker" && !enabled {
+            if let Some(ref mut mm) = self.market_maker {
+                for slug in mm.take_quoted_markets() {
+                    self.toggle_cancels.push(cancel_all(slug, "Market maker disabled"));
+                }
+            }
+        }
+        let applied = match name {
+            "market_maker" => self.market_maker.as_mut().map(|s| s.set_enabled(enabled)),
+            "live_arbitrage" => self.live_arbitrage.as_mut().map(|s| s.set_enabled(enabled)),
+            "statistical_edge" => self.statistical_edge.as_mut().map(|s| s.set_enabled(enabled)),
+            "completeness_arb" => self.completeness_arb.as_mut().map(|s| s.set_enabled(enabled)),
+            _ => None,
+        };
+        if applied.is_some() {
+            info!(strategy = name, enabled, "Strategy toggled");
+        } else {
+            warn!(strategy = name, "Toggle for unconfigured strategy ignored");
+        }
+        applied.is_some()
+    }
+
+    /// Whether a configured strategy is enabled; `None` if not configured.
+    pub fn is_strategy_enabled(&self, name: &str) -> Option<bool> {
+        match name {
+            "market_maker" => self.market_maker.as_ref().map(|s| s.is_enabled()),
+            "live_arbitrage" => self.live_arbitrage.as_ref().map(|s| s.is_enabled()),
+            "statistical_edge" => self.statistical_edge.as_ref().map(|s| s.is_enabled()),
+            "completeness_arb" => self.completeness_arb.as_ref().map(|s| s.is_enabled()),
+            _ => None,
+        }
+    }
+
+    /// Apply queued strategy toggles. Returns the number applied.
+    pub fn process_pending_toggles(&mut self) -> usize {
+        let mut toggles = Vec::new();
+        if let Some(ref mut rx) = self.toggle_rx {
+            while let Ok(toggle) = rx.try_recv() {
+                toggles.push(toggle);
+            }
+        }
+        toggles
+            .iter()
+            .filter(|t| self.set_strategy_enabled(&t.strategy, t.enabled))
+            .count()
+    }
+
+    /// Drain pending fill notifications, dispatching each to `on_fill`.
+    /// Returns the number of fills processed.
+    pub fn process_pending_fills(&mut self) -> usize {
+        let mut fills = Vec::new();
+        if let Some(ref mut rx) = self.fill_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(fill) => fills.push(fill),
+                    Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                        warn!(missed, "Fill channel lagged, some fills were dropped");
+                    }
+                    Err(_) => break,
+                }
+            }
+        }
+        for fill in &fills {
+            self.on_fill(fill);
+        }
+        fills.len()
+    }
+
+    /// Dispatch a single fill to the strategies.
+    pub fn on_fill(&mut self, fill: &FillEvent) {
+        debug!(
+            order_id = %fill.order_id,
+            market_slug = %fill.market_slug,
+            strategy = %fill.strategy_name,
+            quantity = fill.quantity,
+            price = %fill.price,
+            "Fill dispatched"
+        );
+        if let Some(ref mut mm) = self.market_maker {
+            mm.on_fill(fill);
+        }
+        if let Some(ref mut se) = self.statistical_edge {
+            se.on_fill(fill);
         }
     }
 
@@ -49,14 +244,15 @@ impl StrategyEngine {
         market: &MarketState,
         risk_manager: &mut RiskManager,
     ) -> EngineOutput {
-        let mut all_signals = Vec::new();
-
+        let mut all_signals = self.disabled_market_cancels();
+        let tradeable = self.is_tradeable(&market.market_slug);
         let position = self.state.get_position(&market.market_slug);
 
-        // Market maker signals.
+        // Market maker signals; a disabled or cold market is only exited.
         if let Some(ref mut mm) = self.market_maker {
-            let mm_signals = mm.on_market_update(market, position.as_ref());
-            all_signals.extend(mm_signals);
+            if tradeable {
+                all_signals.extend(mm.on_market_update(market, position.as_ref()));
+            }
 
             // Check stop-loss for existing positions.
             if let Some(ref pos) = position {
@@ -65,22 +261,35 @@ impl StrategyEngine {
             }
         }
 
+        // Every order goes out with its book, exits included.
+        self.attach_book_context(&mut all_signals);
+
         // Apply risk management.
-        self.filter_through_risk(all_signals, risk_manager)
+        let (signals, conflicted) = self.resolve_conflicts(all_signals);
+        let mut output = self.filter_through_risk(signals, risk_manager);
+        output.conflicted_count = conflicted;
+        output
     }
 
     /// Process a tick (time-based triggers) through all strategies.
     pub fn on_tick(&mut self, risk_manager: &mut RiskManager) -> EngineOutput {
-        let mut all_signals = Vec::new();
+        // Apply operator toggles, then let strategies see their latest
+        // executions.
+        self.process_pending_toggles();
+        self.process_pending_fills();
+
+        let mut all_signals = self.disabled_market_cancels();
 
         // Market maker: iterate all tracked markets and generate quotes.
         if self.market_maker.is_some() {
             let markets = self.state.get_all_markets();
             for market in &markets {
+                let tradeable = self.is_tradeable(&market.market_slug);
                 let position = self.state.get_position(&market.market_slug);
                 if let Some(ref mut mm) = self.market_maker {
-                    let mm_signals = mm.on_market_update(market, position.as_ref());
-                    all_signals.extend(mm_signals);
+                    if tradeable {
+                        all_signals.extend(mm.on_market_update(market, position.as_ref()));
+                    }
 
                     // Check stop-loss for existing positions.
                     if let Some(ref pos) = position {
@@ -105,8 +314,40 @@ impl StrategyEngine {
             all_signals.extend(se_signals);
         }
 
+        // Completeness arbitrage: paired YES + NO buys.
+        if let Some(ref mut ca) = self.completeness_arb {
+            all_signals.extend(ca.on_tick());
+        }
+
+        // Drop entries a strategy produced for a disabled or cold market;
+        // exits still go out so held inventory is never stranded.
+        all_signals.retain(|s| s.is_cancel() || s.is_exit() || self.is_tradeable(&s.market_slug));
+        let edge_gated = self.apply_net_edge_gate(&mut all_signals);
+
+        // Aged positions are exited even in cold markets: a quiet market is
+        // exactly where they get stuck.
+        if let Some(ref mut pm) = self.position_manager {
+            all_signals.extend(pm.sweep());
+        }
+
+        // Panic liquidation preempts every strategy: only cancels and the
+        // exits survive this tick.
+        let panic_exits = risk_manager.check_panic_liquidation();
+        if !panic_exits.is_empty() {
+            all_signals.retain(|s| s.is_cancel());
+            all_signals.extend(panic_exits);
+        }
+
+        // Every order goes out with its book, exits included.
+        self.attach_book_context(&mut all_signals);
+
         // Apply risk management.
-        self.filter_through_risk(all_signals, risk_manager)
+        let (signals, conflicted) = self.resolve_conflicts(all_signals);
+        let mut output = self.filter_through_risk(signals, risk_manager);
+        output.conflicted_count = conflicted;
+        output.edge_gated_count = edge_gated;
+        balance_arb_legs(&mut output);
+        output
     }
 
     /// Ingest game state for live arbitrage.
@@ -123,6 +364,127 @@ impl StrategyEngine {
         }
     }
 
+    /// Drop entries below the net edge floor; returns how many.
+    fn apply_net_edge_gate(&self, signals: &mut Vec<Signal>) -> usize {
+        let Some(ref gate) = self.net_edge_gate else {
+            return 0;
+        };
+        let before = signals.len();
+        signals.retain(|signal| {
+            let passes = gate.passes(signal);
+            if !passes {
+                debug!(
+                    market_slug = %signal.market_slug,
+                    strategy = %signal.strategy_name,
+                    edge = ?gate.net_edge(signal),
+                    "Net edge below floor, dropping signal"
+                );
+            }
+            passes
+        });
+        before - signals.len()
+    }
+
+    /// Fill in `BOOK_CONTEXT_KEYS` on order signals, keeping any value a
+    /// strategy already set.
+    fn attach_book_context(&self, signals: &mut [Signal]) {
+        let now = self.clock.now();
+        let mut markets: HashMap<String, Option<MarketState>> = HashMap::new();
+        for signal in signals.iter_mut().filter(|s| !s.is_cancel()) {
+            let market = markets
+                .entry(signal.market_slug.clone())
+                .or_insert_with(|| self.state.get_market(&signal.market_slug));
+            let Some(market) = market else { continue };
+            for (key, value) in BOOK_CONTEXT_KEYS.into_iter().zip(book_context(market, now)) {
+                signal.metadata.entry(key.to_string()).or_insert(value);
+            }
+        }
+    }
+
+    /// Enabled and past its data warmup.
+    fn is_tradeable(&self, slug: &str) -> bool {
+        self.state.is_market_enabled(slug) && self.state.is_market_warm(slug)
+    }
+
+    /// One CancelAll per newly disabled market, so its resting orders are
+    /// pulled, plus any cancels queued by strategy toggles. Re-enabled
+    /// markets are forgotten and get quoted afresh.
+    fn disabled_market_cancels(&mut self) -> Vec<Signal> {
+        let disabled: HashSet<String> = self.state.get_disabled_markets().into_iter().collect();
+        self.cancelled_disabled.retain(|slug| disabled.contains(slug));
+
+        let mut signals = std::mem::take(&mut self.toggle_cancels);
+        for slug in disabled {
+            if !self.cancelled_disabled.insert(slug.clone()) {
+                continue;
+            }
+            info!(market_slug = %slug, "Market disabled, cancelling resting orders");
+            if let Some(ref mut mm) = self.market_maker {
+                mm.clear_quote(&slug);
+            }
+            signals.push(cancel_all(slug, "Market disabled"));
+        }
+        signals
+    }
+
+    /// Collapse opposing strategies per market-side to the one whose best
+    /// signal has the highest priority. A strategy's own signals never
+    /// conflict (e.g. a two-sided quote), and cancels always pass.
+    /// Returns the surviving signals and the number dropped.
+    fn resolve_conflicts(&self, signals: Vec<Signal>) -> (Vec<Signal>, usize) {
+        if self.conflict_policy == ConflictPolicy::KeepAll {
+            return (signals, 0);
+        }
+
+        // Winning strategy and its priority per (market, side).
+        let mut winners: HashMap<(String, Side), (String, SignalPriority)> = HashMap::new();
+        for signal in &signals {
+            let Some(key) = market_side(signal) else { continue };
+            let priority = self.priority(signal);
+            match winners.get(&key) {
+                Some((_, best)) if *best >= priority => {}
+                _ => {
+                    winners.insert(key, (signal.strategy_name.clone(), priority));
+                }
+            }
+        }
+
+        let before = signals.len();
+        let kept: Vec<Signal> = signals
+            .into_iter()
+            .filter(|signal| match market_side(signal) {
+                Some(key) => {
+                    let winner = &winners[&key].0;
+                    if *winner != signal.strategy_name {
+                        debug!(
+                            market_slug = %signal.market_slug,
+                            strategy = %signal.strategy_name,
+                            winner = %winner,
+                            action = ?signal.action,
+                            "Dropping conflicting signal"
+                        );
+                    }
+                    *winner == signal.strategy_name
+                }
+                None => true,
+            })
+            .collect();
+        let dropped = before - kept.len();
+        (kept, dropped)
+    }
+
+    fn priority(&self, signal: &Signal) -> SignalPriority {
+        let reduces_risk = self
+            .state
+            .get_position(&signal.market_slug)
+            .is_some_and(|pos| signal.action.reduced_side() == Some(pos.side));
+        SignalPriority {
+            exit: signal.reduce_only,
+            urgency: signal.urgency,
+            reduces_risk,
+        }
+    }
+
     /// Filter signals through risk manager, prioritize by urgency.
     fn filter_through_risk(
         &self,
@@ -130,7 +492,8 @@ impl StrategyEngine {
         risk_manager: &mut RiskManager,
     ) -> EngineOutput {
         // Sort by urgency (highest first).
-        signals.sort_by(|a, b| b.urgency.cmp(&a.urgency));
+        signals.sort_by_key(|s| std::cmp::Reverse(s.urgency));
+        risk_manager.begin_cycle();
 
         let mut approved = Vec::new();
         let mut rejected = 0;
@@ -158,6 +521,94 @@ impl StrategyEngine {
         EngineOutput {
             approved_signals: approved,
             rejected_count: rejected,
+            conflicted_count: 0,
+            edge_gated_count: 0,
+        }
+    }
+}
+
+/// Engine-issued CancelAll for `market_slug`.
+fn cancel_all(market_slug: String, reason: &str) -> Signal {
+    Signal {
+        market_slug,
+        action: SignalAction::CancelAll,
+        price: Decimal::ZERO,
+        quantity: 0,
+        urgency: Urgency::Critical,
+        confidence: 1.0,
+        strategy_name: "engine".to_string(),
+        reason: reason.to_string(),
+        metadata: HashMap::new(),
+        timestamp: Utc::now(),
+        reduce_only: false,
+        good_till: None,
+    }
+}
+
+/// Conflict-resolution rank; fields compare in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct SignalPriority {
+    exit: bool,
+    urgency: Urgency,
+    reduces_risk: bool,
+}
+
+/// The market and position side a signal trades, or `None` for cancels.
+fn market_side(signal: &Signal) -> Option<(String, Side)> {
+    let side = signal.action.to_intent()?.side();
+    Some((signal.market_slug.clone(), side))
+}
+
+fn arb_pair(signal: &Signal) -> Option<String> {
+    signal.arb_pair().map(str::to_string)
+}
+
+/// Values for `BOOK_CONTEXT_KEYS`, in order.
+fn book_context(market: &MarketState, now: chrono::DateTime<Utc>) -> [serde_json::Value; 4] {
+    let spread = match (market.yes_bid, market.yes_ask) {
+        (Some(bid), Some(ask)) => Some(ask - bid),
+        _ => None,
+    };
+    let imbalance = match (market.yes_bid_depth, market.yes_ask_depth) {
+        (Some(bids), Some(asks)) if bids + asks > 0 => {
+            Some((bids - asks) as f64 / (bids + asks) as f64)
         }
+        _ => None,
+    };
+    [
+        serde_json::json!((now - market.last_updated).num_milliseconds().max(0)),
+        serde_json::json!(spread.map(to_f64)),
+        serde_json::json!(market.yes_mid_price().map(to_f64)),
+        serde_json::json!(imbalance),
+    ]
+}
+
+/// Keep arb legs matched after risk: both legs take the smaller approved
+/// size, and a leg whose partner was rejected is dropped too.
+fn balance_arb_legs(output: &mut EngineOutput) {
+    let mut pairs: HashMap<String, (usize, i64)> = HashMap::new();
+    for signal in &output.approved_signals {
+        if let Some(pair) = arb_pair(signal) {
+            let entry = pairs.entry(pair).or_insert((0, i64::MAX));
+            entry.0 += 1;
+            entry.1 = entry.1.min(signal.quantity);
+        }
+    }
+    if pairs.is_empty() {
+        return;
     }
+
+    let before = output.approved_signals.len();
+    output.approved_signals.retain_mut(|signal| match arb_pair(signal) {
+        Some(pair) => {
+            let (legs, quantity) = pairs[&pair];
+            signal.quantity = quantity;
+            if legs < 2 {
+                debug!(market_slug = %signal.market_slug, "Dropping unpaired arb leg");
+            }
+            legs >= 2
+        }
+        None => true,
+    });
+    output.rejected_count += before - output.approved_signals.len();
 }
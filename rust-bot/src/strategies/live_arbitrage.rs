@@ -2,15 +2,32 @@
 //!
 //! Generates signals on score changes and game events, capturing
 //! mispricing from stale market prices during live games.
+//!
+//! Right after a score change the market often lags books that have
+//! already moved. For `lag_window_seconds` after each change the strategy
+//! prices the game with a logistic in the score margin, scaled up as the
+//! game runs out:
+//!     P(home) = 1 / (1 + exp(-k * diff / sqrt(max(1 - progress, 0.05))))
+//! and fires one `Critical` signal per scoring event when the market trails
+//! that model by at least `min_edge`.
+//!
+//! With a `taker_margin_threshold`, urgency follows the edge instead: fat
+//! edges cross the spread as `Critical` (market) orders, trading the fee
+//! for a certain fill, and thinner ones post `Medium` limits.
 
 #![allow(dead_code)]
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::data::models::{Signal, SignalAction, Urgency};
 use crate::state::state_manager::MarketState;
+use crate::util::dec::{from_f64, to_f64};
+
+/// Floor on the remaining-game fraction, so the model stays finite at the
+/// final whistle.
+const MIN_REMAINING: f64 = 0.05;
 
 /// Configuration for live arbitrage.
 #[derive(Debug, Clone)]
@@ -21,6 +38,17 @@ pub struct LiveArbitrageConfig {
     pub max_prob_shift: Decimal,
     pub cooldown_seconds: f64,
     pub enabled_markets: Vec<String>,
+    /// How long after a score change the lag model may fire. Zero disables,
+    /// the default: the model needs `GameState::progress`, which no feed
+    /// populates yet.
+    pub lag_window_seconds: f64,
+    /// Logistic slope per point of margin at the start of a game (`k`
+    /// above). Sport specific: larger for low-scoring games.
+    pub lag_logit_per_point: f64,
+    /// Edge at or above which a signal is sent `Critical`; below it,
+    /// `Medium`. Zero disables, keeping the regular `High` and lag
+    /// `Critical` urgencies.
+    pub taker_margin_threshold: Decimal,
 }
 
 impl Default for LiveArbitrageConfig {
@@ -32,6 +60,9 @@ impl Default for LiveArbitrageConfig {
             max_prob_shift: Decimal::new(25, 2), // 0.25
             cooldown_seconds: 5.0,
             enabled_markets: Vec::new(),
+            lag_window_seconds: 0.0,
+            lag_logit_per_point: 0.5,
+            taker_margin_threshold: Decimal::ZERO,
         }
     }
 }
@@ -45,6 +76,8 @@ pub struct GameState {
     pub away_score: i32,
     pub home_is_yes: bool,
     pub is_final: bool,
+    /// Fraction of the game played, in [0, 1]; 0 when unknown.
+    pub progress: f64,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -60,6 +93,10 @@ pub struct LiveArbitrageStrategy {
     enabled: bool,
     latest_states: HashMap<String, GameState>,
     last_signal_at: HashMap<String, DateTime<Utc>>,
+    /// When each event's score last changed.
+    score_changed_at: HashMap<String, DateTime<Utc>>,
+    /// Events whose latest score change already produced a lag signal.
+    lag_fired: HashSet<String>,
 }
 
 impl LiveArbitrageStrategy {
@@ -69,6 +106,8 @@ impl LiveArbitrageStrategy {
             enabled: true,
             latest_states: HashMap::new(),
             last_signal_at: HashMap::new(),
+            score_changed_at: HashMap::new(),
+            lag_fired: HashSet::new(),
         }
     }
 
@@ -76,8 +115,21 @@ impl LiveArbitrageStrategy {
         self.enabled = enabled;
     }
 
-    /// Ingest a new game state update.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Ingest a new game state update. A changed score opens a new lag
+    /// window for the event.
     pub fn ingest_game_state(&mut self, state: GameState) {
+        let scored = self.latest_states.get(&state.event_id).is_some_and(|prev| {
+            (prev.home_score, prev.away_score) != (state.home_score, state.away_score)
+        });
+        if scored {
+            self.score_changed_at
+                .insert(state.event_id.clone(), state.timestamp);
+            self.lag_fired.remove(&state.event_id);
+        }
         self.latest_states
             .insert(state.event_id.clone(), state);
     }
@@ -110,6 +162,18 @@ impl LiveArbitrageStrategy {
                 continue;
             }
 
+            // Post-score lag: once per scoring event, ahead of the cooldown.
+            if self.in_lag_window(&state, now) {
+                if let Some(signal) = get_market(&market_slug)
+                    .and_then(|market| self.generate_lag_signal(&market, &state))
+                {
+                    self.lag_fired.insert(state.event_id.clone());
+                    self.last_signal_at.insert(market_slug, now);
+                    signals.push(signal);
+                    continue;
+                }
+            }
+
             // Cooldown check.
             if let Some(last) = self.last_signal_at.get(&market_slug) {
                 let elapsed = (now - *last).num_milliseconds() as f64 / 1000.0;
@@ -132,12 +196,46 @@ impl LiveArbitrageStrategy {
         signals
     }
 
+    fn in_lag_window(&self, state: &GameState, now: DateTime<Utc>) -> bool {
+        if self.config.lag_window_seconds <= 0.0 || self.lag_fired.contains(&state.event_id) {
+            return false;
+        }
+        self.score_changed_at.get(&state.event_id).is_some_and(|changed| {
+            let elapsed = (now - *changed).num_milliseconds() as f64 / 1000.0;
+            elapsed <= self.config.lag_window_seconds
+        })
+    }
+
+    fn generate_lag_signal(&self, market: &MarketState, state: &GameState) -> Option<Signal> {
+        let p_home = score_model_probability(
+            state.score_diff(),
+            state.progress,
+            self.config.lag_logit_per_point,
+        );
+        let p_yes = if state.home_is_yes { p_home } else { 1.0 - p_home };
+        let fair_yes = from_f64(p_yes)?.max(Decimal::new(1, 2)).min(Decimal::new(99, 2));
+        self.build_signal(market, state, fair_yes, "Post-score lag", Urgency::Critical)
+    }
+
     fn generate_signal(&self, market: &MarketState, state: &GameState) -> Option<Signal> {
+        let fair_yes = self.estimate_yes_probability(state);
+        self.build_signal(market, state, fair_yes, "Live edge", Urgency::High)
+    }
+
+    /// Buy whichever side trails `fair_yes` by at least `min_edge`, the
+    /// larger edge winning. `urgency` applies without a taker threshold.
+    fn build_signal(
+        &self,
+        market: &MarketState,
+        state: &GameState,
+        fair_yes: Decimal,
+        label: &str,
+        urgency: Urgency,
+    ) -> Option<Signal> {
         if market.yes_ask.is_none() && market.no_ask.is_none() {
             return None;
         }
 
-        let fair_yes = self.estimate_yes_probability(state);
         let mut best_signal: Option<Signal> = None;
         let mut best_edge = Decimal::ZERO;
 
@@ -154,7 +252,7 @@ impl LiveArbitrageStrategy {
                     let mut metadata = HashMap::new();
                     metadata.insert(
                         "true_probability".to_string(),
-                        serde_json::json!(fair_yes.to_string().parse::<f64>().unwrap_or(0.5)),
+                        serde_json::json!(to_f64(fair_yes)),
                     );
                     metadata.insert("allow_in_game".to_string(), serde_json::json!(true));
 
@@ -163,12 +261,14 @@ impl LiveArbitrageStrategy {
                         action: SignalAction::BuyYes,
                         price,
                         quantity,
-                        urgency: Urgency::High,
+                        urgency,
                         confidence,
                         strategy_name: "live_arbitrage".to_string(),
-                        reason: format!("Live edge {:.3} on score update", edge),
+                        reason: format!("{} {:.3} on score update", label, edge),
                         metadata,
                         timestamp: Utc::now(),
+                        reduce_only: false,
+                        good_till: None,
                     });
                 }
             }
@@ -185,12 +285,13 @@ impl LiveArbitrageStrategy {
                 let price = clamp_price(no_ask);
                 let quantity = self.calculate_quantity(price);
                 if quantity > 0 {
+                    best_edge = edge;
                     let confidence = (0.55 + (state.score_diff().unsigned_abs() as f64 * 0.05))
                         .min(0.9);
                     let mut metadata = HashMap::new();
                     metadata.insert(
                         "true_probability".to_string(),
-                        serde_json::json!(fair_no.to_string().parse::<f64>().unwrap_or(0.5)),
+                        serde_json::json!(to_f64(fair_no)),
                     );
                     metadata.insert("allow_in_game".to_string(), serde_json::json!(true));
 
@@ -199,18 +300,35 @@ impl LiveArbitrageStrategy {
                         action: SignalAction::BuyNo,
                         price,
                         quantity,
-                        urgency: Urgency::High,
+                        urgency,
                         confidence,
                         strategy_name: "live_arbitrage".to_string(),
-                        reason: format!("Live edge {:.3} on score update", edge),
+                        reason: format!("{} {:.3} on score update", label, edge),
                         metadata,
                         timestamp: Utc::now(),
+                        reduce_only: false,
+                        good_till: None,
                     });
                 }
             }
         }
 
-        best_signal
+        best_signal.map(|mut signal| {
+            signal.urgency = self.urgency_for(best_edge, urgency);
+            signal
+        })
+    }
+
+    /// Cross the spread only when the edge pays for the taker fee.
+    fn urgency_for(&self, edge: Decimal, default: Urgency) -> Urgency {
+        let threshold = self.config.taker_margin_threshold;
+        if threshold <= Decimal::ZERO {
+            default
+        } else if edge >= threshold {
+            Urgency::Critical
+        } else {
+            Urgency::Medium
+        }
     }
 
     fn estimate_yes_probability(&self, state: &GameState) -> Decimal {
@@ -256,6 +374,14 @@ impl LiveArbitrageStrategy {
     }
 }
 
+/// Home win probability from the score margin and game progress: a
+/// logistic whose slope grows as the remaining game shrinks.
+pub fn score_model_probability(score_diff: i32, progress: f64, logit_per_point: f64) -> f64 {
+    let remaining = (1.0 - progress.clamp(0.0, 1.0)).max(MIN_REMAINING);
+    let z = logit_per_point * score_diff as f64 / remaining.sqrt();
+    1.0 / (1.0 + (-z).exp())
+}
+
 fn clamp_price(price: Decimal) -> Decimal {
     price
         .max(Decimal::new(1, 2))
@@ -2,7 +2,12 @@
 //!
 //! Two-sided market making: posts bid and ask orders around mid-price,
 //! capturing the spread when both sides fill. Includes inventory management,
-//! maker-only enforcement, and stop-loss exits.
+//! maker-only enforcement, a no-arbitrage check against the NO book,
+//! stop-loss exits, and adaptive spread widening after adverse fills.
+//!
+//! With `num_levels > 1` each side is a ladder: the inside quote plus
+//! deeper levels stepped `level_spacing` away from mid. The ladder is
+//! skewed, cancelled and inventory-limited as a unit.
 
 #![allow(dead_code)]
 
@@ -11,8 +16,15 @@ use rust_decimal::Decimal;
 use std::collections::HashMap;
 use tracing::{debug, info};
 
-use crate::data::models::{Signal, SignalAction, Urgency};
+use crate::clock::{RealClock, SharedClock};
+use crate::data::models::{
+    FeeSchedule, FillEvent, OrderBook, PriceLevel, Side, Signal, SignalAction, Urgency,
+    LADDER_LEVEL_KEY, REPLACEMENT_KEY,
+};
+use crate::data::orderbook::OrderBookTracker;
 use crate::state::state_manager::{MarketState, PositionState};
+use crate::util::dec::{floor_i64, from_f64, to_f64};
+use crate::util::rng::SplitMix64;
 
 /// Market maker configuration.
 #[derive(Debug, Clone)]
@@ -20,17 +32,65 @@ pub struct MarketMakerConfig {
     pub spread: Decimal,
     pub order_size: Decimal,
     pub max_inventory: Decimal,
+    /// Notional inventory the skew pulls toward: positive leans YES,
+    /// negative leans NO. Zero skews back to flat.
+    pub inventory_target: Decimal,
     pub refresh_interval_secs: f64,
+    /// Spread of per-market refresh intervals: each market refreshes every
+    /// `refresh_interval_secs * (1 + u)` with a fixed `u` drawn from
+    /// [-jitter, jitter], so markets stop re-quoting in the same tick.
+    /// Zero disables.
+    pub refresh_jitter_pct: f64,
     pub min_spread: Decimal,
+    /// Widest YES book spread still quoted; also caps our own widened spread.
     pub max_spread: Decimal,
+    /// Fair-value move that triggers a re-quote.
     pub price_tolerance: Decimal,
+    /// How long a `price_tolerance` move must persist before re-quoting, so
+    /// a mid flickering across the boundary does not churn quotes. Zero
+    /// re-quotes on the first tick past the tolerance.
+    pub refresh_dwell_secs: f64,
+    /// Move that re-quotes at once, skipping the dwell. Zero disables.
+    pub refresh_hysteresis: Decimal,
     pub enabled_markets: Vec<String>,
     pub inventory_skew_factor: Decimal,
     pub min_spread_pct: Decimal,
+    /// Fees a quote round trip pays; sets the break-even spread.
+    pub fees: FeeSchedule,
+    /// Relative spread required on top of the round-trip fee cost.
+    pub spread_safety_margin: Decimal,
     pub maker_only: bool,
     pub stop_loss_pct: Decimal,
     pub aggressive_stop_loss_pct: Decimal,
     pub max_underwater_hold_seconds: i64,
+    /// Spread multiplier applied per fill that is immediately underwater at
+    /// the next mark (1.0 disables adaptive widening).
+    pub adverse_selection_widen_factor: Decimal,
+    /// Half-life over which the widened spread decays back to `spread`.
+    pub adverse_selection_half_life_secs: f64,
+    /// Skip markets whose best bid or ask has fewer contracts (0 disables).
+    pub min_top_depth: i64,
+    /// Cap each quote at this fraction of the tracker's displayed depth at
+    /// its price level (0 disables).
+    pub max_depth_fraction: Decimal,
+    /// Exchange price increment. Bids round down and asks up to it.
+    pub tick_size: Decimal,
+    /// Weight of the newest mid in the fair-value EWMA that quotes are
+    /// centered on. Below 1 the anchor lags fast moves instead of chasing
+    /// them; 1 quotes around the instantaneous mid.
+    pub mid_ewma_alpha: Decimal,
+    /// How strongly top-of-book imbalance skews quote sizes: the bid is
+    /// scaled by `1 - factor * imbalance` and the ask by `1 + factor *
+    /// imbalance`, so the side the book leans on (and that is more likely
+    /// to be run over) is quoted smaller. In [0, 1]; zero disables.
+    pub imbalance_size_factor: Decimal,
+    /// Quotes per side. 1 posts a single bid and ask.
+    pub num_levels: usize,
+    /// Price step between ladder levels, away from mid.
+    pub level_spacing: Decimal,
+    /// Size of each level relative to the one inside it, in (0, 1]; 1
+    /// quotes every level at full size.
+    pub level_size_decay: Decimal,
 }
 
 impl Default for MarketMakerConfig {
@@ -39,17 +99,36 @@ impl Default for MarketMakerConfig {
             spread: Decimal::new(2, 2),        // 0.02
             order_size: Decimal::new(10, 0),    // $10
             max_inventory: Decimal::new(50, 0), // $50
+            inventory_target: Decimal::ZERO,
             refresh_interval_secs: 5.0,
+            refresh_jitter_pct: 0.2,
             min_spread: Decimal::new(1, 2),    // 0.01
             max_spread: Decimal::new(10, 2),   // 0.10
             price_tolerance: Decimal::new(5, 3), // 0.005
+            refresh_dwell_secs: 0.0,
+            refresh_hysteresis: Decimal::ZERO,
             enabled_markets: Vec::new(),
             inventory_skew_factor: Decimal::new(5, 1), // 0.5
             min_spread_pct: Decimal::new(2, 2),        // 0.02 = 2%
+            fees: FeeSchedule {
+                maker_fee_rate: Decimal::ZERO,
+                taker_fee_rate: Decimal::new(1, 3), // 10 bps
+            },
+            spread_safety_margin: Decimal::new(2, 3), // 0.2%
             maker_only: true,
             stop_loss_pct: Decimal::new(5, 2),            // 5%
             aggressive_stop_loss_pct: Decimal::new(3, 2), // 3%
             max_underwater_hold_seconds: 600,              // 10 min
+            adverse_selection_widen_factor: Decimal::new(15, 1), // 1.5x
+            adverse_selection_half_life_secs: 60.0,
+            min_top_depth: 0,
+            max_depth_fraction: Decimal::ZERO,
+            tick_size: Decimal::new(1, 2), // 0.01
+            mid_ewma_alpha: Decimal::ONE,
+            imbalance_size_factor: Decimal::ZERO,
+            num_levels: 1,
+            level_spacing: Decimal::new(1, 2), // 0.01
+            level_size_decay: Decimal::ONE,
         }
     }
 }
@@ -61,17 +140,46 @@ struct QuoteState {
     ask_price: Option<Decimal>,
     last_refresh: DateTime<Utc>,
     last_mid_price: Option<Decimal>,
+    /// Set when one of our orders fills; forces a refresh on next update.
+    needs_refresh: bool,
+    /// This market's share of the refresh jitter, added to the interval.
+    refresh_offset_secs: f64,
+    /// Since when the fair value has been at least `price_tolerance` away
+    /// from `last_mid_price`.
+    moved_since: Option<DateTime<Utc>>,
+}
+
+/// Recent fill outcomes for a market, driving adaptive spread widening.
+#[derive(Debug, Clone)]
+struct AdverseSelectionState {
+    /// Spread multiplier (>= 1) as of `updated_at`; decays toward 1.
+    multiplier: Decimal,
+    updated_at: DateTime<Utc>,
+    /// Fills awaiting their first post-fill mark: (YES-equivalent price,
+    /// whether the fill added YES exposure).
+    pending: Vec<(Decimal, bool)>,
 }
 
 /// Two-sided market making strategy.
 pub struct MarketMakerStrategy {
     config: MarketMakerConfig,
     quotes: HashMap<String, QuoteState>,
+    adverse: HashMap<String, AdverseSelectionState>,
+    /// EWMA of each market's mid; the quote anchor.
+    fair_values: HashMap<String, Decimal>,
     enabled: bool,
+    clock: SharedClock,
+    /// When set, books below the tracker's quality floor are not quoted.
+    orderbook: Option<OrderBookTracker>,
 }
 
 impl MarketMakerStrategy {
     pub fn new(config: MarketMakerConfig) -> Self {
+        Self::with_clock(config, RealClock::shared())
+    }
+
+    /// Create with an explicit time source (e.g. `MockClock` in tests).
+    pub fn with_clock(config: MarketMakerConfig, clock: SharedClock) -> Self {
         info!(
             spread = %config.spread,
             order_size = %config.order_size,
@@ -81,7 +189,11 @@ impl MarketMakerStrategy {
         Self {
             config,
             quotes: HashMap::new(),
+            adverse: HashMap::new(),
+            fair_values: HashMap::new(),
             enabled: true,
+            clock,
+            orderbook: None,
         }
     }
 
@@ -89,6 +201,66 @@ impl MarketMakerStrategy {
         self.enabled = enabled;
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Skip markets whose tracked book falls below the quality floor.
+    pub fn set_orderbook(&mut self, orderbook: OrderBookTracker) {
+        self.orderbook = Some(orderbook);
+    }
+
+    /// Forget quote state for a market whose orders were cancelled
+    /// externally, so the next update quotes it afresh.
+    pub fn clear_quote(&mut self, market_slug: &str) {
+        self.quotes.remove(market_slug);
+    }
+
+    /// Forget all quote state, returning the markets that were quoted so
+    /// their resting orders can be cancelled.
+    pub fn take_quoted_markets(&mut self) -> Vec<String> {
+        self.quotes.drain().map(|(slug, _)| slug).collect()
+    }
+
+    /// React to a fill of one of our own orders: force a quote refresh and
+    /// queue the fill for adverse-selection marking. Inventory itself is
+    /// read from the `StateManager` position passed to `on_market_update`.
+    pub fn on_fill(&mut self, fill: &FillEvent) {
+        if fill.strategy_name != "market_maker" || fill.quantity <= 0 {
+            return;
+        }
+
+        if let Some(quote) = self.quotes.get_mut(&fill.market_slug) {
+            quote.needs_refresh = true;
+        }
+
+        // Queue the fill to be marked against the next mid. Buying NO at p
+        // is equivalent to selling YES at 1 - p.
+        let (yes_price, long_yes) = match fill.side {
+            Side::Yes => (fill.price, fill.is_buy),
+            Side::No => (Decimal::ONE - fill.price, !fill.is_buy),
+        };
+        let now = self.clock.now();
+        self.adverse
+            .entry(fill.market_slug.clone())
+            .or_insert_with(|| AdverseSelectionState {
+                multiplier: Decimal::ONE,
+                updated_at: now,
+                pending: Vec::new(),
+            })
+            .pending
+            .push((yes_price, long_yes));
+
+        debug!(
+            market_slug = %fill.market_slug,
+            side = %fill.side,
+            is_buy = fill.is_buy,
+            price = %fill.price,
+            quantity = fill.quantity,
+            "MM fill received"
+        );
+    }
+
     /// Generate signals on market update.
     pub fn on_market_update(
         &mut self,
@@ -104,11 +276,36 @@ impl MarketMakerStrategy {
         if !market.has_valid_prices() {
             return Vec::new();
         }
+        if let Some(ref orderbook) = self.orderbook {
+            if !orderbook.passes_quality_floor(&market.market_slug) {
+                debug!(
+                    market_slug = %market.market_slug,
+                    quality = ?orderbook.book_quality(&market.market_slug),
+                    "Book below quality floor, not quoting"
+                );
+                return Vec::new();
+            }
+        }
+
+        self.mark_pending_fills(market);
+        self.update_fair_value(market);
+
+        if self.is_book_too_thin(market) {
+            debug!(
+                market_slug = %market.market_slug,
+                bid_depth = ?market.yes_bid_depth,
+                ask_depth = ?market.yes_ask_depth,
+                min_top_depth = self.config.min_top_depth,
+                "Top of book too thin, not quoting"
+            );
+            return Vec::new();
+        }
 
-        let quote_state = self.get_or_create_quote(&market.market_slug);
-        if self.should_refresh(&market, &quote_state) {
+        let mut quote_state = self.get_or_create_quote(&market.market_slug);
+        if self.should_refresh(market, &mut quote_state) {
             self.generate_quote_signals(market, position)
         } else {
+            self.quotes.insert(market.market_slug.clone(), quote_state);
             Vec::new()
         }
     }
@@ -139,7 +336,7 @@ impl MarketMakerStrategy {
         };
 
         let pnl_pct = (effective_close_price - position.avg_price) / position.avg_price;
-        let age_seconds = (Utc::now() - position.created_at).num_seconds();
+        let age_seconds = (self.clock.now() - position.created_at).num_seconds();
 
         let stop_loss_trigger = pnl_pct <= -self.config.aggressive_stop_loss_pct;
         let hard_stop_trigger = pnl_pct <= -self.config.stop_loss_pct;
@@ -157,13 +354,10 @@ impl MarketMakerStrategy {
                 )
             };
 
-            let (action, price) = match position.side {
-                crate::data::models::Side::Yes => {
-                    (SignalAction::SellYes, clamp_price(exit_price))
-                }
-                crate::data::models::Side::No => {
-                    (SignalAction::BuyYes, clamp_price(exit_price))
-                }
+            let price = round_to_tick(clamp_price(exit_price), self.tick_size(market));
+            let action = match position.side {
+                crate::data::models::Side::Yes => SignalAction::SellYes,
+                crate::data::models::Side::No => SignalAction::BuyYes,
             };
 
             info!(
@@ -184,7 +378,9 @@ impl MarketMakerStrategy {
                 strategy_name: "market_maker".to_string(),
                 reason,
                 metadata: HashMap::new(),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
+                reduce_only: true,
+                good_till: None,
             });
         }
 
@@ -201,30 +397,62 @@ impl MarketMakerStrategy {
 
         // Check minimum spread requirement.
         let spread_pct = self.market_spread_pct(market);
-        if spread_pct.map(|s| s < self.config.min_spread_pct).unwrap_or(true) {
+        if spread_pct.map(|s| s < self.min_profitable_spread_pct()).unwrap_or(true) {
             return signals;
         }
 
+        // A book this wide shows no real two-sided interest.
+        if let (Some(bid), Some(ask)) = (market.yes_bid, market.yes_ask) {
+            if ask - bid > self.config.max_spread {
+                debug!(
+                    market_slug = %market.market_slug,
+                    spread = %(ask - bid),
+                    max_spread = %self.config.max_spread,
+                    "Book spread above max, not quoting"
+                );
+                return signals;
+            }
+        }
+
         // Calculate quotes.
         let (bid_price, ask_price) = match self.calculate_quotes(market, position) {
             Some(prices) => prices,
             None => return signals,
         };
+        let Some((bid_price, ask_price)) = no_arbitrage_quotes(
+            bid_price,
+            ask_price,
+            market.no_bid,
+            market.no_ask,
+            self.tick_size(market),
+        ) else {
+            debug!(
+                market_slug = %market.market_slug,
+                bid = %bid_price,
+                ask = %ask_price,
+                no_bid = ?market.no_bid,
+                no_ask = ?market.no_ask,
+                "Quotes inconsistent with the NO book, not quoting"
+            );
+            return signals;
+        };
 
-        let bid_qty = self.calculate_quantity(bid_price);
-        let ask_qty = self.calculate_quantity(ask_price);
+        let book = self
+            .orderbook
+            .as_ref()
+            .and_then(|ob| ob.get_full(&market.market_slug));
+        let (bid_scale, ask_scale) = self.imbalance_size_scales(&market.market_slug);
+        let tick = self.tick_size(market);
+        let bids = self.quote_ladder(bid_price, bid_scale, tick, book.as_ref(), true);
+        let asks = self.quote_ladder(ask_price, ask_scale, tick, book.as_ref(), false);
 
-        // Inventory limit: only quote to reduce if at max.
-        let (mut final_bid_qty, mut final_ask_qty) = (bid_qty, ask_qty);
-        if let Some(pos) = position {
-            let pos_value = pos.cost_basis();
-            if pos_value >= self.config.max_inventory {
-                match pos.side {
-                    crate::data::models::Side::Yes => final_bid_qty = 0,
-                    crate::data::models::Side::No => final_ask_qty = 0,
-                }
-            }
-        }
+        // Inventory limit over the whole ladder: only quote to reduce if
+        // at max, and drop deeper levels that would carry inventory past it.
+        let held = signed_inventory(position);
+        let bids = self.limit_ladder_to_inventory(bids, held, true);
+        let asks = self.limit_ladder_to_inventory(asks, -held, false);
+        let final_bid_qty = bids.first().map_or(0, |&(_, qty)| qty);
+        let final_ask_qty = asks.first().map_or(0, |&(_, qty)| qty);
 
         // Cancel existing.
         let has_active = self
@@ -244,40 +472,42 @@ impl MarketMakerStrategy {
                 strategy_name: "market_maker".to_string(),
                 reason: "Refreshing quotes".to_string(),
                 metadata: HashMap::new(),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
+                reduce_only: false,
+                good_till: None,
             });
         }
 
-        // Post bid (buy YES).
-        if final_bid_qty > 0 {
-            signals.push(Signal {
-                market_slug: market.market_slug.clone(),
-                action: SignalAction::BuyYes,
-                price: bid_price,
-                quantity: final_bid_qty,
-                urgency: Urgency::Low,
-                confidence: 0.8,
-                strategy_name: "market_maker".to_string(),
-                reason: format!("MM bid at {:.4}", bid_price),
-                metadata: self.quote_metadata(market, spread_pct),
-                timestamp: Utc::now(),
-            });
-        }
-
-        // Post ask (sell YES).
-        if final_ask_qty > 0 {
-            signals.push(Signal {
-                market_slug: market.market_slug.clone(),
-                action: SignalAction::SellYes,
-                price: ask_price,
-                quantity: final_ask_qty,
-                urgency: Urgency::Low,
-                confidence: 0.8,
-                strategy_name: "market_maker".to_string(),
-                reason: format!("MM ask at {:.4}", ask_price),
-                metadata: self.quote_metadata(market, spread_pct),
-                timestamp: Utc::now(),
-            });
+        // Post bids (buy YES) and asks (sell YES), inside level first.
+        let ladders = [(SignalAction::BuyYes, "bid", &bids), (SignalAction::SellYes, "ask", &asks)];
+        for (action, label, ladder) in ladders {
+            for (level, &(price, quantity)) in ladder.iter().enumerate() {
+                let reason = if level == 0 {
+                    format!("MM {} at {:.4}", label, price)
+                } else {
+                    format!("MM {} L{} at {:.4}", label, level + 1, price)
+                };
+                let mut metadata = self.quote_metadata(market, spread_pct);
+                metadata.insert(LADDER_LEVEL_KEY.to_string(), serde_json::json!(level + 1));
+                if has_active {
+                    // Follows the cancel above; not a stacked duplicate.
+                    metadata.insert(REPLACEMENT_KEY.to_string(), serde_json::json!(true));
+                }
+                signals.push(Signal {
+                    market_slug: market.market_slug.clone(),
+                    action,
+                    price,
+                    quantity,
+                    urgency: Urgency::Low,
+                    confidence: 0.8,
+                    strategy_name: "market_maker".to_string(),
+                    reason,
+                    metadata,
+                    timestamp: self.clock.now(),
+                    reduce_only: false,
+                    good_till: None,
+                });
+            }
         }
 
         // Update quote state.
@@ -294,8 +524,11 @@ impl MarketMakerStrategy {
                 } else {
                     None
                 },
-                last_refresh: Utc::now(),
-                last_mid_price: market.yes_mid_price(),
+                last_refresh: self.clock.now(),
+                last_mid_price: self.fair_value(market),
+                needs_refresh: false,
+                refresh_offset_secs: self.refresh_offset(&market.market_slug),
+                moved_since: None,
             },
         );
 
@@ -305,46 +538,124 @@ impl MarketMakerStrategy {
             ask = %ask_price,
             bid_qty = final_bid_qty,
             ask_qty = final_ask_qty,
+            bid_levels = bids.len(),
+            ask_levels = asks.len(),
             "Generated MM quotes"
         );
 
         signals
     }
 
+    /// Mark queued fills against the current mid. Each fill that is already
+    /// underwater multiplies the (decayed) spread multiplier by the widen
+    /// factor; benign fills leave it to decay.
+    fn mark_pending_fills(&mut self, market: &MarketState) {
+        let Some(mid) = market.yes_mid_price() else {
+            return;
+        };
+        let now = self.clock.now();
+        let current = self.spread_multiplier(&market.market_slug);
+        let widen = self.config.adverse_selection_widen_factor;
+
+        let Some(state) = self.adverse.get_mut(&market.market_slug) else {
+            return;
+        };
+        if state.pending.is_empty() {
+            return;
+        }
+
+        let adverse = state
+            .pending
+            .drain(..)
+            .filter(|&(price, long_yes)| if long_yes { mid < price } else { mid > price })
+            .count();
+        if adverse == 0 {
+            return;
+        }
+
+        let mut multiplier = current;
+        for _ in 0..adverse {
+            multiplier *= widen;
+        }
+        state.multiplier = multiplier.max(Decimal::ONE);
+        state.updated_at = now;
+
+        info!(
+            market_slug = %market.market_slug,
+            adverse_fills = adverse,
+            spread_multiplier = %state.multiplier,
+            "Adverse selection: widening spread"
+        );
+    }
+
+    /// Fold the current mid into the market's fair-value EWMA.
+    fn update_fair_value(&mut self, market: &MarketState) {
+        let Some(mid) = market.yes_mid_price() else {
+            return;
+        };
+        let alpha = self.config.mid_ewma_alpha.clamp(Decimal::ZERO, Decimal::ONE);
+        self.fair_values
+            .entry(market.market_slug.clone())
+            .and_modify(|ewma| *ewma = alpha * mid + (Decimal::ONE - alpha) * *ewma)
+            .or_insert(mid);
+    }
+
+    /// Price quotes are centered on: the mid EWMA, or the mid itself
+    /// before the first update.
+    pub fn fair_value(&self, market: &MarketState) -> Option<Decimal> {
+        self.fair_values
+            .get(&market.market_slug)
+            .copied()
+            .or_else(|| market.yes_mid_price())
+    }
+
+    /// Current spread multiplier for a market after half-life decay.
+    fn spread_multiplier(&self, market_slug: &str) -> Decimal {
+        let state = match self.adverse.get(market_slug) {
+            Some(s) if s.multiplier > Decimal::ONE => s,
+            _ => return Decimal::ONE,
+        };
+        let half_life = self.config.adverse_selection_half_life_secs;
+        if half_life <= 0.0 {
+            return Decimal::ONE;
+        }
+        let elapsed = (self.clock.now() - state.updated_at).num_milliseconds() as f64 / 1000.0;
+        let decay = from_f64(0.5f64.powf(elapsed.max(0.0) / half_life))
+            .unwrap_or(Decimal::ZERO);
+        Decimal::ONE + (state.multiplier - Decimal::ONE) * decay
+    }
+
+    /// Base spread scaled by the adverse-selection multiplier, capped at
+    /// `max_spread` and rounded to 4 dp.
+    fn effective_spread(&self, market_slug: &str) -> Decimal {
+        let widened = self.config.spread * self.spread_multiplier(market_slug);
+        widened
+            .min(self.config.max_spread.max(self.config.spread))
+            .round_dp(4)
+    }
+
     /// Calculate bid and ask prices from mid-price and spread.
     fn calculate_quotes(
         &self,
         market: &MarketState,
         position: Option<&PositionState>,
     ) -> Option<(Decimal, Decimal)> {
-        let mid = market.yes_mid_price()?;
-        let half_spread = self.config.spread / Decimal::TWO;
-
-        // Inventory skew.
-        let mut bid_skew = Decimal::ZERO;
-        let mut ask_skew = Decimal::ZERO;
-        if let Some(pos) = position {
-            if pos.quantity > 0 && self.config.max_inventory > Decimal::ZERO {
-                let pos_value = pos.cost_basis();
-                let inventory_ratio =
-                    (pos_value / self.config.max_inventory).min(Decimal::TWO);
-                let skew = inventory_ratio * self.config.inventory_skew_factor * half_spread;
-
-                match pos.side {
-                    crate::data::models::Side::Yes => {
-                        bid_skew = -skew;
-                        ask_skew = -skew;
-                    }
-                    crate::data::models::Side::No => {
-                        bid_skew = skew;
-                        ask_skew = skew;
-                    }
-                }
-            }
+        let mid = self.fair_value(market)?;
+        let spread = self.effective_spread(&market.market_slug);
+        let half_spread = spread / Decimal::TWO;
+
+        // Inventory skew: long YES beyond the target shifts both quotes
+        // down to shed it, short of the target shifts them up to buy more.
+        let mut skew = Decimal::ZERO;
+        if self.config.max_inventory > Decimal::ZERO {
+            let excess = signed_inventory(position) - self.config.inventory_target;
+            let inventory_ratio =
+                (excess / self.config.max_inventory).clamp(-Decimal::TWO, Decimal::TWO);
+            skew = -inventory_ratio * self.config.inventory_skew_factor * half_spread;
         }
 
-        let mut bid = clamp_price(mid - half_spread + bid_skew);
-        let mut ask = clamp_price(mid + half_spread + ask_skew);
+        let mut bid = clamp_price(mid - half_spread + skew);
+        let mut ask = clamp_price(mid + half_spread + skew);
 
         // Ensure bid < ask.
         if bid >= ask {
@@ -365,7 +676,7 @@ impl MarketMakerStrategy {
 
             if bid >= ask {
                 let m = market.yes_mid_price().unwrap_or(mid);
-                let h = self.config.spread / Decimal::TWO;
+                let h = half_spread;
                 bid = clamp_price(
                     m - h
                         + market
@@ -390,19 +701,145 @@ impl MarketMakerStrategy {
             }
         }
 
-        Some((bid, ask))
+        let tick = self.tick_size(market);
+        Some((round_bid_to_tick(bid, tick), round_ask_to_tick(ask, tick)))
     }
 
-    fn calculate_quantity(&self, price: Decimal) -> i64 {
-        if price <= Decimal::ZERO {
+    /// The market's exchange tick size, or the configured one if unknown.
+    fn tick_size(&self, market: &MarketState) -> Decimal {
+        market.tick_size.unwrap_or(self.config.tick_size)
+    }
+
+    /// Bid and ask size multipliers from the tracked book's YES imbalance;
+    /// (1, 1) without a factor, a tracker or depth on the book.
+    fn imbalance_size_scales(&self, slug: &str) -> (Decimal, Decimal) {
+        let factor = self.config.imbalance_size_factor;
+        let imbalance = self
+            .orderbook
+            .as_ref()
+            .and_then(|ob| ob.get_top(slug))
+            .and_then(|top| top.yes_imbalance());
+        match imbalance {
+            Some(imbalance) if factor > Decimal::ZERO => {
+                let skew = factor * imbalance;
+                (
+                    (Decimal::ONE - skew).max(Decimal::ZERO),
+                    (Decimal::ONE + skew).max(Decimal::ZERO),
+                )
+            }
+            _ => (Decimal::ONE, Decimal::ONE),
+        }
+    }
+
+    /// Contracts for `order_size * scale` dollars at `price`, capped to
+    /// `max_depth_fraction` of the YES book's depth at the quote's level.
+    /// Zero when the scale is zero or the cap rounds below one contract.
+    fn calculate_quantity(
+        &self,
+        price: Decimal,
+        scale: Decimal,
+        book: Option<&OrderBook>,
+        is_bid: bool,
+    ) -> i64 {
+        if price <= Decimal::ZERO || scale <= Decimal::ZERO {
             return 0;
         }
-        let qty = (self.config.order_size / price)
-            .floor()
-            .to_string()
-            .parse::<i64>()
-            .unwrap_or(0);
-        qty.max(1)
+        let qty = floor_i64(self.config.order_size * scale / price).max(1);
+
+        if self.config.max_depth_fraction <= Decimal::ZERO {
+            return qty;
+        }
+        let levels = match book {
+            Some(b) if is_bid => &b.yes.bids,
+            Some(b) => &b.yes.asks,
+            None => return qty,
+        };
+        match quote_level_depth(levels, price, is_bid) {
+            Some(depth) => {
+                let cap = floor_i64(Decimal::from(depth) * self.config.max_depth_fraction);
+                qty.min(cap)
+            }
+            None => qty,
+        }
+    }
+
+    /// Prices and sizes of one side's ladder, inside level first: `inside`
+    /// stepped `level_spacing` per level away from mid (bids down, asks
+    /// up), each level `level_size_decay` the size of the one before.
+    /// Stops at the edge of the price range; zero-size levels are skipped.
+    fn quote_ladder(
+        &self,
+        inside: Decimal,
+        scale: Decimal,
+        tick: Decimal,
+        book: Option<&OrderBook>,
+        is_bid: bool,
+    ) -> Vec<(Decimal, i64)> {
+        let mut ladder: Vec<(Decimal, i64)> = Vec::new();
+        let mut level_scale = scale;
+        for level in 0..self.config.num_levels.max(1) {
+            let step = self.config.level_spacing * Decimal::from(level);
+            let price = if is_bid {
+                round_bid_to_tick(inside - step, tick)
+            } else {
+                round_ask_to_tick(inside + step, tick)
+            };
+            if clamp_price(price) != price {
+                break;
+            }
+            let qty = self.calculate_quantity(price, level_scale, book, is_bid);
+            level_scale *= self.config.level_size_decay;
+            let repeats = ladder.last().is_some_and(|&(last, _)| last == price);
+            if qty > 0 && !repeats {
+                ladder.push((price, qty));
+            }
+        }
+        ladder
+    }
+
+    /// Trim a ladder so that, if every level filled, inventory on the side
+    /// it adds to (`held` notional, negative when the position is on the
+    /// other side, plus bids' YES cost or asks' NO cost) stays within
+    /// `max_inventory`. The inside level only needs a position below the
+    /// limit, as a single quote always has.
+    fn limit_ladder_to_inventory(
+        &self,
+        ladder: Vec<(Decimal, i64)>,
+        held: Decimal,
+        is_bid: bool,
+    ) -> Vec<(Decimal, i64)> {
+        let max = self.config.max_inventory;
+        if held > Decimal::ZERO && held >= max {
+            return Vec::new();
+        }
+        let mut committed = held;
+        ladder
+            .into_iter()
+            .enumerate()
+            .take_while(|&(level, (price, qty))| {
+                let cost = if is_bid { price } else { Decimal::ONE - price };
+                committed += cost * Decimal::from(qty);
+                level == 0 || committed <= max
+            })
+            .map(|(_, quote)| quote)
+            .collect()
+    }
+
+    /// Whether either best level is known to be thinner than `min_top_depth`.
+    fn is_book_too_thin(&self, market: &MarketState) -> bool {
+        let min = self.config.min_top_depth;
+        min > 0
+            && [market.yes_bid_depth, market.yes_ask_depth]
+                .into_iter()
+                .flatten()
+                .any(|depth| depth < min)
+    }
+
+    /// Smallest relative book spread worth quoting: the round-trip fee
+    /// cost plus the safety margin, never below `min_spread_pct`.
+    pub fn min_profitable_spread_pct(&self) -> Decimal {
+        let fee_floor = self.config.fees.round_trip_rate() + self.config.spread_safety_margin;
+        fee_floor.max(self.config.min_spread_pct)
     }
 
     fn market_spread_pct(&self, market: &MarketState) -> Option<Decimal> {
@@ -417,22 +854,36 @@ impl MarketMakerStrategy {
         Some((ask - bid) / mid)
     }
 
-    fn should_refresh(&self, market: &MarketState, quote: &QuoteState) -> bool {
+    /// Whether to re-quote now. Tracks in `quote.moved_since` how long the
+    /// fair value has sat outside the tolerance.
+    fn should_refresh(&self, market: &MarketState, quote: &mut QuoteState) -> bool {
+        if quote.needs_refresh {
+            return true;
+        }
         if quote.bid_price.is_none() && quote.ask_price.is_none() {
             return true;
         }
-        let elapsed = (Utc::now() - quote.last_refresh).num_milliseconds() as f64 / 1000.0;
-        if elapsed >= self.config.refresh_interval_secs {
+        let elapsed = (self.clock.now() - quote.last_refresh).num_milliseconds() as f64 / 1000.0;
+        if elapsed >= self.config.refresh_interval_secs + quote.refresh_offset_secs {
             return true;
         }
-        if let (Some(current_mid), Some(last_mid)) =
-            (market.yes_mid_price(), quote.last_mid_price)
-        {
-            if (current_mid - last_mid).abs() >= self.config.price_tolerance {
-                return true;
-            }
+        // Against the anchor, so a lagging EWMA also lags re-quotes.
+        let (Some(current_mid), Some(last_mid)) = (self.fair_value(market), quote.last_mid_price)
+        else {
+            return false;
+        };
+        let moved = (current_mid - last_mid).abs();
+        let hysteresis = self.config.refresh_hysteresis;
+        if hysteresis > Decimal::ZERO && moved >= hysteresis {
+            return true;
         }
-        false
+        if moved < self.config.price_tolerance {
+            quote.moved_since = None;
+            return false;
+        }
+        let now = self.clock.now();
+        let since = *quote.moved_since.get_or_insert(now);
+        (now - since).num_milliseconds() as f64 / 1000.0 >= self.config.refresh_dwell_secs
     }
 
     fn is_market_enabled(&self, slug: &str) -> bool {
@@ -452,17 +903,37 @@ impl MarketMakerStrategy {
     }
 
     fn get_or_create_quote(&mut self, slug: &str) -> QuoteState {
+        let refresh_offset_secs = self.refresh_offset(slug);
         self.quotes
             .entry(slug.to_string())
             .or_insert_with(|| QuoteState {
                 bid_price: None,
                 ask_price: None,
-                last_refresh: Utc::now(),
+                last_refresh: self.clock.now(),
                 last_mid_price: None,
+                needs_refresh: false,
+                refresh_offset_secs,
+                moved_since: None,
             })
             .clone()
     }
 
+    /// Fixed refresh-interval offset for `slug`, uniform in
+    /// ±`refresh_jitter_pct` of the interval. Seeded from the slug, so a
+    /// market keeps its offset across restarts and quote resets.
+    fn refresh_offset(&self, slug: &str) -> f64 {
+        let jitter = self.config.refresh_jitter_pct.clamp(0.0, 1.0);
+        if jitter <= 0.0 {
+            return 0.0;
+        }
+        // FNV-1a of the slug as the seed.
+        let seed = slug.bytes().fold(0xCBF2_9CE4_8422_2325u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01B3)
+        });
+        let u = SplitMix64::new(seed).next_f64() * 2.0 - 1.0;
+        self.config.refresh_interval_secs * jitter * u
+    }
+
     fn quote_metadata(
         &self,
         market: &MarketState,
@@ -472,17 +943,17 @@ impl MarketMakerStrategy {
         if let Some(mid) = market.yes_mid_price() {
             m.insert(
                 "mid_price".to_string(),
-                serde_json::json!(mid.to_string().parse::<f64>().unwrap_or(0.0)),
+                serde_json::json!(to_f64(mid)),
             );
         }
         m.insert(
             "spread".to_string(),
-            serde_json::json!(self.config.spread.to_string().parse::<f64>().unwrap_or(0.0)),
+            serde_json::json!(to_f64(self.config.spread)),
         );
         if let Some(sp) = spread_pct {
             m.insert(
                 "spread_pct".to_string(),
-                serde_json::json!(sp.to_string().parse::<f64>().unwrap_or(0.0)),
+                serde_json::json!(to_f64(sp)),
             );
         }
         m.insert("maker_only".to_string(), serde_json::json!(self.config.maker_only));
@@ -491,9 +962,747 @@ impl MarketMakerStrategy {
     }
 }
 
+/// Position notional signed toward YES (positive) or NO (negative).
+fn signed_inventory(position: Option<&PositionState>) -> Decimal {
+    match position {
+        Some(pos) if pos.quantity > 0 => match pos.side {
+            crate::data::models::Side::Yes => pos.cost_basis(),
+            crate::data::models::Side::No => -pos.cost_basis(),
+        },
+        _ => Decimal::ZERO,
+    }
+}
+
 /// Clamp price to [0.01, 0.99] range (valid Polymarket binary contract prices).
 fn clamp_price(price: Decimal) -> Decimal {
     let min = Decimal::new(1, 2); // 0.01
     let max = Decimal::new(99, 2); // 0.99
     price.max(min).min(max)
 }
+
+/// Make a YES quote pair consistent with the complementary NO book.
+///
+/// Our ask is also a NO bid at `1 - ask` and our bid a NO ask at
+/// `1 - bid`, so the pair must satisfy `bid + no_bid < 1` and
+/// `ask + no_ask > 1` (no sure profit for whoever trades against us on both
+/// books) and `bid < ask` (no self-crossing). The bid is lowered and the
+/// ask raised to the nearest consistent tick; `None` when the adjusted pair
+/// crosses or leaves [0.01, 0.99].
+pub fn no_arbitrage_quotes(
+    bid: Decimal,
+    ask: Decimal,
+    no_bid: Option<Decimal>,
+    no_ask: Option<Decimal>,
+    tick: Decimal,
+) -> Option<(Decimal, Decimal)> {
+    let mut bid = bid;
+    let mut ask = ask;
+    if let Some(no_bid) = no_bid {
+        bid = bid.min(round_bid_to_tick(Decimal::ONE - no_bid - tick, tick));
+    }
+    if let Some(no_ask) = no_ask {
+        ask = ask.max(round_ask_to_tick(Decimal::ONE - no_ask + tick, tick));
+    }
+    let valid = bid < ask && clamp_price(bid) == bid && clamp_price(ask) == ask;
+    valid.then_some((bid, ask))
+}
+
+/// Round `price` to the nearest multiple of `tick`. A non-positive tick
+/// leaves the price unchanged.
+pub fn round_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    (price / tick).round() * tick
+}
+
+/// Round a bid down to `tick` so rounding never makes it more aggressive.
+pub fn round_bid_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    (price / tick).floor() * tick
+}
+
+/// Round an ask up to `tick` so rounding never makes it more aggressive.
+pub fn round_ask_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    (price / tick).ceil() * tick
+}
+
+/// Displayed size at `price` on the quote's side of the book, or at the
+/// best level when the quote improves on it.
+fn quote_level_depth(levels: &[PriceLevel], price: Decimal, is_bid: bool) -> Option<i64> {
+    if let Some(level) = levels.iter().find(|l| l.price == price) {
+        return Some(level.quantity);
+    }
+    let best = if is_bid {
+        levels.iter().max_by_key(|l| l.price)
+    } else {
+        levels.iter().min_by_key(|l| l.price)
+    };
+    best.map(|l| l.quantity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use crate::data::models::Side;
+    use std::sync::Arc;
+
+    fn market(yes_bid: Decimal, yes_ask: Decimal) -> MarketState {
+        MarketState {
+            market_slug: "test-market".to_string(),
+            title: "Test".to_string(),
+            yes_bid: Some(yes_bid),
+            yes_ask: Some(yes_ask),
+            no_bid: Some(Decimal::ONE - yes_ask),
+            no_ask: Some(Decimal::ONE - yes_bid),
+            yes_bid_depth: None,
+            yes_ask_depth: None,
+            resolution_date: None,
+            tick_size: None,
+            min_order_size: None,
+            last_updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_time_based_exit_after_max_underwater_hold() {
+        let clock = MockClock::default();
+        let mm =
+            MarketMakerStrategy::with_clock(MarketMakerConfig::default(), Arc::new(clock.clone()));
+
+        // Entry at 0.50, bid at 0.495 => -1% (above both stop-loss levels).
+        let position = PositionState {
+            market_slug: "test-market".to_string(),
+            side: Side::Yes,
+            quantity: 20,
+            avg_price: Decimal::new(50, 2),
+            created_at: clock.now(),
+        };
+        let m = market(Decimal::new(495, 3), Decimal::new(55, 2));
+
+        clock.advance(chrono::Duration::seconds(599));
+        assert!(mm.check_stop_loss(&position, &m).is_empty());
+
+        clock.advance(chrono::Duration::seconds(1));
+        let signals = mm.check_stop_loss(&position, &m);
+        assert_eq!(signals.len(), 1);
+        assert!(signals[0].reason.starts_with("Time-based exit"));
+        assert!(signals[0].reduce_only, "Risk exits must never open exposure");
+    }
+
+    #[test]
+    fn test_quotes_refresh_only_after_interval() {
+        let clock = MockClock::default();
+        let mut mm =
+            MarketMakerStrategy::with_clock(MarketMakerConfig::default(), Arc::new(clock.clone()));
+        let m = market(Decimal::new(45, 2), Decimal::new(55, 2));
+
+        let first = mm.on_market_update(&m, None);
+        assert!(!first.is_empty(), "First update quotes");
+        assert!(first.iter().all(|s| !s.is_replacement()));
+        assert!(mm.on_market_update(&m, None).is_empty(), "No refresh before interval");
+
+        // A refresh cancels first, so its quotes replace rather than stack.
+        clock.advance(chrono::Duration::seconds(5));
+        let refresh = mm.on_market_update(&m, None);
+        assert_eq!(refresh[0].action, SignalAction::CancelAll, "Refresh after 5s");
+        assert!(refresh[1..].iter().all(|s| s.is_replacement()));
+    }
+
+    #[test]
+    fn test_jittered_refreshes_spread_across_ticks() {
+        let refresh_ticks = |jitter: f64| {
+            let clock = MockClock::default();
+            let config = MarketMakerConfig {
+                refresh_jitter_pct: jitter,
+                ..MarketMakerConfig::default()
+            };
+            let mut mm = MarketMakerStrategy::with_clock(config, Arc::new(clock.clone()));
+            let markets: Vec<MarketState> = (0..100)
+                .map(|i| MarketState {
+                    market_slug: format!("aec-nba-t{i}-t{}-2026-10-16", i + 1),
+                    ..market(Decimal::new(45, 2), Decimal::new(55, 2))
+                })
+                .collect();
+            for m in &markets {
+                assert!(!mm.on_market_update(m, None).is_empty());
+            }
+
+            // 1s ticks: the tick on which each market first re-quotes.
+            let mut first_refresh: HashMap<String, i64> = HashMap::new();
+            for tick in 1..=10 {
+                clock.advance(chrono::Duration::seconds(1));
+                for m in &markets {
+                    if !mm.on_market_update(m, None).is_empty() {
+                        first_refresh.entry(m.market_slug.clone()).or_insert(tick);
+                    }
+                }
+            }
+            let mut per_tick: HashMap<i64, usize> = HashMap::new();
+            for tick in first_refresh.values() {
+                *per_tick.entry(*tick).or_default() += 1;
+            }
+            per_tick
+        };
+
+        // Without jitter every market re-quotes on the 5s tick.
+        assert_eq!(refresh_ticks(0.0), HashMap::from([(5, 100)]));
+
+        // ±50% of 5s spreads them over ticks 3..=8, none taking a majority.
+        let jittered = refresh_ticks(0.5);
+        assert_eq!(jittered.values().sum::<usize>(), 100);
+        assert!(jittered.len() >= 5, "{jittered:?}");
+        assert!(jittered.keys().all(|t| (3..=8).contains(t)), "{jittered:?}");
+        assert!(jittered.values().all(|&n| n <= 35), "{jittered:?}");
+    }
+
+    #[test]
+    fn test_refresh_hysteresis_ignores_oscillation_but_follows_sustained_moves() {
+        let clock = MockClock::default();
+        let config = MarketMakerConfig {
+            refresh_interval_secs: 600.0,
+            refresh_dwell_secs: 3.0,
+            refresh_hysteresis: Decimal::new(3, 2), // 0.03
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::with_clock(config, Arc::new(clock.clone()));
+        let at_mid = |cents: i64| market(Decimal::new(cents - 5, 2), Decimal::new(cents + 5, 2));
+        let tick = |mm: &mut MarketMakerStrategy, cents: i64| {
+            clock.advance(chrono::Duration::seconds(1));
+            !mm.on_market_update(&at_mid(cents), None).is_empty()
+        };
+        assert!(!mm.on_market_update(&at_mid(50), None).is_empty());
+
+        // The mid flips 0.50 <-> 0.51 every second: past the 0.005
+        // tolerance, but never for the 3s dwell.
+        for i in 0..10 {
+            assert!(!tick(&mut mm, 51 - i % 2), "oscillation re-quoted on tick {i}");
+        }
+
+        // Held at 0.51: re-quotes once it has been out for 3s.
+        assert!(!tick(&mut mm, 51));
+        assert!(!tick(&mut mm, 51));
+        assert!(!tick(&mut mm, 51));
+        assert!(tick(&mut mm, 51));
+
+        // A 0.03 jump clears the hysteresis band and re-quotes at once.
+        assert!(tick(&mut mm, 54));
+
+        // Without a dwell the first tick past the tolerance re-quotes.
+        let mut eager = MarketMakerStrategy::with_clock(
+            MarketMakerConfig {
+                refresh_interval_secs: 600.0,
+                ..MarketMakerConfig::default()
+            },
+            Arc::new(clock.clone()),
+        );
+        assert!(!eager.on_market_update(&at_mid(50), None).is_empty());
+        assert!(tick(&mut eager, 51));
+    }
+
+    #[test]
+    fn test_own_bid_fill_skews_next_quote_immediately() {
+        let clock = MockClock::default();
+        let config = MarketMakerConfig {
+            spread: Decimal::new(4, 2),
+            maker_only: false,
+            tick_size: Decimal::ZERO, // exact skew, no tick rounding
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::with_clock(config, Arc::new(clock.clone()));
+        let m = market(Decimal::new(45, 2), Decimal::new(55, 2));
+
+        let bid_of = |signals: &[Signal]| {
+            signals
+                .iter()
+                .find(|s| s.action == SignalAction::BuyYes)
+                .map(|s| s.price)
+                .unwrap()
+        };
+
+        let initial_bid = bid_of(&mm.on_market_update(&m, None));
+        assert_eq!(initial_bid, Decimal::new(48, 2));
+
+        // Our bid fills: 20 YES @ 0.48 and the StateManager position now
+        // reflects it. No time passes, yet the next update must re-quote
+        // with skew.
+        mm.on_fill(&FillEvent {
+            order_id: "paper-000001".to_string(),
+            market_slug: "test-market".to_string(),
+            strategy_name: "market_maker".to_string(),
+            side: Side::Yes,
+            is_buy: true,
+            price: Decimal::new(48, 2),
+            quantity: 20,
+            fee: Decimal::ZERO,
+            timestamp: clock.now(),
+        });
+
+        let held = PositionState {
+            market_slug: "test-market".to_string(),
+            side: Side::Yes,
+            quantity: 20,
+            avg_price: Decimal::new(48, 2),
+            created_at: clock.now(),
+        };
+        let signals = mm.on_market_update(&m, Some(&held));
+        assert!(!signals.is_empty(), "Fill must force an immediate refresh");
+        // Inventory $9.60 / $50 max = 0.192; skew = 0.192 * 0.5 * 0.02 = 0.00192
+        assert_eq!(bid_of(&signals), Decimal::new(48, 2) - Decimal::new(192, 5));
+    }
+
+    #[test]
+    fn test_quotes_skew_toward_nonzero_inventory_target() {
+        let config = MarketMakerConfig {
+            spread: Decimal::new(4, 2),
+            maker_only: false,
+            tick_size: Decimal::ZERO, // exact skew, no tick rounding
+            inventory_target: Decimal::new(20, 0), // lean $20 YES
+            ..MarketMakerConfig::default()
+        };
+        let mm = MarketMakerStrategy::new(config);
+        let m = market(Decimal::new(45, 2), Decimal::new(55, 2));
+        let yes = |quantity: i64| PositionState {
+            market_slug: "test-market".to_string(),
+            side: Side::Yes,
+            quantity,
+            avg_price: Decimal::new(50, 2),
+            created_at: Utc::now(),
+        };
+
+        // Flat: (0 - 20) / 50 = -0.4; skew = 0.4 * 0.5 * 0.02 = +0.004
+        // Quotes lift above 0.48 / 0.52 to accumulate YES.
+        assert_eq!(
+            mm.calculate_quotes(&m, None),
+            Some((Decimal::new(484, 3), Decimal::new(524, 3)))
+        );
+
+        // At target ($20 = 40 @ 0.50): no skew.
+        assert_eq!(
+            mm.calculate_quotes(&m, Some(&yes(40))),
+            Some((Decimal::new(48, 2), Decimal::new(52, 2)))
+        );
+
+        // Past target ($30): (30 - 20) / 50 = 0.2; skew = -0.002 to shed.
+        assert_eq!(
+            mm.calculate_quotes(&m, Some(&yes(60))),
+            Some((Decimal::new(478, 3), Decimal::new(518, 3)))
+        );
+    }
+
+    #[test]
+    fn test_fills_from_other_strategies_are_ignored() {
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+        mm.on_fill(&FillEvent {
+            order_id: "x".to_string(),
+            market_slug: "test-market".to_string(),
+            strategy_name: "live_arbitrage".to_string(),
+            side: Side::Yes,
+            is_buy: true,
+            price: Decimal::new(50, 2),
+            quantity: 10,
+            fee: Decimal::ZERO,
+            timestamp: Utc::now(),
+        });
+        assert!(mm.adverse.is_empty());
+    }
+
+    #[test]
+    fn test_books_wider_than_max_spread_are_not_quoted() {
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+            max_spread: Decimal::new(10, 2),
+            maker_only: false,
+            ..MarketMakerConfig::default()
+        });
+        let wide = market(Decimal::new(40, 2), Decimal::new(60, 2));
+        assert!(mm.on_market_update(&wide, None).is_empty());
+
+        let tight = market(Decimal::new(48, 2), Decimal::new(52, 2));
+        let quotes = mm.on_market_update(&tight, None);
+        assert!(quotes.iter().any(|s| s.action == SignalAction::BuyYes));
+        assert!(quotes.iter().any(|s| s.action == SignalAction::SellYes));
+    }
+
+    #[test]
+    fn test_adverse_fills_widen_spread_then_decay() {
+        let clock = MockClock::default();
+        let config = MarketMakerConfig {
+            spread: Decimal::new(4, 2),
+            max_spread: Decimal::new(20, 2),
+            maker_only: false,
+            adverse_selection_widen_factor: Decimal::TWO,
+            adverse_selection_half_life_secs: 60.0,
+            tick_size: Decimal::ZERO, // exact spreads, no tick rounding
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::with_clock(config, Arc::new(clock.clone()));
+
+        let quoted_spread = |signals: &[Signal]| {
+            let bid = signals.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+            let ask = signals.iter().find(|s| s.action == SignalAction::SellYes).unwrap();
+            ask.price - bid.price
+        };
+        let bid_fill = |price: Decimal| FillEvent {
+            order_id: "paper-000001".to_string(),
+            market_slug: "test-market".to_string(),
+            strategy_name: "market_maker".to_string(),
+            side: Side::Yes,
+            is_buy: true,
+            price,
+            quantity: 1,
+            fee: Decimal::ZERO,
+            timestamp: clock.now(),
+        };
+
+        let m = market(Decimal::new(45, 2), Decimal::new(55, 2));
+        assert_eq!(quoted_spread(&mm.on_market_update(&m, None)), Decimal::new(4, 2));
+
+        // Bid filled at 0.48, then the market drops to mid 0.46: underwater.
+        mm.on_fill(&bid_fill(Decimal::new(48, 2)));
+        let m = market(Decimal::new(41, 2), Decimal::new(51, 2));
+        assert_eq!(
+            quoted_spread(&mm.on_market_update(&m, None)),
+            Decimal::new(8, 2),
+            "One adverse fill doubles the spread"
+        );
+
+        // Picked off again at 0.44, market drops to mid 0.42.
+        mm.on_fill(&bid_fill(Decimal::new(44, 2)));
+        let m = market(Decimal::new(37, 2), Decimal::new(47, 2));
+        assert_eq!(quoted_spread(&mm.on_market_update(&m, None)), Decimal::new(16, 2));
+
+        // Benign fill (market moves up after our bid) does not widen further.
+        mm.on_fill(&bid_fill(Decimal::new(34, 2)));
+        let m = market(Decimal::new(38, 2), Decimal::new(48, 2));
+        assert_eq!(quoted_spread(&mm.on_market_update(&m, None)), Decimal::new(16, 2));
+
+        // One half-life: multiplier 1 + 3 * 0.5 = 2.5 => 0.10
+        clock.advance(chrono::Duration::seconds(60));
+        assert_eq!(quoted_spread(&mm.on_market_update(&m, None)), Decimal::new(10, 2));
+
+        // Quiet period of 15 half-lives restores the base spread.
+        clock.advance(chrono::Duration::seconds(900));
+        assert_eq!(quoted_spread(&mm.on_market_update(&m, None)), Decimal::new(4, 2));
+    }
+
+    #[test]
+    fn test_thin_top_of_book_is_not_quoted() {
+        let config = MarketMakerConfig {
+            min_top_depth: 10,
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::new(config);
+
+        let mut thin = market(Decimal::new(45, 2), Decimal::new(55, 2));
+        thin.yes_bid_depth = Some(500);
+        thin.yes_ask_depth = Some(1);
+        assert!(mm.on_market_update(&thin, None).is_empty(), "1-contract ask is too thin");
+
+        let mut deep = thin.clone();
+        deep.yes_ask_depth = Some(10);
+        assert!(!mm.on_market_update(&deep, None).is_empty());
+    }
+
+    #[test]
+    fn test_quote_size_capped_to_depth_fraction() {
+        use crate::data::models::OrderBookSide;
+
+        let book = |depth: i64| OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(45, 2), quantity: depth }],
+                asks: vec![PriceLevel { price: Decimal::new(55, 2), quantity: depth }],
+            },
+            no: OrderBookSide::default(),
+        };
+        let quoted_qty = |depth: i64| {
+            let tracker = OrderBookTracker::new();
+            tracker.update(book(depth));
+            let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+                max_depth_fraction: Decimal::new(25, 2),
+                ..MarketMakerConfig::default()
+            });
+            mm.set_orderbook(tracker);
+            mm.on_market_update(&market(Decimal::new(45, 2), Decimal::new(55, 2)), None)
+                .iter()
+                .find(|s| s.action == SignalAction::BuyYes)
+                .map(|s| s.quantity)
+        };
+
+        // The bid joins 0.45: $10 / 0.45 = 22 contracts uncapped.
+        // Thin: 0.25 * 40 = 10 contracts.
+        assert_eq!(quoted_qty(40), Some(10));
+        // Deep: 0.25 * 1000 = 250 leaves the full 22.
+        assert_eq!(quoted_qty(1000), Some(22));
+        // Cap below one contract: that side is not quoted.
+        assert_eq!(quoted_qty(3), None);
+    }
+
+    #[test]
+    fn test_bid_heavy_book_shrinks_bid_and_grows_ask() {
+        use crate::data::models::OrderBookSide;
+
+        // 900 bid vs 100 ask at the top: imbalance (900 - 100) / 1000 = 0.8.
+        let tracker = OrderBookTracker::new();
+        tracker.update(OrderBook {
+            market_slug: "test-market".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: Decimal::new(45, 2), quantity: 900 }],
+                asks: vec![PriceLevel { price: Decimal::new(55, 2), quantity: 100 }],
+            },
+            no: OrderBookSide::default(),
+        });
+        let sizes = |factor: Decimal| {
+            let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+                imbalance_size_factor: factor,
+                ..MarketMakerConfig::default()
+            });
+            mm.set_orderbook(tracker.clone());
+            let signals =
+                mm.on_market_update(&market(Decimal::new(45, 2), Decimal::new(55, 2)), None);
+            let qty = |action: SignalAction| {
+                signals.iter().find(|s| s.action == action).map(|s| s.quantity)
+            };
+            (qty(SignalAction::BuyYes), qty(SignalAction::SellYes))
+        };
+
+        // Symmetric $10 without the factor: 22 bid at 0.45, 18 ask at 0.55.
+        assert_eq!(sizes(Decimal::ZERO), (Some(22), Some(18)));
+        // Factor 0.5 skews by 0.4: bid $6 / 0.45 = 13, ask $14 / 0.55 = 25.
+        assert_eq!(sizes(Decimal::new(5, 1)), (Some(13), Some(25)));
+        // Full factor: bid $10 * 0.2 / 0.45 = 4, ask $10 * 1.8 / 0.55 = 32.
+        assert_eq!(sizes(Decimal::ONE), (Some(4), Some(32)));
+    }
+
+    #[test]
+    fn test_mid_ewma_lags_a_step_change() {
+        let bid_after_step = |alpha: Decimal| {
+            let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+                mid_ewma_alpha: alpha,
+                ..MarketMakerConfig::default()
+            });
+            let before = market(Decimal::new(49, 2), Decimal::new(51, 2));
+            let after = market(Decimal::new(59, 2), Decimal::new(61, 2));
+            mm.on_market_update(&before, None);
+            assert_eq!(mm.fair_value(&before), Some(Decimal::new(50, 2)));
+
+            let signals = mm.on_market_update(&after, None);
+            let fair = mm.fair_value(&after).unwrap();
+            let bid = signals
+                .iter()
+                .find(|s| s.action == SignalAction::BuyYes)
+                .map(|s| s.price);
+            (fair, bid)
+        };
+
+        // alpha 1: the anchor jumps to the new mid 0.60, bid 0.59.
+        assert_eq!(
+            bid_after_step(Decimal::ONE),
+            (Decimal::new(60, 2), Some(Decimal::new(59, 2)))
+        );
+        // alpha 0.25: 0.25 * 0.60 + 0.75 * 0.50 = 0.525; bid 0.515 rounds
+        // down to 0.51, well behind the market.
+        assert_eq!(
+            bid_after_step(Decimal::new(25, 2)),
+            (Decimal::new(525, 3), Some(Decimal::new(51, 2)))
+        );
+    }
+
+    #[test]
+    fn test_mid_ewma_converges_to_a_sustained_mid() {
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+            mid_ewma_alpha: Decimal::new(5, 1),
+            ..MarketMakerConfig::default()
+        });
+        mm.on_market_update(&market(Decimal::new(49, 2), Decimal::new(51, 2)), None);
+        let after = market(Decimal::new(59, 2), Decimal::new(61, 2));
+        // 0.50 -> 0.55 -> 0.575 -> 0.5875 -> 0.59375: halving the gap each update.
+        let mut fair = Vec::new();
+        for _ in 0..4 {
+            mm.on_market_update(&after, None);
+            fair.push(mm.fair_value(&after).unwrap());
+        }
+        assert_eq!(
+            fair,
+            vec![
+                Decimal::new(55, 2),
+                Decimal::new(575, 3),
+                Decimal::new(5875, 4),
+                Decimal::new(59375, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_off_tick_prices_round_away_from_crossing() {
+        let tick = Decimal::new(1, 2);
+        let price = Decimal::new(5237, 4); // 0.5237
+        assert_eq!(round_bid_to_tick(price, tick), Decimal::new(52, 2));
+        assert_eq!(round_ask_to_tick(price, tick), Decimal::new(53, 2));
+        assert_eq!(round_to_tick(price, tick), Decimal::new(52, 2));
+        // On-tick prices are unchanged; a zero tick disables rounding.
+        assert_eq!(round_ask_to_tick(Decimal::new(53, 2), tick), Decimal::new(53, 2));
+        assert_eq!(round_bid_to_tick(price, Decimal::ZERO), price);
+    }
+
+    #[test]
+    fn test_quotes_are_rounded_to_tick() {
+        let config = MarketMakerConfig {
+            maker_only: false,
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::new(config);
+        // Mid 0.5237, half spread 0.01 => raw bid 0.5137, raw ask 0.5337.
+        let m = market(Decimal::new(5037, 4), Decimal::new(5437, 4));
+
+        let signals = mm.on_market_update(&m, None);
+        let price_of = |action: SignalAction| {
+            signals.iter().find(|s| s.action == action).map(|s| s.price).unwrap()
+        };
+        assert_eq!(price_of(SignalAction::BuyYes), Decimal::new(51, 2));
+        assert_eq!(price_of(SignalAction::SellYes), Decimal::new(54, 2));
+    }
+
+    #[test]
+    fn test_quotes_clamped_or_dropped_when_inconsistent_with_no_book() {
+        let d = |n: i64| Decimal::new(n, 2);
+        let tick = d(1);
+        // Consistent books leave the pair alone.
+        assert_eq!(
+            no_arbitrage_quotes(d(49), d(51), Some(d(49)), Some(d(51)), tick),
+            Some((d(49), d(51)))
+        );
+        // 0.50 + NO bid 0.55 > 1: bid lowered to 0.44 (0.44 + 0.55 = 0.99).
+        // 0.52 + NO ask 0.45 < 1: ask raised to 0.56 (0.56 + 0.45 = 1.01).
+        assert_eq!(
+            no_arbitrage_quotes(d(50), d(52), Some(d(55)), Some(d(45)), tick),
+            Some((d(44), d(56)))
+        );
+        // Self-crossing, or no room left below a 0.995 NO bid: dropped.
+        assert_eq!(no_arbitrage_quotes(d(55), d(50), None, None, tick), None);
+        assert_eq!(
+            no_arbitrage_quotes(d(2), d(10), Some(Decimal::new(995, 3)), None, tick),
+            None
+        );
+
+        // Through the quote path: YES 0.45 / 0.55 with a stray NO bid of
+        // 0.60. Mid quotes 0.49 / 0.51; the bid is capped at 1 - 0.60 - 0.01.
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+            maker_only: false,
+            ..MarketMakerConfig::default()
+        });
+        let mut m = market(d(45), d(55));
+        m.no_bid = Some(d(60));
+        let signals = mm.on_market_update(&m, None);
+        let price_of = |action: SignalAction| {
+            signals.iter().find(|s| s.action == action).map(|s| s.price).unwrap()
+        };
+        assert_eq!(price_of(SignalAction::BuyYes), d(39));
+        assert_eq!(price_of(SignalAction::SellYes), d(51));
+    }
+
+    #[test]
+    fn test_fee_rate_sets_minimum_quotable_spread() {
+        // Book 0.49 / 0.51: relative spread 0.02 / 0.50 = 4%.
+        let m = market(Decimal::new(49, 2), Decimal::new(51, 2));
+        let mm_with_taker_fee = |taker_fee_rate: Decimal| {
+            MarketMakerStrategy::new(MarketMakerConfig {
+                min_spread_pct: Decimal::ZERO,
+                fees: FeeSchedule {
+                    maker_fee_rate: Decimal::ZERO,
+                    taker_fee_rate,
+                },
+                spread_safety_margin: Decimal::new(2, 3),
+                ..MarketMakerConfig::default()
+            })
+        };
+
+        // 50 bps taker: floor 2 * 0.005 + 0.002 = 1.2% < 4%, quoted.
+        let mut cheap = mm_with_taker_fee(Decimal::new(5, 3));
+        assert_eq!(cheap.min_profitable_spread_pct(), Decimal::new(12, 3));
+        assert!(!cheap.on_market_update(&m, None).is_empty());
+
+        // 200 bps taker: floor 2 * 0.02 + 0.002 = 4.2% > 4%, skipped.
+        let mut costly = mm_with_taker_fee(Decimal::new(2, 2));
+        assert_eq!(costly.min_profitable_spread_pct(), Decimal::new(42, 3));
+        assert!(costly.on_market_update(&m, None).is_empty());
+    }
+
+    /// (price, quantity) of each quote with `action`, in emitted order.
+    fn ladder_of(signals: &[Signal], action: SignalAction) -> Vec<(Decimal, i64)> {
+        signals
+            .iter()
+            .filter(|s| s.action == action)
+            .map(|s| (s.price, s.quantity))
+            .collect()
+    }
+
+    #[test]
+    fn test_three_level_ladder_steps_prices_and_decays_sizes() {
+        let d = |cents: i64| Decimal::new(cents, 2);
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+            num_levels: 3,
+            level_spacing: d(1),
+            level_size_decay: Decimal::new(5, 1),
+            ..MarketMakerConfig::default()
+        });
+        let signals = mm.on_market_update(&market(d(45), d(55)), None);
+
+        // $10, $5, $2.50 per level: 10 / 0.45 = 22, 5 / 0.44 = 11, 2.5 / 0.43 = 5.
+        assert_eq!(
+            ladder_of(&signals, SignalAction::BuyYes),
+            vec![(d(45), 22), (d(44), 11), (d(43), 5)]
+        );
+        // 10 / 0.55 = 18, 5 / 0.56 = 8, 2.5 / 0.57 = 4.
+        assert_eq!(
+            ladder_of(&signals, SignalAction::SellYes),
+            vec![(d(55), 18), (d(56), 8), (d(57), 4)]
+        );
+        let levels: Vec<_> = signals.iter().map(|s| s.metadata["level"].clone()).collect();
+        assert_eq!(levels, [1, 2, 3, 1, 2, 3].map(|l| serde_json::json!(l)));
+    }
+
+    #[test]
+    fn test_ladder_skews_as_a_unit_and_fits_inventory_limit() {
+        let config = MarketMakerConfig {
+            spread: Decimal::new(4, 2),
+            maker_only: false,
+            tick_size: Decimal::ZERO, // exact skew, no tick rounding
+            num_levels: 3,
+            ..MarketMakerConfig::default()
+        };
+        let mut mm = MarketMakerStrategy::new(config);
+        // $25 YES of a $50 max: skew = -0.5 * 0.5 * 0.02 = -0.005.
+        let position = PositionState {
+            market_slug: "test-market".to_string(),
+            side: Side::Yes,
+            quantity: 50,
+            avg_price: Decimal::new(50, 2),
+            created_at: Utc::now(),
+        };
+        let signals =
+            mm.on_market_update(&market(Decimal::new(45, 2), Decimal::new(55, 2)), Some(&position));
+
+        // Bids 0.475 / 0.465 / 0.455 at 21 each: $25 + $9.98 + $9.77 fits,
+        // the third level's $9.56 would take inventory to $54.30.
+        assert_eq!(
+            ladder_of(&signals, SignalAction::BuyYes),
+            vec![(Decimal::new(475, 3), 21), (Decimal::new(465, 3), 21)]
+        );
+        // Asks reduce the position, so the whole shifted ladder is quoted.
+        let asks: Vec<_> = ladder_of(&signals, SignalAction::SellYes)
+            .into_iter()
+            .map(|(price, _)| price)
+            .collect();
+        assert_eq!(
+            asks,
+            vec![Decimal::new(515, 3), Decimal::new(525, 3), Decimal::new(535, 3)]
+        );
+    }
+}
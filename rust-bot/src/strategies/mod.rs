@@ -1,4 +1,6 @@
 pub mod market_maker;
 pub mod live_arbitrage;
 pub mod statistical_edge;
+pub mod completeness_arb;
+pub mod net_edge;
 pub mod engine;
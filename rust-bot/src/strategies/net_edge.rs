@@ -0,0 +1,121 @@
+//! Shared minimum net edge gate.
+//!
+//! Each strategy fires on its own gross edge (`true_probability - price`),
+//! but what a fill actually earns is smaller: fees are paid on the way in
+//! and out, a large order walks the book past the quoted price, and the
+//! counterparty who lets us trade is often better informed. The engine
+//! runs every entry that carries a `true_probability` through one gate
+//! that subtracts those costs and drops signals below a single floor,
+//! before they reach the risk manager.
+//!
+//! Signals without an edge estimate (market maker quotes, completeness
+//! arb legs), sells, reduce-only exits and cancels pass untouched.
+
+#![allow(dead_code)]
+
+use rust_decimal::Decimal;
+
+use crate::data::models::{FeeSchedule, Signal};
+use crate::data::orderbook::OrderBookTracker;
+use crate::util::dec::from_f64;
+
+/// Costs charged against gross edge and the floor the remainder must meet.
+#[derive(Debug, Clone)]
+pub struct NetEdgeConfig {
+    /// Smallest net edge (in price) a signal may carry.
+    pub min_net_edge: Decimal,
+    /// Round-trip fees, charged as a fraction of the entry price.
+    pub fees: FeeSchedule,
+    /// Flat allowance (in price) for being picked off by better-informed
+    /// flow.
+    pub adverse_selection_haircut: Decimal,
+}
+
+impl Default for NetEdgeConfig {
+    fn default() -> Self {
+        Self {
+            min_net_edge: Decimal::new(1, 2), // 0.01
+            fees: FeeSchedule {
+                maker_fee_rate: Decimal::ZERO,
+                taker_fee_rate: Decimal::new(1, 3), // 10 bps
+            },
+            adverse_selection_haircut: Decimal::new(5, 3), // 0.005
+        }
+    }
+}
+
+/// A signal's edge broken down by cost, all in price units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetEdge {
+    pub gross: Decimal,
+    pub fees: Decimal,
+    /// Fill VWAP for the full quantity above the best level on the book.
+    pub impact: Decimal,
+    pub adverse_selection: Decimal,
+    pub net: Decimal,
+}
+
+/// Drops entries whose edge does not survive fees, impact and adverse
+/// selection.
+#[derive(Debug, Clone)]
+pub struct NetEdgeGate {
+    config: NetEdgeConfig,
+    /// Books to estimate impact from; without one impact counts as zero.
+    orderbook: Option<OrderBookTracker>,
+}
+
+impl NetEdgeGate {
+    pub fn new(config: NetEdgeConfig) -> Self {
+        Self {
+            config,
+            orderbook: None,
+        }
+    }
+
+    pub fn with_orderbook(mut self, orderbook: OrderBookTracker) -> Self {
+        self.orderbook = Some(orderbook);
+        self
+    }
+
+    /// Net edge of an entry carrying `true_probability`; `None` for
+    /// signals the gate does not judge.
+    pub fn net_edge(&self, signal: &Signal) -> Option<NetEdge> {
+        if !signal.is_buy() || signal.reduce_only {
+            return None;
+        }
+        let side = signal.action.to_intent()?.side();
+        let true_prob = signal
+            .metadata
+            .get("true_probability")
+            .and_then(|v| v.as_f64())
+            .and_then(from_f64)?;
+
+        let gross = true_prob - signal.price;
+        let fees = self.config.fees.round_trip_rate() * signal.price;
+        let impact = self
+            .orderbook
+            .as_ref()
+            .and_then(|ob| {
+                let (_, best) = ob.vwap_for_qty(&signal.market_slug, side, true, 1)?;
+                let (_, vwap) =
+                    ob.vwap_for_qty(&signal.market_slug, side, true, signal.quantity)?;
+                Some((vwap - best).max(Decimal::ZERO))
+            })
+            .unwrap_or(Decimal::ZERO);
+        let adverse_selection = self.config.adverse_selection_haircut;
+        Some(NetEdge {
+            gross,
+            fees,
+            impact,
+            adverse_selection,
+            net: gross - fees - impact - adverse_selection,
+        })
+    }
+
+    /// Whether the signal may proceed: not judged, or net edge at or above
+    /// the floor.
+    pub fn passes(&self, signal: &Signal) -> bool {
+        self.net_edge(signal)
+            .is_none_or(|edge| edge.net >= self.config.min_net_edge)
+    }
+}
@@ -2,15 +2,41 @@
 //!
 //! Generates signals when sportsbook implied probabilities diverge
 //! significantly from Polymarket contract prices.
+//!
+//! Odds are kept per provider. By default the fair value is the consensus
+//! (mean) of all books; in line-shopping mode the single book offering the
+//! largest edge against Polymarket is used instead.
+//!
+//! A snapshot's confidence decays exponentially with its age, so a line
+//! polled minutes ago carries less weight than a fresh one. A signal needs
+//! `edge * confidence >= min_edge`; stale lines fall below that floor.
+//!
+//! Pairs mode trades declared pairs of correlated markets against each
+//! other: when the spread `mid_a - hedge_ratio * mid_b` strays more than
+//! `pair_entry_z` standard deviations from its rolling mean, the cheap leg
+//! is bought and the rich leg shorted (bought NO) for equal dollars. The
+//! trade is unwound once the spread reverts within `pair_exit_z`, and stays
+//! tracked until the exit fills have sold both legs.
 
 #![allow(dead_code)]
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use crate::data::models::{Signal, SignalAction, Urgency};
+use crate::data::models::{FillEvent, Side, Signal, SignalAction, Urgency};
 use crate::state::state_manager::MarketState;
+use crate::util::dec::{floor_i64, to_f64};
+
+/// How odds from multiple bookmakers are combined into a fair value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OddsAggregation {
+    /// Average devigged probability across all books.
+    #[default]
+    Consensus,
+    /// Most favorable single book against the current Polymarket prices.
+    BestLine,
+}
 
 /// Configuration for statistical edge strategy.
 #[derive(Debug, Clone)]
@@ -19,6 +45,18 @@ pub struct StatisticalEdgeConfig {
     pub order_size: Decimal,
     pub cooldown_seconds: f64,
     pub enabled_markets: Vec<String>,
+    pub aggregation: OddsAggregation,
+    /// Snapshot age at which confidence has halved. Zero disables decay.
+    pub confidence_half_life_seconds: f64,
+    /// Market pairs traded in pairs mode. Each leg gets `order_size`
+    /// dollars; the hedge ratio only shapes the spread.
+    pub pairs: Vec<PairConfig>,
+    /// |z| of the spread at which a pair trade is opened.
+    pub pair_entry_z: f64,
+    /// |z| at or below which an open pair trade is unwound.
+    pub pair_exit_z: f64,
+    /// Spread samples in the rolling window; no trades until it is full.
+    pub pair_lookback: usize,
 }
 
 impl Default for StatisticalEdgeConfig {
@@ -28,10 +66,113 @@ impl Default for StatisticalEdgeConfig {
             order_size: Decimal::new(10, 0), // $10
             cooldown_seconds: 10.0,
             enabled_markets: Vec::new(),
+            aggregation: OddsAggregation::Consensus,
+            confidence_half_life_seconds: 300.0,
+            pairs: Vec::new(),
+            pair_entry_z: 2.0,
+            pair_exit_z: 0.5,
+            pair_lookback: 30,
         }
     }
 }
 
+/// Two correlated markets traded against each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairConfig {
+    pub leg_a: String,
+    pub leg_b: String,
+    /// Spread is `mid_a - hedge_ratio * mid_b`.
+    pub hedge_ratio: Decimal,
+}
+
+impl PairConfig {
+    /// Parse `slug_a:slug_b:hedge_ratio`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (legs, ratio) = s.rsplit_once(':')?;
+        let (leg_a, leg_b) = legs.split_once(':')?;
+        if leg_a.is_empty() || leg_b.is_empty() {
+            return None;
+        }
+        Some(Self {
+            leg_a: leg_a.to_string(),
+            leg_b: leg_b.to_string(),
+            hedge_ratio: ratio.trim().parse().ok()?,
+        })
+    }
+
+    fn key(&self) -> String {
+        format!("{}/{}", self.leg_a, self.leg_b)
+    }
+}
+
+/// Fixed-size window of recent values with mean and standard deviation.
+#[derive(Debug, Clone)]
+pub struct RollingWindow {
+    capacity: usize,
+    values: VecDeque<f64>,
+}
+
+impl RollingWindow {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(2),
+            values: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        Some(self.values.iter().sum::<f64>() / self.values.len() as f64)
+    }
+
+    /// Sample standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        if self.values.len() < 2 {
+            return None;
+        }
+        let mean = self.mean()?;
+        let var = self.values.iter().map(|v| (v - mean).powi(2)).sum::<f64>()
+            / (self.values.len() - 1) as f64;
+        Some(var.sqrt())
+    }
+
+    /// Standard score of `value` against the window. `None` until the
+    /// window is full or while it has no dispersion.
+    pub fn z_score(&self, value: f64) -> Option<f64> {
+        if !self.is_full() {
+            return None;
+        }
+        let std = self.std_dev()?;
+        if std <= 0.0 {
+            return None;
+        }
+        Some((value - self.mean()?) / std)
+    }
+}
+
+/// An open pair trade: YES bought on `long_leg`, NO bought on `short_leg`.
+/// Quantities are what each leg holds: entry fills less exit fills.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairTrade {
+    pub long_leg: String,
+    pub short_leg: String,
+    pub long_quantity: i64,
+    pub short_quantity: i64,
+}
+
 /// Snapshot of odds from an external sportsbook.
 #[derive(Debug, Clone)]
 pub struct OddsSnapshot {
@@ -43,12 +184,93 @@ pub struct OddsSnapshot {
     pub timestamp: DateTime<Utc>,
 }
 
+/// The single book offering the largest edge, with the consensus alongside.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestLine {
+    pub provider: String,
+    /// Devigged YES probability from `provider`.
+    pub yes_probability: Decimal,
+    /// Polymarket side the edge is on.
+    pub side: Side,
+    /// Fair probability of `side` minus its Polymarket ask.
+    pub edge: Decimal,
+    /// Consensus YES probability across all books, for comparison.
+    pub consensus: Decimal,
+}
+
+/// Confidence after exponential decay over `age_seconds`: halves every
+/// `half_life_seconds`. A non-positive half-life leaves it unchanged.
+pub fn decayed_confidence(confidence: f64, age_seconds: f64, half_life_seconds: f64) -> f64 {
+    if half_life_seconds <= 0.0 {
+        return confidence;
+    }
+    confidence * 0.5_f64.powf(age_seconds.max(0.0) / half_life_seconds)
+}
+
+/// Lowest confidence at which `edge` still clears `min_edge` once weighted
+/// by confidence, i.e. `min_edge / edge`.
+pub fn min_confidence_for_edge(edge: Decimal, min_edge: Decimal) -> f64 {
+    if edge <= Decimal::ZERO {
+        return f64::INFINITY;
+    }
+    to_f64(min_edge / edge)
+}
+
+/// Mean devigged YES probability across all books.
+pub fn consensus_probability(snapshots: &[OddsSnapshot]) -> Option<Decimal> {
+    if snapshots.is_empty() {
+        return None;
+    }
+    let sum: Decimal = snapshots.iter().map(|s| s.yes_probability).sum();
+    Some(sum / Decimal::from(snapshots.len() as u64))
+}
+
+/// The single book whose devigged probability gives the largest edge
+/// against the market's YES/NO asks.
+///
+/// Returns `None` when there are no books or the market has no asks.
+pub fn best_line_probability(snapshots: &[OddsSnapshot], market: &MarketState) -> Option<BestLine> {
+    let consensus = consensus_probability(snapshots)?;
+    let no_ask = market
+        .no_ask
+        .or_else(|| market.yes_bid.map(|b| Decimal::ONE - b));
+
+    let mut best: Option<BestLine> = None;
+    for snapshot in snapshots {
+        let p = snapshot.yes_probability;
+        let candidates = [
+            (Side::Yes, market.yes_ask.map(|ask| p - ask)),
+            (Side::No, no_ask.map(|ask| (Decimal::ONE - p) - ask)),
+        ];
+        for (side, edge) in candidates {
+            let Some(edge) = edge else { continue };
+            if best.as_ref().is_none_or(|b| edge > b.edge) {
+                best = Some(BestLine {
+                    provider: snapshot.provider.clone(),
+                    yes_probability: p,
+                    side,
+                    edge,
+                    consensus,
+                });
+            }
+        }
+    }
+    best
+}
+
 /// Statistical edge strategy.
 pub struct StatisticalEdgeStrategy {
     config: StatisticalEdgeConfig,
     enabled: bool,
-    latest_odds: HashMap<String, OddsSnapshot>,
+    /// Latest snapshot per market key, then per provider.
+    latest_odds: HashMap<String, HashMap<String, OddsSnapshot>>,
     last_signal_at: HashMap<String, DateTime<Utc>>,
+    /// Spread history and open trade per pair key.
+    pair_spreads: HashMap<String, RollingWindow>,
+    pair_trades: HashMap<String, PairTrade>,
+    /// Entry legs awaiting fills, by (market, side): pair key and
+    /// contracts still expected.
+    pair_orders: HashMap<(String, Side), (String, i64)>,
 }
 
 impl StatisticalEdgeStrategy {
@@ -58,6 +280,9 @@ impl StatisticalEdgeStrategy {
             enabled: true,
             latest_odds: HashMap::new(),
             last_signal_at: HashMap::new(),
+            pair_spreads: HashMap::new(),
+            pair_trades: HashMap::new(),
+            pair_orders: HashMap::new(),
         }
     }
 
@@ -65,13 +290,67 @@ impl StatisticalEdgeStrategy {
         self.enabled = enabled;
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Credit a fill of a pair entry leg to its trade, so the unwind sells
+    /// only what was actually bought, and debit exit fills from it. A trade
+    /// is dropped once its exits have sold both legs.
+    pub fn on_fill(&mut self, fill: &FillEvent) {
+        if fill.strategy_name != "statistical_edge" || fill.quantity <= 0 {
+            return;
+        }
+        if !fill.is_buy {
+            self.debit_pair_exit(fill);
+            return;
+        }
+        let leg = (fill.market_slug.clone(), fill.side);
+        let Some((key, expected)) = self.pair_orders.get_mut(&leg) else {
+            return;
+        };
+        let quantity = fill.quantity.min(*expected);
+        *expected -= quantity;
+        if let Some(trade) = self.pair_trades.get_mut(key.as_str()) {
+            match fill.side {
+                Side::Yes => trade.long_quantity += quantity,
+                Side::No => trade.short_quantity += quantity,
+            }
+        }
+        if *expected <= 0 {
+            self.pair_orders.remove(&leg);
+        }
+    }
+
+    fn debit_pair_exit(&mut self, fill: &FillEvent) {
+        let slug = fill.market_slug.as_str();
+        let Some((key, trade)) = self.pair_trades.iter_mut().find(|(_, t)| match fill.side {
+            Side::Yes => t.long_leg == slug,
+            Side::No => t.short_leg == slug,
+        }) else {
+            return;
+        };
+        let held = match fill.side {
+            Side::Yes => &mut trade.long_quantity,
+            Side::No => &mut trade.short_quantity,
+        };
+        *held = (*held - fill.quantity).max(0);
+        if trade.long_quantity == 0 && trade.short_quantity == 0 {
+            let key = key.clone();
+            self.pair_trades.remove(&key);
+        }
+    }
+
     /// Ingest a new odds snapshot from sportsbook.
     pub fn ingest_odds(&mut self, snapshot: OddsSnapshot) {
         let key = snapshot
             .market_slug
             .clone()
             .unwrap_or_else(|| snapshot.event_id.clone());
-        self.latest_odds.insert(key, snapshot);
+        self.latest_odds
+            .entry(key)
+            .or_default()
+            .insert(snapshot.provider.clone(), snapshot);
     }
 
     /// Generate signals from pending odds updates.
@@ -86,11 +365,27 @@ impl StatisticalEdgeStrategy {
         let now = Utc::now();
         let mut signals = Vec::new();
 
-        let snapshots: Vec<OddsSnapshot> = self.latest_odds.values().cloned().collect();
+        let half_life = self.config.confidence_half_life_seconds;
+        let books: Vec<Vec<OddsSnapshot>> = self
+            .latest_odds
+            .values()
+            .map(|by_provider| {
+                by_provider
+                    .values()
+                    .map(|s| {
+                        let age = (now - s.timestamp).num_milliseconds() as f64 / 1000.0;
+                        OddsSnapshot {
+                            confidence: decayed_confidence(s.confidence, age, half_life),
+                            ..s.clone()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
 
-        for snapshot in snapshots {
-            let market_slug = match &snapshot.market_slug {
-                Some(slug) => slug.clone(),
+        for snapshots in books {
+            let market_slug = match snapshots.first().and_then(|s| s.market_slug.clone()) {
+                Some(slug) => slug,
                 None => continue,
             };
 
@@ -111,15 +406,153 @@ impl StatisticalEdgeStrategy {
                 None => continue,
             };
 
+            let snapshot = match self.fair_value_snapshot(&market, &snapshots) {
+                Some(s) => s,
+                None => continue,
+            };
+
             if let Some(signal) = self.generate_signal(&market, &snapshot) {
                 self.last_signal_at.insert(market_slug, now);
                 signals.push(signal);
             }
         }
 
+        signals.extend(self.on_pairs_tick(&get_market));
+        signals
+    }
+
+    /// Sample each configured pair's spread and open or unwind pair trades.
+    pub fn on_pairs_tick(
+        &mut self,
+        get_market: impl Fn(&str) -> Option<MarketState>,
+    ) -> Vec<Signal> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let mut signals = Vec::new();
+        for pair in self.config.pairs.clone() {
+            let (Some(a), Some(b)) = (get_market(&pair.leg_a), get_market(&pair.leg_b)) else {
+                continue;
+            };
+            let (Some(mid_a), Some(mid_b)) = (a.yes_mid_price(), b.yes_mid_price()) else {
+                continue;
+            };
+            let spread = to_f64(mid_a - pair.hedge_ratio * mid_b);
+
+            // Score against the history before this sample joins it.
+            let key = pair.key();
+            let window = self
+                .pair_spreads
+                .entry(key.clone())
+                .or_insert_with(|| RollingWindow::new(self.config.pair_lookback));
+            let z = window.z_score(spread);
+            window.push(spread);
+            let Some(z) = z else { continue };
+
+            // Exits go out on every tick in the band until their fills have
+            // flattened the trade, so rejected exits are retried.
+            if let Some(trade) = self.pair_trades.get(&key) {
+                if z.abs() <= self.config.pair_exit_z {
+                    let markets = |slug: &str| if slug == a.market_slug { &a } else { &b };
+                    let long = markets(&trade.long_leg);
+                    let short = markets(&trade.short_leg);
+                    if trade.long_quantity > 0 {
+                        signals.push(pair_exit(long, Side::Yes, trade, &key, z));
+                    }
+                    if trade.short_quantity > 0 {
+                        signals.push(pair_exit(short, Side::No, trade, &key, z));
+                    }
+                    if trade.long_quantity == 0 && trade.short_quantity == 0 {
+                        self.pair_trades.remove(&key);
+                    }
+                    self.pair_orders.retain(|_, (pair_key, _)| *pair_key != key);
+                }
+                continue;
+            }
+
+            if z.abs() < self.config.pair_entry_z {
+                continue;
+            }
+            // Spread above its mean: A is rich against B.
+            let (long, short) = if z > 0.0 { (&b, &a) } else { (&a, &b) };
+            let (Some(long_price), Some(short_price)) = (long.yes_ask, no_ask(short)) else {
+                continue;
+            };
+            let (long_price, short_price) = (clamp_price(long_price), clamp_price(short_price));
+            // Dollar-neutral: each leg buys `order_size` dollars.
+            let long_quantity = quantity_for(self.config.order_size, long_price);
+            let short_quantity = quantity_for(self.config.order_size, short_price);
+            if long_quantity <= 0 || short_quantity <= 0 {
+                continue;
+            }
+
+            // The trade opens empty; fills credit each leg in `on_fill`.
+            let trade = PairTrade {
+                long_leg: long.market_slug.clone(),
+                short_leg: short.market_slug.clone(),
+                long_quantity: 0,
+                short_quantity: 0,
+            };
+            let long_signal =
+                pair_entry(long, SignalAction::BuyYes, long_price, long_quantity, &key, z);
+            let short_signal =
+                pair_entry(short, SignalAction::BuyNo, short_price, short_quantity, &key, z);
+            signals.push(long_signal);
+            signals.push(short_signal);
+            self.pair_orders.insert(
+                (long.market_slug.clone(), Side::Yes),
+                (key.clone(), long_quantity),
+            );
+            self.pair_orders.insert(
+                (short.market_slug.clone(), Side::No),
+                (key.clone(), short_quantity),
+            );
+            self.pair_trades.insert(key, trade);
+        }
         signals
     }
 
+    /// Open pair trades with their filled quantities, keyed by `leg_a/leg_b`.
+    pub fn open_pair_trades(&self) -> &HashMap<String, PairTrade> {
+        &self.pair_trades
+    }
+
+    /// Collapse the per-book snapshots for a market into the one used for
+    /// signal generation, according to the configured aggregation.
+    fn fair_value_snapshot(
+        &self,
+        market: &MarketState,
+        snapshots: &[OddsSnapshot],
+    ) -> Option<OddsSnapshot> {
+        if snapshots.len() == 1 {
+            return snapshots.first().cloned();
+        }
+        let latest = snapshots.iter().max_by_key(|s| s.timestamp)?;
+
+        match self.config.aggregation {
+            OddsAggregation::Consensus => {
+                let yes_probability = consensus_probability(snapshots)?;
+                let confidence =
+                    snapshots.iter().map(|s| s.confidence).sum::<f64>() / snapshots.len() as f64;
+                Some(OddsSnapshot {
+                    provider: format!("consensus of {}", snapshots.len()),
+                    yes_probability,
+                    confidence,
+                    ..latest.clone()
+                })
+            }
+            OddsAggregation::BestLine => {
+                let best = best_line_probability(snapshots, market)?;
+                let book = snapshots.iter().find(|s| s.provider == best.provider)?;
+                Some(OddsSnapshot {
+                    provider: format!("{} (consensus {:.3})", best.provider, best.consensus),
+                    ..book.clone()
+                })
+            }
+        }
+    }
+
     fn generate_signal(&self, market: &MarketState, snapshot: &OddsSnapshot) -> Option<Signal> {
         if market.yes_ask.is_none() && market.no_ask.is_none() {
             return None;
@@ -132,7 +565,7 @@ impl StatisticalEdgeStrategy {
         // YES side.
         if let Some(yes_ask) = market.yes_ask {
             let edge = fair_yes - yes_ask;
-            if edge >= self.config.min_edge && edge > best_edge {
+            if self.is_actionable(edge, snapshot.confidence) && edge > best_edge {
                 let price = clamp_price(yes_ask);
                 let quantity = self.calculate_quantity(price);
                 if quantity > 0 {
@@ -140,7 +573,7 @@ impl StatisticalEdgeStrategy {
                     let mut metadata = HashMap::new();
                     metadata.insert(
                         "true_probability".to_string(),
-                        serde_json::json!(fair_yes.to_string().parse::<f64>().unwrap_or(0.5)),
+                        serde_json::json!(to_f64(fair_yes)),
                     );
 
                     best_signal = Some(Signal {
@@ -154,26 +587,25 @@ impl StatisticalEdgeStrategy {
                         reason: format!("Odds edge {:.3} vs {}", edge, snapshot.provider),
                         metadata,
                         timestamp: Utc::now(),
+                        reduce_only: false,
+                        good_till: None,
                     });
                 }
             }
         }
 
         // NO side.
-        let no_ask = market
-            .no_ask
-            .or_else(|| market.yes_bid.map(|b| Decimal::ONE - b));
-        if let Some(no_ask) = no_ask {
+        if let Some(no_ask) = no_ask(market) {
             let fair_no = Decimal::ONE - fair_yes;
             let edge = fair_no - no_ask;
-            if edge >= self.config.min_edge && edge > best_edge {
+            if self.is_actionable(edge, snapshot.confidence) && edge > best_edge {
                 let price = clamp_price(no_ask);
                 let quantity = self.calculate_quantity(price);
                 if quantity > 0 {
                     let mut metadata = HashMap::new();
                     metadata.insert(
                         "true_probability".to_string(),
-                        serde_json::json!(fair_no.to_string().parse::<f64>().unwrap_or(0.5)),
+                        serde_json::json!(to_f64(fair_no)),
                     );
 
                     best_signal = Some(Signal {
@@ -187,6 +619,8 @@ impl StatisticalEdgeStrategy {
                         reason: format!("Odds edge {:.3} vs {}", edge, snapshot.provider),
                         metadata,
                         timestamp: Utc::now(),
+                        reduce_only: false,
+                        good_till: None,
                     });
                 }
             }
@@ -195,16 +629,15 @@ impl StatisticalEdgeStrategy {
         best_signal
     }
 
+    /// Edge clears `min_edge` and the confidence behind it clears the floor
+    /// that edge implies.
+    fn is_actionable(&self, edge: Decimal, confidence: f64) -> bool {
+        edge >= self.config.min_edge
+            && confidence >= min_confidence_for_edge(edge, self.config.min_edge)
+    }
+
     fn calculate_quantity(&self, price: Decimal) -> i64 {
-        if price <= Decimal::ZERO {
-            return 0;
-        }
-        (self.config.order_size / price)
-            .floor()
-            .to_string()
-            .parse::<i64>()
-            .unwrap_or(0)
-            .max(0)
+        quantity_for(self.config.order_size, price)
     }
 
     fn is_market_enabled(&self, slug: &str) -> bool {
@@ -218,6 +651,82 @@ impl StatisticalEdgeStrategy {
     }
 }
 
+fn no_ask(market: &MarketState) -> Option<Decimal> {
+    market
+        .no_ask
+        .or_else(|| market.yes_bid.map(|b| Decimal::ONE - b))
+}
+
+fn no_bid(market: &MarketState) -> Option<Decimal> {
+    market
+        .no_bid
+        .or_else(|| market.yes_ask.map(|a| Decimal::ONE - a))
+}
+
+/// Whole contracts `dollars` buys at `price`.
+fn quantity_for(dollars: Decimal, price: Decimal) -> i64 {
+    if price <= Decimal::ZERO {
+        return 0;
+    }
+    floor_i64(dollars / price).max(0)
+}
+
+fn pair_signal(
+    market: &MarketState,
+    action: SignalAction,
+    price: Decimal,
+    quantity: i64,
+    urgency: Urgency,
+    reason: String,
+    pair_key: &str,
+) -> Signal {
+    let mut metadata = HashMap::new();
+    metadata.insert("pair".to_string(), serde_json::json!(pair_key));
+    Signal {
+        market_slug: market.market_slug.clone(),
+        action,
+        price,
+        quantity,
+        urgency,
+        confidence: 1.0,
+        strategy_name: "statistical_edge".to_string(),
+        reason,
+        metadata,
+        timestamp: Utc::now(),
+        reduce_only: action.is_sell(),
+        good_till: None,
+    }
+}
+
+fn pair_entry(
+    market: &MarketState,
+    action: SignalAction,
+    price: Decimal,
+    quantity: i64,
+    pair_key: &str,
+    z: f64,
+) -> Signal {
+    let reason = format!("Pair {} spread z {:.2}: open", pair_key, z);
+    pair_signal(market, action, price, quantity, Urgency::Medium, reason, pair_key)
+}
+
+/// Sell back the `side` leg of `trade` held in `market` at its bid.
+fn pair_exit(
+    market: &MarketState,
+    side: Side,
+    trade: &PairTrade,
+    pair_key: &str,
+    z: f64,
+) -> Signal {
+    let (action, price, quantity) = match side {
+        Side::Yes => (SignalAction::SellYes, market.yes_bid, trade.long_quantity),
+        Side::No => (SignalAction::SellNo, no_bid(market), trade.short_quantity),
+    };
+    let reason = format!("Pair {} spread z {:.2}: unwind", pair_key, z);
+    let price = clamp_price(price.unwrap_or(Decimal::new(1, 2)));
+    pair_signal(market, action, price, quantity, Urgency::High, reason, pair_key)
+}
+
 fn clamp_price(price: Decimal) -> Decimal {
     price
         .max(Decimal::new(1, 2))
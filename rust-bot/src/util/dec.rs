@@ -0,0 +1,78 @@
+//! Decimal conversion and percentage helpers.
+//!
+//! Money stays in `Decimal`; these are the only places it crosses to or
+//! from `f64` (JSON metrics, confidences, decay factors), using
+//! `rust_decimal`'s native conversions rather than string round-trips.
+
+#![allow(dead_code)]
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// Nearest `f64` to `value`.
+pub fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+/// Shortest `Decimal` that round-trips to `value` (0.6 -> 0.6, not
+/// 0.59999...). `None` for NaN and infinities.
+pub fn from_f64(value: f64) -> Option<Decimal> {
+    Decimal::from_f64(value)
+}
+
+/// `part` as a percentage of `whole`. Zero when `whole` is zero.
+pub fn pct(part: Decimal, whole: Decimal) -> Decimal {
+    if whole.is_zero() {
+        return Decimal::ZERO;
+    }
+    part / whole * Decimal::ONE_HUNDRED
+}
+
+/// Whole units of `value`, rounded down (2.9 -> 2, -0.5 -> -1). Zero if
+/// it does not fit an `i64`.
+pub fn floor_i64(value: Decimal) -> i64 {
+    value.floor().to_i64().unwrap_or(0)
+}
+
+/// `bps` basis points of `value` (5 bps of 0.50 is 0.00025).
+pub fn scale_bps(value: Decimal, bps: Decimal) -> Decimal {
+    value * bps / BPS_PER_UNIT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_f64_conversions_round_trip_known_values() {
+        assert_eq!(to_f64(dec!(0.6)), 0.6);
+        assert_eq!(to_f64(dec!(1234.5678)), 1234.5678);
+        assert_eq!(to_f64(dec!(-0.05)), -0.05);
+
+        assert_eq!(from_f64(0.6), Some(dec!(0.6)));
+        assert_eq!(from_f64(0.75), Some(dec!(0.75)));
+        assert_eq!(from_f64(-12.05), Some(dec!(-12.05)));
+        assert_eq!(from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_floor_i64() {
+        assert_eq!(floor_i64(dec!(2.9)), 2);
+        assert_eq!(floor_i64(dec!(7)), 7);
+        assert_eq!(floor_i64(dec!(-0.5)), -1);
+        assert_eq!(floor_i64(Decimal::MAX), 0);
+    }
+
+    #[test]
+    fn test_pct_and_bps() {
+        assert_eq!(pct(dec!(25), dec!(200)), dec!(12.5));
+        assert_eq!(pct(dec!(-10), dec!(1000)), dec!(-1));
+        assert_eq!(pct(dec!(5), Decimal::ZERO), Decimal::ZERO);
+
+        assert_eq!(scale_bps(dec!(0.50), dec!(5)), dec!(0.00025));
+        assert_eq!(scale_bps(dec!(1000), dec!(10)), dec!(1));
+    }
+}
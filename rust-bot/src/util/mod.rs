@@ -0,0 +1,2 @@
+pub mod dec;
+pub mod rng;
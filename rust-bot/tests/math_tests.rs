@@ -10,20 +10,96 @@
 //!   4. Exposure monitor                (src/risk/exposure.rs)
 //!   5. Risk manager integration        (src/risk/risk_manager.rs)
 //!   6. Order book mechanics            (src/data/orderbook.rs)
+//!   7. Portfolio VaR / shortfall       (src/risk/risk_manager.rs)
+//!   8. Order-rate limiter              (src/risk/risk_manager.rs)
+//!   9. Bookmaker line shopping         (src/strategies/statistical_edge.rs)
+//!  10. Position reconciliation drift   (src/state/state_manager.rs)
+//!  11. Market discovery filter         (src/data/market_filter.rs)
+//!  12. Runtime market toggles          (src/strategies/engine.rs)
+//!  13. Odds staleness decay            (src/strategies/statistical_edge.rs)
+//!  14. Statistical pairs mode          (src/strategies/statistical_edge.rs)
+//!  15. Signal conflict resolution      (src/strategies/engine.rs)
+//!  16. Per-market warmup               (src/state/state_manager.rs)
+//!  17. Position aging                  (src/risk/position_manager.rs)
+//!  18. Shadow execution                (src/execution/shadow.rs)
+//!  19. Hedged exposure netting         (src/risk/exposure.rs)
+//!  20. Status server book depth        (src/status_server.rs)
+//!  21. Panic liquidation               (src/risk/risk_manager.rs)
+//!  22. Price band filter               (src/state/state_manager.rs)
+//!  23. Feed poll scheduling            (src/data/feed_scheduler.rs)
+//!  24. Market resolution               (src/state/state_manager.rs)
+//!  25. Runtime strategy toggles        (src/strategies/engine.rs)
+//!  26. Feed poll throughput            (src/data/market_feed.rs)
+//!  27. Post-score price lag            (src/strategies/live_arbitrage.rs)
+//!  28. Minimum resolution horizon     (src/risk/risk_manager.rs)
+//!  29. Event log endpoint             (src/status_server.rs)
+//!  30. Mark-to-market conventions     (src/state/state_manager.rs)
+//!  31. Odds event matching          (src/data/odds_matching.rs)
+//!  32. Strategy confidence floors     (src/risk/risk_manager.rs)
+//!  33. Cash buffer consistency        (src/data/models.rs)
+//!  34. Market metadata                (src/state/state_manager.rs)
+//!  35. Last-trade marks               (src/state/state_manager.rs)
+//!  36. Strategy size ramps            (src/risk/size_ramp.rs)
+//!  37. Signal book context            (src/strategies/engine.rs)
+//!  38. Risk decision replay           (src/risk/risk_manager.rs)
+//!  39. Live auth preflight            (src/data/startup.rs)
+//!  40. Net edge gate                  (src/strategies/net_edge.rs)
+//!  41. Per-market fee overrides       (src/state/state_manager.rs)
+//!  42. Drawdown-scaled Kelly          (src/risk/circuit_breaker.rs)
+//!  43. Per-market order interval      (src/risk/risk_manager.rs)
+//!  44. Typed reject reasons           (src/execution/executor.rs)
+//!  45. Flip protection                (src/risk/risk_manager.rs)
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::collections::HashMap;
 
+use polymarket_us_bot::api::client::{ConnectionConfig, ConnectionStats, PolymarketClient};
+use polymarket_us_bot::api::errors::ApiError;
+use polymarket_us_bot::auth::PolymarketAuth;
+use polymarket_us_bot::data::market_feed::{MarketFeed, MarketFeedConfig};
+use polymarket_us_bot::data::market_filter::{select_markets, MarketFilterConfig};
 use polymarket_us_bot::data::models::{
-    OrderBook, OrderBookSide, PriceLevel, Side, Signal, SignalAction, Urgency,
+    fee_adjusted_min_edge, max_affordable_quantity, parse_fee_override, FillEvent, Market,
+    OrderBook, OrderBookSide, OrderStatus, Position, PriceLevel, Side, Signal, SignalAction,
+    Urgency, ARB_PAIR_KEY,
 };
-use polymarket_us_bot::data::orderbook::{OrderBookTracker, TopOfBook};
+use polymarket_us_bot::data::orderbook::{
+    BookQualityConfig, OrderBookTracker, SignalBookLog, TopOfBook,
+};
+use polymarket_us_bot::clock::{Clock, MockClock};
+use polymarket_us_bot::data::feed_scheduler::{FeedScheduler, FeedSchedulerConfig};
+use polymarket_us_bot::data::odds_matching::{match_report, EventMatch, OddsEvent};
+use polymarket_us_bot::data::poll_breaker::PollBreakerConfig;
+use polymarket_us_bot::execution::executor::RejectReason;
+use polymarket_us_bot::execution::paper::PaperExecutor;
+use polymarket_us_bot::execution::shadow::ShadowExecutor;
 use polymarket_us_bot::risk::circuit_breaker::CircuitBreaker;
 use polymarket_us_bot::risk::exposure::{ExposureConfig, ExposureMonitor};
+use polymarket_us_bot::risk::position_manager::{PositionAgingConfig, PositionManager};
 use polymarket_us_bot::risk::position_sizer::{EdgeEstimate, KellyPositionSizer};
-use polymarket_us_bot::risk::risk_manager::{RiskConfig, RiskManager};
-use polymarket_us_bot::state::state_manager::StateManager;
+use polymarket_us_bot::risk::risk_manager::{parse_confidence_floor, RiskConfig, RiskManager};
+use polymarket_us_bot::risk::size_ramp::parse_size_ramp;
+use polymarket_us_bot::state::state_manager::{
+    MarkMode, MarketState, PriceBandCheck, PriceBandConfig, StateManager, WarmupConfig,
+};
+use polymarket_us_bot::state::event_log::EventKind;
+use polymarket_us_bot::status_server::{StatusServer, StatusServerConfig};
+use polymarket_us_bot::strategies::completeness_arb::{
+    CompletenessArbConfig, CompletenessArbStrategy,
+};
+use polymarket_us_bot::strategies::engine::{
+    ConflictPolicy, EngineOutput, StrategyEngine, StrategyToggle, BOOK_CONTEXT_KEYS,
+};
+use polymarket_us_bot::strategies::live_arbitrage::{
+    score_model_probability, GameState, LiveArbitrageConfig, LiveArbitrageStrategy,
+};
+use polymarket_us_bot::strategies::market_maker::{MarketMakerConfig, MarketMakerStrategy};
+use polymarket_us_bot::strategies::net_edge::{NetEdgeConfig, NetEdgeGate};
+use polymarket_us_bot::strategies::statistical_edge::{
+    best_line_probability, consensus_probability, decayed_confidence, min_confidence_for_edge,
+    OddsAggregation, OddsSnapshot, PairConfig, StatisticalEdgeConfig, StatisticalEdgeStrategy,
+};
 
 // =============================================================================
 // Helpers
@@ -82,6 +158,8 @@ fn make_buy_signal(
         reason: "test signal".to_string(),
         metadata,
         timestamp: chrono::Utc::now(),
+        reduce_only: false,
+        good_till: None,
     }
 }
 
@@ -97,6 +175,8 @@ fn make_sell_signal(market: &str, price: Decimal, qty: i64) -> Signal {
         reason: "test sell".to_string(),
         metadata: HashMap::new(),
         timestamp: chrono::Utc::now(),
+        reduce_only: false,
+        good_till: None,
     }
 }
 
@@ -112,6 +192,26 @@ fn make_cancel_signal(market: &str) -> Signal {
         reason: "test cancel".to_string(),
         metadata: HashMap::new(),
         timestamp: chrono::Utc::now(),
+        reduce_only: false,
+        good_till: None,
+    }
+}
+
+/// Build a MarketState with the given YES bid/ask.
+fn make_market(slug: &str, yes_bid: Decimal, yes_ask: Decimal) -> MarketState {
+    MarketState {
+        market_slug: slug.to_string(),
+        title: slug.to_string(),
+        yes_bid: Some(yes_bid),
+        yes_ask: Some(yes_ask),
+        no_bid: Some(Decimal::ONE - yes_ask),
+        no_ask: Some(Decimal::ONE - yes_bid),
+        yes_bid_depth: None,
+        yes_ask_depth: None,
+        resolution_date: None,
+        tick_size: None,
+        min_order_size: None,
+        last_updated: chrono::Utc::now(),
     }
 }
 
@@ -121,15 +221,34 @@ fn permissive_risk_config() -> RiskConfig {
     RiskConfig {
         kelly_fraction: dec!(0.25),
         min_edge: dec!(0.02),
+        fee_rate: dec!(0.001),
         max_position_per_market: dec!(500),
+        max_position_pct_of_equity: Decimal::ZERO,
+        max_contracts_per_market: 0,
         max_portfolio_exposure: dec!(2000),
         max_portfolio_exposure_pct: dec!(0.80),
         max_correlated_exposure: dec!(1000),
         max_positions: 20,
+        net_hedged_exposure: false,
         max_daily_loss: dec!(200),
         max_drawdown_pct: dec!(0.10),
         max_total_pnl_drawdown_pct_for_new_buys: dec!(0.05),
         min_trade_size: dec!(1),
+        max_var: Decimal::ZERO,
+        var_confidence: dec!(0.95),
+        max_orders_per_sec: 0,
+        max_orders_per_sec_per_market: 0,
+        warning_threshold_pct: Decimal::ZERO,
+        warning_size_scale: Decimal::ONE,
+        panic_liquidate_drawdown_pct: Decimal::ZERO,
+        conservative_marks: false,
+        min_resolution_horizon: chrono::Duration::zero(),
+        min_confidence: HashMap::new(),
+        cash_buffer_pct: dec!(0.98),
+        size_ramp: HashMap::new(),
+        drawdown_scaled_kelly: false,
+        min_order_interval: chrono::Duration::zero(),
+        flip_protection: false,
     }
 }
 
@@ -159,6 +278,35 @@ fn kelly_hand_verified_quarter_kelly() {
     assert_eq!(result.edge, dec!(0.10), "Edge = 0.60 - 0.50 = 0.10");
 }
 
+#[test]
+fn kelly_incremental_sizes_only_the_remainder_of_the_target() {
+    // Same setup as above: the Kelly target is $50 (100 contracts at 0.50).
+    let sizer = KellyPositionSizer::new(dec!(0.25), dec!(1.0), dec!(0.02));
+    let edge = EdgeEstimate::new(dec!(0.60), Decimal::ONE);
+
+    // Nothing held: the full target.
+    let fresh = sizer
+        .calculate_incremental_position_size(dec!(1000), dec!(0.50), &edge, Decimal::ZERO)
+        .unwrap();
+    assert_eq!(fresh.notional, dec!(50));
+    assert_eq!(fresh.contracts, 100);
+
+    // $30 held: increment = 50 - 30 = $20 => 40 contracts.
+    let topped_up = sizer
+        .calculate_incremental_position_size(dec!(1000), dec!(0.50), &edge, dec!(30))
+        .unwrap();
+    assert_eq!(topped_up.notional, dec!(20));
+    assert_eq!(topped_up.contracts, 40);
+    assert_eq!(topped_up.kelly_adjusted, dec!(0.05), "Target fraction unchanged");
+
+    // At or above the $50 target: nothing more.
+    for held in [dec!(50), dec!(80)] {
+        assert!(sizer
+            .calculate_incremental_position_size(dec!(1000), dec!(0.50), &edge, held)
+            .is_none());
+    }
+}
+
 #[test]
 fn kelly_edge_below_min_returns_none() {
     // Edge = 0.51 - 0.50 = 0.01 < min_edge (0.02) => None
@@ -399,6 +547,119 @@ fn kelly_large_edge_capped_properly() {
     assert_eq!(result.contracts, 400);
 }
 
+#[test]
+fn kelly_three_outcome_home_draw_away_hand_verified() {
+    // Soccer market: home / draw / away
+    //   prices P = 0.40 / 0.28 / 0.36 (sum 1.04, includes vig)
+    //   true   p = 0.50 / 0.30 / 0.20
+    //
+    // Expected return p/P: home 1.25, draw 1.0714, away 0.5556
+    //   Admit home (1.25 > R=1):   R = (1 - 0.50) / (1 - 0.40) = 0.8333
+    //   Admit draw (1.0714 > 0.8333): R = (1 - 0.80) / (1 - 0.68) = 0.20 / 0.32 = 0.625
+    //   Reject away (0.5556 < 0.625)
+    //
+    // f_home = 0.50 - 0.625 * 0.40 = 0.25
+    // f_draw = 0.30 - 0.625 * 0.28 = 0.125
+    //
+    // Home, quarter Kelly: 0.25 * 0.25 = 0.0625 => $62.50 / 0.40 = 156.25 => 156
+    // Draw, quarter Kelly: 0.125 * 0.25 = 0.03125 => $31.25 / 0.28 = 111.6 => 111
+    let sizer = KellyPositionSizer::new(dec!(0.25), dec!(1.0), dec!(0.02));
+    let outcomes = [
+        (dec!(0.40), dec!(0.50)),
+        (dec!(0.28), dec!(0.30)),
+        (dec!(0.36), dec!(0.20)),
+    ];
+
+    let home = sizer
+        .calculate_position_size_multi(dec!(1000), &outcomes, 0, Decimal::ONE)
+        .expect("Home has edge");
+    assert_eq!(home.edge, dec!(0.10));
+    assert_eq!(home.kelly_full, dec!(0.25), "f_home = 0.25");
+    assert_eq!(home.kelly_adjusted, dec!(0.0625));
+    assert_eq!(home.notional, dec!(62.5));
+    assert_eq!(home.contracts, 156, "Home contracts = 156");
+
+    let draw = sizer
+        .calculate_position_size_multi(dec!(1000), &outcomes, 1, Decimal::ONE)
+        .expect("Draw is in the optimal bet set");
+    assert_eq!(draw.kelly_full, dec!(0.125), "f_draw = 0.125");
+    assert_eq!(draw.contracts, 111, "Draw contracts = 111");
+
+    assert!(
+        sizer
+            .calculate_position_size_multi(dec!(1000), &outcomes, 2, Decimal::ONE)
+            .is_none(),
+        "Away has negative edge and must not be bet"
+    );
+}
+
+#[test]
+fn kelly_binary_is_two_outcome_special_case() {
+    // Binary YES @ 0.50 with p = 0.60 is outcomes [(0.50, 0.60), (0.50, 0.40)]
+    //   R = 0.40 / 0.50 = 0.80; f = 0.60 - 0.80 * 0.50 = 0.20 (matches b-ratio form)
+    let sizer = KellyPositionSizer::new(dec!(0.25), dec!(1.0), dec!(0.02));
+    let edge = EdgeEstimate::new(dec!(0.60), Decimal::ONE);
+    let binary = sizer
+        .calculate_position_size(dec!(1000), dec!(0.50), &edge)
+        .unwrap();
+    let multi = sizer
+        .calculate_position_size_multi(
+            dec!(1000),
+            &[(dec!(0.50), dec!(0.60)), (dec!(0.50), dec!(0.40))],
+            0,
+            Decimal::ONE,
+        )
+        .unwrap();
+    assert_eq!(binary.kelly_full, dec!(0.20));
+    assert_eq!(multi.kelly_full, binary.kelly_full);
+    assert_eq!(multi.contracts, binary.contracts);
+}
+
+#[test]
+fn kelly_multi_rejects_bad_inputs() {
+    let sizer = KellyPositionSizer::new(dec!(0.25), dec!(1.0), dec!(0.02));
+    let outcomes = [(dec!(0.40), dec!(0.50)), (dec!(0.60), dec!(0.50))];
+    // Out-of-range index
+    assert!(sizer
+        .calculate_position_size_multi(dec!(1000), &outcomes, 5, Decimal::ONE)
+        .is_none());
+    // Price of 0 on any outcome
+    assert!(sizer
+        .calculate_position_size_multi(
+            dec!(1000),
+            &[(dec!(0.40), dec!(0.50)), (Decimal::ZERO, dec!(0.50))],
+            0,
+            Decimal::ONE
+        )
+        .is_none());
+}
+
+#[test]
+fn fee_adjusted_min_edge_is_proportionally_larger_for_cheap_contracts() {
+    // fee = 0.01: required = 0.01 * (price + 1)
+    // price 0.05 => 0.0105 (21% of price); price 0.50 => 0.015 (3% of price)
+    let cheap = fee_adjusted_min_edge(dec!(0.05), dec!(0.01));
+    let mid = fee_adjusted_min_edge(dec!(0.50), dec!(0.01));
+    assert_eq!(cheap, dec!(0.0105));
+    assert_eq!(mid, dec!(0.015));
+    assert_eq!(cheap / dec!(0.05), dec!(0.21));
+    assert_eq!(mid / dec!(0.50), dec!(0.03));
+
+    // Kelly sizer with min_edge 0.01: edge 0.01 at 0.05 clears the flat
+    // threshold but not the fee floor of 0.0105.
+    let sizer = KellyPositionSizer::new(dec!(0.25), dec!(1.0), dec!(0.01));
+    let edge = EdgeEstimate::new(dec!(0.06), Decimal::ONE);
+    assert!(sizer.calculate_position_size(dec!(1000), dec!(0.05), &edge).is_some());
+
+    let sizer = sizer.with_fee_rate(dec!(0.01));
+    assert_eq!(sizer.effective_min_edge(dec!(0.05)), dec!(0.0105));
+    assert_eq!(sizer.effective_min_edge(dec!(0.50)), dec!(0.015));
+    assert!(
+        sizer.calculate_position_size(dec!(1000), dec!(0.05), &edge).is_none(),
+        "0.01 edge < 0.0105 fee floor at a 0.05 price"
+    );
+}
+
 // =============================================================================
 // 2. Completeness Arbitrage Scanner
 // =============================================================================
@@ -414,7 +675,7 @@ fn arb_yes50_no45_profitable() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("test-market", dec!(0.50), dec!(0.45)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.01));
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
     assert_eq!(signals.len(), 1, "Expected exactly one arb signal");
 
     let sig = &signals[0];
@@ -437,7 +698,7 @@ fn arb_yes50_no50_no_arb() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("market-even", dec!(0.50), dec!(0.50)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.0));
+    let signals = tracker.scan_completeness_arb(dec!(0.0), 0, Decimal::ZERO, dec!(0.001));
     assert!(
         signals.is_empty(),
         "combined=1.00 must produce no arb signal"
@@ -451,7 +712,7 @@ fn arb_yes55_no46_sum_over_one_no_arb() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("overpriced", dec!(0.55), dec!(0.46)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.0));
+    let signals = tracker.scan_completeness_arb(dec!(0.0), 0, Decimal::ZERO, dec!(0.001));
     assert!(
         signals.is_empty(),
         "combined=1.01 (>1.0) must produce no arb signal"
@@ -468,7 +729,7 @@ fn arb_yes48_no48_net_margin_check() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("balanced", dec!(0.48), dec!(0.48)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.01));
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
     assert_eq!(signals.len(), 1);
     let sig = &signals[0];
     assert_eq!(sig.combined_cost, dec!(0.96));
@@ -485,7 +746,7 @@ fn arb_fee_is_exactly_10bps() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("fee-check", dec!(0.40), dec!(0.40)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.0));
+    let signals = tracker.scan_completeness_arb(dec!(0.0), 0, Decimal::ZERO, dec!(0.001));
     assert_eq!(signals.len(), 1);
     let sig = &signals[0];
 
@@ -503,14 +764,14 @@ fn arb_min_margin_filter() {
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("thin-margin", dec!(0.50), dec!(0.49)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.01));
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
     assert!(
         signals.is_empty(),
         "net_margin 0.00901 < min_margin 0.01 must be filtered"
     );
 
     // But with min_margin = 0.005, should pass
-    let signals_lower = tracker.scan_completeness_arb(dec!(0.005));
+    let signals_lower = tracker.scan_completeness_arb(dec!(0.005), 0, Decimal::ZERO, dec!(0.001));
     assert_eq!(
         signals_lower.len(),
         1,
@@ -528,7 +789,7 @@ fn arb_multiple_markets_only_eligible_returned() {
     tracker.update(make_book("market-b", dec!(0.55), dec!(0.47)));
     tracker.update(make_book("market-c", dec!(0.50), dec!(0.45)));
 
-    let signals = tracker.scan_completeness_arb(dec!(0.01));
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
 
     // Should contain market-a and market-c, but not market-b
     let slugs: Vec<&str> = signals.iter().map(|s| s.market_slug.as_str()).collect();
@@ -541,6 +802,205 @@ fn arb_multiple_markets_only_eligible_returned() {
     assert_eq!(signals.len(), 2, "Exactly 2 eligible markets");
 }
 
+#[test]
+fn arb_one_contract_top_level_filtered_by_min_depth() {
+    // Same profitable prices as arb_yes50_no45 (net 0.04905), but the NO ask
+    // has only 1 contract at the top level => phantom arb, filtered at depth 5.
+    let tracker = OrderBookTracker::new();
+    let mut book = make_book("thin-market", dec!(0.50), dec!(0.45));
+    book.no.asks = vec![
+        PriceLevel {
+            price: dec!(0.45),
+            quantity: 1,
+        },
+        PriceLevel {
+            price: dec!(0.47),
+            quantity: 500,
+        },
+    ];
+    tracker.update(book);
+
+    assert_eq!(tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).len(), 1);
+    assert!(
+        tracker.scan_completeness_arb(dec!(0.01), 5, Decimal::ZERO, dec!(0.001)).is_empty(),
+        "1-contract NO ask must not count as an arb at min depth 5"
+    );
+}
+
+#[test]
+fn arb_depth_sums_levels_at_best_price() {
+    // Two YES asks at 0.50 (3 + 4 = 7 contracts) satisfy min depth 5.
+    let tracker = OrderBookTracker::new();
+    let mut book = make_book("split-level", dec!(0.50), dec!(0.45));
+    book.yes.asks = vec![
+        PriceLevel {
+            price: dec!(0.50),
+            quantity: 3,
+        },
+        PriceLevel {
+            price: dec!(0.50),
+            quantity: 4,
+        },
+    ];
+    tracker.update(book);
+
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 5, Decimal::ZERO, dec!(0.001));
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].yes_ask_qty, 7);
+    assert_eq!(signals[0].no_ask_qty, 100);
+}
+
+#[test]
+fn market_state_prices_consistent_with_complement() {
+    // make_market derives NO from YES: no_ask = 1 - 0.48 = 0.52, no_bid = 1 - 0.52 = 0.48
+    // yes_bid + no_ask = 1.00, yes_ask + no_bid = 1.00 => consistent at any tolerance
+    let market = make_market("consistent", dec!(0.48), dec!(0.52));
+    assert!(market.prices_consistent(Decimal::ZERO));
+
+    // Skew NO ask from a second source: yes_bid + no_ask = 0.48 + 0.40 = 0.88
+    // |0.88 - 1| = 0.12 > 0.05 => inconsistent; <= 0.12 => tolerated
+    let mut skewed = make_market("skewed", dec!(0.48), dec!(0.52));
+    skewed.no_ask = Some(dec!(0.40));
+    assert!(!skewed.prices_consistent(dec!(0.05)));
+    assert!(skewed.prices_consistent(dec!(0.12)));
+
+    // Skew NO bid: yes_ask + no_bid = 0.52 + 0.55 = 1.07 => off by 0.07
+    let mut skewed_bid = make_market("skewed-bid", dec!(0.48), dec!(0.52));
+    skewed_bid.no_bid = Some(dec!(0.55));
+    assert!(!skewed_bid.prices_consistent(dec!(0.05)));
+
+    // Missing NO prices cannot be checked and are not flagged.
+    let mut partial = make_market("partial", dec!(0.48), dec!(0.52));
+    partial.no_ask = None;
+    partial.no_bid = None;
+    assert!(partial.prices_consistent(Decimal::ZERO));
+}
+
+#[test]
+fn arb_scanner_skips_inconsistent_books_when_skew_limit_set() {
+    // YES: bid 0.43 / ask 0.45; NO: bid 0.38 / ask 0.40
+    // Completeness: 0.45 + 0.40 = 0.85 => net margin 0.15 - 0.00085 (phantom arb)
+    // yes_bid + no_ask = 0.43 + 0.40 = 0.83 => skew 0.17
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("phantom", dec!(0.45), dec!(0.40)));
+
+    assert_eq!(
+        tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).len(),
+        1,
+        "Skew check disabled => arb reported"
+    );
+    assert!(
+        tracker.scan_completeness_arb(dec!(0.01), 0, dec!(0.10), dec!(0.001)).is_empty(),
+        "0.17 skew > 0.10 limit => phantom arb suppressed"
+    );
+    assert_eq!(
+        tracker.scan_completeness_arb(dec!(0.01), 0, dec!(0.20), dec!(0.001)).len(),
+        1,
+        "0.17 skew within 0.20 limit => arb reported"
+    );
+}
+
+#[test]
+fn arb_scanner_rejects_marginal_penny_market_arb() {
+    // YES ask = 0.02, NO ask = 0.975 => combined = 0.995, gross = 0.005
+    // entry fees = 0.995 * 0.001 = 0.000995
+    // net = 0.005 - 0.000995 = 0.004005 < min_margin 0.0045 => rejected,
+    // though the gross margin alone would clear it
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("penny", dec!(0.02), dec!(0.975)));
+
+    let signals = tracker.scan_completeness_arb(dec!(0.0045), 0, Decimal::ZERO, dec!(0.001));
+    assert!(signals.is_empty(), "Penny arb cannot cover its entry fees");
+
+    // A near-even market with the same min_margin clears it:
+    // YES 0.49 + NO 0.49 => net 0.02 - 0.98 * 0.001 = 0.01902 > 0.0045
+    tracker.update(make_book("even", dec!(0.49), dec!(0.49)));
+    let signals = tracker.scan_completeness_arb(dec!(0.0045), 0, Decimal::ZERO, dec!(0.001));
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].market_slug, "even");
+}
+
+#[test]
+fn arb_scanner_skips_crossed_and_implausible_books() {
+    // A glitched YES book: bid 0.70 over ask 0.30. With NO ask 0.45 the
+    // "arb" would be 1 - 0.75 = 0.25 gross, far past any margin.
+    let tracker = OrderBookTracker::new();
+    let mut crossed = make_book("crossed", dec!(0.30), dec!(0.45));
+    crossed.yes.bids[0].price = dec!(0.70);
+    assert!(crossed.yes.is_crossed());
+    tracker.update(crossed);
+    assert!(tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).is_empty());
+
+    // A crossed NO side is skipped too.
+    let mut no_crossed = make_book("no-crossed", dec!(0.50), dec!(0.45));
+    no_crossed.no.bids[0].price = dec!(0.60);
+    tracker.update(no_crossed);
+    assert!(tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).is_empty());
+
+    // An ask below 0.01: 0.005 + 0.90 looks like a 0.095 arb but is noise.
+    tracker.update(make_book("sub-penny", dec!(0.005), dec!(0.90)));
+    assert!(tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).is_empty());
+
+    // A sane book alongside them still reports.
+    tracker.update(make_book("sane", dec!(0.50), dec!(0.45)));
+    let signals = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].market_slug, "sane");
+}
+
+fn arb_engine(state: &StateManager, tracker: &OrderBookTracker) -> StrategyEngine {
+    let arb = CompletenessArbStrategy::new(CompletenessArbConfig::default(), tracker.clone());
+    StrategyEngine::new(state.clone(), None, None, None, Some(arb))
+}
+
+#[test]
+fn engine_executes_detected_arb_as_two_depth_sized_legs() {
+    // YES ask 0.45 x 40, NO ask 0.50 x 60 => combined 0.95, net 0.04905
+    // Legs sized to the thinner ask: min(40, 60) = 40 contracts each
+    let mut book = make_book("arb", dec!(0.45), dec!(0.50));
+    book.yes.asks[0].quantity = 40;
+    book.no.asks[0].quantity = 60;
+    let tracker = OrderBookTracker::new();
+    tracker.update(book);
+
+    let state = StateManager::new(dec!(1000));
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mut engine = arb_engine(&state, &tracker);
+
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.approved_signals.len(), 2);
+    assert_eq!(out.rejected_count, 0);
+    let yes = out.approved_signals.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+    let no = out.approved_signals.iter().find(|s| s.action == SignalAction::BuyNo).unwrap();
+    assert_eq!((yes.price, yes.quantity), (dec!(0.45), 40));
+    assert_eq!((no.price, no.quantity), (dec!(0.50), 40));
+    assert!(out.approved_signals.iter().all(|s| s.urgency == Urgency::Critical));
+
+    // Same opportunity inside the cooldown does not fire again
+    assert!(engine.on_tick(&mut rm).approved_signals.is_empty());
+}
+
+#[test]
+fn engine_keeps_arb_legs_equal_when_risk_trims_one() {
+    // YES ask 0.30, NO ask 0.65, 100 deep => combined 0.95
+    // Per-market cap $13: YES floor(13 / 0.30) = 43; NO floor(13 / 0.65) = 20
+    // Both legs follow the smaller approved size: 20
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("trim", dec!(0.30), dec!(0.65)));
+
+    let state = StateManager::new(dec!(1000));
+    let config = RiskConfig {
+        max_position_per_market: dec!(13),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state.clone());
+    let mut engine = arb_engine(&state, &tracker);
+
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.approved_signals.len(), 2);
+    assert!(out.approved_signals.iter().all(|s| s.quantity == 20));
+}
+
 // =============================================================================
 // 3. Circuit Breaker
 // =============================================================================
@@ -660,17 +1120,68 @@ fn circuit_breaker_emergency_stop() {
     assert_eq!(reason.unwrap(), "manual halt");
 }
 
+#[test]
+fn circuit_breaker_warning_tier_scales_before_trip() {
+    // daily_loss_limit = $100, warning at 70% => $70 loss, scale 0.5
+    let mut cb =
+        CircuitBreaker::new(dec!(100), dec!(0.50)).with_warning_tier(dec!(0.70), dec!(0.5));
+    cb.initialize(dec!(1000));
+    assert_eq!(cb.risk_scale(), Decimal::ONE, "No loss => full sizing");
+
+    // Loss $65 < $70 => still full
+    cb.update(dec!(935));
+    assert_eq!(cb.risk_scale(), Decimal::ONE);
+
+    // Loss $70 = 0.70 * $100 => warning band, still trading
+    cb.update(dec!(930));
+    assert_eq!(cb.risk_scale(), dec!(0.5));
+    assert!(cb.can_trade().0, "Warning tier must not stop trading");
+
+    // Loss $100 => tripped, zero sizing
+    cb.update(dec!(900));
+    assert_eq!(cb.risk_scale(), Decimal::ZERO);
+}
+
+#[test]
+fn circuit_breaker_warning_tier_on_drawdown() {
+    // max_drawdown = 10%, warning at 70% => 7% from peak
+    // Peak $1000, equity $930 => drawdown 70/1000 = 0.07 => warning
+    let mut cb =
+        CircuitBreaker::new(dec!(500), dec!(0.10)).with_warning_tier(dec!(0.70), dec!(0.5));
+    cb.initialize(dec!(1000));
+    cb.update(dec!(931));
+    assert_eq!(cb.risk_scale(), Decimal::ONE, "6.9% drawdown is below the band");
+    cb.update(dec!(930));
+    assert_eq!(cb.risk_scale(), dec!(0.5));
+}
+
+#[test]
+fn circuit_breaker_warning_tier_disabled_by_default() {
+    // Without with_warning_tier the breaker stays binary.
+    let mut cb = CircuitBreaker::new(dec!(100), dec!(0.10));
+    cb.initialize(dec!(1000));
+    cb.update(dec!(910)); // loss $90 = 90% of limit
+    assert_eq!(cb.risk_scale(), Decimal::ONE);
+}
+
 // =============================================================================
 // 4. Exposure Monitor
 // =============================================================================
 
-fn make_exposure_monitor() -> ExposureMonitor {
-    ExposureMonitor::new(ExposureConfig {
+fn make_exposure_config() -> ExposureConfig {
+    ExposureConfig {
         max_position_per_market: dec!(200),
+        max_position_pct_of_equity: Decimal::ZERO,
+        max_contracts_per_market: 0,
         max_portfolio_exposure: dec!(500),
         max_correlated_exposure: dec!(300),
         max_positions: 3,
-    })
+        net_hedged_exposure: false,
+    }
+}
+
+fn make_exposure_monitor() -> ExposureMonitor {
+    ExposureMonitor::new(make_exposure_config())
 }
 
 #[test]
@@ -683,7 +1194,7 @@ fn exposure_per_market_limit_enforcement() {
     state.update_position("foo", Side::Yes, 300, dec!(0.50));
     // cost_basis = 300 * 0.50 = $150
 
-    let check = monitor.can_add_exposure(&state, "foo", dec!(60));
+    let check = monitor.can_add_exposure(&state, "foo", dec!(60), dec!(0.50));
     assert!(!check.allowed, "Adding $60 to $150 exceeds $200 per-market");
     assert_eq!(
         check.max_additional_exposure,
@@ -702,7 +1213,7 @@ fn exposure_portfolio_wide_limit() {
     state.update_position("mkt-a", Side::Yes, 200, dec!(1.00)); // $200
     state.update_position("mkt-b", Side::Yes, 500, dec!(0.50)); // $250
 
-    let check = monitor.can_add_exposure(&state, "mkt-c", dec!(60));
+    let check = monitor.can_add_exposure(&state, "mkt-c", dec!(60), dec!(0.50));
     assert!(
         !check.allowed,
         "Adding $60 to $450 total exceeds $500 portfolio limit"
@@ -722,11 +1233,11 @@ fn exposure_position_count_limit() {
     state.update_position("c", Side::Yes, 10, dec!(1.00)); // $10
 
     // New market "d"
-    let check = monitor.can_add_exposure(&state, "d", dec!(10));
+    let check = monitor.can_add_exposure(&state, "d", dec!(10), dec!(0.50));
     assert!(!check.allowed, "4th position must be blocked");
 
     // Existing market "a" should still be fine
-    let check_existing = monitor.can_add_exposure(&state, "a", dec!(10));
+    let check_existing = monitor.can_add_exposure(&state, "a", dec!(10), dec!(0.50));
     assert!(
         check_existing.allowed,
         "Adding to existing position should be allowed"
@@ -748,7 +1259,7 @@ fn exposure_correlation_group_limit() {
     state.update_position("a", Side::Yes, 150, dec!(1.00)); // $150
     state.update_position("b", Side::Yes, 100, dec!(1.00)); // $100
 
-    let check = monitor.can_add_exposure(&state, "b", dec!(60));
+    let check = monitor.can_add_exposure(&state, "b", dec!(60), dec!(0.50));
     assert!(
         !check.allowed,
         "Correlation group $310 > $300 limit must block"
@@ -766,7 +1277,7 @@ fn exposure_headroom_calculation() {
     let monitor = make_exposure_monitor();
     let state = StateManager::new(dec!(10000));
 
-    let check = monitor.can_add_exposure(&state, "fresh", dec!(100));
+    let check = monitor.can_add_exposure(&state, "fresh", dec!(100), dec!(0.50));
     assert!(check.allowed);
     assert_eq!(
         check.max_additional_exposure,
@@ -775,6 +1286,65 @@ fn exposure_headroom_calculation() {
     );
 }
 
+#[test]
+fn exposure_equity_pct_binds_before_dollar_cap_at_low_equity() {
+    // 10% of equity vs $200. With $50 held on $1,000 cash, equity is $1,050
+    // and $105 binds; on $10,000 cash the $200 dollar limit does.
+    let monitor = ExposureMonitor::new(ExposureConfig {
+        max_position_pct_of_equity: dec!(0.10),
+        ..make_exposure_config()
+    });
+    let small = StateManager::new(dec!(1000));
+    small.update_position("foo", Side::Yes, 100, dec!(0.50)); // $50
+    assert_eq!(monitor.market_limit(&small), dec!(105));
+    let check = monitor.can_add_exposure(&small, "foo", dec!(60), dec!(0.50));
+    assert!(!check.allowed);
+    assert!(check.reason.ends_with("> $105.00"), "{}", check.reason);
+    assert_eq!(check.max_additional_exposure, dec!(55));
+
+    let large = StateManager::new(dec!(10000));
+    large.update_position("foo", Side::Yes, 100, dec!(0.50));
+    assert_eq!(monitor.market_limit(&large), dec!(200));
+    assert!(monitor.can_add_exposure(&large, "foo", dec!(60), dec!(0.50)).allowed);
+
+    // Through the risk manager: a $100 buy on the small book is cut to the
+    // $55 of headroom left under 10% of equity.
+    let config = RiskConfig {
+        max_position_per_market: dec!(200),
+        max_position_pct_of_equity: dec!(0.10),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, small);
+    let decision = rm.evaluate_signal(make_buy_signal("foo", dec!(0.50), 200, 1.0, None));
+    assert!(decision.approved, "{}", decision.reason);
+    assert_eq!(decision.signal.unwrap().quantity, 110);
+}
+
+#[test]
+fn exposure_contract_cap_binds_before_dollar_cap_on_cheap_market() {
+    // $200 per market allows 10,000 contracts at $0.02; the cap allows 2,000.
+    let monitor = ExposureMonitor::new(ExposureConfig {
+        max_contracts_per_market: 2000,
+        ..make_exposure_config()
+    });
+    let state = StateManager::new(dec!(10000));
+    state.update_position("penny", Side::Yes, 1500, dec!(0.02)); // $30
+
+    // +$20 at $0.02 = 1,000 contracts: $50 is well under $200, but
+    // 1,500 + 1,000 = 2,500 > 2,000.
+    let check = monitor.can_add_exposure(&state, "penny", dec!(20), dec!(0.02));
+    assert!(!check.allowed);
+    assert!(check.reason.starts_with("Per-market contract limit"), "{}", check.reason);
+    // Headroom = min($170 dollars, 500 contracts x $0.02 = $10) = $10.
+    assert_eq!(check.max_additional_exposure, dec!(10));
+
+    // At $0.50 the same cap is looser than the dollar limit:
+    // 1,500 + 20 = 1,520 contracts passes; headroom min($170, 500 x 0.50) = $170.
+    let check = monitor.can_add_exposure(&state, "penny", dec!(10), dec!(0.50));
+    assert!(check.allowed);
+    assert_eq!(check.max_additional_exposure, dec!(170));
+}
+
 // =============================================================================
 // 5. Risk Manager Integration
 // =============================================================================
@@ -825,6 +1395,24 @@ fn risk_buy_exceeding_per_market_limit_resized() {
     );
 }
 
+#[test]
+fn risk_buy_on_cheap_market_resized_to_contract_cap() {
+    // $200 per market would allow 10,000 contracts at $0.02; the
+    // 3,000-contract cap resizes the buy to 3,000 ($60).
+    let state = StateManager::new(dec!(5000));
+    let config = RiskConfig {
+        max_position_per_market: dec!(200),
+        max_contracts_per_market: 3000,
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state);
+
+    let signal = make_buy_signal("penny", dec!(0.02), 8000, 1.0, None);
+    let decision = rm.evaluate_signal(signal);
+    assert!(decision.approved, "{}", decision.reason);
+    assert_eq!(decision.signal.unwrap().quantity, 3000);
+}
+
 #[test]
 fn risk_buy_with_kelly_sizing() {
     // Setup: equity=$1000, price=0.50, true_prob=0.60, confidence=1.0
@@ -832,8 +1420,7 @@ fn risk_buy_with_kelly_sizing() {
     // Signal requests 500, Kelly caps near 100.
     //
     // NOTE: true_probability passes through serde_json (f64) -> Decimal.
-    // f64 0.6 = 0.599999...97, so Kelly computes 49.99.../0.50 = 99.99... => floor = 99.
-    // This is correct behavior: the f64 imprecision is a realistic input path.
+    // The conversion yields exactly 0.6, so Kelly computes 50.00/0.50 = 100.
     let state = StateManager::new(dec!(1000));
     let config = permissive_risk_config();
     let mut rm = RiskManager::new(config, state);
@@ -843,11 +1430,7 @@ fn risk_buy_with_kelly_sizing() {
     assert!(decision.approved, "Kelly-sized buy should be approved");
 
     let approved = decision.signal.unwrap();
-    assert!(
-        approved.quantity == 99 || approved.quantity == 100,
-        "Kelly should cap near 100 contracts (got {}; 99 expected due to f64->Decimal rounding)",
-        approved.quantity
-    );
+    assert_eq!(approved.quantity, 100, "Kelly should cap at 100 contracts");
 }
 
 #[test]
@@ -930,6 +1513,63 @@ fn risk_drawdown_blocks_new_buys() {
     );
 }
 
+#[test]
+fn risk_warning_tier_scales_buy_sizing() {
+    // daily loss limit $200, warning at 70% ($140), scale 0.5.
+    // Buy 100 @ $0.50 (no true_probability => raw qty):
+    //   equity $1000 (loss $0)   => 100 contracts
+    //   equity  $850 (loss $150) => floor(100 * 0.5) = 50 contracts
+    //   equity  $790 (loss $210) => tripped => rejected
+    let state = StateManager::new(dec!(1000));
+    let config = RiskConfig {
+        max_daily_loss: dec!(200),
+        max_drawdown_pct: dec!(0.50),
+        max_total_pnl_drawdown_pct_for_new_buys: Decimal::ZERO,
+        warning_threshold_pct: dec!(0.70),
+        warning_size_scale: dec!(0.5),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state.clone());
+
+    let decision = rm.evaluate_signal(make_buy_signal("mkt", dec!(0.50), 100, 1.0, None));
+    assert!(decision.approved);
+    assert_eq!(decision.signal.unwrap().quantity, 100, "Normal band: full size");
+
+    state.update_balance(dec!(850));
+    let decision = rm.evaluate_signal(make_buy_signal("mkt", dec!(0.50), 100, 1.0, None));
+    assert!(decision.approved, "Warning band must still trade");
+    assert_eq!(decision.signal.unwrap().quantity, 50, "Warning band: half size");
+
+    state.update_balance(dec!(790));
+    let decision = rm.evaluate_signal(make_buy_signal("mkt", dec!(0.50), 100, 1.0, None));
+    assert!(!decision.approved, "Tripped: zero size");
+}
+
+#[test]
+fn risk_repeat_kelly_signal_only_tops_up_to_target() {
+    // Equity $1000, price 0.50, p = 0.60, quarter Kelly => $50 target
+    // (100 contracts). Equity is cash + cost basis, so cash is lowered by
+    // whatever is held to keep equity at $1000.
+    let state = StateManager::new(dec!(1000));
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let signal = || make_buy_signal("kelly", dec!(0.50), 500, 1.0, Some(0.60));
+
+    let first = rm.evaluate_signal(signal());
+    assert_eq!(first.signal.unwrap().quantity, 100);
+
+    // 60 held ($30): the second signal only adds $20 => 40 contracts.
+    state.update_position("kelly", Side::Yes, 60, dec!(0.50));
+    state.update_balance(dec!(970));
+    let second = rm.evaluate_signal(signal());
+    assert_eq!(second.signal.unwrap().quantity, 40);
+
+    // Fully at target: rejected.
+    state.update_position("kelly", Side::Yes, 100, dec!(0.50));
+    state.update_balance(dec!(950));
+    let third = rm.evaluate_signal(signal());
+    assert!(!third.approved, "Already at Kelly target: {}", third.reason);
+}
+
 // =============================================================================
 // 6. Order Book
 // =============================================================================
@@ -1018,6 +1658,7 @@ fn orderbook_top_of_book_mid_price() {
         yes_best_ask: Some(dec!(0.52)),
         no_best_bid: None,
         no_best_ask: None,
+        ..TopOfBook::default()
     };
     assert_eq!(top.yes_mid(), Some(dec!(0.50)), "Mid = (0.48+0.52)/2 = 0.50");
 }
@@ -1030,6 +1671,7 @@ fn orderbook_top_of_book_spread() {
         yes_best_ask: Some(dec!(0.52)),
         no_best_bid: None,
         no_best_ask: None,
+        ..TopOfBook::default()
     };
     assert_eq!(top.yes_spread(), Some(dec!(0.04)), "Spread = 0.52 - 0.48 = 0.04");
 }
@@ -1042,6 +1684,7 @@ fn orderbook_completeness_sum() {
         yes_best_ask: Some(dec!(0.52)),
         no_best_bid: None,
         no_best_ask: Some(dec!(0.49)),
+        ..TopOfBook::default()
     };
     assert_eq!(
         top.completeness_sum(),
@@ -1167,6 +1810,59 @@ fn orderbook_tracker_update_side() {
     );
 }
 
+#[test]
+fn orderbook_snapshot_round_trips_through_serde() {
+    // Two YES bid levels so ordering and quantities must survive the trip.
+    let tracker = OrderBookTracker::new();
+    let mut book = make_book("snap", dec!(0.55), dec!(0.40));
+    book.yes.bids.push(PriceLevel { price: dec!(0.51), quantity: 37 });
+    tracker.update(book);
+
+    let snapshot = tracker.export_snapshot("snap").unwrap();
+    assert_eq!(snapshot["market_slug"], "snap");
+    assert!(snapshot["captured_at"].is_string());
+    assert!(snapshot["updated_at"].is_string());
+
+    let restored: OrderBook = serde_json::from_value(snapshot["book"].clone()).unwrap();
+    let original = tracker.get_full("snap").unwrap();
+    assert_eq!(restored.market_slug, original.market_slug);
+    assert_eq!(restored.yes.bids.len(), 2);
+    assert_eq!(restored.yes.bids[1].price, dec!(0.51));
+    assert_eq!(restored.yes.bids[1].quantity, 37);
+    assert_eq!(restored.yes.best_ask(), original.yes.best_ask());
+    assert_eq!(restored.no.best_ask(), original.no.best_ask());
+
+    assert!(tracker.export_snapshot("unknown").is_none());
+
+    let all = tracker.export_all();
+    assert_eq!(all["markets"].as_object().unwrap().len(), 1);
+    assert_eq!(all["markets"]["snap"]["book"], snapshot["book"]);
+}
+
+#[test]
+fn signal_book_log_keeps_latest_entries_with_book() {
+    // Capacity 2: the third record evicts the first.
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("logged", dec!(0.50), dec!(0.45)));
+
+    let mut log = SignalBookLog::new(2);
+    for qty in [1, 2, 3] {
+        let mut signal = make_buy_signal("logged", dec!(0.50), qty, 0.8, None);
+        signal.reason = format!("entry {qty}");
+        log.record(&signal, &tracker);
+    }
+
+    assert_eq!(log.len(), 2);
+    let entries: Vec<_> = log.entries().collect();
+    assert_eq!(entries[0]["signal"]["quantity"], 2);
+    assert_eq!(entries[1]["signal"]["quantity"], 3);
+    assert_eq!(entries[1]["book"]["book"]["market_slug"], "logged");
+
+    let mut disabled = SignalBookLog::new(0);
+    disabled.record(&make_buy_signal("logged", dec!(0.50), 1, 0.8, None), &tracker);
+    assert!(disabled.is_empty());
+}
+
 // =============================================================================
 // Edge cases and regression guards
 // =============================================================================
@@ -1190,23 +1886,19 @@ fn kelly_tiny_bankroll_produces_zero_contracts() {
 fn arb_combined_just_below_one() {
     // YES=0.499, NO=0.500 => combined = 0.999
     // gross = 0.001, fee = 0.999*0.001 = 0.000999
-    // net = 0.001 - 0.000999 = 0.000001
-    // With min_margin = 0, this should still be detected
+    // net = 0.001 - 0.000999 = 0.000001 < min_margin 0.00001 => skipped
     let tracker = OrderBookTracker::new();
     tracker.update(make_book("razor-thin", dec!(0.499), dec!(0.500)));
 
-    let signals = tracker.scan_completeness_arb(Decimal::ZERO);
-    assert_eq!(signals.len(), 1, "Razor-thin arb should be detected");
+    let signals = tracker.scan_completeness_arb(dec!(0.00001), 0, Decimal::ZERO, dec!(0.001));
+    assert!(signals.is_empty(), "Razor-thin arb cannot cover its entry fees");
+
+    // With no fees the full 0.001 gross margin is an arb
+    let signals = tracker.scan_completeness_arb(Decimal::ZERO, 0, Decimal::ZERO, Decimal::ZERO);
+    assert_eq!(signals.len(), 1, "Razor-thin arb should be detected fee-free");
     let sig = &signals[0];
     assert_eq!(sig.combined_cost, dec!(0.999));
-
-    // Verify net margin is positive but very small
-    assert!(
-        sig.net_margin > Decimal::ZERO,
-        "Net margin must be positive"
-    );
-    // net = 0.001 - 0.000999 = 0.000001
-    assert_eq!(sig.net_margin, dec!(0.000001));
+    assert_eq!(sig.net_margin, dec!(0.001));
 }
 
 #[test]
@@ -1231,7 +1923,7 @@ fn exposure_all_limits_pass() {
     let monitor = make_exposure_monitor();
     let state = StateManager::new(dec!(10000));
 
-    let check = monitor.can_add_exposure(&state, "fresh-market", dec!(50));
+    let check = monitor.can_add_exposure(&state, "fresh-market", dec!(50), dec!(0.50));
     assert!(check.allowed, "Small trade in empty portfolio must pass");
     assert_eq!(check.reason, "OK");
 }
@@ -1283,3 +1975,3002 @@ fn risk_buy_zero_quantity_rejected() {
         "Reason should mention non-positive quantity"
     );
 }
+
+fn quality_floor(tracker: &OrderBookTracker, min_score: Decimal) {
+    tracker.set_quality_config(BookQualityConfig {
+        min_score,
+        ..BookQualityConfig::default()
+    });
+}
+
+#[test]
+fn crossed_book_is_flagged_and_excluded() {
+    // YES bid 0.50 above YES ask 0.45 => crossed, score 0
+    // Completeness 0.45 + 0.45 = 0.90 would look like an arb on raw prices
+    let mut book = make_book("crossed", dec!(0.45), dec!(0.45));
+    book.yes.bids[0].price = dec!(0.50);
+    let tracker = OrderBookTracker::new();
+    tracker.update(book);
+
+    let q = tracker.book_quality("crossed").unwrap();
+    assert!(q.crossed);
+    assert!(!q.one_sided);
+    assert_eq!(q.score, Decimal::ZERO);
+
+    let scan = |t: &OrderBookTracker| {
+        t.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001)).len()
+    };
+    assert_eq!(scan(&tracker), 0, "Crossed book skipped by the scanner even without a floor");
+    quality_floor(&tracker, dec!(0.5));
+    assert_eq!(scan(&tracker), 0, "Floor 0.5 => crossed book skipped");
+    assert!(!tracker.passes_quality_floor("crossed"));
+
+    // The market maker also stands aside, even though the state prices look fine
+    let mut mm = MarketMakerStrategy::new(MarketMakerConfig {
+        maker_only: false,
+        ..MarketMakerConfig::default()
+    });
+    let market = make_market("crossed", dec!(0.45), dec!(0.55));
+    mm.set_orderbook(tracker.clone());
+    assert!(mm.on_market_update(&market, None).is_empty());
+    quality_floor(&tracker, Decimal::ZERO);
+    assert!(!mm.on_market_update(&market, None).is_empty());
+}
+
+#[test]
+fn one_sided_and_wide_books_score_below_half() {
+    // One-sided: YES bids removed => score 0.25
+    let mut book = make_book("one-sided", dec!(0.45), dec!(0.45));
+    book.yes.bids.clear();
+    let tracker = OrderBookTracker::new();
+    tracker.update(book);
+    tracker.update(make_book("healthy", dec!(0.52), dec!(0.50)));
+
+    let q = tracker.book_quality("one-sided").unwrap();
+    assert!(q.one_sided);
+    assert!(!q.crossed);
+    assert_eq!(q.spread, None);
+    assert_eq!(q.score, dec!(0.25));
+
+    // Healthy: spread 0.02, threshold 0.20 => 1 - 0.02 / 0.40 = 0.95
+    let q = tracker.book_quality("healthy").unwrap();
+    assert!(!q.one_sided && !q.crossed && !q.wide_spread);
+    assert_eq!(q.score, dec!(0.95));
+
+    // Wide: bid 0.30 / ask 0.55 => spread 0.25 > 0.20, score 1 - 0.625 = 0.375
+    let mut wide = make_book("wide", dec!(0.55), dec!(0.50));
+    wide.yes.bids[0].price = dec!(0.30);
+    tracker.update(wide);
+    let q = tracker.book_quality("wide").unwrap();
+    assert!(q.wide_spread);
+    assert_eq!(q.score, dec!(0.375));
+
+    quality_floor(&tracker, dec!(0.5));
+    assert!(!tracker.passes_quality_floor("one-sided"));
+    assert!(!tracker.passes_quality_floor("wide"));
+    assert!(tracker.passes_quality_floor("healthy"));
+    assert!(tracker.passes_quality_floor("untracked"));
+    let slugs: Vec<String> = tracker
+        .scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001))
+        .into_iter()
+        .map(|a| a.market_slug)
+        .collect();
+    assert!(!slugs.contains(&"one-sided".to_string()), "One-sided book excluded");
+}
+
+// =============================================================================
+// 7. Portfolio VaR / Expected Shortfall
+// =============================================================================
+
+/// Two independent YES positions:
+///   A: 100 contracts, mid 0.60 => value $60, P(lose) = 0.40
+///   B:  50 contracts, mid 0.80 => value $40, P(lose) = 0.20
+fn two_position_state() -> StateManager {
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("mkt-a", dec!(0.59), dec!(0.61)));
+    state.update_market(make_market("mkt-b", dec!(0.79), dec!(0.81)));
+    state.update_position("mkt-a", Side::Yes, 100, dec!(0.55));
+    state.update_position("mkt-b", Side::Yes, 50, dec!(0.75));
+    state
+}
+
+#[test]
+fn var_two_independent_positions_hand_computed() {
+    // Loss distribution:
+    //   $0   : 0.60 * 0.80 = 0.48   (cum 0.48)
+    //   $40  : 0.60 * 0.20 = 0.12   (cum 0.60)
+    //   $60  : 0.40 * 0.80 = 0.32   (cum 0.92)
+    //   $100 : 0.40 * 0.20 = 0.08   (cum 1.00)
+    let rm = RiskManager::new(permissive_risk_config(), two_position_state());
+
+    assert_eq!(rm.portfolio_var(dec!(0.50)), dec!(40));
+    assert_eq!(rm.portfolio_var(dec!(0.90)), dec!(60));
+    assert_eq!(rm.portfolio_var(dec!(0.95)), dec!(100));
+
+    // ES(0.90): worst 10% tail = 0.08 @ $100 + 0.02 @ $60
+    //   = (8.0 + 1.2) / 0.10 = $92
+    assert_eq!(rm.portfolio_expected_shortfall(dec!(0.90)), dec!(92));
+}
+
+#[test]
+fn var_correlated_group_resolves_together() {
+    // Same positions in one correlation group: the group loses $100 with
+    // P = max(0.40, 0.20) = 0.40, otherwise $0.
+    //   $0   : 0.60 (cum 0.60)
+    //   $100 : 0.40 (cum 1.00)
+    let mut rm = RiskManager::new(permissive_risk_config(), two_position_state());
+    rm.set_correlation_group(
+        "same-game",
+        vec!["mkt-a".to_string(), "mkt-b".to_string()],
+    );
+
+    assert_eq!(rm.portfolio_var(dec!(0.50)), dec!(0));
+    assert_eq!(rm.portfolio_var(dec!(0.90)), dec!(100));
+    assert_eq!(rm.portfolio_expected_shortfall(dec!(0.90)), dec!(100));
+}
+
+#[test]
+fn var_counts_hedge_legs() {
+    // mkt-a also holds a 50 NO hedge leg: value 0.40 * 50 = $20, P(lose)
+    // 0.60. Merged with the YES leg, mkt-a loses $80 with P = 0.60.
+    //   $0  : 0.40 * 0.80 = 0.32 (cum 0.32)
+    //   $40 : 0.40 * 0.20 = 0.08 (cum 0.40)
+    //   $80 : 0.60 * 0.80 = 0.48 (cum 0.88)
+    let state = two_position_state();
+    state.update_hedge_position("mkt-a", Side::No, 50, dec!(0.40));
+    let rm = RiskManager::new(permissive_risk_config(), state);
+
+    assert_eq!(rm.portfolio_var(dec!(0.50)), dec!(80));
+    assert_eq!(rm.portfolio_var(dec!(0.95)), dec!(120));
+}
+
+#[test]
+fn var_empty_portfolio_is_zero() {
+    let rm = RiskManager::new(permissive_risk_config(), StateManager::new(dec!(1000)));
+    assert_eq!(rm.portfolio_var(dec!(0.95)), dec!(0));
+    assert_eq!(rm.portfolio_expected_shortfall(dec!(0.95)), dec!(0));
+}
+
+#[test]
+fn var_limit_rejects_buy_that_pushes_var_over_max() {
+    // Existing VaR(0.90) = $60. Buying 100 YES in mkt-c at mid 0.50 adds an
+    // independent $50 unit with P(lose) = 0.50, so VaR(0.90) jumps to $100+.
+    let state = two_position_state();
+    state.update_market(make_market("mkt-c", dec!(0.49), dec!(0.51)));
+    let config = RiskConfig {
+        max_var: dec!(80),
+        var_confidence: dec!(0.90),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state);
+
+    let signal = make_buy_signal("mkt-c", dec!(0.50), 100, 1.0, None);
+    let decision = rm.evaluate_signal(signal);
+    assert!(!decision.approved, "Buy pushing VaR above max must be rejected");
+    assert!(decision.reason.contains("VaR"));
+
+    // A small buy keeps VaR(0.90) at $60 + $1 = $61 <= $80.
+    let small = make_buy_signal("mkt-c", dec!(0.50), 2, 1.0, None);
+    assert!(rm.evaluate_signal(small).approved);
+}
+
+#[test]
+fn var_of_many_positions_is_bucketed_and_errs_high() {
+    // 40 independent YES positions at mid 0.50 with distinct sizes (10..50
+    // contracts): 2^40 loss combinations, far too many to enumerate.
+    let state = StateManager::new(dec!(10000));
+    let sizes: Vec<i64> = (10..50).collect();
+    for (i, qty) in sizes.iter().enumerate() {
+        let slug = format!("many-{i}");
+        state.update_market(make_market(&slug, dec!(0.49), dec!(0.51)));
+        state.update_position(&slug, Side::Yes, *qty, dec!(0.50));
+    }
+    let rm = RiskManager::new(permissive_risk_config(), state);
+
+    // Exact VaR(0.95) by counting lost contracts, each worth $0.50.
+    let total: i64 = sizes.iter().sum();
+    let mut ways = vec![0.0_f64; total as usize + 1];
+    ways[0] = 1.0;
+    for qty in &sizes {
+        for lost in (*qty as usize..=total as usize).rev() {
+            ways[lost] = (ways[lost] + ways[lost - *qty as usize]) / 2.0;
+        }
+        for p in &mut ways[..*qty as usize] {
+            *p /= 2.0;
+        }
+    }
+    let mut cumulative = 0.0;
+    let lost = ways.iter().position(|p| {
+        cumulative += p;
+        cumulative >= 0.95
+    });
+    let exact = Decimal::from(lost.unwrap() as i64) / dec!(2);
+
+    // Within 1% of the $590 at risk, and never below the exact value.
+    let var = rm.portfolio_var(dec!(0.95));
+    assert!(var >= exact, "{var} < {exact}");
+    assert!(var - exact <= dec!(5.90), "{var} vs {exact}");
+    assert!(rm.portfolio_expected_shortfall(dec!(0.95)) >= var);
+}
+
+// =============================================================================
+// 8. Order-Rate Limiter
+// =============================================================================
+
+#[test]
+fn order_rate_global_limit_caps_tight_loop() {
+    // Global bucket of 10/s: 50 back-to-back signals across 50 markets
+    // => only the first 10 (burst capacity) are approved.
+    let config = RiskConfig {
+        max_orders_per_sec: 10,
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, StateManager::new(dec!(10000)));
+
+    let approved = (0..50)
+        .map(|i| make_buy_signal(&format!("mkt-{i}"), dec!(0.50), 2, 1.0, None))
+        .filter(|s| rm.evaluate_signal(s.clone()).approved)
+        .count();
+    assert_eq!(approved, 10, "Only the 10-token burst may pass within 1s");
+}
+
+#[test]
+fn order_rate_per_market_limit_is_independent_per_market() {
+    // Per-market bucket of 5/s: 50 signals on one market => 5 approved,
+    // while another market still has its own full bucket.
+    let config = RiskConfig {
+        max_orders_per_sec_per_market: 5,
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, StateManager::new(dec!(10000)));
+
+    let mut approved = 0;
+    for _ in 0..50 {
+        let decision = rm.evaluate_signal(make_buy_signal("hot", dec!(0.50), 2, 1.0, None));
+        if decision.approved {
+            approved += 1;
+        } else {
+            assert!(decision.reason.contains("rate limit"));
+        }
+    }
+    assert_eq!(approved, 5);
+
+    let other = rm.evaluate_signal(make_buy_signal("cold", dec!(0.50), 2, 1.0, None));
+    assert!(other.approved, "Other market has its own bucket");
+
+    // Cancels are never rate limited.
+    assert!(rm.evaluate_signal(make_cancel_signal("hot")).approved);
+}
+
+#[test]
+fn order_rate_rejection_by_one_bucket_does_not_spend_the_other() {
+    // Global 10/s, per-market 5/s. Once the global bucket is empty, the
+    // signals it refuses must not drain "hot"'s own bucket.
+    let config = RiskConfig {
+        max_orders_per_sec: 10,
+        max_orders_per_sec_per_market: 5,
+        ..permissive_risk_config()
+    };
+    let clock = MockClock::default();
+    let mut rm = RiskManager::new(config, StateManager::new(dec!(10000)));
+    rm.set_clock(std::sync::Arc::new(clock.clone()));
+    let mut approved = |slug: &str, n: usize| {
+        (0..n)
+            .map(|_| make_buy_signal(slug, dec!(0.50), 2, 1.0, None))
+            .filter(|signal| rm.evaluate_signal(signal.clone()).approved)
+            .count()
+    };
+
+    let spread: usize = (0..10).map(|i| approved(&format!("mkt-{i}"), 1)).sum();
+    assert_eq!(spread, 10);
+    assert_eq!(approved("hot", 5), 0, "Global bucket is empty");
+
+    // Half a second refills 5 global tokens; "hot" still has all 5 of its.
+    clock.advance(chrono::Duration::milliseconds(500));
+    assert_eq!(approved("hot", 50), 5);
+}
+
+// =============================================================================
+// 9. Bookmaker Line Shopping
+// =============================================================================
+
+fn make_odds(slug: &str, provider: &str, yes_probability: Decimal) -> OddsSnapshot {
+    OddsSnapshot {
+        event_id: slug.to_string(),
+        market_slug: Some(slug.to_string()),
+        provider: provider.to_string(),
+        yes_probability,
+        confidence: 0.8,
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+fn true_probability(signal: &Signal) -> f64 {
+    signal.metadata["true_probability"].as_f64().unwrap()
+}
+
+#[test]
+fn line_shop_outlier_book_beats_consensus_edge() {
+    // Polymarket YES ask = 0.50, NO ask = 1 - 0.48 = 0.52
+    // Books: 0.51, 0.51, 0.60 => consensus = 1.62 / 3 = 0.54
+    // Consensus YES edge = 0.54 - 0.50 = 0.04
+    // Best line: book "sharp" 0.60 => YES edge = 0.60 - 0.50 = 0.10
+    let market = make_market("nba-lal-bos", dec!(0.48), dec!(0.50));
+    let books = vec![
+        make_odds("nba-lal-bos", "book-a", dec!(0.51)),
+        make_odds("nba-lal-bos", "book-b", dec!(0.51)),
+        make_odds("nba-lal-bos", "sharp", dec!(0.60)),
+    ];
+
+    assert_eq!(consensus_probability(&books), Some(dec!(0.54)));
+
+    let best = best_line_probability(&books, &market).unwrap();
+    assert_eq!(best.provider, "sharp");
+    assert_eq!(best.side, Side::Yes);
+    assert_eq!(best.yes_probability, dec!(0.60));
+    assert_eq!(best.edge, dec!(0.10));
+    assert_eq!(best.consensus, dec!(0.54));
+    assert!(best.edge > best.consensus - dec!(0.50));
+}
+
+#[test]
+fn line_shop_low_outlier_selects_no_side() {
+    // Books: 0.50, 0.40 => consensus = 0.45
+    // YES edges: 0.50 - 0.50 = 0.00, 0.40 - 0.50 = -0.10
+    // NO edges:  0.50 - 0.52 = -0.02, 0.60 - 0.52 = 0.08
+    // Best = book-b on NO with edge 0.08
+    let market = make_market("nfl-kc-buf", dec!(0.48), dec!(0.50));
+    let books = vec![
+        make_odds("nfl-kc-buf", "book-a", dec!(0.50)),
+        make_odds("nfl-kc-buf", "book-b", dec!(0.40)),
+    ];
+
+    let best = best_line_probability(&books, &market).unwrap();
+    assert_eq!(best.provider, "book-b");
+    assert_eq!(best.side, Side::No);
+    assert_eq!(best.edge, dec!(0.08));
+    assert_eq!(best.consensus, dec!(0.45));
+}
+
+#[test]
+fn line_shop_no_books_returns_none() {
+    let market = make_market("empty", dec!(0.48), dec!(0.50));
+    assert_eq!(consensus_probability(&[]), None);
+    assert!(best_line_probability(&[], &market).is_none());
+}
+
+#[test]
+fn line_shop_strategy_acts_on_outlier_only_in_best_line_mode() {
+    // min_edge = 0.05. Consensus edge 0.04 is below threshold; the sharp
+    // book's 0.10 edge is above it.
+    let market = make_market("nba-lal-bos", dec!(0.48), dec!(0.50));
+    let run = |aggregation: OddsAggregation| {
+        let mut strategy = StatisticalEdgeStrategy::new(StatisticalEdgeConfig {
+            min_edge: dec!(0.05),
+            aggregation,
+            ..StatisticalEdgeConfig::default()
+        });
+        for (provider, p) in [
+            ("book-a", dec!(0.51)),
+            ("book-b", dec!(0.51)),
+            ("sharp", dec!(0.60)),
+        ] {
+            strategy.ingest_odds(make_odds("nba-lal-bos", provider, p));
+        }
+        strategy.on_tick(|_| Some(market.clone()))
+    };
+
+    assert!(
+        run(OddsAggregation::Consensus).is_empty(),
+        "Consensus (default) must not trade a 0.04 edge"
+    );
+
+    let signals = run(OddsAggregation::BestLine);
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].action, SignalAction::BuyYes);
+    assert_eq!(signals[0].price, dec!(0.50));
+    assert!((true_probability(&signals[0]) - 0.60).abs() < 1e-9);
+    assert!(signals[0].reason.contains("sharp"));
+}
+
+#[test]
+fn line_shop_default_is_consensus() {
+    assert_eq!(
+        StatisticalEdgeConfig::default().aggregation,
+        OddsAggregation::Consensus
+    );
+}
+
+// =============================================================================
+// 10. Position Reconciliation Drift
+// =============================================================================
+
+fn make_api_position(slug: &str, side: Side, quantity: i64, avg_price: Decimal) -> Position {
+    Position {
+        market_slug: slug.to_string(),
+        side,
+        quantity,
+        avg_price,
+        current_price: None,
+        current_value: None,
+        unrealized_pnl: None,
+    }
+}
+
+#[test]
+fn reconcile_flags_wrong_local_position_with_deltas() {
+    // Local: 100 YES @ 0.50 (cost $50). API: 80 YES @ 0.55 (cost $44).
+    // quantity_delta = 80 - 100 = -20
+    // avg_price_delta = 0.55 - 0.50 = 0.05
+    // notional_drift = |44 - 50| = 6
+    let state = StateManager::new(dec!(1000));
+    state.update_position("nba-lal-bos", Side::Yes, 100, dec!(0.50));
+    state.update_position("nba-gsw-phx", Side::No, 10, dec!(0.40));
+
+    let report = state.reconcile_positions(&[
+        make_api_position("nba-lal-bos", Side::Yes, 80, dec!(0.55)),
+        make_api_position("nba-gsw-phx", Side::No, 10, dec!(0.40)),
+    ]);
+
+    assert_eq!(report.markets_checked, 2);
+    assert_eq!(report.drifts.len(), 1, "Matching position must not be flagged");
+    let drift = &report.drifts[0];
+    assert_eq!(drift.market_slug, "nba-lal-bos");
+    assert_eq!(drift.quantity_delta(), -20);
+    assert_eq!(drift.avg_price_delta(), dec!(0.05));
+    assert_eq!(drift.notional_drift(), dec!(6));
+
+    // $6 drift alerts at a $5 threshold but not at $10.
+    assert_eq!(report.drifts_above(dec!(5)).len(), 1);
+    assert!(report.drifts_above(dec!(10)).is_empty());
+
+    // Local state is overwritten with API truth after reporting.
+    let pos = state.get_position("nba-lal-bos").unwrap();
+    assert_eq!(pos.quantity, 80);
+    assert_eq!(pos.avg_price, dec!(0.55));
+}
+
+#[test]
+fn reconcile_flags_side_mismatch_and_missing_positions() {
+    // Side flip: local 50 YES @ 0.40 ($20), API 50 NO @ 0.60 ($30)
+    //   notional_drift = 20 + 30 = 50 (no offset across sides)
+    // Unknown to local: API 10 YES @ 0.30 => drift $3
+    // Unknown to API: local 5 NO @ 0.20 => drift $1
+    let state = StateManager::new(dec!(1000));
+    state.update_position("flip", Side::Yes, 50, dec!(0.40));
+    state.update_position("ghost", Side::No, 5, dec!(0.20));
+
+    let report = state.reconcile_positions(&[
+        make_api_position("flip", Side::No, 50, dec!(0.60)),
+        make_api_position("new", Side::Yes, 10, dec!(0.30)),
+    ]);
+
+    assert_eq!(report.markets_checked, 3);
+    let by_slug: HashMap<_, _> = report
+        .drifts
+        .iter()
+        .map(|d| (d.market_slug.as_str(), d))
+        .collect();
+    assert_eq!(by_slug["flip"].notional_drift(), dec!(50));
+    assert_eq!(by_slug["new"].local_side, None);
+    assert_eq!(by_slug["new"].quantity_delta(), 10);
+    assert_eq!(by_slug["new"].notional_drift(), dec!(3));
+    assert_eq!(by_slug["ghost"].api_side, None);
+    assert_eq!(by_slug["ghost"].quantity_delta(), -5);
+    assert_eq!(by_slug["ghost"].notional_drift(), dec!(1));
+}
+
+#[test]
+fn reconcile_clean_when_local_matches_api() {
+    let state = StateManager::new(dec!(1000));
+    state.update_position("m", Side::Yes, 10, dec!(0.50));
+    let report = state.reconcile_positions(&[make_api_position("m", Side::Yes, 10, dec!(0.50))]);
+    assert!(report.is_clean());
+}
+
+// =============================================================================
+// 11. Market Discovery Filter
+// =============================================================================
+
+fn make_listed_market(slug: &str, volume_24h: Option<&str>, liquidity: Option<&str>) -> Market {
+    serde_json::from_value(serde_json::json!({
+        "slug": slug,
+        "active": true,
+        "volume24h": volume_24h,
+        "liquidity": liquidity,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn market_filter_drops_thin_markets_and_ranks_by_liquidity() {
+    let markets = vec![
+        make_listed_market("quiet", Some("50"), Some("5000")), // volume 50 < 100
+        make_listed_market("shallow", Some("900"), Some("200")), // liquidity 200 < 500
+        make_listed_market("unknown", None, None),              // missing => 0
+        make_listed_market("mid", Some("400"), Some("1500")),
+        make_listed_market("deep", Some("300"), Some("8000")),
+        make_listed_market("tie-b", Some("200"), Some("1000")),
+        make_listed_market("tie-a", Some("900"), Some("1000")),
+    ];
+    let config = MarketFilterConfig {
+        min_volume_24h: dec!(100),
+        min_liquidity: dec!(500),
+        max_markets: 0,
+    };
+
+    let selection = select_markets(&markets, &config);
+    let slugs: Vec<&str> = selection.selected.iter().map(|m| m.slug.as_str()).collect();
+    // Liquidity desc; the 1000-liquidity tie breaks on volume (900 > 200)
+    assert_eq!(slugs, vec!["deep", "mid", "tie-a", "tie-b"]);
+    assert_eq!(selection.below_volume, 2, "quiet + unknown");
+    assert_eq!(selection.below_liquidity, 1, "shallow");
+    assert_eq!(selection.over_limit, 0);
+
+    // Cap at 2 keeps the two deepest books
+    let capped = select_markets(&markets, &MarketFilterConfig { max_markets: 2, ..config });
+    let slugs: Vec<&str> = capped.selected.iter().map(|m| m.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["deep", "mid"]);
+    assert_eq!(capped.over_limit, 2);
+    assert_eq!(capped.filtered_out(), 5);
+}
+
+#[test]
+fn market_filter_with_zero_thresholds_keeps_everything() {
+    let markets = vec![
+        make_listed_market("a", None, None),
+        make_listed_market("b", Some("10"), Some("20")),
+    ];
+    let selection = select_markets(&markets, &MarketFilterConfig::default());
+    let slugs: Vec<&str> = selection.selected.iter().map(|m| m.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["b", "a"]);
+    assert_eq!(selection.filtered_out(), 0);
+}
+
+// =============================================================================
+// 12. Runtime Market Toggles
+// =============================================================================
+
+#[test]
+fn disabled_market_produces_no_signals_until_reenabled() {
+    let state = StateManager::new(dec!(1000));
+    let market = make_market("toggle", dec!(0.45), dec!(0.55));
+    state.update_market(market.clone());
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+
+    let quoted = engine.on_market_update(&market, &mut rm);
+    assert!(quoted.approved_signals.iter().any(|s| !s.is_cancel()));
+
+    // Disabling pulls resting orders once, then the market goes quiet.
+    state.set_market_enabled("toggle", false);
+    assert!(!state.is_market_enabled("toggle"));
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.approved_signals.len(), 1);
+    assert_eq!(out.approved_signals[0].action, SignalAction::CancelAll);
+    assert_eq!(out.approved_signals[0].market_slug, "toggle");
+
+    let out = engine.on_market_update(&market, &mut rm);
+    assert!(out.approved_signals.is_empty());
+    assert_eq!(out.rejected_count, 0);
+    assert!(engine.on_tick(&mut rm).approved_signals.is_empty());
+
+    // Re-enabling quotes immediately, without waiting for a refresh.
+    state.set_market_enabled("toggle", true);
+    let out = engine.on_market_update(&market, &mut rm);
+    assert!(out.approved_signals.iter().any(|s| s.action == SignalAction::BuyYes));
+}
+
+#[test]
+fn disabled_or_cold_market_still_exits_held_inventory() {
+    let state = StateManager::new(dec!(1000));
+    let market = make_market("stuck", dec!(0.45), dec!(0.55));
+    state.update_market(market.clone());
+    // Bought at 0.80, now bid 0.45: well past the stop-loss.
+    state.update_position("stuck", Side::Yes, 50, dec!(0.80));
+    state.set_market_enabled("stuck", false);
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+
+    let exits = |out: &EngineOutput| {
+        out.approved_signals
+            .iter()
+            .filter(|s| s.action == SignalAction::SellYes)
+            .count()
+    };
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(exits(&out), 1, "{:?}", out.approved_signals);
+    assert!(out.approved_signals.iter().all(|s| !s.is_buy()));
+    let out = engine.on_market_update(&market, &mut rm);
+    assert_eq!(exits(&out), 1);
+    assert!(out.approved_signals.iter().all(|s| !s.is_buy()));
+
+    // A cold market is exited the same way.
+    state.set_market_enabled("stuck", true);
+    state.set_warmup_config(WarmupConfig { min_updates: 100, ..WarmupConfig::default() });
+    assert!(!state.is_market_warm("stuck"));
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(exits(&out), 1);
+    assert!(out.approved_signals.iter().all(|s| !s.is_buy()));
+}
+
+// =============================================================================
+// 13. Odds Staleness Decay
+// =============================================================================
+
+#[test]
+fn odds_confidence_halves_every_half_life() {
+    // 0.8 * 0.5^(age / 300)
+    assert!((decayed_confidence(0.8, 0.0, 300.0) - 0.8).abs() < 1e-12);
+    assert!((decayed_confidence(0.8, 300.0, 300.0) - 0.4).abs() < 1e-12);
+    assert!((decayed_confidence(0.8, 1200.0, 300.0) - 0.05).abs() < 1e-12);
+    // Zero half-life disables decay; a future timestamp counts as fresh.
+    assert!((decayed_confidence(0.8, 1200.0, 0.0) - 0.8).abs() < 1e-12);
+    assert!((decayed_confidence(0.8, -60.0, 300.0) - 0.8).abs() < 1e-12);
+}
+
+#[test]
+fn stale_odds_stop_trading_below_min_edge_floor() {
+    // YES ask 0.50, book 0.56 => edge 0.06; min_edge 0.02
+    // Floor = 0.02 / 0.06 = 1/3
+    //   age 0:              0.8   >= 1/3 => trade
+    //   age 1 half-life:    0.4   >= 1/3 => trade
+    //   age 3 half-lives:   0.1   <  1/3 => no trade
+    let floor = min_confidence_for_edge(dec!(0.06), dec!(0.02));
+    assert!((floor - 1.0 / 3.0).abs() < 1e-9);
+
+    let market = make_market("nba-stale", dec!(0.48), dec!(0.50));
+    let run = |age_seconds: i64| {
+        let mut strategy = StatisticalEdgeStrategy::new(StatisticalEdgeConfig {
+            min_edge: dec!(0.02),
+            confidence_half_life_seconds: 300.0,
+            ..StatisticalEdgeConfig::default()
+        });
+        let mut odds = make_odds("nba-stale", "book-a", dec!(0.56));
+        odds.timestamp -= chrono::Duration::seconds(age_seconds);
+        strategy.ingest_odds(odds);
+        strategy.on_tick(|_| Some(market.clone()))
+    };
+
+    let fresh = run(0);
+    assert_eq!(fresh.len(), 1);
+    assert!((fresh[0].confidence - 0.8).abs() < 1e-3);
+
+    let one_half_life = run(300);
+    assert_eq!(one_half_life.len(), 1);
+    assert!((one_half_life[0].confidence - 0.4).abs() < 1e-3);
+
+    assert!(run(900).is_empty(), "0.1 confidence is below the 1/3 floor");
+}
+
+// =============================================================================
+// 14. Statistical Pairs Mode
+// =============================================================================
+
+#[test]
+fn pair_config_parses_slugs_and_hedge_ratio() {
+    let pair = PairConfig::parse("nba-lal-win:nba-lal-cover:0.8").unwrap();
+    assert_eq!(pair.leg_a, "nba-lal-win");
+    assert_eq!(pair.leg_b, "nba-lal-cover");
+    assert_eq!(pair.hedge_ratio, dec!(0.8));
+    assert!(PairConfig::parse("only-one:0.8").is_none());
+    assert!(PairConfig::parse("a:b:not-a-number").is_none());
+}
+
+/// Pairs-mode strategy on `leg-a:leg-b:<hedge_ratio>`, $10 per leg.
+fn pairs_strategy(hedge_ratio: &str) -> StatisticalEdgeStrategy {
+    StatisticalEdgeStrategy::new(StatisticalEdgeConfig {
+        order_size: dec!(10),
+        pairs: vec![PairConfig::parse(&format!("leg-a:leg-b:{hedge_ratio}")).unwrap()],
+        pair_entry_z: 2.0,
+        pair_exit_z: 0.5,
+        pair_lookback: 10,
+        ..StatisticalEdgeConfig::default()
+    })
+}
+
+/// One pairs tick with leg A at `a_bid`/`a_ask` and leg B at 0.49/0.51.
+fn pairs_tick(
+    strategy: &mut StatisticalEdgeStrategy,
+    a_bid: Decimal,
+    a_ask: Decimal,
+) -> Vec<Signal> {
+    let leg_a = make_market("leg-a", a_bid, a_ask);
+    let leg_b = make_market("leg-b", dec!(0.49), dec!(0.51));
+    strategy.on_pairs_tick(move |slug| match slug {
+        "leg-a" => Some(leg_a.clone()),
+        _ => Some(leg_b.clone()),
+    })
+}
+
+/// Ticks 1-20: A alternates mid 0.50 / 0.51 around a steady B, keeping
+/// |z| ~= 0.95 once the 10-sample window fills (below entry 2.0).
+fn pairs_baseline(strategy: &mut StatisticalEdgeStrategy) {
+    for i in 0..20 {
+        let signals = if i % 2 == 0 {
+            pairs_tick(strategy, dec!(0.49), dec!(0.51))
+        } else {
+            pairs_tick(strategy, dec!(0.50), dec!(0.52))
+        };
+        assert!(signals.is_empty(), "Baseline tick {} must not trade", i);
+    }
+}
+
+/// A statistical_edge buy fill; set `is_buy: false` for an exit fill.
+fn pair_fill(market: &str, side: Side, price: Decimal, quantity: i64) -> FillEvent {
+    FillEvent {
+        order_id: format!("{market}-{side}"),
+        market_slug: market.to_string(),
+        strategy_name: "statistical_edge".to_string(),
+        side,
+        is_buy: true,
+        price,
+        quantity,
+        fee: Decimal::ZERO,
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn pair_spread_crossing_opens_dollar_neutral_trade_and_reversion_unwinds() {
+    // Spread = mid_a - 1.0 * mid_b; B sits at mid 0.50 throughout.
+    // Tick 21: A jumps to mid 0.60 => spread 0.10, z ~= 18.0 => open:
+    //   A is rich: buy NO on A at 1 - 0.59 = 0.41 => floor(10 / 0.41) = 24
+    //   B is cheap: buy YES on B at 0.51         => floor(10 / 0.51) = 19
+    // Tick 22: spread still 0.10, z ~= 2.8 => hold (no re-entry)
+    // Tick 23: A back to mid 0.505 => spread 0.005, z ~= -0.47 => unwind
+    // Tick 24: still in the band, the exits were rejected => unwind again
+    let mut strategy = pairs_strategy("1");
+    pairs_baseline(&mut strategy);
+
+    let open = pairs_tick(&mut strategy, dec!(0.59), dec!(0.61));
+    assert_eq!(open.len(), 2);
+    let long = open.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+    let short = open.iter().find(|s| s.action == SignalAction::BuyNo).unwrap();
+    assert_eq!(long.market_slug, "leg-b");
+    assert_eq!((long.price, long.quantity), (dec!(0.51), 19));
+    assert_eq!(short.market_slug, "leg-a");
+    assert_eq!((short.price, short.quantity), (dec!(0.41), 24));
+    // Dollar-neutral within one contract: 9.69 vs 9.84
+    let long_dollars = long.price * Decimal::from(long.quantity);
+    let short_dollars = short.price * Decimal::from(short.quantity);
+    assert!((long_dollars - short_dollars).abs() <= short.price);
+    strategy.on_fill(&pair_fill("leg-b", Side::Yes, dec!(0.51), 19));
+    strategy.on_fill(&pair_fill("leg-a", Side::No, dec!(0.41), 24));
+
+    let held = pairs_tick(&mut strategy, dec!(0.59), dec!(0.61));
+    assert!(held.is_empty(), "Open trade holds while z is wide");
+
+    let unwind = pairs_tick(&mut strategy, dec!(0.495), dec!(0.515));
+    assert_eq!(unwind.len(), 2);
+    let sell_yes = unwind.iter().find(|s| s.action == SignalAction::SellYes).unwrap();
+    let sell_no = unwind.iter().find(|s| s.action == SignalAction::SellNo).unwrap();
+    assert_eq!((sell_yes.market_slug.as_str(), sell_yes.quantity), ("leg-b", 19));
+    assert_eq!((sell_no.market_slug.as_str(), sell_no.quantity), ("leg-a", 24));
+    assert!(unwind.iter().all(|s| s.reduce_only));
+
+    // No exit filled: the trade stays tracked and the exits go out again.
+    assert!(strategy.open_pair_trades().contains_key("leg-a/leg-b"));
+    let retry = pairs_tick(&mut strategy, dec!(0.495), dec!(0.515));
+    assert_eq!(retry.len(), 2);
+
+    // The trade is dropped once the exit fills have sold both legs.
+    let exit_fill = |market, side, quantity| FillEvent {
+        is_buy: false,
+        ..pair_fill(market, side, dec!(0.50), quantity)
+    };
+    strategy.on_fill(&exit_fill("leg-b", Side::Yes, 19));
+    let trade = &strategy.open_pair_trades()["leg-a/leg-b"];
+    assert_eq!((trade.long_quantity, trade.short_quantity), (0, 24));
+    strategy.on_fill(&exit_fill("leg-a", Side::No, 24));
+    assert!(strategy.open_pair_trades().is_empty());
+}
+
+#[test]
+fn pair_trade_stays_dollar_neutral_under_hedge_ratio_and_unwinds_only_filled_quantity() {
+    // Spread = mid_a - 0.5 * mid_b; B is constant, so z follows A as
+    // above. The ratio shapes only the spread: each leg still gets $10.
+    let mut strategy = pairs_strategy("0.5");
+    pairs_baseline(&mut strategy);
+
+    let open = pairs_tick(&mut strategy, dec!(0.59), dec!(0.61));
+    let long = open.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+    let short = open.iter().find(|s| s.action == SignalAction::BuyNo).unwrap();
+    assert_eq!((long.market_slug.as_str(), long.quantity), ("leg-b", 19));
+    assert_eq!((short.market_slug.as_str(), short.quantity), ("leg-a", 24));
+
+    // Nothing has filled yet: the trade is open but holds no contracts.
+    let trade = &strategy.open_pair_trades()["leg-a/leg-b"];
+    assert_eq!((trade.long_quantity, trade.short_quantity), (0, 0));
+
+    // B fills in full (extra fills are not credited), A only partly;
+    // fills from other strategies are ignored.
+    strategy.on_fill(&pair_fill("leg-b", Side::Yes, dec!(0.51), 19));
+    strategy.on_fill(&pair_fill("leg-b", Side::Yes, dec!(0.51), 5));
+    strategy.on_fill(&pair_fill("leg-a", Side::No, dec!(0.41), 10));
+    strategy.on_fill(&FillEvent {
+        strategy_name: "market_maker".to_string(),
+        ..pair_fill("leg-a", Side::No, dec!(0.41), 10)
+    });
+    let trade = &strategy.open_pair_trades()["leg-a/leg-b"];
+    assert_eq!((trade.long_quantity, trade.short_quantity), (19, 10));
+
+    let unwind = pairs_tick(&mut strategy, dec!(0.495), dec!(0.515));
+    let sell_yes = unwind.iter().find(|s| s.action == SignalAction::SellYes).unwrap();
+    let sell_no = unwind.iter().find(|s| s.action == SignalAction::SellNo).unwrap();
+    assert_eq!(sell_yes.quantity, 19);
+    assert_eq!(sell_no.quantity, 10);
+
+    // A pair whose legs never filled unwinds without orders.
+    let mut unfilled = pairs_strategy("1");
+    pairs_baseline(&mut unfilled);
+    assert_eq!(pairs_tick(&mut unfilled, dec!(0.59), dec!(0.61)).len(), 2);
+    assert!(pairs_tick(&mut unfilled, dec!(0.495), dec!(0.515)).is_empty());
+    assert!(unfilled.open_pair_trades().is_empty());
+}
+
+// =============================================================================
+// 15. Signal Conflict Resolution
+// =============================================================================
+
+/// Market maker plus a statistical edge strategy that sees YES worth 0.70.
+fn mm_and_stat_edge_engine(state: &StateManager, slug: &str) -> StrategyEngine {
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut se = StatisticalEdgeStrategy::new(StatisticalEdgeConfig::default());
+    se.ingest_odds(make_odds(slug, "book-a", dec!(0.70)));
+    StrategyEngine::new(state.clone(), Some(mm), None, Some(se), None)
+}
+
+#[test]
+fn conflicting_strategies_collapse_to_higher_urgency() {
+    // Market 0.45 / 0.55. Market maker quotes YES both ways at Low urgency;
+    // stat edge buys YES at 0.55 (edge 0.70 - 0.55 = 0.15) at Medium.
+    // Same market-side => stat edge wins, the quotes are dropped.
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("conflict", dec!(0.45), dec!(0.55)));
+
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mut engine = mm_and_stat_edge_engine(&state, "conflict");
+    let out = engine.on_tick(&mut rm);
+
+    assert!(out.conflicted_count >= 1);
+    assert!(out.approved_signals.iter().all(|s| s.strategy_name != "market_maker"));
+    let buy = out.approved_signals.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+    assert_eq!(buy.strategy_name, "statistical_edge");
+    assert_eq!(buy.price, dec!(0.55));
+
+    // KeepAll restores the old behavior: both strategies get through.
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mut engine = mm_and_stat_edge_engine(&state, "conflict");
+    engine.set_conflict_policy(ConflictPolicy::KeepAll);
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.conflicted_count, 0);
+    assert!(out.approved_signals.iter().any(|s| s.strategy_name == "market_maker"));
+    assert!(out.approved_signals.iter().any(|s| s.strategy_name == "statistical_edge"));
+}
+
+#[test]
+fn exit_beats_entry_on_the_same_market_side() {
+    // Holding 20 YES @ 0.60 with the bid at 0.45 => -25%, so the market
+    // maker emits a reduce-only SellYes stop-loss while stat edge wants to
+    // buy more YES. The exit wins and the entry is dropped.
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("exit", dec!(0.45), dec!(0.55)));
+    state.update_position("exit", Side::Yes, 20, dec!(0.60));
+
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mut engine = mm_and_stat_edge_engine(&state, "exit");
+    let out = engine.on_tick(&mut rm);
+
+    assert!(out.conflicted_count >= 1);
+    assert!(out.approved_signals.iter().all(|s| s.strategy_name != "statistical_edge"));
+    assert!(out
+        .approved_signals
+        .iter()
+        .any(|s| s.action == SignalAction::SellYes && s.reduce_only));
+}
+
+// =============================================================================
+// 16. Per-Market Warmup
+// =============================================================================
+
+#[test]
+fn market_is_not_traded_until_warmup_is_met() {
+    // Warm after 3 priced updates spanning >= 5s; a 30s+ gap restarts.
+    let state = StateManager::new(dec!(1000));
+    state.set_warmup_config(WarmupConfig {
+        min_updates: 3,
+        warmup_secs: 5.0,
+        max_gap_secs: 30.0,
+    });
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+
+    let t0 = chrono::Utc::now();
+    let mut update_at = |secs: i64| {
+        let mut market = make_market("warm", dec!(0.45), dec!(0.55));
+        market.last_updated = t0 + chrono::Duration::seconds(secs);
+        state.update_market(market.clone());
+        engine.on_market_update(&market, &mut rm).approved_signals
+    };
+
+    // One update: cold.
+    assert!(update_at(0).is_empty());
+    // Two updates over 2s: still cold.
+    assert!(update_at(2).is_empty());
+    // Three updates over 4s: enough updates, not yet fresh for 5s.
+    assert!(update_at(4).is_empty());
+    // Fourth update at 5s: both conditions met, the market is quoted.
+    assert!(update_at(5).iter().any(|s| s.action == SignalAction::BuyYes));
+    assert!(state.is_market_warm("warm"));
+
+    // A 60s feed gap restarts the warmup from a single update.
+    assert!(update_at(65).is_empty());
+    assert_eq!(state.update_count("warm"), 1);
+    assert!(!state.is_market_warm("warm"));
+}
+
+#[test]
+fn unpriced_seed_updates_do_not_count_toward_warmup() {
+    let state = StateManager::new(dec!(1000));
+    state.set_warmup_config(WarmupConfig {
+        min_updates: 1,
+        ..WarmupConfig::default()
+    });
+    let mut seed = make_market("seed", dec!(0.45), dec!(0.55));
+    seed.yes_bid = None;
+    seed.yes_ask = None;
+    state.update_market(seed);
+    assert_eq!(state.update_count("seed"), 0);
+    assert!(!state.is_market_warm("seed"));
+
+    state.update_market(make_market("seed", dec!(0.45), dec!(0.55)));
+    assert!(state.is_market_warm("seed"));
+}
+
+// =============================================================================
+// 17. Position Aging
+// =============================================================================
+
+/// State with an arb position opened 400s ago and a stat-edge one just now.
+fn aged_and_fresh_positions() -> (StateManager, MockClock) {
+    let clock = MockClock::default();
+    let state = StateManager::new(dec!(1000));
+    state.set_clock(std::sync::Arc::new(clock.clone()));
+    state.update_market(make_market("arb-old", dec!(0.42), dec!(0.46)));
+    state.update_market(make_market("edge-fresh", dec!(0.60), dec!(0.64)));
+
+    state.update_position("arb-old", Side::Yes, 10, dec!(0.45));
+    clock.advance(chrono::Duration::seconds(400));
+    state.update_position("edge-fresh", Side::No, 5, dec!(0.35));
+    (state, clock)
+}
+
+fn aging_manager(state: &StateManager, clock: &MockClock) -> PositionManager {
+    let mut pm = PositionManager::new(
+        PositionAgingConfig {
+            max_position_age_secs: 300,
+            exit_retry_secs: 30,
+        },
+        state.clone(),
+    );
+    pm.set_clock(std::sync::Arc::new(clock.clone()));
+    pm
+}
+
+#[test]
+fn aged_position_is_exited_and_fresh_one_is_left_alone() {
+    let (state, clock) = aged_and_fresh_positions();
+    let mut pm = aging_manager(&state, &clock);
+
+    let exits = pm.sweep();
+
+    // Only arb-old (400s >= 300s) is exited: a reduce-only sell of the full
+    // position at the YES best bid.
+    assert_eq!(exits.len(), 1);
+    let exit = &exits[0];
+    assert_eq!(exit.market_slug, "arb-old");
+    assert_eq!(exit.action, SignalAction::SellYes);
+    assert_eq!(exit.quantity, 10);
+    assert_eq!(exit.price, dec!(0.42));
+    assert!(exit.reduce_only);
+    assert_eq!(exit.urgency, Urgency::Critical);
+
+    // Still held 10s later: not re-sent inside the 30s retry window.
+    clock.advance(chrono::Duration::seconds(10));
+    assert!(pm.sweep().is_empty());
+    // Past the retry window the exit is sent again.
+    clock.advance(chrono::Duration::seconds(25));
+    assert_eq!(pm.sweep().len(), 1);
+}
+
+#[test]
+fn aging_report_lists_oldest_first() {
+    let (state, clock) = aged_and_fresh_positions();
+    let pm = aging_manager(&state, &clock);
+
+    let report = pm.report();
+    let rows: Vec<(&str, i64, bool)> = report
+        .iter()
+        .map(|a| (a.market_slug.as_str(), a.age_secs, a.aged))
+        .collect();
+    assert_eq!(rows, vec![("arb-old", 400, true), ("edge-fresh", 0, false)]);
+}
+
+#[test]
+fn engine_exits_aged_position_without_any_strategy() {
+    let (state, clock) = aged_and_fresh_positions();
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mut engine = StrategyEngine::new(state.clone(), None, None, None, None);
+    engine.set_position_manager(aging_manager(&state, &clock));
+
+    let out = engine.on_tick(&mut rm);
+
+    assert_eq!(out.approved_signals.len(), 1);
+    assert_eq!(out.approved_signals[0].market_slug, "arb-old");
+    assert_eq!(out.approved_signals[0].strategy_name, "position_aging");
+    for key in BOOK_CONTEXT_KEYS {
+        assert!(out.approved_signals[0].metadata.contains_key(key), "missing {key}");
+    }
+}
+
+#[test]
+fn position_aging_disabled_at_zero_max_age() {
+    let (state, _clock) = aged_and_fresh_positions();
+    let mut pm = PositionManager::new(PositionAgingConfig::default(), state);
+    assert!(!pm.is_enabled());
+    assert!(pm.sweep().is_empty());
+}
+
+// =============================================================================
+// 18. Shadow Execution
+// =============================================================================
+
+#[test]
+fn shadow_counts_approved_signals_without_touching_state() {
+    let state = StateManager::new(dec!(1000));
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+    let market = make_market("shadow", dec!(0.45), dec!(0.55));
+    state.update_market(market.clone());
+
+    let approved = engine.on_market_update(&market, &mut rm).approved_signals;
+    // Market maker quotes both sides: a bid (BuyYes) and an ask (SellYes).
+    assert!(approved.len() >= 2);
+
+    let mut shadow = ShadowExecutor::new();
+    for signal in &approved {
+        let result = shadow.execute_signal(signal);
+        assert_eq!(result.filled_quantity, 0);
+        assert!(result.error.is_none());
+    }
+
+    let perf = shadow.get_performance();
+    assert_eq!(perf["mode"], "shadow");
+    assert_eq!(perf["would_be_orders"], approved.len() as u64);
+    assert_eq!(perf["would_be_by_strategy"]["market_maker"], approved.len() as u64);
+    let buys = approved.iter().filter(|s| s.action == SignalAction::BuyYes).count();
+    assert_eq!(perf["would_be_by_action"]["BuyYes"], buys as u64);
+
+    // No executor ran: balance, positions and orders are untouched.
+    assert_eq!(state.get_balance(), dec!(1000));
+    assert!(state.get_all_positions().is_empty());
+    assert!(state.get_open_orders(None).is_empty());
+}
+
+// =============================================================================
+// 19. Hedged Exposure Netting
+// =============================================================================
+
+/// "hedged": 100 YES @ 0.48 + 100 NO @ 0.50 ($98 gross, fully matched).
+/// "one-sided": 100 YES @ 0.48 ($48).
+fn hedged_and_one_sided_state() -> StateManager {
+    let state = StateManager::new(dec!(1000));
+    state.update_position("hedged", Side::Yes, 100, dec!(0.48));
+    state.update_hedge_position("hedged", Side::No, 100, dec!(0.50));
+    state.update_position("one-sided", Side::Yes, 100, dec!(0.48));
+    state
+}
+
+fn netting_monitor(net_hedged_exposure: bool) -> ExposureMonitor {
+    ExposureMonitor::new(ExposureConfig {
+        max_position_per_market: dec!(60),
+        max_position_pct_of_equity: Decimal::ZERO,
+        max_contracts_per_market: 0,
+        max_portfolio_exposure: dec!(1000),
+        max_correlated_exposure: dec!(1000),
+        max_positions: 10,
+        net_hedged_exposure,
+    })
+}
+
+#[test]
+fn matched_yes_no_quantity_is_netted_from_market_exposure() {
+    let state = hedged_and_one_sided_state();
+
+    // Gross counts both legs: 48 + 50 = 98. Net: all 100 matched => 0.
+    assert_eq!(state.market_exposure("hedged"), dec!(98));
+    assert_eq!(state.net_market_exposure("hedged"), Decimal::ZERO);
+    // One-sided: gross and net agree.
+    assert_eq!(state.market_exposure("one-sided"), dec!(48));
+    assert_eq!(state.net_market_exposure("one-sided"), dec!(48));
+
+    // Partial hedge: 60 NO matched, 40 YES @ 0.48 unmatched = 19.20.
+    state.update_hedge_position("hedged", Side::No, 60, dec!(0.50));
+    assert_eq!(state.net_market_exposure("hedged"), dec!(19.20));
+
+    // Capital usage stays gross: 48 + 30 + 48.
+    assert_eq!(state.get_total_position_value(), dec!(126));
+}
+
+#[test]
+fn netting_lets_hedged_market_take_follow_on_arb() {
+    let state = hedged_and_one_sided_state();
+
+    // Netting on: hedged market has $0 directional, $25 more fits under $60.
+    let netted = netting_monitor(true);
+    assert!(netted.can_add_exposure(&state, "hedged", dec!(25), dec!(0.50)).allowed);
+    // One-sided market: 48 + 25 > 60 either way.
+    assert!(!netted.can_add_exposure(&state, "one-sided", dec!(25), dec!(0.50)).allowed);
+
+    // Netting off: the hedged market's gross $98 already breaches $60.
+    let gross = netting_monitor(false);
+    assert!(!gross.can_add_exposure(&state, "hedged", dec!(25), dec!(0.50)).allowed);
+}
+
+#[test]
+fn netting_does_not_relax_portfolio_capital_limit() {
+    let state = hedged_and_one_sided_state();
+    let monitor = ExposureMonitor::new(ExposureConfig {
+        max_position_per_market: dec!(60),
+        max_position_pct_of_equity: Decimal::ZERO,
+        max_contracts_per_market: 0,
+        max_portfolio_exposure: dec!(160),
+        max_correlated_exposure: dec!(1000),
+        max_positions: 10,
+        net_hedged_exposure: true,
+    });
+
+    // Gross capital 98 + 48 = 146; +$25 exceeds the $160 portfolio cap.
+    let check = monitor.can_add_exposure(&state, "hedged", dec!(25), dec!(0.50));
+    assert!(!check.allowed);
+    assert!(check.reason.starts_with("Portfolio limit"));
+}
+
+// =============================================================================
+// 20. Status Server Book Depth
+// =============================================================================
+
+/// YES 0.48 x100 / 0.52 x300 at the top, NO best ask 0.50. Levels are
+/// deliberately unsorted.
+fn depth_tracker() -> OrderBookTracker {
+    let level = |price, quantity| PriceLevel { price, quantity };
+    let tracker = OrderBookTracker::new();
+    tracker.update(OrderBook {
+        market_slug: "depth".to_string(),
+        yes: OrderBookSide {
+            bids: vec![level(dec!(0.46), 10), level(dec!(0.48), 100), level(dec!(0.47), 50)],
+            asks: vec![level(dec!(0.53), 20), level(dec!(0.52), 300)],
+        },
+        no: OrderBookSide {
+            bids: vec![level(dec!(0.47), 40)],
+            asks: vec![level(dec!(0.50), 60), level(dec!(0.51), 80)],
+        },
+    });
+    tracker
+}
+
+fn json_decimal(value: &serde_json::Value) -> Decimal {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn status_book_endpoint_serves_depth_and_derived_prices() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::sync::Arc::new(StatusServer::new(
+        StatusServerConfig {
+            book_depth: 2,
+            ..StatusServerConfig::default()
+        },
+        depth_tracker(),
+    ));
+    let handle = tokio::spawn(server.serve(listener));
+
+    let response = reqwest::get(format!("http://{}/book/depth", addr)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    // Top 2 levels per side, best first.
+    let prices = |levels: &serde_json::Value| -> Vec<Decimal> {
+        levels.as_array().unwrap().iter().map(|l| json_decimal(&l["price"])).collect()
+    };
+    assert_eq!(prices(&body["yes"]["bids"]), vec![dec!(0.48), dec!(0.47)]);
+    assert_eq!(prices(&body["yes"]["asks"]), vec![dec!(0.52), dec!(0.53)]);
+    assert_eq!(prices(&body["no"]["asks"]), vec![dec!(0.50), dec!(0.51)]);
+    assert_eq!(body["yes"]["bids"][0]["quantity"], 100);
+
+    // mid = (0.48 + 0.52) / 2 = 0.50
+    assert_eq!(json_decimal(&body["mid"]), dec!(0.50));
+    // microprice = (0.48 * 300 + 0.52 * 100) / 400 = 196 / 400 = 0.49
+    assert_eq!(json_decimal(&body["microprice"]), dec!(0.49));
+    // imbalance = (100 - 300) / 400 = -0.5
+    assert_eq!(json_decimal(&body["imbalance"]), dec!(-0.5));
+    // completeness = 0.52 + 0.50 = 1.02
+    assert_eq!(json_decimal(&body["completeness_sum"]), dec!(1.02));
+    assert_eq!(body["stale"], false);
+
+    let missing = reqwest::get(format!("http://{}/book/nope", addr)).await.unwrap();
+    assert_eq!(missing.status(), 404);
+
+    handle.abort();
+}
+
+#[test]
+fn status_book_flags_stale_books() {
+    let tracker = depth_tracker();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    let stale = tracker
+        .depth_snapshot("depth", 5, chrono::Duration::milliseconds(10))
+        .unwrap();
+    assert_eq!(stale["stale"], true);
+    assert!(stale["age_secs"].as_f64().unwrap() >= 0.02);
+
+    let fresh = tracker.depth_snapshot("depth", 5, chrono::Duration::seconds(30)).unwrap();
+    assert_eq!(fresh["stale"], false);
+}
+
+// =============================================================================
+// 21. Panic Liquidation
+// =============================================================================
+
+/// $400 cash, 200 YES of crash-a at 0.50 and 100 NO of crash-b at 0.40.
+/// Cost-basis equity = 400 + 100 + 40 = $540.
+fn panic_state() -> StateManager {
+    let state = StateManager::new(dec!(400));
+    state.update_market(make_market("crash-a", dec!(0.48), dec!(0.52)));
+    state.update_market(make_market("crash-b", dec!(0.58), dec!(0.60)));
+    state.update_position("crash-a", Side::Yes, 200, dec!(0.50));
+    state.update_position("crash-b", Side::No, 100, dec!(0.40));
+    state
+}
+
+fn panic_risk_manager(state: &StateManager) -> RiskManager {
+    let config = RiskConfig {
+        panic_liquidate_drawdown_pct: dec!(0.15),
+        ..permissive_risk_config()
+    };
+    RiskManager::new(config, state.clone())
+}
+
+#[test]
+fn panic_threshold_crossing_exits_every_position() {
+    let state = panic_state();
+    let mut rm = panic_risk_manager(&state);
+
+    // Marked at the bids: 400 + 200 * 0.48 + 100 * 0.40 = $536.
+    // Drawdown from $540 = 0.74% < 15%: nothing happens.
+    assert_eq!(rm.marked_equity(), dec!(536));
+    assert!(rm.check_panic_liquidation().is_empty());
+    assert!(!rm.is_halted());
+
+    // Crash: crash-a bid 0.10, crash-b NO bid 1 - 0.92 = 0.08.
+    // Marked = 400 + 20 + 8 = $428; drawdown = 112 / 540 = 20.7% >= 15%.
+    state.update_market(make_market("crash-a", dec!(0.10), dec!(0.14)));
+    state.update_market(make_market("crash-b", dec!(0.88), dec!(0.92)));
+    assert_eq!(rm.marked_equity(), dec!(428));
+
+    let mut exits = rm.check_panic_liquidation();
+    exits.sort_by(|a, b| a.market_slug.cmp(&b.market_slug));
+    let rows: Vec<(&str, SignalAction, i64, Decimal)> = exits
+        .iter()
+        .map(|s| (s.market_slug.as_str(), s.action, s.quantity, s.price))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("crash-a", SignalAction::SellYes, 200, dec!(0.10)),
+            ("crash-b", SignalAction::SellNo, 100, dec!(0.08)),
+        ]
+    );
+    assert!(exits.iter().all(|s| s.reduce_only && s.urgency == Urgency::Critical));
+    assert!(rm.is_halted(), "panic liquidation must trip the breaker");
+
+    // Fires once.
+    assert!(rm.check_panic_liquidation().is_empty());
+}
+
+#[test]
+fn engine_tick_routes_panic_exits_through_risk() {
+    let state = panic_state();
+    let mut rm = panic_risk_manager(&state);
+    let mut engine = StrategyEngine::new(state.clone(), None, None, None, None);
+
+    state.update_market(make_market("crash-a", dec!(0.10), dec!(0.14)));
+    state.update_market(make_market("crash-b", dec!(0.88), dec!(0.92)));
+    let out = engine.on_tick(&mut rm);
+
+    // Breaker is tripped, but exits are still approved.
+    assert_eq!(out.approved_signals.len(), 2);
+    assert!(out
+        .approved_signals
+        .iter()
+        .all(|s| s.strategy_name == "panic_liquidation" && s.is_sell()));
+}
+
+#[test]
+fn panic_liquidation_disabled_at_zero() {
+    let state = panic_state();
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    state.update_market(make_market("crash-a", dec!(0.01), dec!(0.03)));
+    assert!(rm.check_panic_liquidation().is_empty());
+    assert!(!rm.is_halted());
+}
+
+// =============================================================================
+// 22. Price Band Filter
+// =============================================================================
+
+/// Screen `mid` on `side` of the "band" market.
+fn band(state: &StateManager, side: Side, mid: Decimal) -> PriceBandCheck {
+    state.check_price_band("band", side, Some(mid))
+}
+
+/// 20% max jump per poll; a move must hold for 3 polls to be accepted.
+fn banded_state() -> StateManager {
+    let state = StateManager::new(dec!(1000));
+    state.set_price_band_config(PriceBandConfig {
+        max_jump_pct: dec!(0.20),
+        confirm_polls: 3,
+    });
+    assert_eq!(band(&state, Side::Yes, dec!(0.50)), PriceBandCheck::Accepted);
+    state
+}
+
+#[test]
+fn price_band_rejects_transient_spike() {
+    let state = banded_state();
+
+    // 0.50 -> 0.55 is a 10% move: in band.
+    assert_eq!(band(&state, Side::Yes, dec!(0.55)), PriceBandCheck::Accepted);
+
+    // A garbage 0.0001-ask tick drags the mid to ~0.05: |0.05 - 0.55| / 0.55 = 91%.
+    assert_eq!(
+        band(&state, Side::Yes, dec!(0.05)),
+        PriceBandCheck::Rejected {
+            side: Side::Yes,
+            last_good_mid: dec!(0.55),
+            mid: dec!(0.05),
+            anomalies: 1,
+        }
+    );
+    // Next poll is back near 0.55: accepted, the spike never landed.
+    assert_eq!(band(&state, Side::Yes, dec!(0.56)), PriceBandCheck::Accepted);
+
+    // Two different spikes in a row do not confirm each other.
+    assert!(matches!(
+        band(&state, Side::Yes, dec!(0.05)),
+        PriceBandCheck::Rejected { anomalies: 1, .. }
+    ));
+    assert!(matches!(
+        band(&state, Side::Yes, dec!(0.95)),
+        PriceBandCheck::Rejected { anomalies: 1, .. }
+    ));
+}
+
+#[test]
+fn price_band_accepts_sustained_move() {
+    let state = banded_state();
+
+    // 0.50 -> 0.80 (60%) and it stays there.
+    for anomalies in 1..=2 {
+        assert_eq!(
+            band(&state, Side::Yes, dec!(0.80)),
+            PriceBandCheck::Rejected {
+                side: Side::Yes,
+                last_good_mid: dec!(0.50),
+                mid: dec!(0.80),
+                anomalies,
+            }
+        );
+    }
+    // Third consecutive poll near 0.80: a real move, and the new baseline.
+    assert_eq!(band(&state, Side::Yes, dec!(0.81)), PriceBandCheck::Accepted);
+    assert_eq!(band(&state, Side::Yes, dec!(0.82)), PriceBandCheck::Accepted);
+}
+
+#[test]
+fn price_band_screens_each_side_against_its_own_mid() {
+    let state = banded_state();
+    assert_eq!(band(&state, Side::No, dec!(0.50)), PriceBandCheck::Accepted);
+
+    // A NO spike is caught even though the YES mid holds steady.
+    assert_eq!(band(&state, Side::Yes, dec!(0.51)), PriceBandCheck::Accepted);
+    assert_eq!(
+        band(&state, Side::No, dec!(0.95)),
+        PriceBandCheck::Rejected {
+            side: Side::No,
+            last_good_mid: dec!(0.50),
+            mid: dec!(0.95),
+            anomalies: 1,
+        }
+    );
+    // The NO band's baseline is untouched by YES moves.
+    assert_eq!(band(&state, Side::No, dec!(0.49)), PriceBandCheck::Accepted);
+}
+
+#[test]
+fn price_band_disabled_at_zero() {
+    let state = StateManager::new(dec!(1000));
+    assert_eq!(band(&state, Side::Yes, dec!(0.50)), PriceBandCheck::Accepted);
+    assert_eq!(band(&state, Side::Yes, dec!(0.01)), PriceBandCheck::Accepted);
+}
+
+// =============================================================================
+// 23. Feed Poll Scheduling
+// =============================================================================
+
+fn yes_top(bid: Decimal, ask: Decimal) -> TopOfBook {
+    TopOfBook {
+        yes_best_bid: Some(bid),
+        yes_best_ask: Some(ask),
+        ..TopOfBook::default()
+    }
+}
+
+#[test]
+fn feed_scheduler_polls_held_and_moving_markets_more_often() {
+    let state = StateManager::new(dec!(1000));
+    state.update_position("held", Side::Yes, 10, dec!(0.50));
+    let clock = MockClock::default();
+    let mut scheduler = FeedScheduler::new(
+        FeedSchedulerConfig {
+            min_interval: chrono::Duration::seconds(5),
+            max_interval: chrono::Duration::seconds(25),
+            active_move: dec!(0.01),
+            wide_spread: dec!(0.20),
+            max_polls_per_cycle: 0,
+        },
+        state,
+    );
+    scheduler.set_clock(std::sync::Arc::new(clock.clone()));
+
+    // Quiet market: 0.20-wide spread, no movement -> score 0, 25s interval.
+    scheduler.observe("quiet", &yes_top(dec!(0.40), dec!(0.60)));
+    scheduler.observe("quiet", &yes_top(dec!(0.40), dec!(0.60)));
+    assert_eq!(scheduler.score("quiet"), Decimal::ZERO);
+    assert_eq!(scheduler.poll_interval("quiet"), chrono::Duration::seconds(25));
+
+    // Moving market: mid 0.50 -> 0.52 -> EWMA 0.3 * 0.02 = 0.006;
+    // 0.006 / 0.01 = 0.6 beats tightness (1 - 0.20/0.20) / 2 = 0.
+    // Interval = 5 + 20 * 0.4 = 13s.
+    scheduler.observe("moving", &yes_top(dec!(0.40), dec!(0.60)));
+    scheduler.observe("moving", &yes_top(dec!(0.42), dec!(0.62)));
+    assert_eq!(scheduler.score("moving"), dec!(0.6));
+    assert_eq!(scheduler.poll_interval("moving"), chrono::Duration::seconds(13));
+
+    // Held position: score 1, polled every cycle.
+    assert_eq!(scheduler.score("held"), Decimal::ONE);
+    assert_eq!(scheduler.poll_interval("held"), chrono::Duration::seconds(5));
+
+    let slugs: Vec<String> = ["held", "moving", "quiet"].iter().map(|s| s.to_string()).collect();
+    let mut polls: HashMap<String, usize> = HashMap::new();
+    // 60 seconds of 5s cycles, first cycle included.
+    for _ in 0..12 {
+        for slug in scheduler.due_markets(&slugs) {
+            *polls.entry(slug).or_insert(0) += 1;
+        }
+        clock.advance(chrono::Duration::seconds(5));
+    }
+    // held: every cycle (12). moving: 0s, 15s, 30s, 45s (4). quiet: 0s, 25s, 50s (3).
+    assert_eq!(polls["held"], 12);
+    assert_eq!(polls["moving"], 4);
+    assert_eq!(polls["quiet"], 3);
+}
+
+#[test]
+fn feed_scheduler_caps_polls_per_cycle_most_overdue_first() {
+    let clock = MockClock::default();
+    let mut scheduler = FeedScheduler::new(
+        FeedSchedulerConfig {
+            max_polls_per_cycle: 2,
+            ..FeedSchedulerConfig::default()
+        },
+        StateManager::new(dec!(1000)),
+    );
+    scheduler.set_clock(std::sync::Arc::new(clock.clone()));
+    let slugs: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+    // Never-polled markets go first, alphabetically, two per cycle.
+    assert_eq!(scheduler.due_markets(&slugs), vec!["a", "b"]);
+    clock.advance(chrono::Duration::seconds(5));
+    assert_eq!(scheduler.due_markets(&slugs), vec!["c"]);
+
+    // At 25s a and b are 25/25 = 1.0 overdue, c only 20/25: a and b qualify.
+    clock.advance(chrono::Duration::seconds(20));
+    assert_eq!(scheduler.due_markets(&slugs), vec!["a", "b"]);
+}
+
+// =============================================================================
+// 24. Market Resolution
+// =============================================================================
+
+#[test]
+fn market_status_parses_closure_and_outcome() {
+    let market = |json: serde_json::Value| -> Market { serde_json::from_value(json).unwrap() };
+
+    let open = market(serde_json::json!({"slug": "m", "status": "OPEN", "active": true}));
+    assert!(!open.is_closed());
+    assert_eq!(open.yes_won(), None);
+
+    // Closed by flag, not yet resolved: stop trading, nothing to settle.
+    let closed = market(serde_json::json!({"slug": "m", "closed": true}));
+    assert!(closed.is_closed());
+    assert_eq!(closed.yes_won(), None);
+
+    let yes = market(serde_json::json!({"slug": "m", "status": "RESOLVED", "outcome": "Yes"}));
+    assert!(yes.is_closed());
+    assert_eq!(yes.yes_won(), Some(true));
+    let no = market(serde_json::json!({"slug": "m", "status": "RESOLVED", "winningOutcome": "NO"}));
+    assert_eq!(no.yes_won(), Some(false));
+}
+
+#[test]
+fn closed_market_is_disabled_and_resolution_queued_once() {
+    let state = StateManager::new(dec!(1000));
+
+    state.mark_market_closed("m", None);
+    assert!(state.is_market_closed("m"));
+    assert!(!state.is_market_enabled("m"));
+    assert!(state.take_unsettled_resolutions().is_empty());
+
+    // Resolution arrives on a later status check, then repeats.
+    state.mark_market_closed("m", Some(true));
+    state.mark_market_closed("m", Some(true));
+    assert_eq!(state.market_resolution("m"), Some(true));
+    assert_eq!(state.take_unsettled_resolutions(), vec![("m".to_string(), true)]);
+    assert!(state.take_unsettled_resolutions().is_empty());
+}
+
+// =============================================================================
+// 25. Runtime Strategy Toggles
+// =============================================================================
+
+fn strategy_signal_count(out: &EngineOutput, name: &str) -> usize {
+    out.approved_signals.iter().filter(|s| s.strategy_name == name).count()
+}
+
+#[test]
+fn disabled_strategies_are_silent_and_resume_with_inputs_kept() {
+    // Market 0.45 / 0.50 with YES worth 0.56 to both signal strategies:
+    //   live arb: home up 3-0 => 0.5 + 3 * 0.02 = 0.56, edge 0.06 >= 0.03
+    //   stat edge: book at 0.56, edge 0.06 >= 0.02
+    // Completeness arb: YES 0.45 + NO 0.50 = 0.95 < 1.
+    for name in ["live_arbitrage", "statistical_edge", "completeness_arb"] {
+        let state = StateManager::new(dec!(1000));
+        state.update_market(make_market("toggle", dec!(0.45), dec!(0.50)));
+        let tracker = OrderBookTracker::new();
+        tracker.update(make_book("toggle", dec!(0.45), dec!(0.50)));
+        let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+        let mut engine = match name {
+            "live_arbitrage" => {
+                let la = LiveArbitrageStrategy::new(LiveArbitrageConfig::default());
+                StrategyEngine::new(state.clone(), None, Some(la), None, None)
+            }
+            "statistical_edge" => {
+                let se = StatisticalEdgeStrategy::new(StatisticalEdgeConfig::default());
+                StrategyEngine::new(state.clone(), None, None, Some(se), None)
+            }
+            _ => arb_engine(&state, &tracker),
+        };
+
+        assert!(engine.set_strategy_enabled(name, false));
+        assert_eq!(engine.is_strategy_enabled(name), Some(false));
+
+        // Inputs arriving while disabled are kept but produce nothing.
+        engine.ingest_game_state(GameState {
+            event_id: "toggle".to_string(),
+            market_slug: Some("toggle".to_string()),
+            home_score: 3,
+            away_score: 0,
+            home_is_yes: true,
+            is_final: false,
+            progress: 0.5,
+            timestamp: chrono::Utc::now(),
+        });
+        engine.ingest_odds(make_odds("toggle", "book-a", dec!(0.56)));
+        let out = engine.on_tick(&mut rm);
+        assert_eq!(strategy_signal_count(&out, name), 0, "{name} silent while disabled");
+
+        assert!(engine.set_strategy_enabled(name, true));
+        let out = engine.on_tick(&mut rm);
+        assert!(strategy_signal_count(&out, name) > 0, "{name} resumes when re-enabled");
+    }
+}
+
+#[test]
+fn disabled_market_maker_cancels_its_quotes_and_requotes_on_resume() {
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("mm-toggle", dec!(0.45), dec!(0.55)));
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+
+    assert!(strategy_signal_count(&engine.on_tick(&mut rm), "market_maker") > 0);
+
+    // Disabled: its resting quotes are pulled once, and as the market
+    // moves no requote goes out.
+    engine.set_strategy_enabled("market_maker", false);
+    state.update_market(make_market("mm-toggle", dec!(0.55), dec!(0.65)));
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.approved_signals.len(), 1);
+    assert_eq!(out.approved_signals[0].action, SignalAction::CancelAll);
+    assert_eq!(out.approved_signals[0].market_slug, "mm-toggle");
+    assert!(engine.on_tick(&mut rm).approved_signals.is_empty());
+
+    // Re-enabled: the market is quoted afresh at the new prices.
+    engine.set_strategy_enabled("market_maker", true);
+    let out = engine.on_tick(&mut rm);
+    assert!(out
+        .approved_signals
+        .iter()
+        .any(|s| s.strategy_name == "market_maker" && s.action == SignalAction::BuyYes));
+}
+
+#[test]
+fn status_control_route_queues_toggles_for_the_engine() {
+    let state = StateManager::new(dec!(1000));
+    let mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+    let mut engine = StrategyEngine::new(state.clone(), Some(mm), None, None, None);
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+
+    // Read-only by default.
+    let read_only = StatusServer::new(StatusServerConfig::default(), OrderBookTracker::new());
+    assert_eq!(read_only.handle("POST", "/strategies/market_maker/disable").status, 405);
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<StrategyToggle>();
+    let server = StatusServer::new(StatusServerConfig::default(), OrderBookTracker::new())
+        .with_control(tx, "s3cret");
+    engine.subscribe_toggles(rx);
+
+    // Toggles need the control token.
+    let disable = "/strategies/market_maker/disable";
+    assert_eq!(server.handle("POST", disable).status, 401);
+    assert_eq!(server.handle_with_token("POST", disable, Some("guess")).status, 401);
+
+    let post = |path: &str| server.handle_with_token("POST", path, Some("s3cret"));
+    let response = post(disable);
+    assert_eq!(response.status, 202);
+    assert_eq!(response.body["enabled"], false);
+    assert_eq!(post("/strategies/nope/disable").status, 404);
+    assert_eq!(post("/strategies/market_maker/pause").status, 404);
+
+    // Applied on the next tick.
+    assert_eq!(engine.is_strategy_enabled("market_maker"), Some(true));
+    engine.on_tick(&mut rm);
+    assert_eq!(engine.is_strategy_enabled("market_maker"), Some(false));
+
+    // Valid name, but not configured in this engine.
+    assert!(!engine.set_strategy_enabled("live_arbitrage", false));
+    assert_eq!(engine.is_strategy_enabled("live_arbitrage"), None);
+}
+
+#[test]
+fn status_market_route_toggles_trading_in_a_market() {
+    let state = StateManager::new(dec!(1000));
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel::<StrategyToggle>();
+    let server = StatusServer::new(StatusServerConfig::default(), OrderBookTracker::new())
+        .with_control(tx, "s3cret")
+        .with_markets(state.clone());
+
+    assert_eq!(server.handle("POST", "/markets/nba-game/disable").status, 401);
+    assert!(state.is_market_enabled("nba-game"));
+
+    let post = |path: &str| server.handle_with_token("POST", path, Some("s3cret"));
+    let response = post("/markets/nba-game/disable");
+    assert_eq!(response.status, 202);
+    assert_eq!(response.body["market"], "nba-game");
+    assert!(!state.is_market_enabled("nba-game"));
+
+    assert_eq!(post("/markets/nba-game/pause").status, 404);
+    assert_eq!(post("/markets/nba-game/enable").status, 202);
+    assert!(state.is_market_enabled("nba-game"));
+}
+
+// =============================================================================
+// 26. Feed Poll Throughput
+// =============================================================================
+//
+// Wall-clock benchmarks, ignored by default: `cargo test -- --ignored`.
+
+/// Keep-alive mock exchange: book requests answer after `latency`, every
+/// other path at once. Counts book responses and accepted connections.
+async fn serve_books(
+    listener: tokio::net::TcpListener,
+    latency: std::time::Duration,
+    books: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    connections: std::sync::Arc<std::sync::atomic::AtomicU64>,
+) {
+    use std::sync::atomic::Ordering;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    loop {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        connections.fetch_add(1, Ordering::Relaxed);
+        let books = books.clone();
+        tokio::spawn(async move {
+            let mut pending = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let Some(end) = pending.windows(4).position(|w| w == b"\r\n\r\n") else {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => pending.extend_from_slice(&buf[..n]),
+                    }
+                    continue;
+                };
+                let head = String::from_utf8_lossy(&pending[..end]).to_string();
+                pending.drain(..end + 4);
+                let path = head.split_whitespace().nth(1).unwrap_or("").to_string();
+                let is_book = path.ends_with("/book");
+                if is_book {
+                    tokio::time::sleep(latency).await;
+                }
+                let body = "{}";
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                if socket.write_all(response.as_bytes()).await.is_err() {
+                    return;
+                }
+                if is_book {
+                    books.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+    }
+}
+
+struct PollBench {
+    polls_per_sec: f64,
+    stats: ConnectionStats,
+    /// Connections opened during the measured window.
+    connections: u64,
+}
+
+/// Run the feed over 16 markets against a 20ms-latency book endpoint and
+/// measure completed polls per second over a fixed window.
+async fn bench_polling(concurrency: usize, pool_max_idle_per_host: usize) -> PollBench {
+    use base64::Engine;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let books = Arc::new(AtomicU64::new(0));
+    let connections = Arc::new(AtomicU64::new(0));
+    let server = tokio::spawn(serve_books(
+        listener,
+        Duration::from_millis(20),
+        books.clone(),
+        connections.clone(),
+    ));
+
+    let key = base64::engine::general_purpose::STANDARD.encode([1u8; 32]);
+    let auth = PolymarketAuth::new("key", &key).unwrap();
+    let client = PolymarketClient::new(auth, &format!("http://{}", addr), 10_000, 1, 5)
+        .unwrap()
+        .with_connection_config(&ConnectionConfig {
+            pool_max_idle_per_host,
+            ..ConnectionConfig::default()
+        })
+        .unwrap();
+    let client = Arc::new(client);
+
+    let state = StateManager::new(dec!(1000));
+    for i in 0..16 {
+        state.update_market(make_market(&format!("m{i}"), dec!(0.45), dec!(0.55)));
+    }
+    let feed = MarketFeed::new(
+        client.clone(),
+        OrderBookTracker::new(),
+        state,
+        MarketFeedConfig {
+            poll_interval: Duration::from_millis(1),
+            max_concurrency: concurrency,
+            staleness_threshold: Duration::from_secs(30),
+            status_check_interval: Duration::from_secs(3600),
+            breaker: PollBreakerConfig::default(),
+        },
+        Arc::new(tokio::sync::Notify::new()),
+    );
+    let handle = feed.spawn();
+
+    // Skip the first cycle's status checks and connection setup.
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let start = Instant::now();
+    let (start_books, start_connections) = (
+        books.load(Ordering::Relaxed),
+        connections.load(Ordering::Relaxed),
+    );
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let polls = books.load(Ordering::Relaxed) - start_books;
+    let polls_per_sec = polls as f64 / start.elapsed().as_secs_f64();
+
+    handle.abort();
+    server.abort();
+    PollBench {
+        polls_per_sec,
+        stats: client.connection_stats(),
+        connections: connections.load(Ordering::Relaxed) - start_connections,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "wall-clock benchmark; run with --ignored"]
+async fn poll_throughput_scales_with_concurrency_over_reused_connections() {
+    // 20ms per book: one at a time tops out near 50 polls/s; 8 concurrent
+    // polls cover 16 markets in 2 waves, ~8x that.
+    let serial = bench_polling(1, 1).await;
+    let parallel = bench_polling(8, 8).await;
+
+    assert!(serial.polls_per_sec < 60.0);
+    assert!(
+        parallel.polls_per_sec > 3.0 * serial.polls_per_sec,
+        "8-way polling should far outpace serial: {:.0} vs {:.0}",
+        parallel.polls_per_sec,
+        serial.polls_per_sec
+    );
+    assert_eq!(serial.stats.peak_in_flight, 1);
+    assert_eq!(parallel.stats.peak_in_flight, 8);
+
+    // A pool sized to the concurrency keeps every connection alive.
+    assert!(parallel.stats.reuse_rate() > 0.9);
+    assert!(parallel.connections < 8);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "wall-clock benchmark; run with --ignored"]
+async fn undersized_pool_reconnects_for_most_polls() {
+    // Only 1 idle connection kept for 8 concurrent polls: connections
+    // released beyond the first are closed and reopened on the next wave.
+    let run = bench_polling(8, 1).await;
+    assert!(run.stats.reuse_rate() < 0.75);
+    assert!(run.connections > 8 * 4);
+}
+
+// =============================================================================
+// 27. Post-Score Price Lag
+// =============================================================================
+
+fn game(home_score: i32, away_score: i32, progress: f64, age_secs: i64) -> GameState {
+    GameState {
+        event_id: "lag".to_string(),
+        market_slug: Some("lag".to_string()),
+        home_score,
+        away_score,
+        home_is_yes: true,
+        is_final: false,
+        progress,
+        timestamp: chrono::Utc::now() - chrono::Duration::seconds(age_secs),
+    }
+}
+
+/// Lag model on with a 30s window; it is off by default.
+fn lag_config() -> LiveArbitrageConfig {
+    LiveArbitrageConfig {
+        lag_window_seconds: 30.0,
+        ..LiveArbitrageConfig::default()
+    }
+}
+
+/// Score moves from `before` to `after` (home, away) at `progress`; the
+/// market still shows `yes_bid` / `yes_ask`. Returns the tick's signals.
+fn lag_tick(
+    before: (i32, i32),
+    after: (i32, i32),
+    progress: f64,
+    yes_bid: Decimal,
+    yes_ask: Decimal,
+) -> Vec<Signal> {
+    let mut strategy = LiveArbitrageStrategy::new(lag_config());
+    strategy.ingest_game_state(game(before.0, before.1, progress, 0));
+    strategy.ingest_game_state(game(after.0, after.1, progress, 0));
+    let market = make_market("lag", yes_bid, yes_ask);
+    strategy.on_tick(|_| Some(market.clone()))
+}
+
+fn critical(signals: &[Signal]) -> Option<&Signal> {
+    signals.iter().find(|s| s.urgency == Urgency::Critical)
+}
+
+#[test]
+fn score_model_sharpens_with_margin_and_progress() {
+    // k = 0.5: z = 0.5 * diff / sqrt(max(1 - progress, 0.05))
+    let p = |diff, progress| score_model_probability(diff, progress, 0.5);
+    assert!((p(0, 0.5) - 0.5).abs() < 1e-12);
+    // z = -0.5 at kickoff => 1 / (1 + e^0.5) = 0.3775
+    assert!((p(-1, 0.0) - 0.3775).abs() < 1e-4);
+    // z = 0.5 / sqrt(0.5) = 0.7071 => 0.6698
+    assert!((p(1, 0.5) - 0.6698).abs() < 1e-4);
+    // z = 1.0 / sqrt(0.25) = 2 => 0.8808
+    assert!((p(2, 0.75) - 0.8808).abs() < 1e-4);
+    // Full time floors remaining at 0.05: z = 0.5 / 0.2236 = 2.236 => 0.9034
+    assert!((p(1, 1.0) - 0.9034).abs() < 1e-4);
+}
+
+#[test]
+fn post_score_lag_fires_critical_when_market_trails_model() {
+    // Home goal at half time: model 0.6698 vs YES ask 0.52 => gap 0.150
+    let signals = lag_tick((0, 0), (1, 0), 0.5, dec!(0.48), dec!(0.52));
+    let signal = critical(&signals).expect("lagging YES ask");
+    assert_eq!(signal.action, SignalAction::BuyYes);
+    assert_eq!(signal.price, dec!(0.52));
+    assert!((true_probability(signal) - 0.6698).abs() < 1e-4);
+
+    // Away goal: model YES 0.3302 => NO fair 0.6698 vs NO ask 0.52 => 0.150
+    let signals = lag_tick((0, 0), (0, 1), 0.5, dec!(0.48), dec!(0.52));
+    assert_eq!(critical(&signals).unwrap().action, SignalAction::BuyNo);
+
+    // Second home goal at 75%: model 0.8808 vs YES ask 0.72 => 0.161
+    let signals = lag_tick((1, 0), (2, 0), 0.75, dec!(0.70), dec!(0.72));
+    assert_eq!(critical(&signals).unwrap().action, SignalAction::BuyYes);
+
+    // Equaliser while the market still prices the lead: model 0.50 =>
+    // NO fair 0.50 vs NO ask 1 - 0.66 = 0.34 => 0.16
+    let signals = lag_tick((1, 0), (1, 1), 0.5, dec!(0.66), dec!(0.68));
+    assert_eq!(critical(&signals).unwrap().action, SignalAction::BuyNo);
+
+    // Market already caught up: 0.6698 - 0.66 = 0.0098 < 0.03 min edge.
+    let signals = lag_tick((0, 0), (1, 0), 0.5, dec!(0.64), dec!(0.66));
+    assert!(critical(&signals).is_none());
+}
+
+#[test]
+fn post_score_lag_fires_once_per_goal_inside_window() {
+    let market = make_market("lag", dec!(0.48), dec!(0.52));
+    let mut strategy = LiveArbitrageStrategy::new(lag_config());
+    strategy.ingest_game_state(game(0, 0, 0.5, 0));
+
+    // A goal 60s ago is outside the 30s window.
+    strategy.ingest_game_state(game(1, 0, 0.5, 60));
+    assert!(critical(&strategy.on_tick(|_| Some(market.clone()))).is_none());
+
+    // A fresh goal fires once; the same event does not fire again.
+    strategy.ingest_game_state(game(2, 0, 0.5, 0));
+    assert!(critical(&strategy.on_tick(|_| Some(market.clone()))).is_some());
+    assert!(strategy.on_tick(|_| Some(market.clone())).is_empty());
+
+    // The next goal opens a new window despite the regular cooldown.
+    strategy.ingest_game_state(game(3, 0, 0.5, 0));
+    assert!(critical(&strategy.on_tick(|_| Some(market.clone()))).is_some());
+}
+
+#[test]
+fn taker_threshold_crosses_fat_edges_and_rests_thin_ones() {
+    let config = LiveArbitrageConfig {
+        taker_margin_threshold: dec!(0.10),
+        ..lag_config()
+    };
+    let goal_tick = |yes_bid, yes_ask| {
+        let mut strategy = LiveArbitrageStrategy::new(config.clone());
+        strategy.ingest_game_state(game(0, 0, 0.5, 0));
+        strategy.ingest_game_state(game(1, 0, 0.5, 0));
+        let market = make_market("lag", yes_bid, yes_ask);
+        strategy.on_tick(|_| Some(market.clone()))
+    };
+
+    // Model 0.6698 vs YES ask 0.52: a 0.150 edge goes out as a market order.
+    let fat = goal_tick(dec!(0.48), dec!(0.52));
+    assert_eq!(fat.len(), 1);
+    assert_eq!(fat[0].urgency, Urgency::Critical);
+
+    // Against an ask of 0.62 the 0.050 edge only pays as a resting limit.
+    let thin = goal_tick(dec!(0.58), dec!(0.62));
+    assert_eq!(thin.len(), 1);
+    assert_eq!(thin[0].urgency, Urgency::Medium);
+    assert_eq!(thin[0].price, dec!(0.62));
+
+    // The paper executor takes the book for the first (the ask has since
+    // moved to 0.63) and rests the second below it.
+    let orderbook = OrderBookTracker::new();
+    orderbook.update(make_book("lag", dec!(0.63), dec!(0.39)));
+    let mut paper = PaperExecutor::new(StateManager::new(dec!(1000)), orderbook);
+    assert!(paper.execute_signal(&fat[0]).filled_quantity > 0);
+    assert!(paper.get_resting_orders().is_empty());
+    assert_eq!(paper.execute_signal(&thin[0]).filled_quantity, 0);
+    assert_eq!(paper.get_resting_orders().len(), 1);
+}
+
+// =============================================================================
+// 28. Minimum Resolution Horizon
+// =============================================================================
+
+#[test]
+fn market_resolving_within_horizon_blocks_entries_but_not_exits() {
+    let state = StateManager::new(dec!(1000));
+    let mut closing = make_market("closing", dec!(0.48), dec!(0.52));
+    closing.resolution_date = Some(chrono::Utc::now() + chrono::Duration::seconds(30));
+    state.update_market(closing);
+    let mut later = make_market("later", dec!(0.48), dec!(0.52));
+    later.resolution_date = Some(chrono::Utc::now() + chrono::Duration::hours(2));
+    state.update_market(later);
+    state.update_position("closing", Side::Yes, 100, dec!(0.50));
+
+    let config = RiskConfig {
+        min_resolution_horizon: chrono::Duration::seconds(60),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state.clone());
+
+    let entry = rm.evaluate_signal(make_buy_signal("closing", dec!(0.52), 10, 1.0, None));
+    assert!(!entry.approved);
+    assert!(entry.reason.contains("resolves within 60s"), "{}", entry.reason);
+
+    let exit = rm.evaluate_signal(make_sell_signal("closing", dec!(0.48), 50));
+    assert!(exit.approved, "{}", exit.reason);
+
+    // Further out, or with the check off, entries go through.
+    let entry = rm.evaluate_signal(make_buy_signal("later", dec!(0.52), 10, 1.0, None));
+    assert!(entry.approved, "{}", entry.reason);
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let entry = rm.evaluate_signal(make_buy_signal("closing", dec!(0.52), 10, 1.0, None));
+    assert!(entry.approved, "{}", entry.reason);
+}
+
+// =============================================================================
+// 29. Event Log Endpoint
+// =============================================================================
+
+#[test]
+fn status_server_serves_recent_events_newest_first() {
+    let state = StateManager::new(dec!(1000))
+        .with_event_log(polymarket_us_bot::state::event_log::EventLog::new(3));
+    let events = state.events();
+    events.push(EventKind::OrderPlaced, Some("m"), "BuyYes 10 @ 0.50", serde_json::Value::Null);
+    events.push(EventKind::Fill, Some("m"), "BuyYes filled 10/10 @ 0.50", serde_json::Value::Null);
+    events.push(EventKind::Reject, Some("m"), "Insufficient balance", serde_json::Value::Null);
+    events.push(EventKind::BreakerTrip, None, "Daily loss limit", serde_json::Value::Null);
+
+    let server = StatusServer::new(StatusServerConfig::default(), OrderBookTracker::new())
+        .with_events(state.events().clone());
+    let response = server.handle("GET", "/events");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body["capacity"], 3);
+    let kinds: Vec<&str> = response.body["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["kind"].as_str().unwrap())
+        .collect();
+    // The order placement was evicted.
+    assert_eq!(kinds, vec!["breaker_trip", "reject", "fill"]);
+
+    let limited = server.handle("GET", "/events?limit=1");
+    assert_eq!(limited.body["events"].as_array().unwrap().len(), 1);
+
+    let without = StatusServer::new(StatusServerConfig::default(), OrderBookTracker::new());
+    assert_eq!(without.handle("GET", "/events").status, 404);
+}
+
+// =============================================================================
+// 30. Mark-to-Market Conventions
+// =============================================================================
+
+#[test]
+fn conservative_marks_take_the_worse_exit_and_liquidation_the_own_bid() {
+    let state = StateManager::new(dec!(1000));
+    // YES book 0.50 / 0.65; the NO book is out of line: bid 0.40 / ask 0.52.
+    let mut m = make_market("skewed", dec!(0.50), dec!(0.65));
+    m.no_bid = Some(dec!(0.40));
+    m.no_ask = Some(dec!(0.52));
+    state.update_market(m.clone());
+
+    // YES: own bid 0.50 vs 1 - NO ask 0.52 = 0.48.
+    assert_eq!(m.mark_price(Side::Yes, MarkMode::Liquidation), Some(dec!(0.50)));
+    assert_eq!(m.mark_price(Side::Yes, MarkMode::Conservative), Some(dec!(0.48)));
+    // NO: own bid 0.40 vs 1 - YES ask 0.65 = 0.35.
+    assert_eq!(m.mark_price(Side::No, MarkMode::Liquidation), Some(dec!(0.40)));
+    assert_eq!(m.mark_price(Side::No, MarkMode::Conservative), Some(dec!(0.35)));
+    // Without a NO bid both fall back to the YES ask's complement.
+    m.no_bid = None;
+    assert_eq!(m.mark_price(Side::No, MarkMode::Liquidation), Some(dec!(0.35)));
+    assert_eq!(m.mark_price(Side::No, MarkMode::Conservative), Some(dec!(0.35)));
+
+    // 100 NO: 100 * 0.40 = 40 liquidation vs 100 * 0.35 = 35 conservative.
+    state.update_position("skewed", Side::No, 100, dec!(0.45));
+    assert_eq!(state.mark_to_market(MarkMode::Liquidation), dec!(40));
+    assert_eq!(state.mark_to_market(MarkMode::Conservative), dec!(35));
+
+    let liquidation = RiskManager::new(permissive_risk_config(), state.clone());
+    let conservative = RiskManager::new(
+        RiskConfig {
+            conservative_marks: true,
+            ..permissive_risk_config()
+        },
+        state.clone(),
+    );
+    let balance = state.get_balance();
+    assert_eq!(liquidation.marked_equity(), balance + dec!(40));
+    assert_eq!(conservative.marked_equity(), balance + dec!(35));
+}
+
+// =============================================================================
+// 31. Odds Event Matching
+// =============================================================================
+
+fn make_titled_market(slug: &str, title: &str) -> Market {
+    serde_json::from_value(serde_json::json!({ "slug": slug, "title": title })).unwrap()
+}
+
+fn odds_event(event_id: &str, home_team: &str, away_team: &str) -> OddsEvent {
+    OddsEvent {
+        event_id: event_id.to_string(),
+        home_team: home_team.to_string(),
+        away_team: away_team.to_string(),
+    }
+}
+
+fn render_match(m: &EventMatch) -> String {
+    let reason = serde_json::to_value(m.reason).unwrap();
+    format!(
+        "{} -> {} (yes: {}, {}) {:?}",
+        m.event_id,
+        m.market_slug.as_deref().unwrap_or("-"),
+        m.yes_team.as_deref().unwrap_or("-"),
+        reason.as_str().unwrap(),
+        m.candidates,
+    )
+}
+
+#[test]
+fn odds_matching_corpus_snapshot() {
+    let markets = vec![
+        make_titled_market("nba-lal-bos", "Los Angeles Lakers vs. Boston Celtics"),
+        make_titled_market("nba-lac-den", "LA Clippers at Denver Nuggets"),
+        make_titled_market("nba-okc-sas", "Thunder vs. Spurs"),
+        make_titled_market("nba-gsw-nop", "Will the Warriors beat the Pelicans?"),
+        make_titled_market("nba-nyk-mia", "Knicks vs Heat"),
+        make_titled_market("nba-por-phx", "Trail Blazers vs. Suns"),
+        make_titled_market("nfl-kc-buf", "Chiefs @ Bills"),
+        make_titled_market("nfl-nyg-nyj", "New York Giants vs. New York Jets"),
+        make_titled_market("nfl-tb-gb", "Tampa Bay vs Green Bay"),
+        make_titled_market("nfl-ne-lv", "New England Patriots vs Las Vegas Raiders"),
+        make_titled_market("nhl-stl-sj", "Blues vs. Sharks"),
+        make_titled_market("mlb-lad-nym-g1", "Dodgers vs. Mets"),
+        make_titled_market("mlb-lad-nym-g2", "Dodgers vs. Mets"),
+        make_titled_market("mlb-sd-sf", "Padres vs. Giants"),
+        make_titled_market("nba-title-lal", "Will the Lakers win the 2026 title?"),
+    ];
+    let events = vec![
+        odds_event("e01", "Boston Celtics", "Los Angeles Lakers"),
+        odds_event("e02", "Denver Nuggets", "Los Angeles Clippers"),
+        odds_event("e03", "Oklahoma City Thunder", "San Antonio Spurs"),
+        odds_event("e04", "New Orleans Pelicans", "Golden State Warriors"),
+        odds_event("e05", "Miami Heat", "New York Knicks"),
+        odds_event("e06", "Phoenix Suns", "Portland Trail Blazers"),
+        odds_event("e07", "Buffalo Bills", "Kansas City Chiefs"),
+        odds_event("e08", "New York Jets", "New York Giants"),
+        odds_event("e09", "Green Bay Packers", "Tampa Bay Buccaneers"),
+        odds_event("e10", "Las Vegas Raiders", "New England Patriots"),
+        odds_event("e11", "San Jose Sharks", "St. Louis Blues"),
+        odds_event("e12", "New York Mets", "Los Angeles Dodgers"),
+        odds_event("e13", "Toronto Raptors", "Chicago Bulls"),
+        odds_event("e14", "San Francisco Giants", "San Diego Padres"),
+    ];
+
+    let report = match_report(&events, &markets);
+    let rendered: Vec<String> = report.iter().map(render_match).collect();
+    assert_eq!(
+        rendered,
+        vec![
+            "e01 -> nba-lal-bos (yes: Los Angeles Lakers, full_name) [\"nba-lal-bos\"]",
+            "e02 -> nba-lac-den (yes: Los Angeles Clippers, nickname) [\"nba-lac-den\"]",
+            "e03 -> nba-okc-sas (yes: Oklahoma City Thunder, nickname) [\"nba-okc-sas\"]",
+            "e04 -> nba-gsw-nop (yes: Golden State Warriors, nickname) [\"nba-gsw-nop\"]",
+            "e05 -> nba-nyk-mia (yes: New York Knicks, nickname) [\"nba-nyk-mia\"]",
+            "e06 -> nba-por-phx (yes: Portland Trail Blazers, nickname) [\"nba-por-phx\"]",
+            "e07 -> nfl-kc-buf (yes: Kansas City Chiefs, nickname) [\"nfl-kc-buf\"]",
+            "e08 -> nfl-nyg-nyj (yes: New York Giants, full_name) [\"nfl-nyg-nyj\"]",
+            // Only the cities are named: the weakest match, but unique.
+            "e09 -> nfl-tb-gb (yes: Tampa Bay Buccaneers, city) [\"nfl-tb-gb\"]",
+            "e10 -> nfl-ne-lv (yes: New England Patriots, full_name) [\"nfl-ne-lv\"]",
+            "e11 -> nhl-stl-sj (yes: St. Louis Blues, nickname) [\"nhl-stl-sj\"]",
+            // Doubleheader: two identical titles, so neither is picked.
+            "e12 -> - (yes: -, ambiguous) [\"mlb-lad-nym-g1\", \"mlb-lad-nym-g2\"]",
+            "e13 -> - (yes: -, no_market) []",
+            "e14 -> mlb-sd-sf (yes: San Diego Padres, nickname) [\"mlb-sd-sf\"]",
+        ]
+    );
+    assert_eq!(report[0].yes_is_home(&events[0]), Some(false));
+    assert_eq!(report[12].yes_is_home(&events[12]), None);
+
+    // Input order does not change the report.
+    let reversed_events: Vec<OddsEvent> = events.iter().rev().cloned().collect();
+    let reversed_markets: Vec<Market> = markets.iter().rev().cloned().collect();
+    assert_eq!(match_report(&reversed_events, &reversed_markets), report);
+}
+
+// =============================================================================
+// 32. Strategy Confidence Floors
+// =============================================================================
+
+#[test]
+fn confidence_floor_rejects_weak_entries_before_kelly_sizing() {
+    let state = StateManager::new(dec!(1000));
+    state.update_position("mkt", Side::Yes, 100, dec!(0.50));
+    let config = RiskConfig {
+        min_confidence: ["statistical_edge:0.6"]
+            .iter()
+            .filter_map(|e| parse_confidence_floor(e))
+            .collect(),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state);
+    let stat_edge = |confidence: f64| Signal {
+        strategy_name: "statistical_edge".to_string(),
+        ..make_buy_signal("fresh", dec!(0.50), 500, confidence, Some(0.60))
+    };
+
+    // 0.51 < 0.60: rejected however small Kelly would size it.
+    let weak = rm.evaluate_signal(stat_edge(0.51));
+    assert!(!weak.approved);
+    assert!(weak.reason.contains("below statistical_edge floor 0.60"), "{}", weak.reason);
+
+    // At full confidence Kelly sizes it as in section 5, on $1050 equity
+    // (the held $50 included): 100 * 1050 / 1000 = 105 contracts.
+    let strong = rm.evaluate_signal(stat_edge(1.0));
+    assert!(strong.approved, "{}", strong.reason);
+    assert_eq!(strong.signal.unwrap().quantity, 105);
+
+    // Other strategies have no floor, and exits are never blocked.
+    assert!(rm.evaluate_signal(make_buy_signal("other", dec!(0.50), 10, 0.3, None)).approved);
+    let mut exit = make_sell_signal("mkt", dec!(0.50), 50);
+    exit.strategy_name = "statistical_edge".to_string();
+    exit.confidence = 0.1;
+    assert!(rm.evaluate_signal(exit).approved);
+
+    assert_eq!(parse_confidence_floor(" market_maker : 0.5 "), Some(("market_maker".into(), 0.5)));
+    assert_eq!(parse_confidence_floor("market_maker:1.5"), None);
+    assert_eq!(parse_confidence_floor("market_maker"), None);
+}
+
+// =============================================================================
+// 33. Cash Buffer Consistency
+// =============================================================================
+
+// The live executor's leg of this check needs the crate's scripted exchange,
+// so it lives with `LiveExecutor` in src/execution/executor.rs.
+#[test]
+fn cash_buffer_caps_buys_identically_in_risk_and_paper() {
+    // $100 cash, buffer 0.50, buy 500 at 0.50: floor(100 * 0.50 / 0.50) = 100.
+    let buffer = dec!(0.50);
+    assert_eq!(max_affordable_quantity(dec!(100), dec!(0.50), buffer), 100);
+    let signal = make_buy_signal("cash", dec!(0.50), 500, 1.0, None);
+    let mut book = make_book("cash", dec!(0.50), dec!(0.52));
+    book.yes.asks[0].quantity = 1000;
+    let tracker = OrderBookTracker::new();
+    tracker.update(book);
+
+    let mut rm = RiskManager::new(
+        RiskConfig {
+            cash_buffer_pct: buffer,
+            ..permissive_risk_config()
+        },
+        StateManager::new(dec!(100)),
+    );
+    let decision = rm.evaluate_signal(signal.clone());
+    assert!(decision.approved, "{}", decision.reason);
+    let risk_qty = decision.signal.unwrap().quantity;
+
+    let mut paper =
+        PaperExecutor::with_params(StateManager::new(dec!(100)), tracker, dec!(0.001), dec!(0))
+            .with_cash_buffer_pct(buffer);
+    let paper_qty = paper.execute_signal(&signal).filled_quantity;
+
+    assert_eq!((risk_qty, paper_qty), (100, 100));
+}
+
+// =============================================================================
+// 34. Market Metadata
+// =============================================================================
+
+#[test]
+fn market_tick_size_and_min_order_size_come_from_the_api() {
+    let market: Market = serde_json::from_value(serde_json::json!({
+        "slug": "nickel",
+        "active": true,
+        "tickSize": "0.05",
+        "minOrderSize": 5,
+    }))
+    .unwrap();
+    let metadata = market.metadata();
+    assert_eq!((metadata.tick_size, metadata.min_order_size), (Some(dec!(0.05)), Some(5)));
+
+    // Feed updates carry no metadata; the cache fills it in.
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("nickel", dec!(0.46), dec!(0.54)));
+    state.set_market_metadata("nickel", metadata);
+    state.update_market(make_market("nickel", dec!(0.46), dec!(0.54)));
+    let nickel = state.get_market("nickel").unwrap();
+    assert_eq!(nickel.tick_size, Some(dec!(0.05)));
+    assert_eq!(state.market_metadata("nickel"), Some(metadata));
+
+    // Quotes land on the market's 0.05 tick, not the configured 0.01.
+    let quote_prices = |market: &MarketState| -> Vec<Decimal> {
+        let mut mm = MarketMakerStrategy::new(MarketMakerConfig::default());
+        mm.on_market_update(market, None)
+            .iter()
+            .filter(|s| !s.is_cancel())
+            .map(|s| s.price)
+            .collect()
+    };
+    let penny = quote_prices(&make_market("penny", dec!(0.46), dec!(0.54)));
+    let nickel_quotes = quote_prices(&nickel);
+    assert_eq!(penny, vec![dec!(0.46), dec!(0.54)]);
+    assert_eq!(nickel_quotes, vec![dec!(0.45), dec!(0.55)]);
+
+    // Orders below the market minimum are rejected; at it they pass.
+    let mut rm = RiskManager::new(permissive_risk_config(), state.clone());
+    let small = rm.evaluate_signal(make_buy_signal("nickel", dec!(0.45), 4, 1.0, None));
+    assert!(!small.approved);
+    assert_eq!(small.reason, "Rejected: 4 contracts below market minimum 5");
+    let at_min = rm.evaluate_signal(make_buy_signal("nickel", dec!(0.45), 5, 1.0, None));
+    assert!(at_min.approved, "{}", at_min.reason);
+}
+
+// =============================================================================
+// 35. Last-Trade Marks
+// =============================================================================
+
+#[test]
+fn recent_trade_marks_a_position_over_a_stale_bid() {
+    let t0 = chrono::Utc::now();
+    let quote = |yes_bid: Decimal, at: chrono::DateTime<chrono::Utc>| MarketState {
+        last_updated: at,
+        ..make_market("illiquid", yes_bid, dec!(0.70))
+    };
+    let state = StateManager::new(dec!(1000));
+    state.update_market(quote(dec!(0.40), t0));
+    state.update_position("illiquid", Side::Yes, 100, dec!(0.50));
+
+    // Traded at 0.55 a minute after the 0.40 bid was last set. Polls
+    // repeating the same quotes leave the bid stale.
+    state.record_trade("illiquid", Side::Yes, dec!(0.55), t0 + chrono::Duration::minutes(1));
+    state.update_market(quote(dec!(0.40), t0 + chrono::Duration::minutes(2)));
+    assert_eq!(state.mark_to_market(MarkMode::Liquidation), dec!(40)); // off by default
+    state.set_mark_last_trade(true);
+    assert_eq!(state.mark_to_market(MarkMode::Liquidation), dec!(55)); // 100 * 0.55
+    // Risk marks never value the position above what the book supports.
+    assert_eq!(state.mark_to_market(MarkMode::Conservative), dec!(40));
+    state.record_trade("illiquid", Side::Yes, dec!(0.35), t0 + chrono::Duration::minutes(2));
+    assert_eq!(state.mark_to_market(MarkMode::Conservative), dec!(35));
+    state.record_trade("illiquid", Side::Yes, dec!(0.55), t0 + chrono::Duration::minutes(2));
+    assert_eq!(state.last_trade("illiquid").unwrap().price(Side::No), dec!(0.45));
+
+    // A new bid after the trade is fresher: back to the book.
+    state.update_market(quote(dec!(0.45), t0 + chrono::Duration::minutes(3)));
+    assert_eq!(state.mark_to_market(MarkMode::Liquidation), dec!(45));
+}
+
+// =============================================================================
+// 36. Strategy Size Ramps
+// =============================================================================
+
+#[test]
+fn size_ramp_scales_early_buys_until_strategy_proves_itself() {
+    let state = StateManager::new(dec!(1000));
+    let config = RiskConfig {
+        size_ramp: ["statistical_edge:0.25:4"]
+            .iter()
+            .filter_map(|e| parse_size_ramp(e))
+            .collect(),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state);
+    let stat_edge = || Signal {
+        strategy_name: "statistical_edge".to_string(),
+        ..make_buy_signal("fresh", dec!(0.50), 100, 0.9, None)
+    };
+    let approved_qty = |rm: &mut RiskManager, signal: Signal| {
+        let decision = rm.evaluate_signal(signal);
+        assert!(decision.approved, "{}", decision.reason);
+        decision.signal.unwrap().quantity
+    };
+
+    // No profitable closes yet: a quarter size.
+    assert_eq!(approved_qty(&mut rm, stat_edge()), 25);
+    // Halfway: 0.25^0.5 = half size.
+    rm.set_profitable_trades("statistical_edge", 2);
+    assert_eq!(rm.size_ramp_scale("statistical_edge"), dec!(0.5));
+    assert_eq!(approved_qty(&mut rm, stat_edge()), 50);
+    // Proven: full size from then on.
+    rm.set_profitable_trades("statistical_edge", 4);
+    assert_eq!(approved_qty(&mut rm, stat_edge()), 100);
+    rm.set_profitable_trades("statistical_edge", 9);
+    assert_eq!(approved_qty(&mut rm, stat_edge()), 100);
+
+    // Strategies without a ramp always trade at full size.
+    assert_eq!(approved_qty(&mut rm, make_buy_signal("fresh", dec!(0.50), 100, 0.9, None)), 100);
+}
+
+// =============================================================================
+// 37. Signal Book Context
+// =============================================================================
+
+#[test]
+fn emitted_signals_carry_the_book_they_fired_on() {
+    let clock = MockClock::default();
+    let state = StateManager::new(dec!(1000));
+    state.update_market(MarketState {
+        yes_bid_depth: Some(300),
+        yes_ask_depth: Some(100),
+        last_updated: clock.now() - chrono::Duration::seconds(2),
+        ..make_market("context", dec!(0.45), dec!(0.55))
+    });
+    let mut se = StatisticalEdgeStrategy::new(StatisticalEdgeConfig::default());
+    se.ingest_odds(make_odds("context", "book-a", dec!(0.70)));
+    let mut engine = StrategyEngine::new(state.clone(), None, None, Some(se), None);
+    engine.set_clock(std::sync::Arc::new(clock.clone()));
+    let mut rm = RiskManager::new(permissive_risk_config(), state);
+
+    let out = engine.on_tick(&mut rm);
+    let buy = out.approved_signals.iter().find(|s| s.action == SignalAction::BuyYes).unwrap();
+    for key in BOOK_CONTEXT_KEYS {
+        assert!(buy.metadata.contains_key(key), "missing {key}");
+    }
+    assert_eq!(buy.metadata["book_age_ms"].as_i64(), Some(2_000));
+    assert!((buy.metadata["spread"].as_f64().unwrap() - 0.10).abs() < 1e-12);
+    assert!((buy.metadata["mid"].as_f64().unwrap() - 0.50).abs() < 1e-12);
+    // (300 - 100) / 400 of the top-of-book depth sits on the bid.
+    assert_eq!(buy.metadata["imbalance"].as_f64(), Some(0.5));
+    // Strategy metadata is kept alongside.
+    assert_eq!(true_probability(buy), 0.70);
+}
+
+// =============================================================================
+// 38. Risk Decision Replay
+// =============================================================================
+//
+// Golden-file regression test: `tests/fixtures/risk_replay/steps.json`
+// records a sequence of state changes and signals; each `RiskDecision` must
+// match `expected.json` field by field. After an intended behavior change,
+// regenerate the expectations with `UPDATE_GOLDEN=1 cargo test
+// risk_decisions_match_recorded_expectations` and review the diff.
+
+#[derive(serde::Deserialize)]
+struct ReplayFixture {
+    initial_balance: Decimal,
+    markets: Vec<ReplayMarket>,
+    steps: Vec<ReplayStep>,
+}
+
+#[derive(serde::Deserialize)]
+struct ReplayMarket {
+    slug: String,
+    bid: Decimal,
+    ask: Decimal,
+    resolves_in_secs: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct ReplayPosition {
+    market: String,
+    side: Side,
+    quantity: i64,
+    avg_price: Decimal,
+}
+
+#[derive(serde::Deserialize)]
+struct ReplaySignal {
+    market: String,
+    action: String,
+    price: Decimal,
+    quantity: i64,
+    confidence: f64,
+    strategy: String,
+    true_probability: Option<f64>,
+}
+
+/// State changes applied before the step's signal is evaluated.
+#[derive(serde::Deserialize)]
+struct ReplayStep {
+    name: String,
+    #[serde(default)]
+    advance_secs: i64,
+    balance: Option<Decimal>,
+    #[serde(default)]
+    markets: Vec<ReplayMarket>,
+    #[serde(default)]
+    positions: Vec<ReplayPosition>,
+    signal: ReplaySignal,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct ReplayDecision {
+    step: String,
+    approved: bool,
+    quantity: Option<i64>,
+    reason: String,
+}
+
+/// Tightened so the fixture exercises each check; order-rate limits stay
+/// off.
+fn replay_risk_config() -> RiskConfig {
+    RiskConfig {
+        max_position_per_market: dec!(100),
+        max_portfolio_exposure_pct: Decimal::ONE,
+        max_daily_loss: dec!(200),
+        max_drawdown_pct: dec!(0.50),
+        max_total_pnl_drawdown_pct_for_new_buys: dec!(0.20),
+        min_resolution_horizon: chrono::Duration::seconds(60),
+        min_confidence: ["statistical_edge:0.6"]
+            .iter()
+            .filter_map(|e| parse_confidence_floor(e))
+            .collect(),
+        ..permissive_risk_config()
+    }
+}
+
+fn replay_market(state: &StateManager, clock: &MockClock, market: &ReplayMarket) {
+    let now = polymarket_us_bot::clock::Clock::now(clock);
+    state.update_market(MarketState {
+        resolution_date: market.resolves_in_secs.map(|s| now + chrono::Duration::seconds(s)),
+        last_updated: now,
+        ..make_market(&market.slug, market.bid, market.ask)
+    });
+}
+
+fn replay_signal(signal: &ReplaySignal, clock: &MockClock) -> Signal {
+    let action = match signal.action.as_str() {
+        "buy_yes" => SignalAction::BuyYes,
+        "buy_no" => SignalAction::BuyNo,
+        "sell_yes" => SignalAction::SellYes,
+        "sell_no" => SignalAction::SellNo,
+        other => panic!("unknown replay action {other}"),
+    };
+    let base = make_buy_signal(
+        &signal.market,
+        signal.price,
+        signal.quantity,
+        signal.confidence,
+        signal.true_probability,
+    );
+    Signal {
+        action,
+        strategy_name: signal.strategy.clone(),
+        timestamp: polymarket_us_bot::clock::Clock::now(clock),
+        ..base
+    }
+}
+
+fn replay_risk_decisions(fixture: &ReplayFixture) -> Vec<ReplayDecision> {
+    let clock = MockClock::default();
+    let state = StateManager::new(fixture.initial_balance);
+    state.set_clock(std::sync::Arc::new(clock.clone()));
+    for market in &fixture.markets {
+        replay_market(&state, &clock, market);
+    }
+    let mut rm = RiskManager::new(replay_risk_config(), state.clone());
+    rm.set_clock(std::sync::Arc::new(clock.clone()));
+
+    fixture
+        .steps
+        .iter()
+        .map(|step| {
+            clock.advance(chrono::Duration::seconds(step.advance_secs));
+            if let Some(balance) = step.balance {
+                state.update_balance(balance);
+            }
+            for market in &step.markets {
+                replay_market(&state, &clock, market);
+            }
+            for p in &step.positions {
+                state.update_position(&p.market, p.side, p.quantity, p.avg_price);
+            }
+            let decision = rm.evaluate_signal(replay_signal(&step.signal, &clock));
+            ReplayDecision {
+                step: step.name.clone(),
+                approved: decision.approved,
+                quantity: decision.signal.map(|s| s.quantity),
+                reason: decision.reason,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn risk_decisions_match_recorded_expectations() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/risk_replay");
+    let fixture: ReplayFixture =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("steps.json")).unwrap()).unwrap();
+    let actual = replay_risk_decisions(&fixture);
+
+    let expected_path = dir.join("expected.json");
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let json = serde_json::to_string_pretty(&actual).unwrap();
+        std::fs::write(&expected_path, json + "\n").unwrap();
+        return;
+    }
+    let expected: Vec<ReplayDecision> =
+        serde_json::from_str(&std::fs::read_to_string(&expected_path).unwrap()).unwrap();
+
+    let mut diffs = Vec::new();
+    if actual.len() != expected.len() {
+        diffs.push(format!("expected {} steps, got {}", expected.len(), actual.len()));
+    }
+    for (got, want) in actual.iter().zip(&expected) {
+        let step = &want.step;
+        if got.step != want.step {
+            diffs.push(format!("{step}: step name is now {}", got.step));
+        }
+        if got.approved != want.approved {
+            diffs.push(format!(
+                "{step}: approved: expected {}, got {}",
+                want.approved, got.approved
+            ));
+        }
+        if got.quantity != want.quantity {
+            diffs.push(format!(
+                "{step}: quantity: expected {:?}, got {:?}",
+                want.quantity, got.quantity
+            ));
+        }
+        if got.reason != want.reason {
+            diffs.push(format!("{step}: reason: expected {:?}, got {:?}", want.reason, got.reason));
+        }
+    }
+    assert!(
+        diffs.is_empty(),
+        "risk decisions diverged from expected.json (UPDATE_GOLDEN=1 to accept):\n{}",
+        diffs.join("\n")
+    );
+}
+
+// =============================================================================
+// 39. Live Auth Preflight
+// =============================================================================
+
+// The preflight needs the crate's scripted exchange, so its tests live with
+// `live_preflight` in src/data/startup.rs.
+
+// =============================================================================
+// 40. Net Edge Gate
+// =============================================================================
+
+/// Stat edge engine buying YES at 0.55 against fair 0.70 (gross edge
+/// 0.15, 18 contracts for $10), gated when `asks` is given.
+fn gated_stat_edge_tick(asks: Option<Vec<PriceLevel>>) -> EngineOutput {
+    let state = StateManager::new(dec!(1000));
+    state.update_market(make_market("gate", dec!(0.45), dec!(0.55)));
+    let mut se = StatisticalEdgeStrategy::new(StatisticalEdgeConfig::default());
+    se.ingest_odds(make_odds("gate", "book-a", dec!(0.70)));
+    let mut engine = StrategyEngine::new(state.clone(), None, None, Some(se), None);
+    if let Some(asks) = asks {
+        let tracker = OrderBookTracker::new();
+        tracker.update(OrderBook {
+            market_slug: "gate".to_string(),
+            yes: OrderBookSide {
+                bids: vec![PriceLevel { price: dec!(0.45), quantity: 1000 }],
+                asks,
+            },
+            no: OrderBookSide::default(),
+        });
+        let gate = NetEdgeGate::new(NetEdgeConfig::default()).with_orderbook(tracker);
+        engine.set_net_edge_gate(gate);
+    }
+    let mut rm = RiskManager::new(permissive_risk_config(), state);
+    engine.on_tick(&mut rm)
+}
+
+#[test]
+fn net_edge_gate_drops_entries_eaten_by_impact() {
+    let level = |price: Decimal, quantity: i64| PriceLevel { price, quantity };
+    let buys = |out: &EngineOutput| {
+        out.approved_signals.iter().filter(|s| s.action == SignalAction::BuyYes).count()
+    };
+
+    let ungated = gated_stat_edge_tick(None);
+    assert_eq!(buys(&ungated), 1);
+    let signal = ungated.approved_signals[0].clone();
+    assert_eq!(signal.quantity, 18);
+
+    // Only 5 contracts at 0.55, the rest at 0.80: VWAP for 18 is
+    // (5 * 0.55 + 13 * 0.80) / 18, so impact = 13 * 0.25 / 18 = 0.1806.
+    // Net = 0.15 - 0.0011 fees (2 * 10 bps of 0.55) - 0.1806 - 0.005 < 0.
+    let thin = vec![level(dec!(0.55), 5), level(dec!(0.80), 1000)];
+    let tracker = OrderBookTracker::new();
+    tracker.update(OrderBook {
+        market_slug: "gate".to_string(),
+        yes: OrderBookSide { bids: vec![], asks: thin.clone() },
+        no: OrderBookSide::default(),
+    });
+    let gate = NetEdgeGate::new(NetEdgeConfig::default()).with_orderbook(tracker);
+    let edge = gate.net_edge(&signal).unwrap();
+    assert_eq!(edge.gross, dec!(0.15));
+    assert_eq!(edge.fees, dec!(0.0011));
+    assert_eq!(edge.impact, dec!(3.25) / dec!(18));
+    assert!(edge.gross > Decimal::ZERO && edge.net < Decimal::ZERO, "{edge:?}");
+    assert!(!gate.passes(&signal));
+
+    let out = gated_stat_edge_tick(Some(thin));
+    assert_eq!(buys(&out), 0);
+    assert_eq!(out.edge_gated_count, 1);
+
+    // The same edge on a deep book clears the 0.01 floor:
+    // 0.15 - 0.0011 - 0 - 0.005 = 0.1439.
+    let out = gated_stat_edge_tick(Some(vec![level(dec!(0.55), 1000)]));
+    assert_eq!(buys(&out), 1);
+    assert_eq!(out.edge_gated_count, 0);
+
+    // Exits carry no edge estimate and are never gated.
+    let exit = Signal {
+        action: SignalAction::SellYes,
+        reduce_only: true,
+        ..signal
+    };
+    assert!(gate.net_edge(&exit).is_none() && gate.passes(&exit));
+}
+
+// =============================================================================
+// 41. Per-Market Fee Overrides
+// =============================================================================
+
+#[test]
+fn fee_override_applies_to_its_market_only() {
+    // Two identical books: YES 0.48 + NO 0.48 = 0.96, gross margin 0.04.
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("cheap", dec!(0.48), dec!(0.48)));
+    tracker.update(make_book("pricey", dec!(0.48), dec!(0.48)));
+    let state = StateManager::new(dec!(1000));
+    state.set_fee_overrides(
+        ["pricey:0.05"].iter().filter_map(|e| parse_fee_override(e)).collect(),
+    );
+    assert_eq!(state.fee_rate_for("pricey", dec!(0.001)), dec!(0.05));
+    assert_eq!(state.fee_rate_for("cheap", dec!(0.001)), dec!(0.001));
+
+    // Default 10 bps: net 0.04 - 0.96 * 0.001 = 0.03904 > min_margin 0.01.
+    // Overridden 5%: net 0.04 - 0.96 * 0.05 = -0.008 < 0.01.
+    let mut arb = CompletenessArbStrategy::new(
+        CompletenessArbConfig {
+            min_top_depth: 0,
+            ..CompletenessArbConfig::default()
+        },
+        tracker.clone(),
+    );
+    arb.set_state(state.clone());
+    let slugs: std::collections::HashSet<String> =
+        arb.on_tick().into_iter().map(|s| s.market_slug).collect();
+    assert_eq!(slugs, ["cheap".to_string()].into());
+    // Without the state every market prices at the default.
+    let plain = tracker.scan_completeness_arb(dec!(0.01), 0, Decimal::ZERO, dec!(0.001));
+    assert_eq!(plain.len(), 2);
+
+    // Paper fills: 10 @ 0.48 = $4.80 notional.
+    let mut paper = PaperExecutor::with_params(state, tracker, dec!(0.001), dec!(0));
+    let fee = |paper: &mut PaperExecutor, slug: &str| {
+        paper.execute_signal(&make_buy_signal(slug, dec!(0.48), 10, 1.0, None)).fee
+    };
+    assert_eq!(fee(&mut paper, "cheap"), dec!(0.0048));
+    assert_eq!(fee(&mut paper, "pricey"), dec!(0.24));
+
+    for bad in ["pricey", "pricey:1", "pricey:-0.01", ":0.01"] {
+        assert!(parse_fee_override(bad).is_none(), "{bad}");
+    }
+}
+
+// =============================================================================
+// 42. Drawdown-Scaled Kelly
+// =============================================================================
+
+#[test]
+fn drawdown_kelly_scale_falls_linearly_to_zero_at_max_drawdown() {
+    let mut cb = CircuitBreaker::new(dec!(10000), dec!(0.20));
+    cb.initialize(dec!(1000));
+    cb.update(dec!(1200));
+    assert_eq!(cb.drawdown_kelly_scale(), Decimal::ONE);
+    cb.update(dec!(1080)); // 10% off the 1200 peak
+    assert_eq!(cb.drawdown_pct(), dec!(0.1));
+    assert_eq!(cb.drawdown_kelly_scale(), dec!(0.5));
+    cb.update(dec!(960)); // 20%: tripped, nothing left to size
+    assert_eq!(cb.drawdown_kelly_scale(), Decimal::ZERO);
+}
+
+#[test]
+fn drawdown_scaled_kelly_halves_fraction_at_half_max_drawdown() {
+    let config = RiskConfig {
+        max_daily_loss: dec!(1000),
+        max_total_pnl_drawdown_pct_for_new_buys: Decimal::ZERO,
+        ..permissive_risk_config()
+    };
+    let state = StateManager::new(dec!(1000));
+    let mut scaled = RiskManager::new(
+        RiskConfig { drawdown_scaled_kelly: true, ..config.clone() },
+        state.clone(),
+    );
+    let mut fixed = RiskManager::new(config, state.clone());
+    let kelly_qty = |rm: &mut RiskManager| {
+        let decision =
+            rm.evaluate_signal(make_buy_signal("dd-kelly", dec!(0.50), 10_000, 1.0, Some(0.60)));
+        assert!(decision.approved, "{}", decision.reason);
+        decision.signal.unwrap().quantity
+    };
+
+    assert_eq!(scaled.effective_kelly_fraction(), dec!(0.25));
+    let full = kelly_qty(&mut scaled);
+    assert_eq!(full, kelly_qty(&mut fixed));
+
+    // 5% off the peak is half of the 10% max drawdown.
+    state.update_balance(dec!(950));
+    scaled.on_state_update();
+    fixed.on_state_update();
+    assert_eq!(scaled.effective_kelly_fraction(), dec!(0.125));
+    assert_eq!(fixed.effective_kelly_fraction(), dec!(0.25));
+    let undrawn = kelly_qty(&mut fixed);
+    let halved = kelly_qty(&mut scaled);
+    assert!(undrawn > full / 2);
+    assert!((halved - undrawn / 2).abs() <= 1, "{halved} vs {undrawn}");
+}
+
+// =============================================================================
+// 43. Per-Market Order Interval
+// =============================================================================
+
+#[test]
+fn min_order_interval_throttles_new_orders_per_market() {
+    let clock = MockClock::default();
+    let config = RiskConfig {
+        min_order_interval: chrono::Duration::seconds(10),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, StateManager::new(dec!(1000)));
+    rm.set_clock(std::sync::Arc::new(clock.clone()));
+    let buy = |market: &str| make_buy_signal(market, dec!(0.50), 10, 0.9, None);
+
+    assert!(rm.evaluate_signal(buy("churny")).approved);
+    clock.advance(chrono::Duration::seconds(9));
+    rm.begin_cycle();
+    let decision = rm.evaluate_signal(buy("churny"));
+    assert!(!decision.approved);
+    assert!(decision.reason.contains("since last order"), "{}", decision.reason);
+
+    // Other markets, reduce-only exits and cancels are not throttled.
+    assert!(rm.evaluate_signal(buy("quiet")).approved);
+    let exit = Signal {
+        action: SignalAction::SellYes,
+        reduce_only: true,
+        ..buy("churny")
+    };
+    assert!(rm.evaluate_signal(exit).approved);
+    let cancel = Signal { action: SignalAction::CancelAll, ..buy("churny") };
+    assert!(rm.evaluate_signal(cancel).approved);
+
+    clock.advance(chrono::Duration::seconds(1));
+    rm.begin_cycle();
+    assert!(rm.evaluate_signal(buy("churny")).approved);
+    // A second order in the same cycle (e.g. the other side of a quote)
+    // counts once; the next cycle is throttled again.
+    assert!(rm.evaluate_signal(buy("churny")).approved);
+    rm.begin_cycle();
+    assert!(!rm.evaluate_signal(buy("churny")).approved);
+}
+
+#[test]
+fn min_order_interval_lets_both_arb_legs_through() {
+    let tracker = OrderBookTracker::new();
+    tracker.update(make_book("arb", dec!(0.45), dec!(0.50)));
+    let state = StateManager::new(dec!(1000));
+    let config = RiskConfig {
+        min_order_interval: chrono::Duration::seconds(10),
+        ..permissive_risk_config()
+    };
+    let mut rm = RiskManager::new(config, state.clone());
+    let mut engine = arb_engine(&state, &tracker);
+
+    let out = engine.on_tick(&mut rm);
+    assert_eq!(out.approved_signals.len(), 2);
+    assert_eq!(out.rejected_count, 0);
+    assert!(out.approved_signals.iter().any(|s| s.action == SignalAction::BuyYes));
+    assert!(out.approved_signals.iter().any(|s| s.action == SignalAction::BuyNo));
+
+    // The pair stamped the market once: a later cycle is still throttled.
+    rm.begin_cycle();
+    let decision = rm.evaluate_signal(make_buy_signal("arb", dec!(0.45), 10, 0.9, None));
+    assert!(decision.reason.contains("since last order"), "{}", decision.reason);
+}
+
+// =============================================================================
+// 44. Typed Reject Reasons
+// =============================================================================
+
+#[test]
+fn paper_rejections_distinguish_liquidity_from_balance() {
+    let orderbook = OrderBookTracker::new();
+    orderbook.update(make_book("booked", dec!(0.52), dec!(0.50)));
+    let mut drained = make_book("drained", dec!(0.52), dec!(0.50));
+    drained.yes.asks.clear();
+    orderbook.update(drained);
+    let taker = |market: &str| Signal {
+        urgency: Urgency::Critical,
+        ..make_buy_signal(market, dec!(0.52), 10, 0.9, None)
+    };
+
+    let mut paper = PaperExecutor::new(StateManager::new(dec!(1000)), orderbook.clone());
+    let no_book = paper.execute_signal(&taker("drained"));
+    assert_eq!(no_book.status, OrderStatus::Rejected);
+    assert_eq!(no_book.reject_reason, Some(RejectReason::InsufficientLiquidity));
+    assert!(no_book.reject_reason.unwrap().is_retryable());
+
+    let mut broke = PaperExecutor::new(StateManager::new(dec!(0.10)), orderbook);
+    let unaffordable = broke.execute_signal(&taker("booked"));
+    assert_eq!(unaffordable.status, OrderStatus::Rejected);
+    assert_eq!(unaffordable.reject_reason, Some(RejectReason::InsufficientBalance));
+    assert!(!unaffordable.reject_reason.unwrap().is_retryable());
+
+    let filled = paper.execute_signal(&taker("booked"));
+    assert_eq!(filled.filled_quantity, 10);
+    assert_eq!(filled.reject_reason, None);
+
+    let sell_nothing = Signal { action: SignalAction::SellNo, ..taker("booked") };
+    assert_eq!(
+        paper.execute_signal(&sell_nothing).reject_reason,
+        Some(RejectReason::RiskBlocked)
+    );
+}
+
+#[test]
+fn api_errors_map_to_reject_reasons() {
+    let cases = [
+        (ApiError::InsufficientBalance("low".into()), RejectReason::InsufficientBalance),
+        (ApiError::RateLimited { retry_after: 1 }, RejectReason::Throttled),
+        (ApiError::Timeout("slow".into()), RejectReason::Throttled),
+        (ApiError::InvalidOrder("tick".into()), RejectReason::ApiRejected),
+        (ApiError::MarketClosed("done".into()), RejectReason::ApiRejected),
+    ];
+    for (error, expected) in cases {
+        assert_eq!(RejectReason::from_api_error(&error), expected, "{error:?}");
+    }
+}
+
+// =============================================================================
+// 45. Flip Protection
+// =============================================================================
+
+#[test]
+fn flip_protection_blocks_opposite_side_buys_unless_arb_or_reduce_only() {
+    let state = StateManager::new(dec!(1000));
+    state.update_position("held", Side::Yes, 50, dec!(0.50));
+    let config = RiskConfig { flip_protection: true, ..permissive_risk_config() };
+    let mut rm = RiskManager::new(config, state.clone());
+    let buy_no = || Signal {
+        action: SignalAction::BuyNo,
+        ..make_buy_signal("held", dec!(0.48), 10, 0.9, None)
+    };
+
+    let decision = rm.evaluate_signal(buy_no());
+    assert!(!decision.approved);
+    assert!(decision.reason.contains("flip protection"), "{}", decision.reason);
+
+    // Adding to the held side, or the opposite side elsewhere, is fine.
+    assert!(rm.evaluate_signal(make_buy_signal("held", dec!(0.52), 10, 0.9, None)).approved);
+    let elsewhere = Signal { market_slug: "flat".to_string(), ..buy_no() };
+    assert!(rm.evaluate_signal(elsewhere).approved);
+
+    let mut arb_leg = buy_no();
+    arb_leg.metadata.insert(ARB_PAIR_KEY.to_string(), serde_json::json!("held"));
+    assert!(rm.evaluate_signal(arb_leg).approved);
+    let exit = Signal { reduce_only: true, ..buy_no() };
+    assert!(rm.evaluate_signal(exit).approved);
+
+    let mut unguarded = RiskManager::new(permissive_risk_config(), state);
+    assert!(unguarded.evaluate_signal(buy_no()).approved);
+}